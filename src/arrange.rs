@@ -0,0 +1,926 @@
+//! Typed footprint placement: move, rotate, flip, align, and distribute.
+//!
+//! Footprints are edited as raw `FootprintInstance` payloads so fields the typed model does not
+//! carry survive the round trip. KiCad serializes footprint children (pads, graphics, text,
+//! zones) in board coordinates, so every transform is applied to them as well as to the
+//! footprint's own position, orientation, and layer. Flipping follows KiCad's left/right flip:
+//! positions mirror about the footprint origin, front and back layers swap (inner copper layers
+//! mirror within the stackup), and text mirroring toggles. Other child types (dimensions,
+//! groups) are passed through unchanged.
+//!
+//! [`KiCadClient::arrange_footprints`](crate::client::KiCadClient::arrange_footprints) applies a
+//! list of [`ArrangeOp`]s in order and sends the result in one `update_items` call inside a
+//! single commit.
+
+use std::fmt;
+use std::str::FromStr;
+
+use prost::Message;
+
+use crate::client::decode_pcb_items;
+use crate::envelope;
+use crate::error::KiCadError;
+use crate::geometry;
+use crate::model::board::Vector2Nm;
+use crate::proto::kiapi::board::types::{
+    self as board_types, BoardLayer, FootprintInstance, PadStack,
+};
+use crate::proto::kiapi::common::types::{
+    self as common_types, graphic_shape, poly_line_node, Angle, PolySet, Vector2,
+};
+use crate::spatial::{self, BoundsNm};
+
+const FOOTPRINT_TYPE: &str = "kiapi.board.types.FootprintInstance";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Picks one footprint on the board.
+pub enum FootprintSelector {
+    /// Footprint KIID.
+    Id(String),
+    /// Reference designator, e.g. `U1`.
+    Reference(String),
+}
+
+impl fmt::Display for FootprintSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "id `{id}`"),
+            Self::Reference(reference) => write!(f, "reference `{reference}`"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Bounding-box edge that [`ArrangeOp::Align`] lines up.
+pub enum AlignEdge {
+    /// Smallest x.
+    Left,
+    /// Largest x.
+    Right,
+    /// Smallest y (KiCad's y axis points down).
+    Top,
+    /// Largest y.
+    Bottom,
+}
+
+impl FromStr for AlignEdge {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "top" => Ok(Self::Top),
+            "bottom" => Ok(Self::Bottom),
+            _ => Err(format!(
+                "unknown align edge `{value}`; expected `left`, `right`, `top`, or `bottom`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AlignEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+        };
+        write!(f, "{value}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Axis along which [`ArrangeOp::Distribute`] spaces footprints.
+pub enum DistributeAxis {
+    /// Along x.
+    Horizontal,
+    /// Along y.
+    Vertical,
+}
+
+impl FromStr for DistributeAxis {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "horizontal" => Ok(Self::Horizontal),
+            "vertical" => Ok(Self::Vertical),
+            _ => Err(format!(
+                "unknown distribute axis `{value}`; expected `horizontal` or `vertical`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DistributeAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::Horizontal => "horizontal",
+            Self::Vertical => "vertical",
+        };
+        write!(f, "{value}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// One placement step applied to every selected footprint.
+pub enum ArrangeOp {
+    /// Moves each footprint's origin to an absolute position.
+    MoveTo(Vector2Nm),
+    /// Moves each footprint by an offset.
+    MoveBy(Vector2Nm),
+    /// Rotates counter-clockwise (as seen on screen) about `center`, or about each footprint's
+    /// own origin when `center` is `None`.
+    Rotate {
+        /// Rotation in degrees.
+        angle_deg: f64,
+        /// Common pivot.
+        center: Option<Vector2Nm>,
+    },
+    /// Flips each footprint to the other side about its origin.
+    Flip,
+    /// Moves footprints so their bounding boxes share the outermost `edge` of the selection.
+    Align(AlignEdge),
+    /// Keeps the first and last footprint along `axis` in place and spaces the others so the
+    /// gaps between bounding boxes are equal. Needs at least three footprints to move anything.
+    Distribute(DistributeAxis),
+}
+
+/// Picks the footprints matching `selectors`, in selector order and without duplicates.
+///
+/// Fails when a selector matches nothing or a reference is shared by several footprints.
+pub(crate) fn select_footprints(
+    footprints: Vec<FootprintInstance>,
+    selectors: &[FootprintSelector],
+) -> Result<Vec<FootprintInstance>, KiCadError> {
+    let mut indices = Vec::new();
+    for selector in selectors {
+        let matches: Vec<usize> = footprints
+            .iter()
+            .enumerate()
+            .filter(|(_, footprint)| match selector {
+                FootprintSelector::Id(id) => {
+                    footprint.id.as_ref().is_some_and(|kiid| &kiid.value == id)
+                }
+                FootprintSelector::Reference(reference) => {
+                    reference_of(footprint) == Some(reference.as_str())
+                }
+            })
+            .map(|(index, _)| index)
+            .collect();
+        match matches.as_slice() {
+            [] => {
+                return Err(KiCadError::Config {
+                    reason: format!("no footprint matches {selector}"),
+                })
+            }
+            [index] => {
+                if !indices.contains(index) {
+                    indices.push(*index);
+                }
+            }
+            _ => {
+                return Err(KiCadError::Config {
+                    reason: format!(
+                        "{} footprints match {selector}; select them by id",
+                        matches.len()
+                    ),
+                })
+            }
+        }
+    }
+
+    let mut picked: Vec<Option<FootprintInstance>> = footprints.into_iter().map(Some).collect();
+    Ok(indices
+        .into_iter()
+        .filter_map(|index| picked[index].take())
+        .collect())
+}
+
+/// Applies `ops` in order to `footprints`.
+///
+/// `copper_layer_count` is used to mirror inner copper layers when flipping.
+pub(crate) fn arrange(
+    footprints: &mut [FootprintInstance],
+    ops: &[ArrangeOp],
+    copper_layer_count: u32,
+) -> Result<(), KiCadError> {
+    for op in ops {
+        match *op {
+            ArrangeOp::MoveTo(target) => {
+                for footprint in footprints.iter_mut() {
+                    let origin = origin_of(footprint)?;
+                    translate(
+                        footprint,
+                        target.x_nm - origin.x_nm,
+                        target.y_nm - origin.y_nm,
+                    );
+                }
+            }
+            ArrangeOp::MoveBy(offset) => {
+                for footprint in footprints.iter_mut() {
+                    translate(footprint, offset.x_nm, offset.y_nm);
+                }
+            }
+            ArrangeOp::Rotate { angle_deg, center } => {
+                for footprint in footprints.iter_mut() {
+                    let center = match center {
+                        Some(center) => center,
+                        None => origin_of(footprint)?,
+                    };
+                    transform_footprint(footprint, &Transform::Rotate { center, angle_deg });
+                }
+            }
+            ArrangeOp::Flip => {
+                for footprint in footprints.iter_mut() {
+                    let origin = origin_of(footprint)?;
+                    // KiCad flips top/bottom and then turns the footprint by 180 degrees, which
+                    // amounts to a left/right mirror with a consistent orientation.
+                    transform_footprint(
+                        footprint,
+                        &Transform::FlipY {
+                            axis_y_nm: origin.y_nm,
+                            copper_layer_count,
+                        },
+                    );
+                    transform_footprint(
+                        footprint,
+                        &Transform::Rotate {
+                            center: origin,
+                            angle_deg: 180.0,
+                        },
+                    );
+                }
+            }
+            ArrangeOp::Align(edge) => align(footprints, edge)?,
+            ArrangeOp::Distribute(axis) => distribute(footprints, axis)?,
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn footprint_to_any(footprint: &FootprintInstance) -> prost_types::Any {
+    envelope::pack_any(footprint, FOOTPRINT_TYPE)
+}
+
+fn reference_of(footprint: &FootprintInstance) -> Option<&str> {
+    footprint
+        .reference_field
+        .as_ref()
+        .and_then(|field| field.text.as_ref())
+        .and_then(|board_text| board_text.text.as_ref())
+        .map(|text| text.text.as_str())
+}
+
+fn describe(footprint: &FootprintInstance) -> String {
+    reference_of(footprint)
+        .filter(|reference| !reference.is_empty())
+        .map(str::to_string)
+        .or_else(|| footprint.id.as_ref().map(|id| id.value.clone()))
+        .unwrap_or_else(|| "<unnamed>".to_string())
+}
+
+fn origin_of(footprint: &FootprintInstance) -> Result<Vector2Nm, KiCadError> {
+    footprint
+        .position
+        .map(|position| Vector2Nm {
+            x_nm: position.x_nm,
+            y_nm: position.y_nm,
+        })
+        .ok_or_else(|| KiCadError::InvalidResponse {
+            reason: format!("footprint {} has no position", describe(footprint)),
+        })
+}
+
+/// Bounding box of the footprint's pads and graphics, falling back to its origin.
+fn bounds_of(footprint: &FootprintInstance) -> Result<BoundsNm, KiCadError> {
+    let decoded = decode_pcb_items(vec![footprint_to_any(footprint)])?;
+    match decoded.first().and_then(spatial::item_bounds) {
+        Some(bounds) => Ok(bounds),
+        None => origin_of(footprint).map(|origin| BoundsNm::new(origin, origin)),
+    }
+}
+
+fn translate(footprint: &mut FootprintInstance, dx_nm: i64, dy_nm: i64) {
+    if dx_nm != 0 || dy_nm != 0 {
+        transform_footprint(footprint, &Transform::Translate { dx_nm, dy_nm });
+    }
+}
+
+fn align(footprints: &mut [FootprintInstance], edge: AlignEdge) -> Result<(), KiCadError> {
+    let bounds = footprints
+        .iter()
+        .map(bounds_of)
+        .collect::<Result<Vec<_>, _>>()?;
+    let edge_of = |bounds: &BoundsNm| match edge {
+        AlignEdge::Left => bounds.min.x_nm,
+        AlignEdge::Right => bounds.max.x_nm,
+        AlignEdge::Top => bounds.min.y_nm,
+        AlignEdge::Bottom => bounds.max.y_nm,
+    };
+    let target = match edge {
+        AlignEdge::Left | AlignEdge::Top => bounds.iter().map(edge_of).min(),
+        AlignEdge::Right | AlignEdge::Bottom => bounds.iter().map(edge_of).max(),
+    };
+    let Some(target) = target else {
+        return Ok(());
+    };
+
+    for (footprint, bounds) in footprints.iter_mut().zip(&bounds) {
+        let delta = target - edge_of(bounds);
+        match edge {
+            AlignEdge::Left | AlignEdge::Right => translate(footprint, delta, 0),
+            AlignEdge::Top | AlignEdge::Bottom => translate(footprint, 0, delta),
+        }
+    }
+    Ok(())
+}
+
+fn distribute(
+    footprints: &mut [FootprintInstance],
+    axis: DistributeAxis,
+) -> Result<(), KiCadError> {
+    if footprints.len() < 3 {
+        return Ok(());
+    }
+    let span = |bounds: &BoundsNm| match axis {
+        DistributeAxis::Horizontal => (bounds.min.x_nm, bounds.max.x_nm),
+        DistributeAxis::Vertical => (bounds.min.y_nm, bounds.max.y_nm),
+    };
+    let mut order = footprints
+        .iter()
+        .map(bounds_of)
+        .map(|bounds| bounds.map(|bounds| span(&bounds)))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .enumerate()
+        .collect::<Vec<_>>();
+    order.sort_by_key(|(_, (min, max))| min / 2 + max / 2);
+
+    let (_, (first_min, first_max)) = order[0];
+    let (_, (_, last_max)) = order[order.len() - 1];
+    let total = (last_max.max(first_max) - first_min) as f64;
+    let occupied: f64 = order.iter().map(|(_, (min, max))| (max - min) as f64).sum();
+    let gap = (total - occupied) / (order.len() - 1) as f64;
+
+    let mut cursor = first_max as f64 + gap;
+    for &(index, (min, max)) in &order[1..order.len() - 1] {
+        let delta = cursor.round() as i64 - min;
+        match axis {
+            DistributeAxis::Horizontal => translate(&mut footprints[index], delta, 0),
+            DistributeAxis::Vertical => translate(&mut footprints[index], 0, delta),
+        }
+        cursor += (max - min) as f64 + gap;
+    }
+    Ok(())
+}
+
+/// Rigid transform applied to a footprint and everything it owns.
+enum Transform {
+    Translate {
+        dx_nm: i64,
+        dy_nm: i64,
+    },
+    Rotate {
+        center: Vector2Nm,
+        angle_deg: f64,
+    },
+    /// Mirrors y about `y = axis_y_nm` and swaps front and back layers.
+    FlipY {
+        axis_y_nm: i64,
+        copper_layer_count: u32,
+    },
+}
+
+impl Transform {
+    fn point(&self, point: Vector2) -> Vector2 {
+        match *self {
+            Self::Translate { dx_nm, dy_nm } => Vector2 {
+                x_nm: point.x_nm + dx_nm,
+                y_nm: point.y_nm + dy_nm,
+            },
+            Self::Rotate { center, angle_deg } => {
+                let rotated = geometry::rotate_about(
+                    Vector2Nm {
+                        x_nm: point.x_nm,
+                        y_nm: point.y_nm,
+                    },
+                    center,
+                    angle_deg,
+                );
+                Vector2 {
+                    x_nm: rotated.x_nm,
+                    y_nm: rotated.y_nm,
+                }
+            }
+            Self::FlipY { axis_y_nm, .. } => Vector2 {
+                x_nm: point.x_nm,
+                y_nm: 2 * axis_y_nm - point.y_nm,
+            },
+        }
+    }
+
+    fn map(&self, point: &mut Option<Vector2>) {
+        if let Some(value) = point {
+            *value = self.point(*value);
+        }
+    }
+
+    fn angle(&self, angle: &mut Option<Angle>) {
+        let degrees = angle.map_or(0.0, |angle| angle.value_degrees);
+        let mapped = match *self {
+            Self::Translate { .. } => return,
+            Self::Rotate { angle_deg, .. } => degrees + angle_deg,
+            Self::FlipY { .. } => -degrees,
+        };
+        *angle = Some(Angle {
+            value_degrees: normalize_deg(mapped),
+        });
+    }
+
+    fn flips(&self) -> bool {
+        matches!(self, Self::FlipY { .. })
+    }
+
+    /// Rotations that keep axis-aligned rectangles axis-aligned.
+    fn keeps_axes(&self) -> bool {
+        match *self {
+            Self::Rotate { angle_deg, .. } => angle_deg.rem_euclid(90.0) == 0.0,
+            _ => true,
+        }
+    }
+
+    fn layer(&self, layer: i32) -> i32 {
+        match *self {
+            Self::FlipY {
+                copper_layer_count, ..
+            } => flip_layer(layer, copper_layer_count),
+            _ => layer,
+        }
+    }
+
+    fn layers(&self, layers: &mut [i32]) {
+        for layer in layers {
+            *layer = self.layer(*layer);
+        }
+    }
+}
+
+/// Angle in `(-180, 180]` degrees, matching KiCad's footprint orientation range.
+fn normalize_deg(degrees: f64) -> f64 {
+    let wrapped = degrees.rem_euclid(360.0);
+    if wrapped > 180.0 {
+        wrapped - 360.0
+    } else {
+        wrapped
+    }
+}
+
+/// Front/back counterpart of `layer`; inner copper layers mirror within the stackup.
+fn flip_layer(layer: i32, copper_layer_count: u32) -> i32 {
+    const PAIRS: [(BoardLayer, BoardLayer); 7] = [
+        (BoardLayer::BlFCu, BoardLayer::BlBCu),
+        (BoardLayer::BlFAdhes, BoardLayer::BlBAdhes),
+        (BoardLayer::BlFPaste, BoardLayer::BlBPaste),
+        (BoardLayer::BlFSilkS, BoardLayer::BlBSilkS),
+        (BoardLayer::BlFMask, BoardLayer::BlBMask),
+        (BoardLayer::BlFCrtYd, BoardLayer::BlBCrtYd),
+        (BoardLayer::BlFFab, BoardLayer::BlBFab),
+    ];
+    for (front, back) in PAIRS {
+        if layer == front as i32 {
+            return back as i32;
+        }
+        if layer == back as i32 {
+            return front as i32;
+        }
+    }
+
+    let inner = i64::from(layer) - BoardLayer::BlFCu as i64;
+    let inner_count = i64::from(copper_layer_count) - 2;
+    if (1..=inner_count).contains(&inner) {
+        (BoardLayer::BlFCu as i64 + inner_count + 1 - inner) as i32
+    } else {
+        layer
+    }
+}
+
+fn transform_footprint(footprint: &mut FootprintInstance, transform: &Transform) {
+    transform.map(&mut footprint.position);
+    transform.angle(&mut footprint.orientation);
+    footprint.layer = transform.layer(footprint.layer);
+    for field in [
+        &mut footprint.reference_field,
+        &mut footprint.value_field,
+        &mut footprint.datasheet_field,
+        &mut footprint.description_field,
+    ] {
+        transform_field(field, transform);
+    }
+
+    let Some(definition) = &mut footprint.definition else {
+        return;
+    };
+    for field in [
+        &mut definition.reference_field,
+        &mut definition.value_field,
+        &mut definition.datasheet_field,
+        &mut definition.description_field,
+    ] {
+        transform_field(field, transform);
+    }
+    for item in &mut definition.items {
+        transform_child(item, transform);
+    }
+}
+
+fn transform_child(item: &mut prost_types::Any, transform: &Transform) {
+    fn edit<T: Message + Default>(item: &mut prost_types::Any, apply: impl FnOnce(&mut T)) {
+        if let Ok(mut message) = T::decode(item.value.as_slice()) {
+            apply(&mut message);
+            item.value = message.encode_to_vec();
+        }
+    }
+
+    let type_name = item
+        .type_url
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    match type_name.as_str() {
+        "kiapi.board.types.Pad" => edit(item, |pad: &mut board_types::Pad| {
+            transform.map(&mut pad.position);
+            if let Some(pad_stack) = &mut pad.pad_stack {
+                transform_pad_stack(pad_stack, transform);
+            }
+        }),
+        "kiapi.board.types.BoardGraphicShape" => {
+            edit(item, |shape: &mut board_types::BoardGraphicShape| {
+                transform_shape(shape, transform)
+            })
+        }
+        "kiapi.board.types.BoardText" => edit(item, |text: &mut board_types::BoardText| {
+            transform_board_text(text, transform)
+        }),
+        "kiapi.board.types.BoardTextBox" => {
+            edit(item, |text_box: &mut board_types::BoardTextBox| {
+                text_box.layer = transform.layer(text_box.layer);
+                if let Some(inner) = &mut text_box.textbox {
+                    transform.map(&mut inner.top_left);
+                    transform.map(&mut inner.bottom_right);
+                    normalize_corners(&mut inner.top_left, &mut inner.bottom_right);
+                    transform_text_attributes(&mut inner.attributes, transform);
+                }
+            })
+        }
+        "kiapi.board.types.Field" => edit(item, |field: &mut board_types::Field| {
+            if let Some(text) = &mut field.text {
+                transform_board_text(text, transform);
+            }
+        }),
+        "kiapi.board.types.Zone" => edit(item, |zone: &mut board_types::Zone| {
+            transform.layers(&mut zone.layers);
+            if let Some(outline) = &mut zone.outline {
+                transform_poly_set(outline, transform);
+            }
+            for fill in &mut zone.filled_polygons {
+                fill.layer = transform.layer(fill.layer);
+                if let Some(shapes) = &mut fill.shapes {
+                    transform_poly_set(shapes, transform);
+                }
+            }
+        }),
+        _ => {}
+    }
+}
+
+fn transform_pad_stack(pad_stack: &mut PadStack, transform: &Transform) {
+    transform.angle(&mut pad_stack.angle);
+    if !transform.flips() {
+        return;
+    }
+
+    transform.layers(&mut pad_stack.layers);
+    for drill in [
+        &mut pad_stack.drill,
+        &mut pad_stack.secondary_drill,
+        &mut pad_stack.tertiary_drill,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let (start, end) = (
+            transform.layer(drill.start_layer),
+            transform.layer(drill.end_layer),
+        );
+        // Copper ids grow from front to back; keep the drill span ordered.
+        (drill.start_layer, drill.end_layer) = (start.min(end), start.max(end));
+    }
+    std::mem::swap(
+        &mut pad_stack.front_outer_layers,
+        &mut pad_stack.back_outer_layers,
+    );
+    std::mem::swap(
+        &mut pad_stack.front_post_machining,
+        &mut pad_stack.back_post_machining,
+    );
+
+    // Pad-local geometry mirrors in the pad's own y axis once the angle is negated.
+    let mirror_y = |vector: &mut Option<Vector2>| {
+        if let Some(vector) = vector {
+            vector.y_nm = -vector.y_nm;
+        }
+    };
+    let local_mirror = Transform::FlipY {
+        axis_y_nm: 0,
+        copper_layer_count: 0,
+    };
+    for copper in &mut pad_stack.copper_layers {
+        copper.layer = transform.layer(copper.layer);
+        mirror_y(&mut copper.offset);
+        mirror_y(&mut copper.trapezoid_delta);
+        if let Some(corners) = &mut copper.chamfered_corners {
+            std::mem::swap(&mut corners.top_left, &mut corners.bottom_left);
+            std::mem::swap(&mut corners.top_right, &mut corners.bottom_right);
+        }
+        for shape in &mut copper.custom_shapes {
+            if let Some(geometry) = shape
+                .shape
+                .as_mut()
+                .and_then(|shape| shape.geometry.as_mut())
+            {
+                transform_geometry(geometry, &local_mirror);
+            }
+        }
+    }
+}
+
+fn transform_shape(shape: &mut board_types::BoardGraphicShape, transform: &Transform) {
+    shape.layer = transform.layer(shape.layer);
+    if let Some(geometry) = shape
+        .shape
+        .as_mut()
+        .and_then(|shape| shape.geometry.as_mut())
+    {
+        transform_geometry(geometry, transform);
+    }
+}
+
+fn transform_geometry(geometry: &mut graphic_shape::Geometry, transform: &Transform) {
+    match geometry {
+        graphic_shape::Geometry::Segment(segment) => {
+            transform.map(&mut segment.start);
+            transform.map(&mut segment.end);
+        }
+        graphic_shape::Geometry::Rectangle(rectangle) => {
+            if transform.keeps_axes() {
+                transform.map(&mut rectangle.top_left);
+                transform.map(&mut rectangle.bottom_right);
+                normalize_corners(&mut rectangle.top_left, &mut rectangle.bottom_right);
+                return;
+            }
+            // Like KiCad, a rectangle turned off-axis becomes a polygon (rounding is dropped).
+            let (Some(top_left), Some(bottom_right)) = (rectangle.top_left, rectangle.bottom_right)
+            else {
+                return;
+            };
+            let corners = [
+                top_left,
+                Vector2 {
+                    x_nm: bottom_right.x_nm,
+                    y_nm: top_left.y_nm,
+                },
+                bottom_right,
+                Vector2 {
+                    x_nm: top_left.x_nm,
+                    y_nm: bottom_right.y_nm,
+                },
+            ];
+            *geometry = graphic_shape::Geometry::Polygon(PolySet {
+                polygons: vec![common_types::PolygonWithHoles {
+                    outline: Some(common_types::PolyLine {
+                        nodes: corners
+                            .into_iter()
+                            .map(|corner| common_types::PolyLineNode {
+                                geometry: Some(poly_line_node::Geometry::Point(
+                                    transform.point(corner),
+                                )),
+                            })
+                            .collect(),
+                        closed: true,
+                    }),
+                    holes: Vec::new(),
+                }],
+            });
+        }
+        graphic_shape::Geometry::Arc(arc) => {
+            transform.map(&mut arc.start);
+            transform.map(&mut arc.mid);
+            transform.map(&mut arc.end);
+        }
+        graphic_shape::Geometry::Circle(circle) => {
+            transform.map(&mut circle.center);
+            transform.map(&mut circle.radius_point);
+        }
+        graphic_shape::Geometry::Polygon(poly_set) => transform_poly_set(poly_set, transform),
+        graphic_shape::Geometry::Bezier(bezier) => {
+            transform.map(&mut bezier.start);
+            transform.map(&mut bezier.control1);
+            transform.map(&mut bezier.control2);
+            transform.map(&mut bezier.end);
+        }
+    }
+}
+
+fn transform_poly_set(poly_set: &mut PolySet, transform: &Transform) {
+    for polygon in &mut poly_set.polygons {
+        for line in polygon.outline.iter_mut().chain(polygon.holes.iter_mut()) {
+            for node in &mut line.nodes {
+                match &mut node.geometry {
+                    Some(poly_line_node::Geometry::Point(point)) => {
+                        *point = transform.point(*point)
+                    }
+                    Some(poly_line_node::Geometry::Arc(arc)) => {
+                        transform.map(&mut arc.start);
+                        transform.map(&mut arc.mid);
+                        transform.map(&mut arc.end);
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+}
+
+fn transform_field(field: &mut Option<board_types::Field>, transform: &Transform) {
+    if let Some(text) = field.as_mut().and_then(|field| field.text.as_mut()) {
+        transform_board_text(text, transform);
+    }
+}
+
+fn transform_board_text(text: &mut board_types::BoardText, transform: &Transform) {
+    text.layer = transform.layer(text.layer);
+    if let Some(inner) = &mut text.text {
+        transform.map(&mut inner.position);
+        transform_text_attributes(&mut inner.attributes, transform);
+    }
+}
+
+fn transform_text_attributes(
+    attributes: &mut Option<common_types::TextAttributes>,
+    transform: &Transform,
+) {
+    if let Some(attributes) = attributes {
+        transform.angle(&mut attributes.angle);
+        if transform.flips() {
+            attributes.mirrored = !attributes.mirrored;
+        }
+    }
+}
+
+fn normalize_corners(top_left: &mut Option<Vector2>, bottom_right: &mut Option<Vector2>) {
+    if let (Some(a), Some(b)) = (top_left.as_mut(), bottom_right.as_mut()) {
+        (a.x_nm, b.x_nm) = (a.x_nm.min(b.x_nm), a.x_nm.max(b.x_nm));
+        (a.y_nm, b.y_nm) = (a.y_nm.min(b.y_nm), a.y_nm.max(b.y_nm));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arrange, select_footprints, AlignEdge, ArrangeOp, DistributeAxis, FootprintSelector,
+    };
+    use crate::model::board::Vector2Nm;
+    use crate::proto::kiapi::board::types::{
+        BoardLayer, Field, Footprint, FootprintInstance, Pad, PadStack, PadStackLayer,
+    };
+    use crate::proto::kiapi::common::types::{Angle, Kiid, Vector2};
+    use prost::Message;
+
+    fn v(x_nm: i64, y_nm: i64) -> Vector2 {
+        Vector2 { x_nm, y_nm }
+    }
+
+    fn footprint(reference: &str, position: Vector2) -> FootprintInstance {
+        FootprintInstance {
+            id: Some(Kiid {
+                value: format!("id-{reference}"),
+            }),
+            position: Some(position),
+            layer: BoardLayer::BlFCu as i32,
+            reference_field: Some(Field {
+                text: Some(crate::proto::kiapi::board::types::BoardText {
+                    text: Some(crate::proto::kiapi::common::types::Text {
+                        text: reference.to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flip_mirrors_children_and_swaps_layers() {
+        let pad = Pad {
+            position: Some(v(12, 1)),
+            pad_stack: Some(PadStack {
+                layers: vec![BoardLayer::BlFCu as i32, BoardLayer::BlFMask as i32],
+                angle: Some(Angle {
+                    value_degrees: 30.0,
+                }),
+                copper_layers: vec![PadStackLayer {
+                    layer: BoardLayer::BlFCu as i32,
+                    offset: Some(v(2, 3)),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut part = footprint("U1", v(10, 0));
+        part.orientation = Some(Angle {
+            value_degrees: 30.0,
+        });
+        part.definition = Some(Footprint {
+            items: vec![crate::envelope::pack_any(&pad, "kiapi.board.types.Pad")],
+            ..Default::default()
+        });
+
+        let mut parts = vec![part];
+        arrange(&mut parts, &[ArrangeOp::Flip], 4).expect("flip should succeed");
+
+        let part = &parts[0];
+        assert_eq!(part.position, Some(v(10, 0)));
+        assert_eq!(part.layer, BoardLayer::BlBCu as i32);
+        let orientation = part.orientation.expect("orientation").value_degrees;
+        assert!((orientation - 150.0).abs() < 1e-9);
+
+        let definition = part.definition.as_ref().expect("definition");
+        let pad = Pad::decode(definition.items[0].value.as_slice()).expect("pad decodes");
+        assert_eq!(pad.position, Some(v(8, 1)));
+        let stack = pad.pad_stack.expect("pad stack");
+        assert_eq!(
+            stack.layers,
+            vec![BoardLayer::BlBCu as i32, BoardLayer::BlBMask as i32]
+        );
+        assert_eq!(stack.copper_layers[0].layer, BoardLayer::BlBCu as i32);
+        assert_eq!(stack.copper_layers[0].offset, Some(v(2, -3)));
+        assert!((stack.angle.expect("angle").value_degrees - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn select_rotate_align_and_distribute() {
+        let board = vec![
+            footprint("R1", v(0, 0)),
+            footprint("R2", v(7_000, 500)),
+            footprint("R3", v(30_000, -200)),
+            footprint("C1", v(5, 5)),
+        ];
+        let mut parts = select_footprints(
+            board.clone(),
+            &[
+                FootprintSelector::Reference("R3".to_string()),
+                FootprintSelector::Id("id-R1".to_string()),
+                FootprintSelector::Reference("R2".to_string()),
+                FootprintSelector::Reference("R1".to_string()),
+            ],
+        )
+        .expect("selectors should match");
+        assert_eq!(parts.len(), 3);
+        assert!(
+            select_footprints(board, &[FootprintSelector::Reference("U9".to_string())]).is_err()
+        );
+
+        arrange(
+            &mut parts,
+            &[
+                ArrangeOp::Align(AlignEdge::Top),
+                ArrangeOp::Distribute(DistributeAxis::Horizontal),
+                ArrangeOp::Rotate {
+                    angle_deg: 90.0,
+                    center: Some(Vector2Nm { x_nm: 0, y_nm: 0 }),
+                },
+            ],
+            2,
+        )
+        .expect("arrange should succeed");
+
+        // Top alignment puts every origin at y = -200, distribution moves R2 to x = 15000,
+        // and a 90 degree turn about (0, 0) maps (x, y) to (y, -x).
+        let positions: Vec<_> = parts.iter().map(|part| part.position).collect();
+        assert_eq!(
+            positions,
+            vec![
+                Some(v(-200, -30_000)),
+                Some(v(-200, 0)),
+                Some(v(-200, -15_000))
+            ]
+        );
+    }
+}
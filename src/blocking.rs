@@ -7,10 +7,22 @@ use std::time::Duration;
 
 use prost_types::Any;
 
+use crate::arrange::{ArrangeOp, FootprintSelector};
+use crate::bom::{Bom, BomOptions};
 use crate::client::{ClientBuilder, KiCadClient};
 use crate::error::KiCadError;
 use crate::model::board::*;
 use crate::model::common::*;
+use crate::model::schematic::{SchematicItem, SchematicItemType};
+use crate::outline::BoardOutline;
+use crate::placement::{Placement, PlacementOptions};
+use crate::query::ItemQuery;
+use crate::render::svg::SvgOptions;
+use crate::snapshot::BoardSnapshot;
+use crate::stackup::{self, StackupEditor};
+use crate::text_vars::{BuildMetadata, TextOccurrence, TextPreview, TextVariableReport};
+use crate::view::{ViewPreset, ViewPresets};
+use crate::watch::SelectionStream;
 
 const BLOCKING_QUEUE_CAPACITY: usize = 64;
 
@@ -154,6 +166,13 @@ impl KiCadClientBlockingBuilder {
         self
     }
 
+    /// Caches the resolved board document; see
+    /// [`ClientBuilder::cache_documents`](crate::client::ClientBuilder::cache_documents).
+    pub fn cache_documents(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.cache_documents(enabled);
+        self
+    }
+
     pub fn connect(self) -> Result<KiCadClientBlocking, KiCadError> {
         let core = BlockingCore::start()?;
         let inner_builder = self.inner;
@@ -210,6 +229,11 @@ impl KiCadClientBlocking {
         &self.inner
     }
 
+    /// Drops the cached board document; see [`KiCadClient::refresh_documents`].
+    pub fn refresh_documents(&self) {
+        self.inner.refresh_documents();
+    }
+
     pub fn run_action_raw(&self, action: impl Into<String>) -> Result<Any, KiCadError> {
         let action = action.into();
         let client = self.inner.clone();
@@ -446,6 +470,24 @@ impl KiCadClientBlocking {
         fn get_items_raw_by_type_codes(&self, type_codes: Vec<i32>) -> Result<Vec<Any>, KiCadError>;
         fn get_items_details_by_type_codes(&self, type_codes: Vec<i32>) -> Result<Vec<SelectionItemDetail>, KiCadError>;
         fn get_items_by_type_codes(&self, type_codes: Vec<i32>) -> Result<Vec<PcbItem>, KiCadError>;
+        fn get_bom(&self, options: BomOptions) -> Result<Bom, KiCadError>;
+        fn export_placement(&self, options: PlacementOptions) -> Result<Placement, KiCadError>;
+        fn get_text_variable_report(&self) -> Result<TextVariableReport, KiCadError>;
+        fn preview_text_expansions(&self, occurrences: Vec<TextOccurrence>) -> Result<Vec<TextPreview>, KiCadError>;
+        fn stamp_build_metadata(&self, metadata: BuildMetadata) -> Result<BTreeMap<String, String>, KiCadError>;
+        fn render_board_svg(&self, options: SvgOptions) -> Result<String, KiCadError>;
+        fn get_board_snapshot(&self) -> Result<BoardSnapshot, KiCadError>;
+        fn find_board(&self, selector: BoardSelector) -> Result<DocumentSpecifier, KiCadError>;
+        fn query_items(&self, query: ItemQuery) -> Result<Vec<PcbItem>, KiCadError>;
+        fn add_query_to_selection(&self, query: ItemQuery) -> Result<SelectionSummary, KiCadError>;
+        fn get_schematic_items_raw(&self, document: DocumentSpecifier, type_codes: Vec<i32>) -> Result<Vec<Any>, KiCadError>;
+        fn get_schematic_items(&self, document: DocumentSpecifier, types: Vec<SchematicItemType>) -> Result<Vec<SchematicItem>, KiCadError>;
+        fn get_schematic_items_by_id(&self, document: DocumentSpecifier, item_ids: Vec<String>) -> Result<Vec<SchematicItem>, KiCadError>;
+        fn get_schematic_selection(&self, document: DocumentSpecifier) -> Result<Vec<SchematicItem>, KiCadError>;
+        fn create_schematic_items(&self, document: DocumentSpecifier, items: Vec<SchematicItem>, container_id: Option<String>) -> Result<Vec<SchematicItem>, KiCadError>;
+        fn update_schematic_items(&self, document: DocumentSpecifier, items: Vec<SchematicItem>) -> Result<Vec<SchematicItem>, KiCadError>;
+        fn delete_schematic_items(&self, document: DocumentSpecifier, item_ids: Vec<String>) -> Result<Vec<String>, KiCadError>;
+        fn get_schematic_title_block_info(&self, document: DocumentSpecifier) -> Result<TitleBlockInfo, KiCadError>;
         fn get_all_pcb_items_raw(&self) -> Result<Vec<(PcbObjectTypeCode, Vec<Any>)>, KiCadError>;
         fn get_all_pcb_items_details(&self) -> Result<Vec<(PcbObjectTypeCode, Vec<SelectionItemDetail>)>, KiCadError>;
         fn get_all_pcb_items(&self) -> Result<Vec<(PcbObjectTypeCode, Vec<PcbItem>)>, KiCadError>;
@@ -472,6 +514,13 @@ impl KiCadClientBlocking {
         fn interactive_move_items_raw(&self, item_ids: Vec<String>) -> Result<Any, KiCadError>;
         fn interactive_move_items(&self, item_ids: Vec<String>) -> Result<(), KiCadError>;
         fn get_title_block_info(&self) -> Result<TitleBlockInfo, KiCadError>;
+        fn set_title_block_info(&self, info: TitleBlockInfo) -> Result<BTreeMap<String, String>, KiCadError>;
+        fn get_board_outline(&self) -> Result<BoardOutline, KiCadError>;
+        fn arrange_footprints(&self, selectors: Vec<FootprintSelector>, ops: Vec<ArrangeOp>) -> Result<Vec<PcbFootprint>, KiCadError>;
+        fn capture_view_preset(&self, name: String) -> Result<ViewPreset, KiCadError>;
+        fn apply_view_preset(&self, preset: ViewPreset) -> Result<(), KiCadError>;
+        fn load_view_presets(&self, identifier: String) -> Result<ViewPresets, KiCadError>;
+        fn save_view_presets(&self, identifier: String, presets: ViewPresets) -> Result<PathBuf, KiCadError>;
         fn save_document_raw(&self) -> Result<Any, KiCadError>;
         fn save_document(&self) -> Result<(), KiCadError>;
         fn revert_document_raw(&self) -> Result<Any, KiCadError>;
@@ -485,6 +534,44 @@ impl KiCadClientBlocking {
         fn hit_test_item(&self, item_id: String, position: Vector2Nm, tolerance_nm: i32) -> Result<ItemHitTestResult, KiCadError>;
     }
 
+    /// Blocking client whose board methods target `document`; see
+    /// [`KiCadClient::board`].
+    pub fn board(&self, document: &DocumentSpecifier) -> KiCadClientBlocking {
+        KiCadClientBlocking {
+            inner: self.inner.board(document).client().clone(),
+            core: Arc::clone(&self.core),
+        }
+    }
+
+    /// Blocking clients for every open PCB document; see
+    /// [`KiCadClient::open_boards`].
+    pub fn open_boards(&self) -> Result<Vec<KiCadClientBlocking>, KiCadError> {
+        Ok(self
+            .get_open_documents(DocumentType::Pcb)?
+            .iter()
+            .map(|document| self.board(document))
+            .collect())
+    }
+
+    /// Validates `editor`, syncs the enabled copper layers, and writes the
+    /// stackup; see [`stackup::apply`].
+    pub fn apply_stackup(&self, editor: &StackupEditor) -> Result<BoardStackup, KiCadError> {
+        let editor = editor.clone();
+        let client = self.inner.clone();
+        self.core.call(move |runtime| {
+            runtime.block_on(async move { stackup::apply(&client, &editor).await })
+        })
+    }
+
+    /// Blocking iterator over settled selection changes; see
+    /// [`KiCadClient::selection_stream`].
+    pub fn selection_stream(&self, poll_interval: Duration) -> BlockingSelectionStream {
+        BlockingSelectionStream {
+            stream: Some(self.inner.selection_stream(poll_interval)),
+            core: Arc::clone(&self.core),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) const MANUAL_BLOCKING_METHOD_NAMES: &'static [&'static str] = &[
         "connect",
@@ -502,9 +589,41 @@ impl KiCadClientBlocking {
         "inject_drc_error",
         "save_copy_of_document_raw",
         "save_copy_of_document",
+        "selection_stream",
+        "open_boards",
     ];
 }
 
+/// Blocking counterpart of [`SelectionStream`].
+///
+/// Each `next` call blocks until the selection settles on a new KIID set and
+/// never returns `None`; stop by dropping the iterator. Once an item is `Err`
+/// from a runtime failure, later calls keep returning
+/// [`KiCadError::BlockingRuntimeClosed`].
+#[derive(Debug)]
+pub struct BlockingSelectionStream {
+    stream: Option<SelectionStream>,
+    core: Arc<BlockingCore>,
+}
+
+impl Iterator for BlockingSelectionStream {
+    type Item = Result<Vec<PcbItem>, KiCadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(mut stream) = self.stream.take() else {
+            return Some(Err(KiCadError::BlockingRuntimeClosed));
+        };
+        let result = self.core.call(move |runtime| {
+            let result = runtime.block_on(stream.next());
+            Ok((stream, result))
+        });
+        Some(result.and_then(|(stream, result)| {
+            self.stream = Some(stream);
+            result
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,441 @@
+//! Bill of materials built from board footprints.
+//!
+//! Use [`KiCadClient::get_bom`](crate::client::KiCadClient::get_bom) to read the open board, or
+//! [`build_bom`] to group footprints you already have.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+use crate::model::board::{PcbFootprint, PcbItem};
+use crate::text_out::{write_csv_row, write_to_string};
+
+/// Field names carried by dedicated footprint properties rather than custom fields.
+const BUILTIN_FIELD_NAMES: [&str; 5] = [
+    "reference",
+    "value",
+    "footprint",
+    "datasheet",
+    "description",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Controls which footprints are listed and how rows are formed.
+pub struct BomOptions {
+    /// Include footprints flagged `exclude_from_bill_of_materials`.
+    pub include_excluded: bool,
+    /// Include footprints flagged `do_not_populate`.
+    pub include_do_not_populate: bool,
+    /// Merge footprints with identical value, footprint, datasheet, DNP flag, and fields.
+    pub group_identical: bool,
+    /// Custom fields to carry; empty means every custom field found on the board.
+    pub fields: Vec<String>,
+}
+
+impl Default for BomOptions {
+    fn default() -> Self {
+        Self {
+            include_excluded: false,
+            include_do_not_populate: false,
+            group_identical: true,
+            fields: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One BOM row: a single footprint, or a group of identical parts.
+pub struct BomEntry {
+    /// Designators, naturally sorted (`R2` before `R10`).
+    pub references: Vec<String>,
+    /// Value field text.
+    pub value: String,
+    /// Footprint library identifier (`nickname:entry`).
+    pub footprint: String,
+    /// Datasheet field text.
+    pub datasheet: String,
+    /// Whether the parts are marked do-not-populate.
+    pub do_not_populate: bool,
+    /// Custom field values keyed by field name.
+    pub fields: BTreeMap<String, String>,
+}
+
+impl BomEntry {
+    /// Number of parts in this row.
+    pub fn quantity(&self) -> usize {
+        self.references.len()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Bill of materials rows plus the custom field columns they carry.
+pub struct Bom {
+    /// Rows sorted by their first reference.
+    pub entries: Vec<BomEntry>,
+    /// Custom field column names, in output order.
+    pub field_names: Vec<String>,
+}
+
+impl Bom {
+    /// Total part count across all rows.
+    pub fn part_count(&self) -> usize {
+        self.entries.iter().map(BomEntry::quantity).sum()
+    }
+
+    /// Writes the BOM as RFC 4180 CSV with one row per entry.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut header = vec![
+            "Reference".to_string(),
+            "Quantity".to_string(),
+            "Value".to_string(),
+            "Footprint".to_string(),
+            "Datasheet".to_string(),
+            "DNP".to_string(),
+        ];
+        header.extend(self.field_names.iter().cloned());
+        write_csv_row(out, &header)?;
+
+        for entry in &self.entries {
+            let mut row = vec![
+                entry.references.join(","),
+                entry.quantity().to_string(),
+                entry.value.clone(),
+                entry.footprint.clone(),
+                entry.datasheet.clone(),
+                if entry.do_not_populate { "DNP" } else { "" }.to_string(),
+            ];
+            row.extend(
+                self.field_names
+                    .iter()
+                    .map(|name| entry.fields.get(name).cloned().unwrap_or_default()),
+            );
+            write_csv_row(out, &row)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the BOM as CSV text.
+    pub fn to_csv(&self) -> String {
+        write_to_string(|out| self.write_csv(out))
+    }
+
+    /// Writes the BOM as a JSON array of entry objects.
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "[")?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let references = entry
+                .references
+                .iter()
+                .map(|reference| json_string(reference))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let fields = entry
+                .fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_string(value)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(
+                out,
+                "  {{\"references\": [{references}], \"quantity\": {}, \"value\": {}, \"footprint\": {}, \"datasheet\": {}, \"do_not_populate\": {}, \"fields\": {{{fields}}}}}",
+                entry.quantity(),
+                json_string(&entry.value),
+                json_string(&entry.footprint),
+                json_string(&entry.datasheet),
+                entry.do_not_populate,
+            )?;
+            writeln!(
+                out,
+                "{}",
+                if index + 1 < self.entries.len() {
+                    ","
+                } else {
+                    ""
+                }
+            )?;
+        }
+        writeln!(out, "]")
+    }
+
+    /// Returns the BOM as JSON text.
+    pub fn to_json(&self) -> String {
+        write_to_string(|out| self.write_json(out))
+    }
+}
+
+/// Builds a BOM from decoded footprints according to `options`.
+pub fn build_bom<'a>(
+    footprints: impl IntoIterator<Item = &'a PcbFootprint>,
+    options: &BomOptions,
+) -> Bom {
+    let mut field_names = BTreeSet::new();
+    let mut rows = Vec::new();
+
+    for footprint in footprints {
+        let (excluded, do_not_populate) =
+            footprint.attributes.map_or((false, false), |attributes| {
+                (
+                    attributes.exclude_from_bill_of_materials,
+                    attributes.do_not_populate,
+                )
+            });
+        if excluded && !options.include_excluded {
+            continue;
+        }
+        if do_not_populate && !options.include_do_not_populate {
+            continue;
+        }
+
+        let fields: BTreeMap<String, String> = custom_fields(footprint)
+            .filter(|(name, _)| options.fields.is_empty() || options.fields.contains(name))
+            .collect();
+        field_names.extend(fields.keys().cloned());
+
+        rows.push(BomEntry {
+            references: vec![footprint.reference.clone().unwrap_or_default()],
+            value: footprint.value.clone().unwrap_or_default(),
+            footprint: footprint.library_id.clone().unwrap_or_default(),
+            datasheet: footprint.datasheet.clone().unwrap_or_default(),
+            do_not_populate,
+            fields,
+        });
+    }
+
+    let mut entries: Vec<BomEntry> = if options.group_identical {
+        let mut groups: Vec<BomEntry> = Vec::new();
+        for row in rows {
+            match groups.iter_mut().find(|group| same_part(group, &row)) {
+                Some(group) => group.references.extend(row.references),
+                None => groups.push(row),
+            }
+        }
+        groups
+    } else {
+        rows
+    };
+
+    for entry in &mut entries {
+        entry.references.sort_by(|a, b| natural_cmp(a, b));
+    }
+    entries.sort_by(|a, b| {
+        natural_cmp(
+            a.references.first().map(String::as_str).unwrap_or_default(),
+            b.references.first().map(String::as_str).unwrap_or_default(),
+        )
+    });
+
+    let field_names = if options.fields.is_empty() {
+        field_names.into_iter().collect()
+    } else {
+        options.fields.clone()
+    };
+
+    Bom {
+        entries,
+        field_names,
+    }
+}
+
+/// Compares designators so that embedded numbers sort numerically (`R2` < `R10`).
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut left = a.chars().peekable();
+    let mut right = b.chars().peekable();
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let take_number = |chars: &mut std::iter::Peekable<std::str::Chars<'_>>| {
+                    let mut digits = String::new();
+                    while let Some(c) = chars.peek().copied().filter(char::is_ascii_digit) {
+                        digits.push(c);
+                        chars.next();
+                    }
+                    digits
+                };
+                let l_digits = take_number(&mut left);
+                let r_digits = take_number(&mut right);
+                let l_trimmed = l_digits.trim_start_matches('0');
+                let r_trimmed = r_digits.trim_start_matches('0');
+                let ordering = l_trimmed
+                    .len()
+                    .cmp(&r_trimmed.len())
+                    .then_with(|| l_trimmed.cmp(r_trimmed))
+                    .then_with(|| l_digits.len().cmp(&r_digits.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(l), Some(r)) => {
+                let ordering = l.cmp(&r);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left.next();
+                right.next();
+            }
+        }
+    }
+}
+
+fn custom_fields(footprint: &PcbFootprint) -> impl Iterator<Item = (String, String)> + '_ {
+    footprint.items.iter().filter_map(|item| match item {
+        PcbItem::Field(field)
+            if !BUILTIN_FIELD_NAMES.contains(&field.name.to_ascii_lowercase().as_str()) =>
+        {
+            Some((field.name.clone(), field.text.clone().unwrap_or_default()))
+        }
+        _ => None,
+    })
+}
+
+fn same_part(a: &BomEntry, b: &BomEntry) -> bool {
+    a.value == b.value
+        && a.footprint == b.footprint
+        && a.datasheet == b.datasheet
+        && a.do_not_populate == b.do_not_populate
+        && a.fields == b.fields
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{build_bom, natural_cmp, BomOptions};
+    use crate::model::board::{
+        BoardLayerInfo, PcbField, PcbFootprint, PcbFootprintAttributes, PcbFootprintMountingStyle,
+        PcbItem,
+    };
+
+    fn footprint(reference: &str, value: &str, mpn: Option<&str>) -> PcbFootprint {
+        PcbFootprint {
+            id: Some(format!("id-{reference}")),
+            reference: Some(reference.to_string()),
+            position_nm: None,
+            orientation_deg: None,
+            layer: BoardLayerInfo {
+                id: 3,
+                name: "BL_F_Cu".to_string(),
+            },
+            pad_count: 2,
+            value: Some(value.to_string()),
+            library_id: Some("Resistor_SMD:R_0603_1608Metric".to_string()),
+            datasheet: None,
+            attributes: None,
+            items: mpn
+                .map(|mpn| {
+                    vec![PcbItem::Field(PcbField {
+                        name: "MPN".to_string(),
+                        visible: false,
+                        text: Some(mpn.to_string()),
+                    })]
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn with_attributes(mut footprint: PcbFootprint, excluded: bool, dnp: bool) -> PcbFootprint {
+        footprint.attributes = Some(PcbFootprintAttributes {
+            not_in_schematic: false,
+            exclude_from_position_files: false,
+            exclude_from_bill_of_materials: excluded,
+            exempt_from_courtyard_requirement: false,
+            do_not_populate: dnp,
+            mounting_style: PcbFootprintMountingStyle::Smd,
+        });
+        footprint
+    }
+
+    #[test]
+    fn natural_cmp_orders_embedded_numbers_numerically() {
+        assert_eq!(natural_cmp("R2", "R10"), Ordering::Less);
+        assert_eq!(natural_cmp("R10", "R9"), Ordering::Greater);
+        assert_eq!(natural_cmp("C1", "R1"), Ordering::Less);
+        assert_eq!(natural_cmp("U1A", "U1B"), Ordering::Less);
+        assert_eq!(natural_cmp("R01", "R1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn build_bom_groups_identical_parts_and_sorts_references() {
+        let footprints = [
+            footprint("R10", "10k", Some("RC0603-10K")),
+            footprint("R2", "10k", Some("RC0603-10K")),
+            footprint("R3", "1k", None),
+            footprint("C1", "100n", None),
+        ];
+
+        let bom = build_bom(&footprints, &BomOptions::default());
+        assert_eq!(bom.entries.len(), 3);
+        assert_eq!(bom.entries[0].references, vec!["C1".to_string()]);
+        assert_eq!(
+            bom.entries[1].references,
+            vec!["R2".to_string(), "R10".to_string()]
+        );
+        assert_eq!(bom.entries[1].quantity(), 2);
+        assert_eq!(bom.field_names, vec!["MPN".to_string()]);
+        assert_eq!(bom.part_count(), 4);
+    }
+
+    #[test]
+    fn build_bom_honours_exclusion_and_dnp_flags() {
+        let footprints = [
+            with_attributes(footprint("R1", "10k", None), true, false),
+            with_attributes(footprint("R2", "10k", None), false, true),
+            footprint("R3", "10k", None),
+        ];
+
+        let bom = build_bom(&footprints, &BomOptions::default());
+        assert_eq!(bom.part_count(), 1);
+
+        let bom = build_bom(
+            &footprints,
+            &BomOptions {
+                include_do_not_populate: true,
+                ..BomOptions::default()
+            },
+        );
+        assert_eq!(bom.entries.len(), 2);
+        assert!(bom.entries[0].do_not_populate);
+        assert_eq!(bom.entries[0].references, vec!["R2".to_string()]);
+    }
+
+    #[test]
+    fn bom_writers_quote_and_escape_values() {
+        let footprints = [
+            footprint("R1", "10k, 1%", Some("A\"B")),
+            footprint("R2", "10k, 1%", Some("A\"B")),
+        ];
+        let bom = build_bom(&footprints, &BomOptions::default());
+
+        assert_eq!(
+            bom.to_csv(),
+            "Reference,Quantity,Value,Footprint,Datasheet,DNP,MPN\n\
+             \"R1,R2\",2,\"10k, 1%\",Resistor_SMD:R_0603_1608Metric,,,\"A\"\"B\"\n"
+        );
+        assert_eq!(
+            bom.to_json(),
+            "[\n  {\"references\": [\"R1\", \"R2\"], \"quantity\": 2, \"value\": \"10k, 1%\", \
+             \"footprint\": \"Resistor_SMD:R_0603_1608Metric\", \"datasheet\": \"\", \
+             \"do_not_populate\": false, \"fields\": {\"MPN\": \"A\\\"B\"}}\n]\n"
+        );
+    }
+}
@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::arrange::{self, ArrangeOp, FootprintSelector};
+use crate::bom::{build_bom, Bom, BomOptions};
 use crate::envelope;
 use crate::error::KiCadError;
 use crate::model::board::{
@@ -10,27 +12,45 @@ use crate::model::board::{
     BoardLayerClass, BoardLayerGraphicsDefault, BoardLayerInfo, BoardNet, BoardOriginKind,
     BoardStackup, BoardStackupDielectricProperties, BoardStackupLayer, BoardStackupLayerType,
     ColorRgba, DrcSeverity, GraphicsDefaults, InactiveLayerDisplayMode, NetClassBoardSettings,
-    NetClassForNetEntry, NetClassInfo, NetClassType, NetColorDisplayMode, PadNetEntry,
-    PadShapeAsPolygonEntry, PadstackPresenceEntry, PadstackPresenceState, PcbArc,
-    PcbBoardGraphicShape, PcbBoardText, PcbBoardTextBox, PcbDimension, PcbField, PcbFootprint,
-    PcbGroup, PcbItem, PcbPad, PcbPadType, PcbTrack, PcbUnknownItem, PcbVia, PcbViaLayers,
-    PcbViaType, PcbZone, PcbZoneType, PolyLineNm, PolyLineNodeGeometryNm, PolygonWithHolesNm,
-    RatsnestDisplayMode, Vector2Nm,
+    NetClassForNetEntry, NetClassInfo, NetClassSchematicSettings, NetClassType,
+    NetColorDisplayMode, PadNetEntry, PadShapeAsPolygonEntry, PadstackPresenceEntry,
+    PadstackPresenceState, PcbArc, PcbBoardGraphicShape, PcbBoardText, PcbBoardTextBox,
+    PcbDimension, PcbDrill, PcbField, PcbFootprint, PcbFootprintAttributes,
+    PcbFootprintMountingStyle, PcbGroup, PcbItem, PcbPad, PcbPadStack, PcbPadStackCopperLayer,
+    PcbPadStackShape, PcbPadType, PcbTrack, PcbUnknownItem, PcbVia, PcbViaLayers, PcbViaType,
+    PcbZone, PcbZoneFilledPolygons, PcbZoneType, PolyLineNm, PolyLineNodeGeometryNm,
+    PolygonWithHolesNm, RatsnestDisplayMode, StrokeLineStyle, Vector2Nm,
 };
 use crate::model::common::{
-    CommitAction, CommitSession, DocumentSpecifier, DocumentType, EditorFrameType, ItemBoundingBox,
-    ItemHitTestResult, MapMergeMode, PcbObjectTypeCode, ProjectInfo, RunActionStatus,
-    SelectionItemDetail, SelectionSummary, SelectionTypeCount, TextAsShapesEntry,
-    TextAttributesSpec, TextBoxSpec, TextExtents, TextHorizontalAlignment, TextObjectSpec,
-    TextShape, TextShapeGeometry, TextSpec, TextVerticalAlignment, TitleBlockInfo, VersionInfo,
+    BoardSelector, CommitAction, CommitSession, DocumentSpecifier, DocumentType, EditorFrameType,
+    ItemBoundingBox, ItemHitTestResult, MapMergeMode, PcbObjectTypeCode, ProjectInfo,
+    RunActionStatus, SelectionItemDetail, SelectionSummary, SelectionTypeCount, SheetPath,
+    TextAsShapesEntry, TextAttributesSpec, TextBoxSpec, TextExtents, TextHorizontalAlignment,
+    TextObjectSpec, TextShape, TextShapeGeometry, TextSpec, TextVerticalAlignment, TitleBlockInfo,
+    VersionInfo,
 };
+use crate::model::schematic::{
+    SchematicItem, SchematicItemType, SchematicLabel, SchematicLine, SchematicUnknownItem,
+};
+use crate::outline::BoardOutline;
+use crate::placement::{build_placement, Placement, PlacementOptions};
 use crate::proto::kiapi::board as board_proto;
 use crate::proto::kiapi::board::commands as board_commands;
 use crate::proto::kiapi::board::types as board_types;
 use crate::proto::kiapi::common::commands as common_commands;
 use crate::proto::kiapi::common::project as common_project;
 use crate::proto::kiapi::common::types as common_types;
+use crate::proto::kiapi::schematic::types as schematic_types;
+use crate::query::{ItemQuery, QueryPlan};
+use crate::render::svg::{render_svg, SvgInput, SvgOptions};
+use crate::snapshot::BoardSnapshot;
+use crate::text_vars::{
+    check_references, is_builtin_variable, lone_variable_reference, previews, scan_text,
+    scan_type_codes, BuildMetadata, TextOccurrence, TextPreview, TextVariableReport,
+};
 use crate::transport::Transport;
+use crate::view::{ViewPreset, ViewPresets};
+use crate::watch::{BoardWatcher, SelectionStream};
 
 const KICAD_API_SOCKET_ENV: &str = "KICAD_API_SOCKET";
 const KICAD_API_TOKEN_ENV: &str = "KICAD_API_TOKEN";
@@ -218,6 +238,38 @@ const PCB_OBJECT_TYPES: [PcbObjectTypeCode; 18] = [
 /// to override socket path, timeout, token, or client name.
 pub struct KiCadClient {
     inner: Arc<ClientInner>,
+    board: Option<Arc<DocumentSpecifier>>,
+}
+
+#[derive(Clone, Debug)]
+/// Client scoped to one open PCB document.
+///
+/// Created with [`KiCadClient::board`]. Every board method reached through
+/// `Deref` targets this document instead of resolving the single open board,
+/// so several handles can read from different boards in one session.
+pub struct BoardHandle {
+    client: KiCadClient,
+    document: Arc<DocumentSpecifier>,
+}
+
+impl BoardHandle {
+    /// Document this handle targets.
+    pub fn document(&self) -> &DocumentSpecifier {
+        &self.document
+    }
+
+    /// Underlying client pinned to [`document`](Self::document).
+    pub fn client(&self) -> &KiCadClient {
+        &self.client
+    }
+}
+
+impl std::ops::Deref for BoardHandle {
+    type Target = KiCadClient;
+
+    fn deref(&self) -> &KiCadClient {
+        &self.client
+    }
 }
 
 #[derive(Debug)]
@@ -227,6 +279,8 @@ struct ClientInner {
     client_name: String,
     timeout: Duration,
     socket_uri: String,
+    /// Resolved board document, when document caching is enabled.
+    document_cache: Option<Mutex<Option<DocumentSpecifier>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -235,6 +289,7 @@ struct ClientConfig {
     socket_uri: Option<String>,
     token: Option<String>,
     client_name: Option<String>,
+    cache_documents: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -245,6 +300,7 @@ struct ClientConfig {
 /// - socket path: `KICAD_API_SOCKET` env var, then platform default
 /// - token: `KICAD_API_TOKEN` env var, then empty
 /// - client name: autogenerated
+/// - document cache: off
 pub struct ClientBuilder {
     config: ClientConfig,
 }
@@ -258,6 +314,7 @@ impl ClientBuilder {
                 socket_uri: None,
                 token: None,
                 client_name: None,
+                cache_documents: false,
             },
         }
     }
@@ -290,6 +347,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Caches the resolved open board instead of querying open documents
+    /// before every board command.
+    ///
+    /// The cache is shared by all clones of the client and is dropped on
+    /// document-related errors (`AS_BAD_REQUEST`, `IRS_DOCUMENT_NOT_FOUND`) or by
+    /// [`KiCadClient::refresh_documents`]. Enable it only when the open board
+    /// does not change behind the client's back, or refresh after it does.
+    pub fn cache_documents(mut self, enabled: bool) -> Self {
+        self.config.cache_documents = enabled;
+        self
+    }
+
     /// Connects to KiCad IPC with the configured options.
     ///
     /// # Errors
@@ -319,7 +388,9 @@ impl ClientBuilder {
                 client_name,
                 timeout,
                 socket_uri,
+                document_cache: self.config.cache_documents.then(|| Mutex::new(None)),
             }),
+            board: None,
         })
     }
 }
@@ -351,6 +422,16 @@ impl KiCadClient {
         &self.inner.socket_uri
     }
 
+    /// Drops the cached board document so the next board command resolves it
+    /// again; a no-op unless [`ClientBuilder::cache_documents`] is enabled.
+    pub fn refresh_documents(&self) {
+        if let Some(cache) = &self.inner.document_cache {
+            if let Ok(mut cached) = cache.lock() {
+                cached.take();
+            }
+        }
+    }
+
     /// Sends a health-check request to KiCad.
     pub async fn ping(&self) -> Result<(), KiCadError> {
         let command = envelope::pack_any(&common_commands::Ping {}, CMD_PING);
@@ -506,15 +587,42 @@ impl KiCadClient {
         Ok(classes)
     }
 
+    /// Sends net classes without reading them back.
+    ///
+    /// Via stacks are written from their [`PcbPadStack`] layers, shapes, sizes, offsets, drill,
+    /// and angle. When any class carries a via stack, this first reads the project's classes
+    /// (one extra `GetNetClasses` request) so padstack fields the model does not carry (solder
+    /// mask, drill fill and capping, unconnected layer removal) keep their current values.
+    /// Classes new to the project, or new padstacks on existing classes, get KiCad's defaults
+    /// for those fields.
     pub async fn set_net_classes_raw(
         &self,
         net_classes: Vec<NetClassInfo>,
         merge_mode: MapMergeMode,
     ) -> Result<prost_types::Any, KiCadError> {
+        let mut current = BTreeMap::new();
+        if net_classes.iter().any(|class| {
+            class
+                .board
+                .as_ref()
+                .is_some_and(|board| board.via_stack.is_some() || board.microvia_stack.is_some())
+        }) {
+            let payload = self.get_net_classes_raw().await?;
+            let response: common_commands::NetClassesResponse =
+                decode_any(&payload, RES_NET_CLASSES_RESPONSE)?;
+            current = response
+                .net_classes
+                .into_iter()
+                .map(|class| (class.name.clone(), class))
+                .collect();
+        }
         let command = common_commands::SetNetClasses {
             net_classes: net_classes
                 .into_iter()
-                .map(net_class_info_to_proto)
+                .map(|class| {
+                    let existing = current.remove(&class.name);
+                    net_class_info_to_proto(class, existing.as_ref())
+                })
                 .collect(),
             merge_mode: map_merge_mode_to_proto(merge_mode),
         };
@@ -525,6 +633,8 @@ impl KiCadClient {
     }
 
     /// Replaces or merges project net classes, then returns current classes.
+    ///
+    /// See [`KiCadClient::set_net_classes_raw`] for how via stacks are written.
     pub async fn set_net_classes(
         &self,
         net_classes: Vec<NetClassInfo>,
@@ -671,10 +781,51 @@ impl KiCadClient {
     ///
     /// Fails if no PCB is open or if multiple project paths are present.
     pub async fn get_current_project_path(&self) -> Result<PathBuf, KiCadError> {
-        let docs = self.get_open_documents(DocumentType::Pcb).await?;
+        let docs = match &self.board {
+            Some(board) => vec![board.as_ref().clone()],
+            None => self.get_open_documents(DocumentType::Pcb).await?,
+        };
         select_single_project_path(&docs)
     }
 
+    /// Returns a handle whose board methods target `document`.
+    pub fn board(&self, document: &DocumentSpecifier) -> BoardHandle {
+        let document = Arc::new(document.clone());
+        BoardHandle {
+            client: KiCadClient {
+                inner: Arc::clone(&self.inner),
+                board: Some(Arc::clone(&document)),
+            },
+            document,
+        }
+    }
+
+    /// Finds the single open PCB document matching `selector`.
+    ///
+    /// Fails with [`KiCadError::BoardNotOpen`] when nothing matches and
+    /// [`KiCadError::AmbiguousBoardSelection`] when several boards do.
+    pub async fn find_board(
+        &self,
+        selector: BoardSelector,
+    ) -> Result<DocumentSpecifier, KiCadError> {
+        let docs = self.get_open_documents(DocumentType::Pcb).await?;
+        let matching: Vec<DocumentSpecifier> = docs
+            .into_iter()
+            .filter(|doc| selector.matches(doc))
+            .collect();
+        select_single_board_document(&matching).cloned()
+    }
+
+    /// Returns a handle for every open PCB document.
+    pub async fn open_boards(&self) -> Result<Vec<BoardHandle>, KiCadError> {
+        Ok(self
+            .get_open_documents(DocumentType::Pcb)
+            .await?
+            .iter()
+            .map(|doc| self.board(doc))
+            .collect())
+    }
+
     /// Returns `true` when at least one PCB document is open in KiCad.
     pub async fn has_open_board(&self) -> Result<bool, KiCadError> {
         let docs = self.get_open_documents(DocumentType::Pcb).await?;
@@ -736,16 +887,8 @@ impl KiCadClient {
         items: Vec<prost_types::Any>,
         container_id: Option<String>,
     ) -> Result<prost_types::Any, KiCadError> {
-        let command = common_commands::CreateItems {
-            header: Some(self.current_board_item_header().await?),
-            items,
-            container: container_id.map(|value| common_types::Kiid { value }),
-        };
-
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_CREATE_ITEMS))
-            .await?;
-        response_payload_as_any(response, RES_CREATE_ITEMS_RESPONSE)
+        let header = self.current_board_item_header().await?;
+        self.create_items_in(header, items, container_id).await
     }
 
     /// Creates items in the active PCB document.
@@ -757,35 +900,15 @@ impl KiCadClient {
         container_id: Option<String>,
     ) -> Result<Vec<prost_types::Any>, KiCadError> {
         let payload = self.create_items_raw(items, container_id).await?;
-        let response: common_commands::CreateItemsResponse =
-            decode_any(&payload, RES_CREATE_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(response.status)?;
-
-        response
-            .created_items
-            .into_iter()
-            .map(|row| {
-                ensure_item_status_ok(row.status)?;
-                row.item.ok_or_else(|| KiCadError::InvalidResponse {
-                    reason: "CreateItemsResponse missing created item payload".to_string(),
-                })
-            })
-            .collect()
+        self.track_document_error(created_items(payload))
     }
 
     pub async fn update_items_raw(
         &self,
         items: Vec<prost_types::Any>,
     ) -> Result<prost_types::Any, KiCadError> {
-        let command = common_commands::UpdateItems {
-            header: Some(self.current_board_item_header().await?),
-            items,
-        };
-
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_UPDATE_ITEMS))
-            .await?;
-        response_payload_as_any(response, RES_UPDATE_ITEMS_RESPONSE)
+        let header = self.current_board_item_header().await?;
+        self.update_items_in(header, items).await
     }
 
     /// Updates existing items in the active PCB document.
@@ -796,38 +919,15 @@ impl KiCadClient {
         items: Vec<prost_types::Any>,
     ) -> Result<Vec<prost_types::Any>, KiCadError> {
         let payload = self.update_items_raw(items).await?;
-        let response: common_commands::UpdateItemsResponse =
-            decode_any(&payload, RES_UPDATE_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(response.status)?;
-
-        response
-            .updated_items
-            .into_iter()
-            .map(|row| {
-                ensure_item_status_ok(row.status)?;
-                row.item.ok_or_else(|| KiCadError::InvalidResponse {
-                    reason: "UpdateItemsResponse missing updated item payload".to_string(),
-                })
-            })
-            .collect()
+        self.track_document_error(updated_items(payload))
     }
 
     pub async fn delete_items_raw(
         &self,
         item_ids: Vec<String>,
     ) -> Result<prost_types::Any, KiCadError> {
-        let command = common_commands::DeleteItems {
-            header: Some(self.current_board_item_header().await?),
-            item_ids: item_ids
-                .into_iter()
-                .map(|value| common_types::Kiid { value })
-                .collect(),
-        };
-
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_DELETE_ITEMS))
-            .await?;
-        response_payload_as_any(response, RES_DELETE_ITEMS_RESPONSE)
+        let header = self.current_board_item_header().await?;
+        self.delete_items_in(header, item_ids).await
     }
 
     /// Deletes items by id from the active PCB document.
@@ -835,22 +935,7 @@ impl KiCadClient {
     /// Returns ids of items deleted by KiCad.
     pub async fn delete_items(&self, item_ids: Vec<String>) -> Result<Vec<String>, KiCadError> {
         let payload = self.delete_items_raw(item_ids).await?;
-        let response: common_commands::DeleteItemsResponse =
-            decode_any(&payload, RES_DELETE_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(response.status)?;
-
-        response
-            .deleted_items
-            .into_iter()
-            .map(|row| {
-                ensure_item_deletion_status_ok(row.status)?;
-                row.id
-                    .map(|id| id.value)
-                    .ok_or_else(|| KiCadError::InvalidResponse {
-                        reason: "DeleteItemsResponse missing deleted item id".to_string(),
-                    })
-            })
-            .collect()
+        self.track_document_error(deleted_item_ids(payload))
     }
 
     pub async fn parse_and_create_items_from_string_raw(
@@ -880,7 +965,7 @@ impl KiCadClient {
             .await?;
         let response: common_commands::CreateItemsResponse =
             decode_any(&payload, RES_CREATE_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(response.status)?;
+        self.track_document_error(ensure_item_request_ok(response.status))?;
 
         response
             .created_items
@@ -1063,19 +1148,8 @@ impl KiCadClient {
     }
 
     pub async fn get_selection_raw(&self) -> Result<Vec<prost_types::Any>, KiCadError> {
-        let command = common_commands::GetSelection {
-            header: Some(self.current_board_item_header().await?),
-            types: Vec::new(),
-        };
-
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_GET_SELECTION))
-            .await?;
-
-        let payload: common_commands::SelectionResponse =
-            envelope::unpack_any(&response, RES_SELECTION_RESPONSE)?;
-
-        Ok(payload.items)
+        let header = self.current_board_item_header().await?;
+        self.get_selection_in(header).await
     }
 
     pub async fn get_selection_details(&self) -> Result<Vec<SelectionItemDetail>, KiCadError> {
@@ -1257,6 +1331,286 @@ impl KiCadClient {
         decode_pcb_items(items)
     }
 
+    /// Watches the open board by polling every `interval`.
+    ///
+    /// See [`BoardWatcher`] for the probe order and event semantics.
+    pub fn watch_board(&self, interval: Duration) -> BoardWatcher {
+        BoardWatcher::new(self.clone(), interval)
+    }
+
+    /// Streams settled selection changes, polling every `poll_interval`.
+    ///
+    /// See [`SelectionStream`] for debouncing and `AS_BUSY` handling.
+    pub fn selection_stream(&self, poll_interval: Duration) -> SelectionStream {
+        SelectionStream::new(self.clone(), poll_interval)
+    }
+
+    /// Runs `query`: fetches with the most selective server-side command
+    /// (see [`ItemQuery::plan`]) and applies the remaining filters locally.
+    pub async fn query_items(&self, query: ItemQuery) -> Result<Vec<PcbItem>, KiCadError> {
+        let type_codes = query.type_codes();
+        let payloads = match query.plan() {
+            QueryPlan::ByNet => {
+                let mut nets: Vec<BoardNet> = self
+                    .get_nets()
+                    .await?
+                    .into_iter()
+                    .filter(|net| query.nets().contains(&net.name))
+                    .collect();
+                if !query.net_classes().is_empty() && !nets.is_empty() {
+                    let classes = self.get_netclass_for_nets(nets.clone()).await?;
+                    nets.retain(|net| {
+                        classes.iter().any(|entry| {
+                            entry.net_name == net.name
+                                && query.net_classes().contains(&entry.net_class.name)
+                        })
+                    });
+                }
+                if nets.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let net_codes = nets.iter().map(|net| net.code).collect();
+                self.get_items_by_net_raw(type_codes, net_codes).await?
+            }
+            QueryPlan::ByNetClass => {
+                let net_classes = query.net_classes().iter().cloned().collect();
+                self.get_items_by_net_class_raw(type_codes, net_classes)
+                    .await?
+            }
+            QueryPlan::ByType => self.get_items_raw(type_codes).await?,
+        };
+
+        let mut items = Vec::new();
+        for payload in payloads {
+            let locked = if query.filters_locked() {
+                pcb_item_locked(&payload)?
+            } else {
+                None
+            };
+            let item = decode_pcb_item(payload)?;
+            if query.matches(&item, locked) {
+                items.push(item);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Adds every item matched by `query` to the editor selection.
+    pub async fn add_query_to_selection(
+        &self,
+        query: ItemQuery,
+    ) -> Result<SelectionSummary, KiCadError> {
+        let item_ids: Vec<String> = self
+            .query_items(query)
+            .await?
+            .iter()
+            .filter_map(PcbItem::id)
+            .map(str::to_string)
+            .collect();
+        if item_ids.is_empty() {
+            return Ok(summarize_selection(Vec::new()));
+        }
+        self.add_to_selection(item_ids).await
+    }
+
+    /// Builds a bill of materials from the footprints on the open board.
+    pub async fn get_bom(&self, options: BomOptions) -> Result<Bom, KiCadError> {
+        let footprints = self.get_footprints().await?;
+        Ok(build_bom(&footprints, &options))
+    }
+
+    /// Builds a pick-and-place file relative to the board's drill/place origin.
+    pub async fn export_placement(
+        &self,
+        options: PlacementOptions,
+    ) -> Result<Placement, KiCadError> {
+        let origin = self.get_board_origin(BoardOriginKind::Drill).await?;
+        let footprints = self.get_footprints().await?;
+        Ok(build_placement(&footprints, origin, &options))
+    }
+
+    /// Checks `${...}` references in board text, footprint fields, dimensions, and the title
+    /// block against the project's text variables.
+    pub async fn get_text_variable_report(&self) -> Result<TextVariableReport, KiCadError> {
+        let items = self.get_items_by_type_codes(scan_type_codes()).await?;
+        let title_block = self.get_title_block_info().await?;
+        let variables = self.get_text_variables().await?;
+        Ok(check_references(
+            scan_text(&items, Some(&title_block)),
+            &variables,
+        ))
+    }
+
+    /// Expands each occurrence's text through KiCad in a single request.
+    pub async fn preview_text_expansions(
+        &self,
+        occurrences: Vec<TextOccurrence>,
+    ) -> Result<Vec<TextPreview>, KiCadError> {
+        if occurrences.is_empty() {
+            return Ok(Vec::new());
+        }
+        let expanded = self
+            .expand_text_variables(
+                occurrences
+                    .iter()
+                    .map(|occurrence| occurrence.text.clone())
+                    .collect(),
+            )
+            .await?;
+        if expanded.len() != occurrences.len() {
+            return Err(KiCadError::InvalidResponse {
+                reason: format!(
+                    "expected {} expanded strings, got {}",
+                    occurrences.len(),
+                    expanded.len()
+                ),
+            });
+        }
+        Ok(previews(occurrences, expanded))
+    }
+
+    /// Merges build metadata into the project's text variables and returns the result.
+    pub async fn stamp_build_metadata(
+        &self,
+        metadata: BuildMetadata,
+    ) -> Result<BTreeMap<String, String>, KiCadError> {
+        self.set_text_variables(metadata.variables(), MapMergeMode::Merge)
+            .await
+    }
+
+    /// Captures the open board and renders it as a standalone SVG document.
+    pub async fn render_board_svg(&self, options: SvgOptions) -> Result<String, KiCadError> {
+        let input = SvgInput::capture(self).await?;
+        Ok(render_svg(&input, &options))
+    }
+
+    /// Captures items, nets, layers, stackup, net classes, origins, and title block in one call.
+    pub async fn get_board_snapshot(&self) -> Result<BoardSnapshot, KiCadError> {
+        BoardSnapshot::capture(self).await
+    }
+
+    /// Fetches raw schematic items of the given object type codes from `document`.
+    ///
+    /// `document` must be a [`DocumentType::Schematic`] specifier from
+    /// [`get_open_documents`](Self::get_open_documents), optionally narrowed
+    /// with [`DocumentSpecifier::with_sheet_path`].
+    pub async fn get_schematic_items_raw(
+        &self,
+        document: DocumentSpecifier,
+        type_codes: Vec<i32>,
+    ) -> Result<Vec<prost_types::Any>, KiCadError> {
+        let header = schematic_item_header(&document)?;
+        self.get_items_in(header, type_codes).await
+    }
+
+    /// Fetches and decodes schematic items; an empty `types` fetches every typed kind.
+    pub async fn get_schematic_items(
+        &self,
+        document: DocumentSpecifier,
+        types: Vec<SchematicItemType>,
+    ) -> Result<Vec<SchematicItem>, KiCadError> {
+        let types = if types.is_empty() {
+            SchematicItemType::ALL.to_vec()
+        } else {
+            types
+        };
+        let type_codes = types
+            .into_iter()
+            .map(SchematicItemType::type_code)
+            .collect();
+        let items = self.get_schematic_items_raw(document, type_codes).await?;
+        decode_schematic_items(items)
+    }
+
+    /// Fetches and decodes schematic items by KIID.
+    pub async fn get_schematic_items_by_id(
+        &self,
+        document: DocumentSpecifier,
+        item_ids: Vec<String>,
+    ) -> Result<Vec<SchematicItem>, KiCadError> {
+        if item_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let header = schematic_item_header(&document)?;
+        let items = self.get_items_by_id_in(header, item_ids).await?;
+        decode_schematic_items(items)
+    }
+
+    /// Returns the schematic editor selection as decoded items.
+    pub async fn get_schematic_selection(
+        &self,
+        document: DocumentSpecifier,
+    ) -> Result<Vec<SchematicItem>, KiCadError> {
+        let header = schematic_item_header(&document)?;
+        let items = self.get_selection_in(header).await?;
+        decode_schematic_items(items)
+    }
+
+    /// Creates items in a schematic document and returns them as created.
+    pub async fn create_schematic_items(
+        &self,
+        document: DocumentSpecifier,
+        items: Vec<SchematicItem>,
+        container_id: Option<String>,
+    ) -> Result<Vec<SchematicItem>, KiCadError> {
+        let header = schematic_item_header(&document)?;
+        let items = items
+            .into_iter()
+            .map(schematic_item_to_any)
+            .collect::<Result<Vec<_>, _>>()?;
+        let payload = self.create_items_in(header, items, container_id).await?;
+        decode_schematic_items(created_items(payload)?)
+    }
+
+    /// Updates existing schematic items, matched by KIID, and returns them as stored.
+    pub async fn update_schematic_items(
+        &self,
+        document: DocumentSpecifier,
+        items: Vec<SchematicItem>,
+    ) -> Result<Vec<SchematicItem>, KiCadError> {
+        let header = schematic_item_header(&document)?;
+        let items = items
+            .into_iter()
+            .map(schematic_item_to_any)
+            .collect::<Result<Vec<_>, _>>()?;
+        let payload = self.update_items_in(header, items).await?;
+        decode_schematic_items(updated_items(payload)?)
+    }
+
+    /// Deletes schematic items by KIID and returns the ids KiCad deleted.
+    pub async fn delete_schematic_items(
+        &self,
+        document: DocumentSpecifier,
+        item_ids: Vec<String>,
+    ) -> Result<Vec<String>, KiCadError> {
+        let header = schematic_item_header(&document)?;
+        let payload = self.delete_items_in(header, item_ids).await?;
+        deleted_item_ids(payload)
+    }
+
+    /// Reads the title block of a schematic document.
+    pub async fn get_schematic_title_block_info(
+        &self,
+        document: DocumentSpecifier,
+    ) -> Result<TitleBlockInfo, KiCadError> {
+        schematic_item_header(&document)?;
+        self.get_title_block_info_in(model_document_to_proto(&document))
+            .await
+    }
+
+    async fn get_footprints(&self) -> Result<Vec<PcbFootprint>, KiCadError> {
+        let items = self
+            .get_items_raw(vec![common_types::KiCadObjectType::KotPcbFootprint as i32])
+            .await?;
+        Ok(decode_pcb_items(items)?
+            .into_iter()
+            .filter_map(|item| match item {
+                PcbItem::Footprint(footprint) => Some(footprint),
+                _ => None,
+            })
+            .collect())
+    }
+
     pub async fn get_all_pcb_items_raw(
         &self,
     ) -> Result<Vec<(PcbObjectTypeCode, Vec<prost_types::Any>)>, KiCadError> {
@@ -1313,7 +1667,7 @@ impl KiCadClient {
             .await?;
         let payload: common_commands::GetItemsResponse =
             envelope::unpack_any(&response, RES_GET_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(payload.status)?;
+        self.track_document_error(ensure_item_request_ok(payload.status))?;
         Ok(payload.items)
     }
 
@@ -1342,7 +1696,7 @@ impl KiCadClient {
             .await?;
         let payload: common_commands::GetItemsResponse =
             envelope::unpack_any(&response, RES_GET_ITEMS_RESPONSE)?;
-        ensure_item_request_ok(payload.status)?;
+        self.track_document_error(ensure_item_request_ok(payload.status))?;
         Ok(payload.items)
     }
 
@@ -1730,40 +2084,153 @@ impl KiCadClient {
 
     /// Reads title block metadata from the active PCB document.
     pub async fn get_title_block_info(&self) -> Result<TitleBlockInfo, KiCadError> {
-        let command = common_commands::GetTitleBlockInfo {
-            document: Some(self.current_board_document_proto().await?),
+        let document = self.current_board_document_proto().await?;
+        self.get_title_block_info_in(document).await
+    }
+
+    /// Writes title block fields through the project text variables they are bound to.
+    ///
+    /// The IPC API has no title block setter, and the title block is not a board item, so
+    /// `parse_and_create_items_from_string` cannot replace it either. Bind each field you want
+    /// to edit to a project variable once in Page Settings (for example Revision =
+    /// `${RELEASE_REV}`, or `${BUILD_REVISION}` to pair with
+    /// [`KiCadClient::stamp_build_metadata`]); this method then sets those variables.
+    ///
+    /// Fields that already hold the desired text are skipped. Any other changed field that is
+    /// not a lone `${NAME}` reference to a non-built-in variable fails with
+    /// [`KiCadError::Config`] before anything is written. Returns the variables written.
+    pub async fn set_title_block_info(
+        &self,
+        info: TitleBlockInfo,
+    ) -> Result<BTreeMap<String, String>, KiCadError> {
+        let current = self.get_title_block_info().await?;
+        let updates = title_block_variable_updates(&current, &info)?;
+        if !updates.is_empty() {
+            self.set_text_variables(updates.clone(), MapMergeMode::Merge)
+                .await?;
+        }
+        Ok(updates)
+    }
+
+    /// Applies `ops` in order to the footprints picked by `selectors` and sends the result as
+    /// one `update_items` call inside a single commit, which is dropped if the update fails.
+    ///
+    /// Returns the footprints as stored by KiCad. See [`crate::arrange`] for how children and
+    /// layers follow each operation.
+    pub async fn arrange_footprints(
+        &self,
+        selectors: Vec<FootprintSelector>,
+        ops: Vec<ArrangeOp>,
+    ) -> Result<Vec<PcbFootprint>, KiCadError> {
+        if selectors.is_empty() || ops.is_empty() {
+            return Ok(Vec::new());
+        }
+        let footprints = self
+            .get_items_raw(vec![common_types::KiCadObjectType::KotPcbFootprint as i32])
+            .await?
+            .iter()
+            .map(|item| {
+                decode_any::<board_types::FootprintInstance>(
+                    item,
+                    "kiapi.board.types.FootprintInstance",
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut selected = arrange::select_footprints(footprints, &selectors)?;
+        let copper_layer_count = if ops.contains(&ArrangeOp::Flip) {
+            self.get_board_enabled_layers().await?.copper_layer_count
+        } else {
+            0
+        };
+        arrange::arrange(&mut selected, &ops, copper_layer_count)?;
+
+        let items = selected.iter().map(arrange::footprint_to_any).collect();
+        let session = self.begin_commit().await?;
+        let updated = match self.update_items(items).await {
+            Ok(updated) => updated,
+            Err(err) => {
+                let _ = self
+                    .end_commit(session, CommitAction::Drop, "Arrange footprints")
+                    .await;
+                return Err(err);
+            }
         };
+        self.end_commit(session, CommitAction::Commit, "Arrange footprints")
+            .await?;
+        Ok(decode_pcb_items(updated)?
+            .into_iter()
+            .filter_map(|item| match item {
+                PcbItem::Footprint(footprint) => Some(footprint),
+                _ => None,
+            })
+            .collect())
+    }
 
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_GET_TITLE_BLOCK_INFO))
+    /// Builds the board outline from Edge.Cuts shapes, including footprint graphics.
+    ///
+    /// See [`BoardOutline`] for chaining rules; gaps and crossings are reported as
+    /// [`KiCadError::BoardOutline`].
+    pub async fn get_board_outline(&self) -> Result<BoardOutline, KiCadError> {
+        let items = self
+            .get_items_by_type_codes(vec![
+                common_types::KiCadObjectType::KotPcbShape as i32,
+                common_types::KiCadObjectType::KotPcbFootprint as i32,
+            ])
             .await?;
-        let payload: common_types::TitleBlockInfo =
-            envelope::unpack_any(&response, RES_TITLE_BLOCK_INFO)?;
+        BoardOutline::from_items(&items)
+    }
 
-        let comments = vec![
-            payload.comment1,
-            payload.comment2,
-            payload.comment3,
-            payload.comment4,
-            payload.comment5,
-            payload.comment6,
-            payload.comment7,
-            payload.comment8,
-            payload.comment9,
-        ]
-        .into_iter()
-        .filter(|comment| !comment.is_empty())
-        .collect();
-
-        Ok(TitleBlockInfo {
-            title: payload.title,
-            date: payload.date,
-            revision: payload.revision,
-            company: payload.company,
-            comments,
+    /// Captures the visible layers, active layer, and appearance settings as a named preset.
+    pub async fn capture_view_preset(&self, name: String) -> Result<ViewPreset, KiCadError> {
+        Ok(ViewPreset {
+            name,
+            visible_layers: self.get_visible_layers().await?,
+            active_layer: self.get_active_layer().await?,
+            appearance: self.get_board_editor_appearance_settings().await?,
         })
     }
 
+    /// Restores the visible layers, active layer, and appearance settings of `preset`.
+    ///
+    /// KiCad has no transaction for view state, so the current view is captured first; if any
+    /// step fails, that view is put back (best effort) and the original error is returned.
+    pub async fn apply_view_preset(&self, preset: ViewPreset) -> Result<(), KiCadError> {
+        let previous = self.capture_view_preset(String::new()).await?;
+        if let Err(err) = self.set_view(&preset).await {
+            let _ = self.set_view(&previous).await;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn set_view(&self, preset: &ViewPreset) -> Result<(), KiCadError> {
+        self.set_visible_layers(preset.visible_layer_ids()).await?;
+        self.set_active_layer(preset.active_layer.id).await?;
+        self.set_board_editor_appearance_settings(preset.appearance.clone())
+            .await?;
+        Ok(())
+    }
+
+    /// Loads the view presets kept in the plugin settings directory for `identifier`.
+    ///
+    /// Returns an empty set when nothing has been saved yet.
+    pub async fn load_view_presets(&self, identifier: String) -> Result<ViewPresets, KiCadError> {
+        let dir = self.get_plugin_settings_path(identifier).await?;
+        ViewPresets::load(ViewPresets::path_in(dir))
+    }
+
+    /// Saves `presets` in the plugin settings directory for `identifier` and returns the file
+    /// path.
+    pub async fn save_view_presets(
+        &self,
+        identifier: String,
+        presets: ViewPresets,
+    ) -> Result<PathBuf, KiCadError> {
+        let path = ViewPresets::path_in(self.get_plugin_settings_path(identifier).await?);
+        presets.save(&path)?;
+        Ok(path)
+    }
+
     pub async fn save_document_raw(&self) -> Result<prost_types::Any, KiCadError> {
         let command = common_commands::SaveDocument {
             document: Some(self.current_board_document_proto().await?),
@@ -1863,23 +2330,8 @@ impl KiCadClient {
             return Ok(Vec::new());
         }
 
-        let command = common_commands::GetItemsById {
-            header: Some(self.current_board_item_header().await?),
-            items: item_ids
-                .into_iter()
-                .map(|id| common_types::Kiid { value: id })
-                .collect(),
-        };
-
-        let response = self
-            .send_command(envelope::pack_any(&command, CMD_GET_ITEMS_BY_ID))
-            .await?;
-
-        let payload: common_commands::GetItemsResponse =
-            envelope::unpack_any(&response, RES_GET_ITEMS_RESPONSE)?;
-
-        ensure_item_request_ok(payload.status)?;
-        Ok(payload.items)
+        let header = self.current_board_item_header().await?;
+        self.get_items_by_id_in(header, item_ids).await
     }
 
     pub async fn get_items_by_id_details(
@@ -1973,7 +2425,7 @@ impl KiCadClient {
         let response = envelope::decode_response(&response_bytes)?;
 
         if let Some(err) = envelope::status_error(&response) {
-            return Err(err);
+            return self.track_document_error(Err(err));
         }
 
         if token.is_empty() {
@@ -1995,11 +2447,34 @@ impl KiCadClient {
     async fn current_board_document_proto(
         &self,
     ) -> Result<common_types::DocumentSpecifier, KiCadError> {
+        if let Some(board) = &self.board {
+            return Ok(model_document_to_proto(board));
+        }
+        if let Some(cache) = &self.inner.document_cache {
+            let cached = cache.lock().map_err(|_| KiCadError::InternalPoisoned)?;
+            if let Some(document) = cached.as_ref() {
+                return Ok(model_document_to_proto(document));
+            }
+        }
+
         let docs = self.get_open_documents(DocumentType::Pcb).await?;
         let selected = select_single_board_document(&docs)?;
+        if let Some(cache) = &self.inner.document_cache {
+            *cache.lock().map_err(|_| KiCadError::InternalPoisoned)? = Some(selected.clone());
+        }
         Ok(model_document_to_proto(selected))
     }
 
+    /// Passes `result` through, dropping the document cache on document-related errors.
+    fn track_document_error<T>(&self, result: Result<T, KiCadError>) -> Result<T, KiCadError> {
+        if let Err(err) = &result {
+            if is_document_error(err) {
+                self.refresh_documents();
+            }
+        }
+        result
+    }
+
     async fn current_board_item_header(&self) -> Result<common_types::ItemHeader, KiCadError> {
         Ok(common_types::ItemHeader {
             document: Some(self.current_board_document_proto().await?),
@@ -2009,8 +2484,17 @@ impl KiCadClient {
     }
 
     async fn get_items_raw(&self, types: Vec<i32>) -> Result<Vec<prost_types::Any>, KiCadError> {
+        let header = self.current_board_item_header().await?;
+        self.get_items_in(header, types).await
+    }
+
+    async fn get_items_in(
+        &self,
+        header: common_types::ItemHeader,
+        types: Vec<i32>,
+    ) -> Result<Vec<prost_types::Any>, KiCadError> {
         let command = common_commands::GetItems {
-            header: Some(self.current_board_item_header().await?),
+            header: Some(header),
             types,
         };
 
@@ -2021,18 +2505,191 @@ impl KiCadClient {
         let payload: common_commands::GetItemsResponse =
             envelope::unpack_any(&response, RES_GET_ITEMS_RESPONSE)?;
 
-        ensure_item_request_ok(payload.status)?;
+        self.track_document_error(ensure_item_request_ok(payload.status))?;
+        Ok(payload.items)
+    }
+
+    async fn get_items_by_id_in(
+        &self,
+        header: common_types::ItemHeader,
+        item_ids: Vec<String>,
+    ) -> Result<Vec<prost_types::Any>, KiCadError> {
+        let command = common_commands::GetItemsById {
+            header: Some(header),
+            items: item_ids
+                .into_iter()
+                .map(|id| common_types::Kiid { value: id })
+                .collect(),
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_GET_ITEMS_BY_ID))
+            .await?;
+
+        let payload: common_commands::GetItemsResponse =
+            envelope::unpack_any(&response, RES_GET_ITEMS_RESPONSE)?;
+
+        self.track_document_error(ensure_item_request_ok(payload.status))?;
+        Ok(payload.items)
+    }
+
+    async fn get_selection_in(
+        &self,
+        header: common_types::ItemHeader,
+    ) -> Result<Vec<prost_types::Any>, KiCadError> {
+        let command = common_commands::GetSelection {
+            header: Some(header),
+            types: Vec::new(),
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_GET_SELECTION))
+            .await?;
+
+        let payload: common_commands::SelectionResponse =
+            envelope::unpack_any(&response, RES_SELECTION_RESPONSE)?;
+
         Ok(payload.items)
     }
+
+    async fn create_items_in(
+        &self,
+        header: common_types::ItemHeader,
+        items: Vec<prost_types::Any>,
+        container_id: Option<String>,
+    ) -> Result<prost_types::Any, KiCadError> {
+        let command = common_commands::CreateItems {
+            header: Some(header),
+            items,
+            container: container_id.map(|value| common_types::Kiid { value }),
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_CREATE_ITEMS))
+            .await?;
+        response_payload_as_any(response, RES_CREATE_ITEMS_RESPONSE)
+    }
+
+    async fn update_items_in(
+        &self,
+        header: common_types::ItemHeader,
+        items: Vec<prost_types::Any>,
+    ) -> Result<prost_types::Any, KiCadError> {
+        let command = common_commands::UpdateItems {
+            header: Some(header),
+            items,
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_UPDATE_ITEMS))
+            .await?;
+        response_payload_as_any(response, RES_UPDATE_ITEMS_RESPONSE)
+    }
+
+    async fn delete_items_in(
+        &self,
+        header: common_types::ItemHeader,
+        item_ids: Vec<String>,
+    ) -> Result<prost_types::Any, KiCadError> {
+        let command = common_commands::DeleteItems {
+            header: Some(header),
+            item_ids: item_ids
+                .into_iter()
+                .map(|value| common_types::Kiid { value })
+                .collect(),
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_DELETE_ITEMS))
+            .await?;
+        response_payload_as_any(response, RES_DELETE_ITEMS_RESPONSE)
+    }
+
+    async fn get_title_block_info_in(
+        &self,
+        document: common_types::DocumentSpecifier,
+    ) -> Result<TitleBlockInfo, KiCadError> {
+        let command = common_commands::GetTitleBlockInfo {
+            document: Some(document),
+        };
+
+        let response = self
+            .send_command(envelope::pack_any(&command, CMD_GET_TITLE_BLOCK_INFO))
+            .await?;
+        let payload: common_types::TitleBlockInfo =
+            envelope::unpack_any(&response, RES_TITLE_BLOCK_INFO)?;
+
+        let comments = [
+            payload.comment1,
+            payload.comment2,
+            payload.comment3,
+            payload.comment4,
+            payload.comment5,
+            payload.comment6,
+            payload.comment7,
+            payload.comment8,
+            payload.comment9,
+        ];
+
+        Ok(TitleBlockInfo {
+            title: payload.title,
+            date: payload.date,
+            revision: payload.revision,
+            company: payload.company,
+            comments,
+        })
+    }
+}
+
+fn title_block_variable_updates(
+    current: &TitleBlockInfo,
+    desired: &TitleBlockInfo,
+) -> Result<BTreeMap<String, String>, KiCadError> {
+    let mut updates: BTreeMap<String, String> = BTreeMap::new();
+    for (field, existing) in current.fields() {
+        let wanted = desired.field(field);
+        if wanted == existing {
+            continue;
+        }
+        let name = lone_variable_reference(existing)
+            .filter(|name| !is_builtin_variable(name))
+            .ok_or_else(|| KiCadError::Config {
+                reason: format!(
+                    "title block {field} is `{existing}`, not bound to a text variable; \
+                     set it to `${{NAME}}` in Page Settings to edit it through the API"
+                ),
+            })?;
+        match updates.get(name) {
+            Some(staged) if staged != wanted => {
+                return Err(KiCadError::Config {
+                    reason: format!(
+                        "title block fields bound to `${{{name}}}` need different values \
+                         (`{staged}` and `{wanted}`)"
+                    ),
+                })
+            }
+            _ => {
+                updates.insert(name.to_string(), wanted.to_string());
+            }
+        }
+    }
+    Ok(updates)
 }
 
 fn map_document_specifier(source: common_types::DocumentSpecifier) -> Option<DocumentSpecifier> {
     let document_type = DocumentType::from_proto(source.r#type)?;
-    let board_filename = match source.identifier {
+    let (board_filename, sheet_path) = match source.identifier {
         Some(common_types::document_specifier::Identifier::BoardFilename(filename)) => {
-            Some(filename)
+            (Some(filename), None)
         }
-        _ => None,
+        Some(common_types::document_specifier::Identifier::SheetPath(path)) => (
+            None,
+            Some(SheetPath {
+                path: path.path.into_iter().map(|id| id.value).collect(),
+                human_readable: path.path_human_readable,
+            }),
+        ),
+        _ => (None, None),
     };
 
     let project = source.project.unwrap_or_default();
@@ -2053,14 +2710,30 @@ fn map_document_specifier(source: common_types::DocumentSpecifier) -> Option<Doc
     Some(DocumentSpecifier {
         document_type,
         board_filename,
+        sheet_path,
         project: project_info,
     })
 }
 
 fn model_document_to_proto(document: &DocumentSpecifier) -> common_types::DocumentSpecifier {
-    let identifier = document.board_filename.as_ref().map(|filename| {
-        common_types::document_specifier::Identifier::BoardFilename(filename.clone())
-    });
+    let identifier = match (&document.board_filename, &document.sheet_path) {
+        (Some(filename), _) => Some(common_types::document_specifier::Identifier::BoardFilename(
+            filename.clone(),
+        )),
+        (None, Some(sheet_path)) => Some(common_types::document_specifier::Identifier::SheetPath(
+            common_types::SheetPath {
+                path: sheet_path
+                    .path
+                    .iter()
+                    .map(|value| common_types::Kiid {
+                        value: value.clone(),
+                    })
+                    .collect(),
+                path_human_readable: sheet_path.human_readable.clone(),
+            },
+        )),
+        (None, None) => None,
+    };
 
     let project = common_types::ProjectSpecifier {
         name: document.project.name.clone().unwrap_or_default(),
@@ -2320,7 +2993,7 @@ fn map_text_with_shapes(
     Ok(TextAsShapesEntry { source, shapes })
 }
 
-fn layer_to_model(layer_id: i32) -> BoardLayerInfo {
+pub(crate) fn layer_to_model(layer_id: i32) -> BoardLayerInfo {
     let name = board_types::BoardLayer::try_from(layer_id)
         .map(|layer| layer.as_str_name().to_string())
         .unwrap_or_else(|_| format!("UNKNOWN_LAYER({layer_id})"));
@@ -2371,6 +3044,59 @@ fn map_merge_mode_to_proto(value: MapMergeMode) -> i32 {
     }
 }
 
+fn created_items(payload: prost_types::Any) -> Result<Vec<prost_types::Any>, KiCadError> {
+    let response: common_commands::CreateItemsResponse =
+        decode_any(&payload, RES_CREATE_ITEMS_RESPONSE)?;
+    ensure_item_request_ok(response.status)?;
+
+    response
+        .created_items
+        .into_iter()
+        .map(|row| {
+            ensure_item_status_ok(row.status)?;
+            row.item.ok_or_else(|| KiCadError::InvalidResponse {
+                reason: "CreateItemsResponse missing created item payload".to_string(),
+            })
+        })
+        .collect()
+}
+
+fn updated_items(payload: prost_types::Any) -> Result<Vec<prost_types::Any>, KiCadError> {
+    let response: common_commands::UpdateItemsResponse =
+        decode_any(&payload, RES_UPDATE_ITEMS_RESPONSE)?;
+    ensure_item_request_ok(response.status)?;
+
+    response
+        .updated_items
+        .into_iter()
+        .map(|row| {
+            ensure_item_status_ok(row.status)?;
+            row.item.ok_or_else(|| KiCadError::InvalidResponse {
+                reason: "UpdateItemsResponse missing updated item payload".to_string(),
+            })
+        })
+        .collect()
+}
+
+fn deleted_item_ids(payload: prost_types::Any) -> Result<Vec<String>, KiCadError> {
+    let response: common_commands::DeleteItemsResponse =
+        decode_any(&payload, RES_DELETE_ITEMS_RESPONSE)?;
+    ensure_item_request_ok(response.status)?;
+
+    response
+        .deleted_items
+        .into_iter()
+        .map(|row| {
+            ensure_item_deletion_status_ok(row.status)?;
+            row.id
+                .map(|id| id.value)
+                .ok_or_else(|| KiCadError::InvalidResponse {
+                    reason: "DeleteItemsResponse missing deleted item id".to_string(),
+                })
+        })
+        .collect()
+}
+
 fn summarize_selection(items: Vec<prost_types::Any>) -> SelectionSummary {
     let mut counts = BTreeMap::<String, usize>::new();
 
@@ -2527,6 +3253,17 @@ fn map_polygon_with_holes(
     })
 }
 
+fn map_poly_set(
+    poly_set: Option<common_types::PolySet>,
+) -> Result<Vec<PolygonWithHolesNm>, KiCadError> {
+    poly_set
+        .map(|set| set.polygons)
+        .unwrap_or_default()
+        .into_iter()
+        .map(map_polygon_with_holes)
+        .collect()
+}
+
 fn map_polyline(line: common_types::PolyLine) -> Result<PolyLineNm, KiCadError> {
     Ok(PolyLineNm {
         nodes: line
@@ -2949,7 +3686,11 @@ fn net_class_type_to_proto(value: NetClassType) -> i32 {
     }
 }
 
-fn net_class_info_to_proto(value: NetClassInfo) -> common_project::NetClass {
+fn net_class_info_to_proto(
+    value: NetClassInfo,
+    existing: Option<&common_project::NetClass>,
+) -> common_project::NetClass {
+    let existing_board = existing.and_then(|class| class.board.as_ref());
     let board = value
         .board
         .map(|board| common_project::NetClassBoardSettings {
@@ -2968,16 +3709,18 @@ fn net_class_info_to_proto(value: NetClassInfo) -> common_project::NetClass {
             diff_pair_via_gap: board
                 .diff_pair_via_gap_nm
                 .map(|value_nm| common_types::Distance { value_nm }),
-            via_stack: if board.has_via_stack {
-                Some(board_types::PadStack::default())
-            } else {
-                None
-            },
-            microvia_stack: if board.has_microvia_stack {
-                Some(board_types::PadStack::default())
-            } else {
-                None
-            },
+            via_stack: board.via_stack.map(|stack| {
+                pad_stack_to_proto(
+                    stack,
+                    existing_board.and_then(|board| board.via_stack.clone()),
+                )
+            }),
+            microvia_stack: board.microvia_stack.map(|stack| {
+                pad_stack_to_proto(
+                    stack,
+                    existing_board.and_then(|board| board.microvia_stack.clone()),
+                )
+            }),
             color: board.color.map(|color| common_types::Color {
                 r: color.r,
                 g: color.g,
@@ -2991,7 +3734,23 @@ fn net_class_info_to_proto(value: NetClassInfo) -> common_project::NetClass {
         name: value.name,
         priority: value.priority,
         board,
-        schematic: None,
+        schematic: value
+            .schematic
+            .map(|schematic| common_project::NetClassSchematicSettings {
+                wire_width: schematic
+                    .wire_width_nm
+                    .map(|value_nm| common_types::Distance { value_nm }),
+                bus_width: schematic
+                    .bus_width_nm
+                    .map(|value_nm| common_types::Distance { value_nm }),
+                color: schematic.color.map(|color| common_types::Color {
+                    r: color.r,
+                    g: color.g,
+                    b: color.b,
+                    a: color.a,
+                }),
+                line_style: schematic.line_style.map(stroke_line_style_to_proto),
+            }),
         r#type: net_class_type_to_proto(value.class_type),
         constituents: value.constituents,
     }
@@ -3014,9 +3773,17 @@ fn map_net_class_info(net_class: common_project::NetClass) -> NetClassInfo {
         diff_pair_via_gap_nm: map_optional_distance_nm(board.diff_pair_via_gap),
         color: map_optional_color(board.color),
         tuning_profile: board.tuning_profile.filter(|value| !value.is_empty()),
-        has_via_stack: board.via_stack.is_some(),
-        has_microvia_stack: board.microvia_stack.is_some(),
+        via_stack: board.via_stack.map(map_pad_stack),
+        microvia_stack: board.microvia_stack.map(map_pad_stack),
     });
+    let schematic = net_class
+        .schematic
+        .map(|schematic| NetClassSchematicSettings {
+            wire_width_nm: map_optional_distance_nm(schematic.wire_width),
+            bus_width_nm: map_optional_distance_nm(schematic.bus_width),
+            color: map_optional_color(schematic.color),
+            line_style: schematic.line_style.map(map_stroke_line_style),
+        });
 
     NetClassInfo {
         name: net_class.name,
@@ -3024,6 +3791,31 @@ fn map_net_class_info(net_class: common_project::NetClass) -> NetClassInfo {
         class_type: map_net_class_type(net_class.r#type),
         constituents: net_class.constituents,
         board,
+        schematic,
+    }
+}
+
+fn map_stroke_line_style(value: i32) -> StrokeLineStyle {
+    match common_types::StrokeLineStyle::try_from(value) {
+        Ok(common_types::StrokeLineStyle::SlsDefault) => StrokeLineStyle::Default,
+        Ok(common_types::StrokeLineStyle::SlsSolid) => StrokeLineStyle::Solid,
+        Ok(common_types::StrokeLineStyle::SlsDash) => StrokeLineStyle::Dash,
+        Ok(common_types::StrokeLineStyle::SlsDot) => StrokeLineStyle::Dot,
+        Ok(common_types::StrokeLineStyle::SlsDashdot) => StrokeLineStyle::DashDot,
+        Ok(common_types::StrokeLineStyle::SlsDashdotdot) => StrokeLineStyle::DashDotDot,
+        _ => StrokeLineStyle::Unknown(value),
+    }
+}
+
+fn stroke_line_style_to_proto(value: StrokeLineStyle) -> i32 {
+    match value {
+        StrokeLineStyle::Default => common_types::StrokeLineStyle::SlsDefault as i32,
+        StrokeLineStyle::Solid => common_types::StrokeLineStyle::SlsSolid as i32,
+        StrokeLineStyle::Dash => common_types::StrokeLineStyle::SlsDash as i32,
+        StrokeLineStyle::Dot => common_types::StrokeLineStyle::SlsDot as i32,
+        StrokeLineStyle::DashDot => common_types::StrokeLineStyle::SlsDashdot as i32,
+        StrokeLineStyle::DashDotDot => common_types::StrokeLineStyle::SlsDashdotdot as i32,
+        StrokeLineStyle::Unknown(raw) => raw,
     }
 }
 
@@ -3071,27 +3863,334 @@ fn map_via_layers(pad_stack: Option<board_types::PadStack>) -> Option<PcbViaLaye
     })
 }
 
-fn map_pad_type(value: i32) -> PcbPadType {
-    match board_types::PadType::try_from(value) {
-        Ok(board_types::PadType::PtPth) => PcbPadType::Pth,
-        Ok(board_types::PadType::PtSmd) => PcbPadType::Smd,
-        Ok(board_types::PadType::PtEdgeConnector) => PcbPadType::EdgeConnector,
-        Ok(board_types::PadType::PtNpth) => PcbPadType::Npth,
-        _ => PcbPadType::Unknown(value),
-    }
-}
-
-fn map_zone_type(value: i32) -> PcbZoneType {
-    match board_types::ZoneType::try_from(value) {
-        Ok(board_types::ZoneType::ZtCopper) => PcbZoneType::Copper,
-        Ok(board_types::ZoneType::ZtGraphical) => PcbZoneType::Graphical,
-        Ok(board_types::ZoneType::ZtRuleArea) => PcbZoneType::RuleArea,
-        Ok(board_types::ZoneType::ZtTeardrop) => PcbZoneType::Teardrop,
-        _ => PcbZoneType::Unknown(value),
+fn map_pad_stack_shape(value: i32) -> PcbPadStackShape {
+    match board_types::PadStackShape::try_from(value) {
+        Ok(board_types::PadStackShape::PssCircle) => PcbPadStackShape::Circle,
+        Ok(board_types::PadStackShape::PssRectangle) => PcbPadStackShape::Rectangle,
+        Ok(board_types::PadStackShape::PssOval) => PcbPadStackShape::Oval,
+        Ok(board_types::PadStackShape::PssTrapezoid) => PcbPadStackShape::Trapezoid,
+        Ok(board_types::PadStackShape::PssRoundrect) => PcbPadStackShape::RoundRect,
+        Ok(board_types::PadStackShape::PssChamferedrect) => PcbPadStackShape::ChamferedRect,
+        Ok(board_types::PadStackShape::PssCustom) => PcbPadStackShape::Custom,
+        _ => PcbPadStackShape::Unknown(value),
     }
 }
 
-fn decode_pcb_items(items: Vec<prost_types::Any>) -> Result<Vec<PcbItem>, KiCadError> {
+fn map_pad_stack(pad_stack: board_types::PadStack) -> PcbPadStack {
+    PcbPadStack {
+        layers: pad_stack.layers.into_iter().map(layer_to_model).collect(),
+        copper_layers: pad_stack
+            .copper_layers
+            .into_iter()
+            .map(|layer| PcbPadStackCopperLayer {
+                layer: layer_to_model(layer.layer),
+                shape: map_pad_stack_shape(layer.shape),
+                size_nm: layer.size.map(map_vector2_nm),
+                offset_nm: layer.offset.map(map_vector2_nm),
+            })
+            .collect(),
+        drill: pad_stack.drill.map(|drill| PcbDrill {
+            start_layer: layer_to_model(drill.start_layer),
+            end_layer: layer_to_model(drill.end_layer),
+            diameter_nm: drill.diameter.map(map_vector2_nm),
+        }),
+        angle_deg: pad_stack.angle.map(|angle| angle.value_degrees),
+    }
+}
+
+fn pad_stack_shape_to_proto(value: PcbPadStackShape) -> i32 {
+    match value {
+        PcbPadStackShape::Circle => board_types::PadStackShape::PssCircle as i32,
+        PcbPadStackShape::Rectangle => board_types::PadStackShape::PssRectangle as i32,
+        PcbPadStackShape::Oval => board_types::PadStackShape::PssOval as i32,
+        PcbPadStackShape::Trapezoid => board_types::PadStackShape::PssTrapezoid as i32,
+        PcbPadStackShape::RoundRect => board_types::PadStackShape::PssRoundrect as i32,
+        PcbPadStackShape::ChamferedRect => board_types::PadStackShape::PssChamferedrect as i32,
+        PcbPadStackShape::Custom => board_types::PadStackShape::PssCustom as i32,
+        PcbPadStackShape::Unknown(raw) => raw,
+    }
+}
+
+/// Stack mode implied by the copper layers: one definition for every layer, KiCad's
+/// front/inner/back triple, or per-layer definitions.
+fn pad_stack_type(copper_layers: &[PcbPadStackCopperLayer]) -> board_types::PadStackType {
+    let ids: Vec<i32> = copper_layers.iter().map(|layer| layer.layer.id).collect();
+    if ids.len() <= 1 {
+        board_types::PadStackType::PstNormal
+    } else if ids
+        == [
+            board_types::BoardLayer::BlFCu as i32,
+            board_types::BoardLayer::BlIn1Cu as i32,
+            board_types::BoardLayer::BlBCu as i32,
+        ]
+    {
+        board_types::PadStackType::PstFrontInnerBack
+    } else {
+        board_types::PadStackType::PstCustom
+    }
+}
+
+/// Writes the modelled fields of `value` over `existing`, so fields [`PcbPadStack`] does not
+/// carry keep their values on copper layers and drills that are still present.
+fn pad_stack_to_proto(
+    value: PcbPadStack,
+    existing: Option<board_types::PadStack>,
+) -> board_types::PadStack {
+    let mut pad_stack = existing.unwrap_or_default();
+    let mut current_layers = std::mem::take(&mut pad_stack.copper_layers);
+    let current_drill = pad_stack.drill.take();
+
+    pad_stack.r#type = pad_stack_type(&value.copper_layers) as i32;
+    pad_stack.layers = value.layers.iter().map(|layer| layer.id).collect();
+    pad_stack.copper_layers = value
+        .copper_layers
+        .into_iter()
+        .map(|layer| {
+            let current = current_layers
+                .iter()
+                .position(|current| current.layer == layer.layer.id)
+                .map(|index| current_layers.swap_remove(index))
+                .unwrap_or_default();
+            board_types::PadStackLayer {
+                layer: layer.layer.id,
+                shape: pad_stack_shape_to_proto(layer.shape),
+                size: layer.size_nm.map(vector2_nm_to_proto),
+                offset: layer.offset_nm.map(vector2_nm_to_proto),
+                ..current
+            }
+        })
+        .collect();
+    pad_stack.drill = value.drill.map(|drill| board_types::DrillProperties {
+        start_layer: drill.start_layer.id,
+        end_layer: drill.end_layer.id,
+        diameter: drill.diameter_nm.map(vector2_nm_to_proto),
+        ..current_drill.unwrap_or_default()
+    });
+    pad_stack.angle = value
+        .angle_deg
+        .map(|value_degrees| common_types::Angle { value_degrees });
+    pad_stack
+}
+
+fn footprint_field_text(field: Option<&board_types::Field>) -> Option<String> {
+    field
+        .and_then(|field| field.text.as_ref())
+        .and_then(|board_text| board_text.text.as_ref())
+        .map(|text| text.text.clone())
+        .filter(|value| !value.is_empty())
+}
+
+fn map_footprint_mounting_style(value: i32) -> PcbFootprintMountingStyle {
+    match board_types::FootprintMountingStyle::try_from(value) {
+        Ok(board_types::FootprintMountingStyle::FmsThroughHole) => {
+            PcbFootprintMountingStyle::ThroughHole
+        }
+        Ok(board_types::FootprintMountingStyle::FmsSmd) => PcbFootprintMountingStyle::Smd,
+        Ok(board_types::FootprintMountingStyle::FmsUnspecified) => {
+            PcbFootprintMountingStyle::Unspecified
+        }
+        _ => PcbFootprintMountingStyle::Unknown(value),
+    }
+}
+
+fn map_footprint_attributes(
+    attributes: &board_types::FootprintAttributes,
+) -> PcbFootprintAttributes {
+    PcbFootprintAttributes {
+        not_in_schematic: attributes.not_in_schematic,
+        exclude_from_position_files: attributes.exclude_from_position_files,
+        exclude_from_bill_of_materials: attributes.exclude_from_bill_of_materials,
+        exempt_from_courtyard_requirement: attributes.exempt_from_courtyard_requirement,
+        do_not_populate: attributes.do_not_populate,
+        mounting_style: map_footprint_mounting_style(attributes.mounting_style),
+    }
+}
+
+fn map_pad_type(value: i32) -> PcbPadType {
+    match board_types::PadType::try_from(value) {
+        Ok(board_types::PadType::PtPth) => PcbPadType::Pth,
+        Ok(board_types::PadType::PtSmd) => PcbPadType::Smd,
+        Ok(board_types::PadType::PtEdgeConnector) => PcbPadType::EdgeConnector,
+        Ok(board_types::PadType::PtNpth) => PcbPadType::Npth,
+        _ => PcbPadType::Unknown(value),
+    }
+}
+
+fn map_zone_type(value: i32) -> PcbZoneType {
+    match board_types::ZoneType::try_from(value) {
+        Ok(board_types::ZoneType::ZtCopper) => PcbZoneType::Copper,
+        Ok(board_types::ZoneType::ZtGraphical) => PcbZoneType::Graphical,
+        Ok(board_types::ZoneType::ZtRuleArea) => PcbZoneType::RuleArea,
+        Ok(board_types::ZoneType::ZtTeardrop) => PcbZoneType::Teardrop,
+        _ => PcbZoneType::Unknown(value),
+    }
+}
+
+/// Lock state of a raw board item payload; `None` for types without one.
+fn pcb_item_locked(item: &prost_types::Any) -> Result<Option<bool>, KiCadError> {
+    macro_rules! locked_state {
+        ($($proto:ty => $name:literal),+ $(,)?) => {
+            $(
+                if item.type_url == envelope::type_url($name) {
+                    let locked = decode_any::<$proto>(item, $name)?.locked;
+                    return Ok(match common_types::LockedState::try_from(locked) {
+                        Ok(common_types::LockedState::LsLocked) => Some(true),
+                        Ok(common_types::LockedState::LsUnlocked) => Some(false),
+                        _ => None,
+                    });
+                }
+            )+
+        };
+    }
+
+    locked_state!(
+        board_types::Track => "kiapi.board.types.Track",
+        board_types::Arc => "kiapi.board.types.Arc",
+        board_types::Via => "kiapi.board.types.Via",
+        board_types::FootprintInstance => "kiapi.board.types.FootprintInstance",
+        board_types::Pad => "kiapi.board.types.Pad",
+        board_types::BoardGraphicShape => "kiapi.board.types.BoardGraphicShape",
+        board_types::BoardText => "kiapi.board.types.BoardText",
+        board_types::BoardTextBox => "kiapi.board.types.BoardTextBox",
+        board_types::Zone => "kiapi.board.types.Zone",
+        board_types::Dimension => "kiapi.board.types.Dimension",
+    );
+    Ok(None)
+}
+
+fn schematic_item_header(
+    document: &DocumentSpecifier,
+) -> Result<common_types::ItemHeader, KiCadError> {
+    if document.document_type != DocumentType::Schematic {
+        return Err(KiCadError::Config {
+            reason: format!(
+                "schematic item request needs a schematic document, got {}",
+                document.document_type
+            ),
+        });
+    }
+
+    Ok(common_types::ItemHeader {
+        document: Some(model_document_to_proto(document)),
+        container: None,
+        field_mask: None,
+    })
+}
+
+fn decode_schematic_items(items: Vec<prost_types::Any>) -> Result<Vec<SchematicItem>, KiCadError> {
+    items.into_iter().map(decode_schematic_item).collect()
+}
+
+fn decode_schematic_item(item: prost_types::Any) -> Result<SchematicItem, KiCadError> {
+    macro_rules! decode_label {
+        ($($proto:ident => $variant:ident),+ $(,)?) => {
+            $(
+                let name = concat!("kiapi.schematic.types.", stringify!($proto));
+                if item.type_url == envelope::type_url(name) {
+                    let label = decode_any::<schematic_types::$proto>(&item, name)?;
+                    return Ok(SchematicItem::$variant(SchematicLabel {
+                        id: label.id.map(|id| id.value),
+                        position_nm: label.position.map(map_vector2_nm),
+                        text: label
+                            .text
+                            .and_then(|text| text.text)
+                            .map(map_text_spec_from_proto),
+                    }));
+                }
+            )+
+        };
+    }
+
+    if item.type_url == envelope::type_url("kiapi.schematic.types.Line") {
+        let line = decode_any::<schematic_types::Line>(&item, "kiapi.schematic.types.Line")?;
+        return Ok(SchematicItem::Line(SchematicLine {
+            id: line.id.map(|id| id.value),
+            start_nm: line.start.map(map_vector2_nm),
+            end_nm: line.end.map(map_vector2_nm),
+            layer: line.layer,
+        }));
+    }
+
+    decode_label!(
+        LocalLabel => LocalLabel,
+        GlobalLabel => GlobalLabel,
+        HierarchicalLabel => HierarchicalLabel,
+        DirectiveLabel => DirectiveLabel,
+    );
+
+    Ok(SchematicItem::Unknown(SchematicUnknownItem {
+        type_url: item.type_url,
+        raw_len: item.value.len(),
+    }))
+}
+
+fn schematic_item_to_any(item: SchematicItem) -> Result<prost_types::Any, KiCadError> {
+    fn label_fields(
+        label: SchematicLabel,
+    ) -> (
+        Option<common_types::Kiid>,
+        Option<common_types::Vector2>,
+        Option<schematic_types::Text>,
+    ) {
+        (
+            label.id.map(|value| common_types::Kiid { value }),
+            label.position_nm.map(vector2_nm_to_proto),
+            label.text.map(|text| schematic_types::Text {
+                text: Some(text_spec_to_proto(text)),
+            }),
+        )
+    }
+
+    Ok(match item {
+        SchematicItem::Line(line) => envelope::pack_any(
+            &schematic_types::Line {
+                id: line.id.map(|value| common_types::Kiid { value }),
+                start: line.start_nm.map(vector2_nm_to_proto),
+                end: line.end_nm.map(vector2_nm_to_proto),
+                layer: line.layer,
+            },
+            "kiapi.schematic.types.Line",
+        ),
+        SchematicItem::LocalLabel(label) => {
+            let (id, position, text) = label_fields(label);
+            envelope::pack_any(
+                &schematic_types::LocalLabel { id, position, text },
+                "kiapi.schematic.types.LocalLabel",
+            )
+        }
+        SchematicItem::GlobalLabel(label) => {
+            let (id, position, text) = label_fields(label);
+            envelope::pack_any(
+                &schematic_types::GlobalLabel { id, position, text },
+                "kiapi.schematic.types.GlobalLabel",
+            )
+        }
+        SchematicItem::HierarchicalLabel(label) => {
+            let (id, position, text) = label_fields(label);
+            envelope::pack_any(
+                &schematic_types::HierarchicalLabel { id, position, text },
+                "kiapi.schematic.types.HierarchicalLabel",
+            )
+        }
+        SchematicItem::DirectiveLabel(label) => {
+            let (id, position, text) = label_fields(label);
+            envelope::pack_any(
+                &schematic_types::DirectiveLabel { id, position, text },
+                "kiapi.schematic.types.DirectiveLabel",
+            )
+        }
+        SchematicItem::Unknown(unknown) => {
+            return Err(KiCadError::Config {
+                reason: format!(
+                    "cannot encode schematic item without a typed model (`{}`)",
+                    unknown.type_url
+                ),
+            })
+        }
+    })
+}
+
+pub(crate) fn decode_pcb_items(items: Vec<prost_types::Any>) -> Result<Vec<PcbItem>, KiCadError> {
     items.into_iter().map(decode_pcb_item).collect()
 }
 
@@ -3127,6 +4226,18 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             id: via.id.map(|id| id.value),
             position_nm: via.position.map(map_vector2_nm),
             via_type: map_via_type(via.r#type),
+            drill_diameter_nm: via
+                .pad_stack
+                .as_ref()
+                .and_then(|pad_stack| pad_stack.drill.as_ref())
+                .and_then(|drill| drill.diameter.map(|diameter| diameter.x_nm)),
+            pad_diameter_nm: via.pad_stack.as_ref().and_then(|pad_stack| {
+                pad_stack
+                    .copper_layers
+                    .iter()
+                    .filter_map(|layer| layer.size.map(|size| size.x_nm.min(size.y_nm)))
+                    .min()
+            }),
             layers: map_via_layers(via.pad_stack),
             net: map_optional_net(via.net),
         }));
@@ -3137,13 +4248,7 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             &item,
             "kiapi.board.types.FootprintInstance",
         )?;
-        let reference = footprint
-            .reference_field
-            .as_ref()
-            .and_then(|field| field.text.as_ref())
-            .and_then(|board_text| board_text.text.as_ref())
-            .map(|text| text.text.clone())
-            .filter(|value| !value.is_empty());
+        let reference = footprint_field_text(footprint.reference_field.as_ref());
         let pad_count = footprint
             .definition
             .as_ref()
@@ -3156,6 +4261,31 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             })
             .unwrap_or(0);
 
+        let value = footprint_field_text(footprint.value_field.as_ref());
+        let datasheet = footprint_field_text(footprint.datasheet_field.as_ref());
+        let library_id = footprint
+            .definition
+            .as_ref()
+            .and_then(|definition| definition.id.as_ref())
+            .filter(|id| !id.entry_name.is_empty())
+            .map(|id| format!("{}:{}", id.library_nickname, id.entry_name));
+        let attributes = footprint
+            .attributes
+            .as_ref()
+            .or_else(|| {
+                footprint
+                    .definition
+                    .as_ref()
+                    .and_then(|definition| definition.attributes.as_ref())
+            })
+            .map(map_footprint_attributes);
+        let items = decode_pcb_items(
+            footprint
+                .definition
+                .map(|definition| definition.items)
+                .unwrap_or_default(),
+        )?;
+
         return Ok(PcbItem::Footprint(PcbFootprint {
             id: footprint.id.map(|id| id.value),
             reference,
@@ -3163,6 +4293,11 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             orientation_deg: footprint.orientation.map(|angle| angle.value_degrees),
             layer: layer_to_model(footprint.layer),
             pad_count,
+            value,
+            library_id,
+            datasheet,
+            attributes,
+            items,
         }));
     }
 
@@ -3174,6 +4309,7 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             pad_type: map_pad_type(pad.r#type),
             position_nm: pad.position.map(map_vector2_nm),
             net: map_optional_net(pad.net),
+            pad_stack: pad.pad_stack.map(map_pad_stack),
         }));
     }
 
@@ -3187,11 +4323,27 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             .as_ref()
             .and_then(|graphic| graphic.geometry.as_ref())
             .map(|value| format!("{value:?}"));
+        let attributes = shape
+            .shape
+            .as_ref()
+            .and_then(|graphic| graphic.attributes)
+            .unwrap_or_default();
+        let geometry = match shape.shape {
+            Some(graphic) => map_text_shape_geometry(graphic)?,
+            None => TextShapeGeometry::Unknown,
+        };
         return Ok(PcbItem::BoardGraphicShape(PcbBoardGraphicShape {
             id: shape.id.map(|id| id.value),
             layer: layer_to_model(shape.layer),
             net: map_optional_net(shape.net),
             geometry_kind,
+            geometry,
+            stroke_width_nm: attributes
+                .stroke
+                .and_then(|stroke| map_optional_distance_nm(stroke.width)),
+            filled: attributes.fill.is_some_and(|fill| {
+                fill.fill_type == common_types::GraphicFillType::GftFilled as i32
+            }),
         }));
     }
 
@@ -3200,7 +4352,8 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
         return Ok(PcbItem::BoardText(PcbBoardText {
             id: text.id.map(|id| id.value),
             layer: layer_to_model(text.layer),
-            text: text.text.map(|value| value.text),
+            text: text.text.as_ref().map(|value| value.text.clone()),
+            spec: text.text.map(map_text_spec_from_proto),
         }));
     }
 
@@ -3210,7 +4363,8 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
         return Ok(PcbItem::BoardTextBox(PcbBoardTextBox {
             id: textbox.id.map(|id| id.value),
             layer: layer_to_model(textbox.layer),
-            text: textbox.textbox.map(|value| value.text),
+            text: textbox.textbox.as_ref().map(|value| value.text.clone()),
+            spec: textbox.textbox.map(map_text_box_spec_from_proto),
         }));
     }
 
@@ -3229,6 +4383,12 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
 
     if item.type_url == envelope::type_url("kiapi.board.types.Zone") {
         let zone = decode_any::<board_types::Zone>(&item, "kiapi.board.types.Zone")?;
+        let net = match zone.settings {
+            Some(board_types::zone::Settings::CopperSettings(settings)) => {
+                map_optional_net(settings.net)
+            }
+            _ => None,
+        };
         return Ok(PcbItem::Zone(PcbZone {
             id: zone.id.map(|id| id.value),
             name: zone.name,
@@ -3236,6 +4396,19 @@ fn decode_pcb_item(item: prost_types::Any) -> Result<PcbItem, KiCadError> {
             layer_count: zone.layers.len(),
             filled: zone.filled,
             polygon_count: zone.filled_polygons.len(),
+            layers: zone.layers.into_iter().map(layer_to_model).collect(),
+            net,
+            outline: map_poly_set(zone.outline)?,
+            filled_polygons: zone
+                .filled_polygons
+                .into_iter()
+                .map(|fill| {
+                    Ok(PcbZoneFilledPolygons {
+                        layer: layer_to_model(fill.layer),
+                        polygons: map_poly_set(fill.shapes)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, KiCadError>>()?,
         }));
     }
 
@@ -3278,13 +4451,7 @@ fn pad_netlist_from_footprint_items(
             "kiapi.board.types.FootprintInstance",
         )?;
 
-        let footprint_reference = footprint
-            .reference_field
-            .as_ref()
-            .and_then(|field| field.text.as_ref())
-            .and_then(|board_text| board_text.text.as_ref())
-            .map(|text| text.text.clone())
-            .filter(|value| !value.is_empty());
+        let footprint_reference = footprint_field_text(footprint.reference_field.as_ref());
 
         let footprint_id = footprint.id.as_ref().map(|id| id.value.clone());
 
@@ -3661,6 +4828,14 @@ fn any_to_pretty_debug(item: &prost_types::Any) -> Result<String, KiCadError> {
     ))
 }
 
+fn is_document_error(err: &KiCadError) -> bool {
+    match err {
+        KiCadError::ApiStatus { code, .. } => code == "AS_BAD_REQUEST",
+        KiCadError::ItemStatus { code } => code == "IRS_DOCUMENT_NOT_FOUND",
+        _ => false,
+    }
+}
+
 fn select_single_board_document(
     docs: &[DocumentSpecifier],
 ) -> Result<&DocumentSpecifier, KiCadError> {
@@ -3779,26 +4954,131 @@ fn default_client_name() -> String {
 mod tests {
     use super::{
         any_to_pretty_debug, board_editor_appearance_settings_to_proto, board_stackup_to_proto,
-        commit_action_to_proto, decode_pcb_item, drc_severity_to_proto,
-        ensure_item_deletion_status_ok, ensure_item_request_ok, ensure_item_status_ok,
-        layer_to_model, map_board_stackup, map_commit_session, map_hit_test_result,
-        map_item_bounding_boxes, map_merge_mode_to_proto, map_polygon_with_holes,
-        map_run_action_status, model_document_to_proto, normalize_socket_uri,
-        pad_netlist_from_footprint_items, response_payload_as_any, select_single_board_document,
+        commit_action_to_proto, decode_pcb_item, decode_schematic_item, decode_schematic_items,
+        drc_severity_to_proto, ensure_item_deletion_status_ok, ensure_item_request_ok,
+        ensure_item_status_ok, is_document_error, layer_to_model, map_board_stackup,
+        map_commit_session, map_document_specifier, map_hit_test_result, map_item_bounding_boxes,
+        map_merge_mode_to_proto, map_net_class_info, map_pad_stack, map_polygon_with_holes,
+        map_run_action_status, model_document_to_proto, net_class_info_to_proto,
+        normalize_socket_uri, pad_netlist_from_footprint_items, response_payload_as_any,
+        schematic_item_header, schematic_item_to_any, select_single_board_document,
         select_single_project_path, selection_item_detail, summarize_item_details,
         summarize_selection, text_horizontal_alignment_to_proto, text_spec_to_proto,
-        PCB_OBJECT_TYPES,
+        title_block_variable_updates, PCB_OBJECT_TYPES,
     };
+    use super::{board_commands, common_commands, common_types, KiCadClient};
     use crate::error::KiCadError;
     use crate::model::board::{
-        BoardLayerInfo, BoardStackup, BoardStackupLayer, BoardStackupLayerType, PcbItem, PcbViaType,
+        BoardLayerInfo, BoardStackup, BoardStackupLayer, BoardStackupLayerType, PcbItem,
+        PcbPadStackShape, PcbViaType, StrokeLineStyle, Vector2Nm,
     };
     use crate::model::common::{
-        CommitAction, DocumentSpecifier, DocumentType, ProjectInfo, TextAttributesSpec,
-        TextHorizontalAlignment, TextSpec,
+        BoardSelector, CommitAction, DocumentSpecifier, DocumentType, ProjectInfo, SheetPath,
+        TextAttributesSpec, TextHorizontalAlignment, TextSpec, TitleBlockField, TitleBlockInfo,
     };
+    use crate::model::schematic::{SchematicItem, SchematicLabel, SchematicLine};
+    use crate::proto::kiapi::common::{ApiRequest, ApiResponse, ApiResponseStatus};
     use prost::Message;
     use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    type Requests = Arc<Mutex<Vec<prost_types::Any>>>;
+
+    /// Serves `reply` on a private IPC socket and records every request payload.
+    async fn fake_kicad(
+        builder: super::ClientBuilder,
+        reply: impl Fn(&prost_types::Any) -> ApiResponse + Send + 'static,
+    ) -> (super::KiCadClient, Requests) {
+        use nng::options::{Options, RecvTimeout};
+
+        static NEXT_SOCKET: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "kicad-ipc-rs-test-{}-{}.sock",
+            std::process::id(),
+            NEXT_SOCKET.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ));
+        let uri = format!("ipc://{}", path.display());
+        let socket = nng::Socket::new(nng::Protocol::Rep0).expect("rep socket should open");
+        socket
+            .set_opt::<RecvTimeout>(Some(std::time::Duration::from_secs(5)))
+            .expect("receive timeout should apply");
+        socket.listen(&uri).expect("fake KiCad should listen");
+
+        let requests = Requests::default();
+        let recorded = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            while let Ok(message) = socket.recv() {
+                let request =
+                    ApiRequest::decode(message.as_slice()).expect("client sends ApiRequest");
+                let command = request.message.expect("request carries a command");
+                let response = reply(&command);
+                recorded.lock().expect("request log").push(command);
+                if socket.send(response.encode_to_vec().as_slice()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let client = builder
+            .socket_path(uri)
+            .connect()
+            .await
+            .expect("client should connect to fake KiCad");
+        (client, requests)
+    }
+
+    fn ok_response(message: prost_types::Any) -> ApiResponse {
+        ApiResponse {
+            header: None,
+            status: Some(ApiResponseStatus {
+                status: 1,
+                error_message: String::new(),
+            }),
+            message: Some(message),
+        }
+    }
+
+    fn pcb_document_proto(filename: &str, project: &str) -> common_types::DocumentSpecifier {
+        common_types::DocumentSpecifier {
+            r#type: common_types::DocumentType::DoctypePcb as i32,
+            project: Some(common_types::ProjectSpecifier {
+                name: project.to_string(),
+                path: format!("/work/{project}"),
+            }),
+            identifier: Some(common_types::document_specifier::Identifier::BoardFilename(
+                filename.to_string(),
+            )),
+        }
+    }
+
+    /// Answers `GetOpenDocuments` with `boards` and `GetNets` with no nets.
+    fn board_server(
+        boards: Vec<common_types::DocumentSpecifier>,
+    ) -> impl Fn(&prost_types::Any) -> ApiResponse + Send + 'static {
+        move |command| {
+            if command.type_url == crate::envelope::type_url(super::CMD_GET_OPEN_DOCUMENTS) {
+                ok_response(crate::envelope::pack_any(
+                    &common_commands::GetOpenDocumentsResponse {
+                        documents: boards.clone(),
+                    },
+                    super::RES_GET_OPEN_DOCUMENTS,
+                ))
+            } else {
+                ok_response(crate::envelope::pack_any(
+                    &board_commands::NetsResponse { nets: Vec::new() },
+                    super::RES_GET_NETS,
+                ))
+            }
+        }
+    }
+
+    fn requested_board(command: &prost_types::Any) -> Option<String> {
+        let request = board_commands::GetNets::decode(command.value.as_slice()).ok()?;
+        match request.board?.identifier? {
+            common_types::document_specifier::Identifier::BoardFilename(filename) => Some(filename),
+            _ => None,
+        }
+    }
 
     #[test]
     fn normalize_socket_uri_adds_ipc_scheme() {
@@ -3817,6 +5097,7 @@ mod tests {
         let docs = vec![DocumentSpecifier {
             document_type: DocumentType::Pcb,
             board_filename: Some("demo.kicad_pcb".to_string()),
+            sheet_path: None,
             project: ProjectInfo {
                 name: Some("demo".to_string()),
                 path: Some(PathBuf::from("/tmp/demo")),
@@ -3834,6 +5115,7 @@ mod tests {
             DocumentSpecifier {
                 document_type: DocumentType::Pcb,
                 board_filename: Some("a.kicad_pcb".to_string()),
+                sheet_path: None,
                 project: ProjectInfo {
                     name: Some("a".to_string()),
                     path: Some(PathBuf::from("/tmp/a")),
@@ -3842,6 +5124,7 @@ mod tests {
             DocumentSpecifier {
                 document_type: DocumentType::Pcb,
                 board_filename: Some("b.kicad_pcb".to_string()),
+                sheet_path: None,
                 project: ProjectInfo {
                     name: Some("b".to_string()),
                     path: Some(PathBuf::from("/tmp/b")),
@@ -3863,12 +5146,108 @@ mod tests {
         assert!(matches!(result, Err(KiCadError::BoardNotOpen)));
     }
 
+    #[tokio::test]
+    async fn document_cache_is_reused_until_refreshed_or_rejected() {
+        use crate::proto::kiapi::common::ApiStatusCode;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let reject = Arc::new(AtomicBool::new(false));
+        let rejecting = Arc::clone(&reject);
+        let serve = board_server(vec![pcb_document_proto("/work/a/a.kicad_pcb", "a")]);
+        let (client, requests) = fake_kicad(
+            KiCadClient::builder().cache_documents(true),
+            move |command| {
+                if rejecting.swap(false, Ordering::SeqCst) {
+                    return ApiResponse {
+                        header: None,
+                        status: Some(ApiResponseStatus {
+                            status: ApiStatusCode::AsBadRequest as i32,
+                            error_message: "document not open".to_string(),
+                        }),
+                        message: None,
+                    };
+                }
+                if command.type_url == crate::envelope::type_url(super::CMD_GET_ITEMS) {
+                    return ok_response(crate::envelope::pack_any(
+                        &common_commands::GetItemsResponse {
+                            header: None,
+                            status: common_types::ItemRequestStatus::IrsDocumentNotFound as i32,
+                            items: Vec::new(),
+                        },
+                        super::RES_GET_ITEMS_RESPONSE,
+                    ));
+                }
+                serve(command)
+            },
+        )
+        .await;
+        let lookups = || {
+            requests
+                .lock()
+                .expect("request log")
+                .iter()
+                .filter(|command| {
+                    command.type_url == crate::envelope::type_url(super::CMD_GET_OPEN_DOCUMENTS)
+                })
+                .count()
+        };
+
+        client
+            .get_nets()
+            .await
+            .expect("first call resolves the board");
+        client.get_nets().await.expect("second call uses the cache");
+        assert_eq!(lookups(), 1);
+
+        client.refresh_documents();
+        client
+            .get_nets()
+            .await
+            .expect("refreshed call resolves again");
+        assert_eq!(lookups(), 2);
+
+        reject.store(true, Ordering::SeqCst);
+        assert!(client.get_nets().await.is_err());
+        client
+            .get_nets()
+            .await
+            .expect("call after rejection resolves again");
+        assert_eq!(lookups(), 3);
+
+        assert!(matches!(
+            client.get_items_by_type_codes(vec![1]).await,
+            Err(KiCadError::ItemStatus { code }) if code == "IRS_DOCUMENT_NOT_FOUND"
+        ));
+        client
+            .get_nets()
+            .await
+            .expect("call after a missing document resolves again");
+        assert_eq!(lookups(), 4);
+    }
+
+    #[test]
+    fn is_document_error_matches_stale_document_codes() {
+        assert!(is_document_error(&KiCadError::ApiStatus {
+            code: "AS_BAD_REQUEST".to_string(),
+            message: "document not open".to_string(),
+        }));
+        assert!(is_document_error(&KiCadError::ItemStatus {
+            code: "IRS_DOCUMENT_NOT_FOUND".to_string(),
+        }));
+        assert!(!is_document_error(&KiCadError::ApiStatus {
+            code: "AS_BUSY".to_string(),
+            message: String::new(),
+        }));
+        assert!(!is_document_error(&KiCadError::BoardNotOpen));
+    }
+
     #[test]
     fn select_single_board_document_errors_on_multiple_open_boards() {
         let docs = vec![
             DocumentSpecifier {
                 document_type: DocumentType::Pcb,
                 board_filename: Some("a.kicad_pcb".to_string()),
+                sheet_path: None,
                 project: ProjectInfo {
                     name: Some("a".to_string()),
                     path: Some(PathBuf::from("/tmp/a")),
@@ -3877,6 +5256,7 @@ mod tests {
             DocumentSpecifier {
                 document_type: DocumentType::Pcb,
                 board_filename: Some("b.kicad_pcb".to_string()),
+                sheet_path: None,
                 project: ProjectInfo {
                     name: Some("b".to_string()),
                     path: Some(PathBuf::from("/tmp/b")),
@@ -3891,6 +5271,104 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn find_board_and_open_boards_pin_handles_to_their_document() {
+        let boards = vec![
+            pcb_document_proto("/work/a/a.kicad_pcb", "a"),
+            pcb_document_proto("/work/b/b.kicad_pcb", "b"),
+        ];
+        let (client, requests) = fake_kicad(KiCadClient::builder(), board_server(boards)).await;
+
+        let found = client
+            .find_board(BoardSelector::Filename("b".to_string()))
+            .await
+            .expect("board b should be found");
+        assert_eq!(found.board_filename.as_deref(), Some("/work/b/b.kicad_pcb"));
+        assert!(matches!(
+            client
+                .find_board(BoardSelector::Project("c".to_string()))
+                .await,
+            Err(KiCadError::BoardNotOpen)
+        ));
+        assert!(matches!(
+            client.get_nets().await,
+            Err(KiCadError::AmbiguousBoardSelection { .. })
+        ));
+
+        let handles = client.open_boards().await.expect("open boards should list");
+        assert_eq!(handles.len(), 2);
+        assert_eq!(handles[1].document(), &found);
+
+        requests.lock().expect("request log").clear();
+        handles[1].get_nets().await.expect("pinned board b");
+        handles[0].get_nets().await.expect("pinned board a");
+        // Pinned handles skip the open-document lookup and name their own board.
+        let targeted: Vec<Option<String>> = requests
+            .lock()
+            .expect("request log")
+            .iter()
+            .map(requested_board)
+            .collect();
+        assert_eq!(
+            targeted,
+            vec![
+                Some("/work/b/b.kicad_pcb".to_string()),
+                Some("/work/a/a.kicad_pcb".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn board_watcher_stream_yields_document_changes() {
+        use crate::watch::BoardChangeEvent;
+        use futures_core::Stream;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let board = pcb_document_proto("/work/a/a.kicad_pcb", "a");
+        let lookups = AtomicUsize::new(0);
+        let (client, _requests) = fake_kicad(KiCadClient::builder(), move |command| {
+            let url = |name| crate::envelope::type_url(name);
+            if command.type_url == url(super::CMD_GET_OPEN_DOCUMENTS) {
+                // No board on the baseline poll, then one board.
+                let documents = if lookups.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Vec::new()
+                } else {
+                    vec![board.clone()]
+                };
+                ok_response(crate::envelope::pack_any(
+                    &common_commands::GetOpenDocumentsResponse { documents },
+                    super::RES_GET_OPEN_DOCUMENTS,
+                ))
+            } else if command.type_url == url(super::CMD_GET_SELECTION) {
+                ok_response(crate::envelope::pack_any(
+                    &common_commands::SelectionResponse { items: Vec::new() },
+                    super::RES_SELECTION_RESPONSE,
+                ))
+            } else {
+                ok_response(crate::envelope::pack_any(
+                    &common_commands::GetItemsResponse {
+                        header: None,
+                        status: common_types::ItemRequestStatus::IrsOk as i32,
+                        items: Vec::new(),
+                    },
+                    super::RES_GET_ITEMS_RESPONSE,
+                ))
+            }
+        })
+        .await;
+
+        let mut watcher = client.watch_board(std::time::Duration::from_millis(1));
+        let event = std::future::poll_fn(|cx| std::pin::Pin::new(&mut watcher).poll_next(cx)).await;
+        let Some(Ok(BoardChangeEvent::OpenDocumentsChanged(documents))) = event else {
+            panic!("expected a document change, got {event:?}");
+        };
+        assert_eq!(
+            documents[0].board_filename.as_deref(),
+            Some("/work/a/a.kicad_pcb")
+        );
+    }
+
     #[test]
     fn layer_to_model_formats_unknown_id() {
         let layer = layer_to_model(999);
@@ -3903,6 +5381,7 @@ mod tests {
         let document = DocumentSpecifier {
             document_type: DocumentType::Pcb,
             board_filename: Some("demo.kicad_pcb".to_string()),
+            sheet_path: None,
             project: ProjectInfo {
                 name: Some("demo".to_string()),
                 path: Some(PathBuf::from("/tmp/demo")),
@@ -3927,6 +5406,90 @@ mod tests {
         assert_eq!(project.path, "/tmp/demo");
     }
 
+    #[test]
+    fn schematic_document_sheet_path_round_trips_through_proto() {
+        let document = DocumentSpecifier {
+            document_type: DocumentType::Schematic,
+            board_filename: None,
+            sheet_path: None,
+            project: ProjectInfo {
+                name: Some("demo".to_string()),
+                path: None,
+            },
+        }
+        .with_sheet_path(SheetPath {
+            path: vec!["root".to_string(), "child".to_string()],
+            human_readable: "/child".to_string(),
+        });
+
+        let proto = model_document_to_proto(&document);
+        assert_eq!(
+            map_document_specifier(proto).expect("schematic type should map"),
+            document
+        );
+
+        let header = schematic_item_header(&document).expect("schematic header");
+        assert!(header.document.is_some());
+        let board = DocumentSpecifier {
+            document_type: DocumentType::Pcb,
+            ..document
+        };
+        assert!(matches!(
+            schematic_item_header(&board),
+            Err(KiCadError::Config { .. })
+        ));
+    }
+
+    #[test]
+    fn schematic_items_round_trip_through_any() {
+        let items = vec![
+            SchematicItem::Line(SchematicLine {
+                id: Some("line-1".to_string()),
+                start_nm: Some(Vector2Nm { x_nm: 0, y_nm: 0 }),
+                end_nm: Some(Vector2Nm {
+                    x_nm: 2_540_000,
+                    y_nm: 0,
+                }),
+                layer: 0,
+            }),
+            SchematicItem::GlobalLabel(SchematicLabel {
+                id: Some("label-1".to_string()),
+                position_nm: Some(Vector2Nm {
+                    x_nm: 2_540_000,
+                    y_nm: 0,
+                }),
+                text: Some(TextSpec::plain("VBUS")),
+            }),
+            SchematicItem::DirectiveLabel(SchematicLabel {
+                id: None,
+                position_nm: None,
+                text: None,
+            }),
+        ];
+
+        let encoded = items
+            .iter()
+            .cloned()
+            .map(schematic_item_to_any)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("typed items should encode");
+        assert_eq!(
+            encoded[1].type_url,
+            "type.googleapis.com/kiapi.schematic.types.GlobalLabel"
+        );
+        let decoded = decode_schematic_items(encoded).expect("items should decode");
+        assert_eq!(decoded, items);
+        assert_eq!(decoded[1].label().map(SchematicLabel::text), Some("VBUS"));
+
+        let unknown = decode_schematic_item(prost_types::Any {
+            type_url: "type.googleapis.com/kiapi.schematic.types.Symbol".to_string(),
+            value: vec![1, 2, 3],
+        })
+        .expect("unknown payloads decode");
+        assert_eq!(unknown.item_type(), None);
+        assert!(schematic_item_to_any(unknown).is_err());
+    }
+
     #[test]
     fn map_commit_session_maps_commit_id() {
         let response = crate::proto::kiapi::common::commands::BeginCommitResponse {
@@ -4127,6 +5690,197 @@ mod tests {
         );
     }
 
+    #[test]
+    fn net_class_round_trips_schematic_settings_and_via_stacks() {
+        use crate::proto::kiapi::board::types as board_types;
+        use crate::proto::kiapi::common::project as common_project;
+        use crate::proto::kiapi::common::types as common_types;
+
+        let distance = |value_nm| Some(common_types::Distance { value_nm });
+        let color = Some(common_types::Color {
+            r: 0.25,
+            g: 0.5,
+            b: 0.75,
+            a: 1.0,
+        });
+        let round = |diameter_nm| {
+            Some(common_types::Vector2 {
+                x_nm: diameter_nm,
+                y_nm: diameter_nm,
+            })
+        };
+        let copper = |layer: board_types::BoardLayer, diameter_nm| board_types::PadStackLayer {
+            layer: layer as i32,
+            shape: board_types::PadStackShape::PssCircle as i32,
+            size: round(diameter_nm),
+            ..Default::default()
+        };
+        // Front/inner/back stack as KiCad reports it for a four-layer board.
+        let via = |diameter_nm, inner_nm, drill_nm| board_types::PadStack {
+            r#type: board_types::PadStackType::PstFrontInnerBack as i32,
+            layers: vec![
+                board_types::BoardLayer::BlFCu as i32,
+                board_types::BoardLayer::BlIn1Cu as i32,
+                board_types::BoardLayer::BlIn2Cu as i32,
+                board_types::BoardLayer::BlBCu as i32,
+            ],
+            drill: Some(board_types::DrillProperties {
+                start_layer: board_types::BoardLayer::BlFCu as i32,
+                end_layer: board_types::BoardLayer::BlBCu as i32,
+                diameter: round(drill_nm),
+                shape: board_types::DrillShape::DsCircle as i32,
+                capped: 0,
+                filled: board_types::ViaDrillFillingMode::VdfmFilled as i32,
+            }),
+            unconnected_layer_removal: board_types::UnconnectedLayerRemoval::UlrRemove as i32,
+            copper_layers: vec![
+                copper(board_types::BoardLayer::BlFCu, diameter_nm),
+                copper(board_types::BoardLayer::BlIn1Cu, inner_nm),
+                copper(board_types::BoardLayer::BlBCu, diameter_nm),
+            ],
+            front_outer_layers: Some(board_types::PadStackOuterLayer {
+                solder_mask_mode: board_types::SolderMaskMode::SmmMasked as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let proto = common_project::NetClass {
+            name: "HighSpeed".to_string(),
+            priority: Some(2),
+            board: Some(common_project::NetClassBoardSettings {
+                clearance: distance(200_000),
+                track_width: distance(250_000),
+                diff_pair_track_width: distance(150_000),
+                diff_pair_gap: distance(120_000),
+                diff_pair_via_gap: distance(250_000),
+                via_stack: Some(via(600_000, 500_000, 300_000)),
+                microvia_stack: Some(via(300_000, 250_000, 100_000)),
+                color,
+                tuning_profile: Some("USB".to_string()),
+            }),
+            schematic: Some(common_project::NetClassSchematicSettings {
+                wire_width: distance(152_400),
+                bus_width: distance(304_800),
+                color,
+                line_style: Some(common_types::StrokeLineStyle::SlsDash as i32),
+            }),
+            r#type: common_project::NetClassType::NctExplicit as i32,
+            constituents: vec!["HighSpeed".to_string()],
+        };
+
+        let model = map_net_class_info(proto.clone());
+        let board = model
+            .board
+            .as_ref()
+            .expect("board settings should be present");
+        let via_stack = board
+            .via_stack
+            .clone()
+            .expect("via stack should be present");
+        assert_eq!(via_stack.layers.len(), 4);
+        assert_eq!(
+            via_stack
+                .copper_layers
+                .iter()
+                .map(|layer| layer.size_nm.map(|size| size.x_nm))
+                .collect::<Vec<_>>(),
+            vec![Some(600_000), Some(500_000), Some(600_000)]
+        );
+        assert_eq!(
+            via_stack.drill.as_ref().and_then(|drill| drill.diameter_nm),
+            Some(Vector2Nm {
+                x_nm: 300_000,
+                y_nm: 300_000,
+            })
+        );
+        let schematic = model
+            .schematic
+            .as_ref()
+            .expect("schematic settings should be present");
+        assert_eq!(schematic.wire_width_nm, Some(152_400));
+        assert_eq!(schematic.line_style, Some(StrokeLineStyle::Dash));
+
+        let encoded = net_class_info_to_proto(model.clone(), Some(&proto));
+        assert_eq!(encoded, proto);
+        assert_eq!(map_net_class_info(encoded), model);
+
+        // Without the project's padstack the typed fields still round-trip.
+        let fresh = net_class_info_to_proto(model.clone(), None)
+            .board
+            .and_then(|board| board.via_stack)
+            .expect("new via stack");
+        assert_eq!(
+            fresh.r#type,
+            board_types::PadStackType::PstFrontInnerBack as i32
+        );
+        assert_eq!(fresh.front_outer_layers, None);
+        assert_eq!(map_pad_stack(fresh), via_stack);
+
+        let mut resized = model;
+        let board = resized.board.as_mut().expect("board settings");
+        let via_stack = board.via_stack.as_mut().expect("via stack");
+        via_stack.copper_layers[1].size_nm = Some(Vector2Nm {
+            x_nm: 650_000,
+            y_nm: 650_000,
+        });
+        via_stack.drill.as_mut().expect("drill").diameter_nm = Some(Vector2Nm {
+            x_nm: 280_000,
+            y_nm: 280_000,
+        });
+        board
+            .microvia_stack
+            .as_mut()
+            .expect("microvia stack")
+            .copper_layers
+            .truncate(1);
+        let encoded = net_class_info_to_proto(resized, Some(&proto));
+        let encoded_board = encoded.board.expect("board settings");
+        assert_eq!(
+            encoded_board.via_stack,
+            Some(via(600_000, 650_000, 280_000))
+        );
+        let mut expected_microvia = via(300_000, 250_000, 100_000);
+        expected_microvia.r#type = board_types::PadStackType::PstNormal as i32;
+        expected_microvia.copper_layers.truncate(1);
+        assert_eq!(encoded_board.microvia_stack, Some(expected_microvia));
+    }
+
+    #[test]
+    fn title_block_updates_write_bound_variables_only() {
+        let current = TitleBlockInfo {
+            title: "Main board".to_string(),
+            date: "${BUILD_DATE}".to_string(),
+            revision: "${RELEASE_REV}".to_string(),
+            company: "${REVISION}".to_string(),
+            comments: Default::default(),
+        }
+        .with_field(TitleBlockField::Comment3, "${RELEASE_REV}");
+
+        let desired = current
+            .clone()
+            .with_field(TitleBlockField::Date, "2026-10-18")
+            .with_field(TitleBlockField::Revision, "C")
+            .with_field(TitleBlockField::Comment3, "C");
+        let updates =
+            title_block_variable_updates(&current, &desired).expect("bound fields should update");
+        assert_eq!(
+            updates.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("BUILD_DATE".to_string(), "2026-10-18".to_string()),
+                ("RELEASE_REV".to_string(), "C".to_string()),
+            ]
+        );
+
+        let unbound = current.clone().with_field(TitleBlockField::Title, "Other");
+        let err = title_block_variable_updates(&current, &unbound)
+            .expect_err("literal fields cannot be written");
+        assert!(err.to_string().contains("title block title"));
+        let builtin = current.clone().with_field(TitleBlockField::Company, "ACME");
+        assert!(title_block_variable_updates(&current, &builtin).is_err());
+        let conflicting = desired.clone().with_field(TitleBlockField::Comment3, "D");
+        assert!(title_block_variable_updates(&current, &conflicting).is_err());
+    }
+
     #[test]
     fn response_payload_as_any_validates_type_url() {
         let response = crate::proto::kiapi::common::ApiResponse {
@@ -4285,6 +6039,187 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_pcb_item_maps_footprint_pad_stack_geometry() {
+        let pad = crate::proto::kiapi::board::types::Pad {
+            id: Some(crate::proto::kiapi::common::types::Kiid {
+                value: "pad-id".to_string(),
+            }),
+            number: "1".to_string(),
+            r#type: crate::proto::kiapi::board::types::PadType::PtPth as i32,
+            position: Some(crate::proto::kiapi::common::types::Vector2 {
+                x_nm: 1_000,
+                y_nm: 2_000,
+            }),
+            pad_stack: Some(crate::proto::kiapi::board::types::PadStack {
+                layers: vec![
+                    crate::proto::kiapi::board::types::BoardLayer::BlFCu as i32,
+                    crate::proto::kiapi::board::types::BoardLayer::BlBCu as i32,
+                ],
+                drill: Some(crate::proto::kiapi::board::types::DrillProperties {
+                    diameter: Some(crate::proto::kiapi::common::types::Vector2 {
+                        x_nm: 800,
+                        y_nm: 800,
+                    }),
+                    ..Default::default()
+                }),
+                copper_layers: vec![crate::proto::kiapi::board::types::PadStackLayer {
+                    layer: crate::proto::kiapi::board::types::BoardLayer::BlFCu as i32,
+                    shape: crate::proto::kiapi::board::types::PadStackShape::PssOval as i32,
+                    size: Some(crate::proto::kiapi::common::types::Vector2 {
+                        x_nm: 1_600,
+                        y_nm: 1_200,
+                    }),
+                    ..Default::default()
+                }],
+                angle: Some(crate::proto::kiapi::common::types::Angle {
+                    value_degrees: 90.0,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let footprint = crate::proto::kiapi::board::types::FootprintInstance {
+            definition: Some(crate::proto::kiapi::board::types::Footprint {
+                items: vec![prost_types::Any {
+                    type_url: super::envelope::type_url("kiapi.board.types.Pad"),
+                    value: pad.encode_to_vec(),
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let item = prost_types::Any {
+            type_url: super::envelope::type_url("kiapi.board.types.FootprintInstance"),
+            value: footprint.encode_to_vec(),
+        };
+
+        let parsed = decode_pcb_item(item).expect("footprint payload should decode");
+        let PcbItem::Footprint(footprint) = parsed else {
+            panic!("expected footprint item, got {parsed:?}");
+        };
+        assert_eq!(footprint.pad_count, 1);
+        let Some(PcbItem::Pad(pad)) = footprint.items.first() else {
+            panic!("expected nested pad, got {:?}", footprint.items);
+        };
+        let pad_stack = pad.pad_stack.as_ref().expect("pad stack should decode");
+        assert_eq!(pad_stack.angle_deg, Some(90.0));
+        assert_eq!(pad_stack.copper_layers[0].shape, PcbPadStackShape::Oval);
+        assert_eq!(
+            pad_stack.copper_layers[0].size_nm,
+            Some(Vector2Nm {
+                x_nm: 1_600,
+                y_nm: 1_200
+            })
+        );
+        assert_eq!(
+            pad_stack.drill.as_ref().and_then(|drill| drill.diameter_nm),
+            Some(Vector2Nm {
+                x_nm: 800,
+                y_nm: 800
+            })
+        );
+    }
+
+    #[test]
+    fn decode_pcb_item_uses_smallest_via_copper_layer() {
+        use crate::proto::kiapi::board::types::{BoardLayer, PadStack, PadStackLayer, Via};
+        use crate::proto::kiapi::common::types::Vector2;
+
+        let layer = |layer: BoardLayer, size_nm: i64| PadStackLayer {
+            layer: layer as i32,
+            size: Some(Vector2 {
+                x_nm: size_nm,
+                y_nm: size_nm,
+            }),
+            ..Default::default()
+        };
+        let via = Via {
+            pad_stack: Some(PadStack {
+                copper_layers: vec![
+                    layer(BoardLayer::BlFCu, 600_000),
+                    layer(BoardLayer::BlIn1Cu, 450_000),
+                    layer(BoardLayer::BlBCu, 600_000),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let item = prost_types::Any {
+            type_url: super::envelope::type_url("kiapi.board.types.Via"),
+            value: via.encode_to_vec(),
+        };
+
+        let PcbItem::Via(via) = decode_pcb_item(item).expect("via payload should decode") else {
+            panic!("expected via item");
+        };
+        assert_eq!(via.pad_diameter_nm, Some(450_000));
+    }
+
+    #[test]
+    fn decode_pcb_item_maps_zone_net_and_fill_polygons() {
+        let node = |x_nm, y_nm| crate::proto::kiapi::common::types::PolyLineNode {
+            geometry: Some(
+                crate::proto::kiapi::common::types::poly_line_node::Geometry::Point(
+                    crate::proto::kiapi::common::types::Vector2 { x_nm, y_nm },
+                ),
+            ),
+        };
+        let square = crate::proto::kiapi::common::types::PolySet {
+            polygons: vec![crate::proto::kiapi::common::types::PolygonWithHoles {
+                outline: Some(crate::proto::kiapi::common::types::PolyLine {
+                    nodes: vec![node(0, 0), node(10, 0), node(10, 10), node(0, 10)],
+                    closed: true,
+                }),
+                holes: Vec::new(),
+            }],
+        };
+        let zone = crate::proto::kiapi::board::types::Zone {
+            id: Some(crate::proto::kiapi::common::types::Kiid {
+                value: "zone-id".to_string(),
+            }),
+            layers: vec![crate::proto::kiapi::board::types::BoardLayer::BlBCu as i32],
+            outline: Some(square.clone()),
+            filled: true,
+            filled_polygons: vec![crate::proto::kiapi::board::types::ZoneFilledPolygons {
+                layer: crate::proto::kiapi::board::types::BoardLayer::BlBCu as i32,
+                shapes: Some(square),
+            }],
+            settings: Some(
+                crate::proto::kiapi::board::types::zone::Settings::CopperSettings(
+                    crate::proto::kiapi::board::types::CopperZoneSettings {
+                        net: Some(crate::proto::kiapi::board::types::Net {
+                            code: Some(crate::proto::kiapi::board::types::NetCode { value: 2 }),
+                            name: "GND".to_string(),
+                        }),
+                        ..Default::default()
+                    },
+                ),
+            ),
+            ..Default::default()
+        };
+        let item = prost_types::Any {
+            type_url: super::envelope::type_url("kiapi.board.types.Zone"),
+            value: zone.encode_to_vec(),
+        };
+
+        let parsed = decode_pcb_item(item).expect("zone payload should decode");
+        let PcbItem::Zone(zone) = parsed else {
+            panic!("expected zone item, got {parsed:?}");
+        };
+        assert_eq!(zone.net.map(|net| net.name), Some("GND".to_string()));
+        assert_eq!(zone.layers[0].name, "BL_B_Cu");
+        assert_eq!(zone.outline.len(), 1);
+        assert_eq!(zone.filled_polygons.len(), 1);
+        assert_eq!(zone.filled_polygons[0].layer.name, "BL_B_Cu");
+        let outline = zone.filled_polygons[0].polygons[0]
+            .outline
+            .as_ref()
+            .expect("fill outline should decode");
+        assert_eq!(outline.nodes.len(), 4);
+        assert!(outline.closed);
+    }
+
     #[test]
     fn selection_item_detail_reports_via_layers() {
         let via = crate::proto::kiapi::board::types::Via {
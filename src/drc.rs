@@ -0,0 +1,1048 @@
+//! Client-side design-rule checks over a board item snapshot.
+//!
+//! KiCad's own DRC cannot be triggered or read back through the IPC API. This module runs
+//! pluggable [`DrcRule`] implementations against decoded [`PcbItem`]s and can push the resulting
+//! violations back into the editor as DRC markers via
+//! [`KiCadClient::inject_drc_error`](crate::client::KiCadClient::inject_drc_error).
+//!
+//! Geometry is approximated: arcs and circles are flattened into polylines, non-circular pads
+//! use their (rotated) bounding rectangle, and courtyards use the convex hull of their shapes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::geometry::{self, Shape};
+use crate::model::board::{
+    BoardNet, DrcSeverity, NetClassForNetEntry, NetClassInfo, PcbBoardGraphicShape, PcbFootprint,
+    PcbItem, PcbPad, PcbPadType, PcbVia, Vector2Nm,
+};
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+
+const DEFAULT_NET_CLASS: &str = "Default";
+
+#[derive(Clone, Debug, PartialEq)]
+/// Items and rule context a [`DrcRule`] runs against.
+pub struct DrcInput {
+    /// Board items; footprint children are visited through [`PcbFootprint::items`].
+    pub items: Vec<PcbItem>,
+    /// Net class definitions.
+    pub net_classes: Vec<NetClassInfo>,
+    /// Net name to effective net class name.
+    pub net_class_by_net: BTreeMap<String, String>,
+}
+
+impl DrcInput {
+    /// Creates an input without net class context.
+    pub fn new(items: Vec<PcbItem>) -> Self {
+        Self {
+            items,
+            net_classes: Vec::new(),
+            net_class_by_net: BTreeMap::new(),
+        }
+    }
+
+    /// Attaches net class definitions and per-net assignments.
+    pub fn with_net_classes(
+        mut self,
+        net_classes: Vec<NetClassInfo>,
+        assignments: Vec<NetClassForNetEntry>,
+    ) -> Self {
+        self.net_classes = net_classes;
+        self.net_class_by_net = assignments
+            .into_iter()
+            .map(|entry| (entry.net_name, entry.net_class.name))
+            .collect();
+        self
+    }
+
+    /// Object types fetched by [`DrcInput::capture`].
+    pub fn capture_type_codes() -> Vec<i32> {
+        vec![
+            KiCadObjectType::KotPcbFootprint as i32,
+            KiCadObjectType::KotPcbTrace as i32,
+            KiCadObjectType::KotPcbArc as i32,
+            KiCadObjectType::KotPcbVia as i32,
+            KiCadObjectType::KotPcbShape as i32,
+        ]
+    }
+
+    /// Reads items, nets, and net classes from the open board.
+    pub async fn capture(client: &KiCadClient) -> Result<Self, KiCadError> {
+        let items = client
+            .get_items_by_type_codes(Self::capture_type_codes())
+            .await?;
+        let net_classes = client.get_net_classes().await?;
+        let nets = client.get_nets().await?;
+        let assignments = if nets.is_empty() {
+            Vec::new()
+        } else {
+            client.get_netclass_for_nets(nets).await?
+        };
+        Ok(Self::new(items).with_net_classes(net_classes, assignments))
+    }
+
+    /// Net class that applies to `net`, falling back to the `Default` class.
+    pub fn net_class_for(&self, net: &BoardNet) -> Option<&NetClassInfo> {
+        let name = self
+            .net_class_by_net
+            .get(&net.name)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_NET_CLASS);
+        self.net_classes.iter().find(|class| class.name == name)
+    }
+
+    /// Footprints in the snapshot.
+    pub fn footprints(&self) -> impl Iterator<Item = &PcbFootprint> {
+        self.items.iter().filter_map(|item| match item {
+            PcbItem::Footprint(footprint) => Some(footprint),
+            _ => None,
+        })
+    }
+
+    /// Top-level items followed by footprint children, each paired with its parent footprint.
+    ///
+    /// Items sharing an id are visited once.
+    pub fn flattened_items(&self) -> Vec<(&PcbItem, Option<&PcbFootprint>)> {
+        let children = self.footprints().flat_map(|footprint| {
+            footprint
+                .items
+                .iter()
+                .map(move |item| (item, Some(footprint)))
+        });
+        let mut seen = BTreeSet::new();
+        self.items
+            .iter()
+            .map(|item| (item, None))
+            .chain(children)
+            .filter(|(item, _)| item.id().is_none_or(|id| seen.insert(id)))
+            .collect()
+    }
+
+    /// All pads, including those nested in footprints.
+    pub fn pads(&self) -> Vec<&PcbPad> {
+        self.flattened_items()
+            .into_iter()
+            .filter_map(|(item, _)| match item {
+                PcbItem::Pad(pad) => Some(pad),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All vias.
+    pub fn vias(&self) -> Vec<&PcbVia> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                PcbItem::Via(via) => Some(via),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All graphic shapes on `layer_id`, including footprint graphics.
+    pub fn graphics_on_layer(&self, layer_id: i32) -> Vec<&PcbBoardGraphicShape> {
+        self.flattened_items()
+            .into_iter()
+            .filter_map(|(item, _)| match item {
+                PcbItem::BoardGraphicShape(shape) if shape.layer.id == layer_id => Some(shape),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// One rule violation.
+pub struct DrcViolation {
+    /// Name of the rule that produced the violation.
+    pub rule: String,
+    /// Severity used when injecting a marker.
+    pub severity: DrcSeverity,
+    /// Human-readable description.
+    pub message: String,
+    /// Marker location, when known.
+    pub position_nm: Option<Vector2Nm>,
+    /// Ids of the offending items.
+    pub item_ids: Vec<String>,
+}
+
+impl DrcViolation {
+    /// Message text used for the injected editor marker.
+    pub fn marker_message(&self) -> String {
+        format!("[{}] {}", self.rule, self.message)
+    }
+}
+
+/// A design rule evaluated on the client.
+pub trait DrcRule: Send + Sync {
+    /// Short rule name used in reports and marker messages.
+    fn name(&self) -> &str;
+
+    /// Returns every violation of this rule in `input`.
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation>;
+}
+
+#[derive(Default)]
+/// Ordered set of rules run together.
+pub struct DrcChecker {
+    rules: Vec<Box<dyn DrcRule>>,
+}
+
+impl DrcChecker {
+    /// Creates an empty checker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a checker with every built-in rule at its default settings.
+    pub fn with_default_rules() -> Self {
+        Self::new()
+            .rule(MinTrackWidthRule::default())
+            .rule(MinAnnularRingRule::default())
+            .rule(ViaInPadRule::default())
+            .rule(SilkOverPadRule::default())
+            .rule(CourtyardOverlapRule::default())
+            .rule(CopperToEdgeRule::default())
+    }
+
+    /// Appends a rule.
+    pub fn rule(mut self, rule: impl DrcRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Names of the configured rules, in run order.
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    /// Runs every rule and concatenates the violations.
+    pub fn run(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.check(input))
+            .collect()
+    }
+}
+
+/// Pushes violations into the editor as DRC markers, returning the created marker ids.
+pub async fn inject_violations(
+    client: &KiCadClient,
+    violations: &[DrcViolation],
+) -> Result<Vec<Option<String>>, KiCadError> {
+    let mut markers = Vec::with_capacity(violations.len());
+    for violation in violations {
+        markers.push(
+            client
+                .inject_drc_error(
+                    violation.severity,
+                    violation.marker_message(),
+                    violation.position_nm,
+                    violation.item_ids.clone(),
+                )
+                .await?,
+        );
+    }
+    Ok(markers)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Tracks and arcs narrower than their net class track width.
+pub struct MinTrackWidthRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+    /// Per-class minimums that take precedence over the class track width.
+    pub min_width_by_class_nm: BTreeMap<String, i64>,
+    /// Minimum used when neither an override nor a class width applies.
+    pub fallback_min_width_nm: Option<i64>,
+}
+
+impl Default for MinTrackWidthRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Error,
+            min_width_by_class_nm: BTreeMap::new(),
+            fallback_min_width_nm: None,
+        }
+    }
+}
+
+impl MinTrackWidthRule {
+    fn min_width_for(&self, input: &DrcInput, net: Option<&BoardNet>) -> Option<(String, i64)> {
+        let class = net.and_then(|net| input.net_class_for(net));
+        let class_name = class
+            .map(|class| class.name.clone())
+            .unwrap_or_else(|| DEFAULT_NET_CLASS.to_string());
+        if let Some(min) = self.min_width_by_class_nm.get(&class_name) {
+            return Some((class_name, *min));
+        }
+        class
+            .and_then(|class| class.board.as_ref())
+            .and_then(|board| board.track_width_nm)
+            .or(self.fallback_min_width_nm)
+            .map(|min| (class_name, min))
+    }
+}
+
+impl DrcRule for MinTrackWidthRule {
+    fn name(&self) -> &str {
+        "min_track_width"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let mut violations = Vec::new();
+        for item in &input.items {
+            let (id, width, net, position) = match item {
+                PcbItem::Track(track) => (
+                    track.id.as_ref(),
+                    track.width_nm,
+                    track.net.as_ref(),
+                    midpoint(track.start_nm, track.end_nm),
+                ),
+                PcbItem::Arc(arc) => (arc.id.as_ref(), arc.width_nm, arc.net.as_ref(), arc.mid_nm),
+                _ => continue,
+            };
+            let Some(width) = width else {
+                continue;
+            };
+            let Some((class_name, min)) = self.min_width_for(input, net) else {
+                continue;
+            };
+            if width < min {
+                violations.push(DrcViolation {
+                    rule: self.name().to_string(),
+                    severity: self.severity,
+                    message: format!(
+                        "track width {width} nm is below {min} nm required by net class `{class_name}`"
+                    ),
+                    position_nm: position,
+                    item_ids: id.cloned().into_iter().collect(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Via and plated-pad copper rings thinner than a minimum.
+pub struct MinAnnularRingRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+    /// Minimum ring width in nm.
+    pub min_annular_ring_nm: i64,
+}
+
+impl Default for MinAnnularRingRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Error,
+            min_annular_ring_nm: 100_000,
+        }
+    }
+}
+
+impl DrcRule for MinAnnularRingRule {
+    fn name(&self) -> &str {
+        "min_annular_ring"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let mut violations = Vec::new();
+        let mut report = |ring: i64, id: Option<&String>, position: Option<Vector2Nm>| {
+            if ring < self.min_annular_ring_nm {
+                violations.push(DrcViolation {
+                    rule: self.name().to_string(),
+                    severity: self.severity,
+                    message: format!(
+                        "annular ring {ring} nm is below minimum {} nm",
+                        self.min_annular_ring_nm
+                    ),
+                    position_nm: position,
+                    item_ids: id.cloned().into_iter().collect(),
+                });
+            }
+        };
+
+        for via in input.vias() {
+            if let (Some(pad), Some(drill)) = (via.pad_diameter_nm, via.drill_diameter_nm) {
+                report((pad - drill) / 2, via.id.as_ref(), via.position_nm);
+            }
+        }
+
+        for pad in input.pads() {
+            if pad.pad_type != PcbPadType::Pth {
+                continue;
+            }
+            let Some(pad_stack) = pad.pad_stack.as_ref() else {
+                continue;
+            };
+            let Some(drill) = pad_stack.drill.as_ref().and_then(|drill| drill.diameter_nm) else {
+                continue;
+            };
+            let hole = drill.x_nm.max(drill.y_nm);
+            let ring = pad_stack
+                .copper_layers
+                .iter()
+                .filter_map(|layer| layer.size_nm)
+                .map(|size| (size.x_nm.min(size.y_nm) - hole) / 2)
+                .min();
+            if let Some(ring) = ring {
+                report(ring, pad.id.as_ref(), pad.position_nm);
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Vias placed inside pad copper.
+pub struct ViaInPadRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+    /// Skip vias on the same net as the pad.
+    pub allow_same_net: bool,
+}
+
+impl Default for ViaInPadRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Warning,
+            allow_same_net: false,
+        }
+    }
+}
+
+impl DrcRule for ViaInPadRule {
+    fn name(&self) -> &str {
+        "via_in_pad"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let pads = input.pads();
+        let mut violations = Vec::new();
+        for via in input.vias() {
+            let Some(position) = via.position_nm else {
+                continue;
+            };
+            let radius = via.pad_diameter_nm.unwrap_or(0) as f64 / 2.0;
+            let via_shape = Shape::point(position, radius);
+            let span = via_copper_span(via);
+
+            for pad in &pads {
+                if self.allow_same_net && same_net(via.net.as_ref(), pad.net.as_ref()) {
+                    continue;
+                }
+                let overlaps = pad_copper_shapes(pad).into_iter().any(|(layer, shape)| {
+                    span.is_none_or(|(top, bottom)| (top..=bottom).contains(&layer))
+                        && geometry::shapes_overlap(&via_shape, &shape)
+                });
+                if overlaps {
+                    violations.push(DrcViolation {
+                        rule: self.name().to_string(),
+                        severity: self.severity,
+                        message: format!("via overlaps pad {}", pad.number),
+                        position_nm: Some(position),
+                        item_ids: ids([via.id.as_ref(), pad.id.as_ref()]),
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Silkscreen graphics overlapping (or too close to) exposed pad copper.
+pub struct SilkOverPadRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+    /// Required gap between silkscreen and pads in nm.
+    pub min_clearance_nm: i64,
+}
+
+impl Default for SilkOverPadRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Warning,
+            min_clearance_nm: 0,
+        }
+    }
+}
+
+impl DrcRule for SilkOverPadRule {
+    fn name(&self) -> &str {
+        "silk_over_pad"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let pads = input.pads();
+        let mut violations = Vec::new();
+        for (silk_layer, copper_layer) in [
+            (BoardLayer::BlFSilkS as i32, BoardLayer::BlFCu as i32),
+            (BoardLayer::BlBSilkS as i32, BoardLayer::BlBCu as i32),
+        ] {
+            let pad_shapes: Vec<_> = pads
+                .iter()
+                .flat_map(|pad| {
+                    pad_copper_shapes(pad)
+                        .into_iter()
+                        .filter(|(layer, _)| *layer == copper_layer)
+                        .map(move |(_, shape)| (*pad, shape))
+                })
+                .collect();
+
+            for silk in input.graphics_on_layer(silk_layer) {
+                for silk_shape in graphic_shapes(silk) {
+                    for (pad, pad_shape) in &pad_shapes {
+                        let gap = geometry::shape_gap_nm(&silk_shape, pad_shape);
+                        if gap <= 0.0 || gap < self.min_clearance_nm as f64 {
+                            violations.push(DrcViolation {
+                                rule: self.name().to_string(),
+                                severity: self.severity,
+                                message: format!(
+                                    "silkscreen on {} is {:.0} nm from pad {}",
+                                    silk.layer.name, gap, pad.number
+                                ),
+                                position_nm: pad.position_nm,
+                                item_ids: ids([silk.id.as_ref(), pad.id.as_ref()]),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Footprints whose courtyards overlap on the same side of the board.
+pub struct CourtyardOverlapRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+}
+
+impl Default for CourtyardOverlapRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Error,
+        }
+    }
+}
+
+impl DrcRule for CourtyardOverlapRule {
+    fn name(&self) -> &str {
+        "courtyard_overlap"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let mut violations = Vec::new();
+        for layer in [BoardLayer::BlFCrtYd as i32, BoardLayer::BlBCrtYd as i32] {
+            let courtyards: Vec<_> = input
+                .footprints()
+                .filter_map(|footprint| {
+                    courtyard_hull(footprint, layer).map(|hull| (footprint, hull))
+                })
+                .collect();
+
+            for (index, (a, hull_a)) in courtyards.iter().enumerate() {
+                for (b, hull_b) in &courtyards[index + 1..] {
+                    if geometry::shapes_overlap(hull_a, hull_b) {
+                        violations.push(DrcViolation {
+                            rule: self.name().to_string(),
+                            severity: self.severity,
+                            message: format!(
+                                "courtyards of {} and {} overlap",
+                                a.reference.as_deref().unwrap_or("?"),
+                                b.reference.as_deref().unwrap_or("?")
+                            ),
+                            position_nm: midpoint(a.position_nm, b.position_nm),
+                            item_ids: ids([a.id.as_ref(), b.id.as_ref()]),
+                        });
+                    }
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Copper closer to the board edge (Edge.Cuts) than a minimum.
+pub struct CopperToEdgeRule {
+    /// Severity of reported violations.
+    pub severity: DrcSeverity,
+    /// Required copper-to-edge gap in nm.
+    pub min_clearance_nm: i64,
+}
+
+impl Default for CopperToEdgeRule {
+    fn default() -> Self {
+        Self {
+            severity: DrcSeverity::Error,
+            min_clearance_nm: 300_000,
+        }
+    }
+}
+
+impl DrcRule for CopperToEdgeRule {
+    fn name(&self) -> &str {
+        "copper_to_edge"
+    }
+
+    fn check(&self, input: &DrcInput) -> Vec<DrcViolation> {
+        let edges: Vec<Shape> = input
+            .graphics_on_layer(BoardLayer::BlEdgeCuts as i32)
+            .into_iter()
+            .flat_map(|shape| geometry::graphic_shapes(&shape.geometry, 0, false))
+            .collect();
+        if edges.is_empty() {
+            return Vec::new();
+        }
+
+        let mut violations = Vec::new();
+        for (item, _) in input.flattened_items() {
+            let (id, shapes, position) = match item {
+                PcbItem::Track(track) => match (track.start_nm, track.end_nm) {
+                    (Some(start), Some(end)) => (
+                        track.id.as_ref(),
+                        vec![Shape::segment(start, end, track.width_nm.unwrap_or(0))],
+                        midpoint(Some(start), Some(end)),
+                    ),
+                    _ => continue,
+                },
+                PcbItem::Arc(arc) => match (arc.start_nm, arc.mid_nm, arc.end_nm) {
+                    (Some(start), Some(mid), Some(end)) => (
+                        arc.id.as_ref(),
+                        vec![Shape {
+                            points: geometry::arc_points(start, mid, end),
+                            closed: false,
+                            filled: false,
+                            half_width_nm: arc.width_nm.unwrap_or(0) as f64 / 2.0,
+                        }],
+                        Some(mid),
+                    ),
+                    _ => continue,
+                },
+                PcbItem::Via(via) => match via.position_nm {
+                    Some(position) => (
+                        via.id.as_ref(),
+                        vec![Shape::point(
+                            position,
+                            via.pad_diameter_nm.unwrap_or(0) as f64 / 2.0,
+                        )],
+                        Some(position),
+                    ),
+                    None => continue,
+                },
+                PcbItem::Pad(pad) => (
+                    pad.id.as_ref(),
+                    pad_copper_shapes(pad)
+                        .into_iter()
+                        .map(|(_, shape)| shape)
+                        .collect(),
+                    pad.position_nm,
+                ),
+                PcbItem::BoardGraphicShape(shape) if is_copper_layer(shape.layer.id) => {
+                    (shape.id.as_ref(), graphic_shapes(shape), None)
+                }
+                _ => continue,
+            };
+
+            let gap = shapes
+                .iter()
+                .flat_map(|shape| {
+                    edges
+                        .iter()
+                        .map(move |edge| geometry::shape_gap_nm(shape, edge))
+                })
+                .fold(f64::INFINITY, f64::min);
+            if gap < self.min_clearance_nm as f64 {
+                violations.push(DrcViolation {
+                    rule: self.name().to_string(),
+                    severity: self.severity,
+                    message: format!(
+                        "copper is {gap:.0} nm from board edge; minimum is {} nm",
+                        self.min_clearance_nm
+                    ),
+                    position_nm: position,
+                    item_ids: id.cloned().into_iter().collect(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+fn ids<const N: usize>(values: [Option<&String>; N]) -> Vec<String> {
+    values.into_iter().flatten().cloned().collect()
+}
+
+fn midpoint(a: Option<Vector2Nm>, b: Option<Vector2Nm>) -> Option<Vector2Nm> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(Vector2Nm {
+            x_nm: (a.x_nm + b.x_nm) / 2,
+            y_nm: (a.y_nm + b.y_nm) / 2,
+        }),
+        (Some(point), None) | (None, Some(point)) => Some(point),
+        (None, None) => None,
+    }
+}
+
+fn same_net(a: Option<&BoardNet>, b: Option<&BoardNet>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if a.code == b.code && a.code != 0)
+}
+
+fn is_copper_layer(layer_id: i32) -> bool {
+    (BoardLayer::BlFCu as i32..=BoardLayer::BlBCu as i32).contains(&layer_id)
+}
+
+/// Copper layer span of a via as `(top, bottom)` layer ids, when the drill span is known.
+fn via_copper_span(via: &PcbVia) -> Option<(i32, i32)> {
+    let layers = via.layers.as_ref()?;
+    let start = layers.drill_start_layer.as_ref()?.id;
+    let end = layers.drill_end_layer.as_ref()?.id;
+    if !is_copper_layer(start) || !is_copper_layer(end) {
+        return None;
+    }
+    Some((start.min(end), start.max(end)))
+}
+
+/// Copper shapes of a pad keyed by copper layer id.
+fn pad_copper_shapes(pad: &PcbPad) -> Vec<(i32, Shape)> {
+    let (Some(position), Some(pad_stack)) = (pad.position_nm, pad.pad_stack.as_ref()) else {
+        return Vec::new();
+    };
+    let angle = pad_stack.angle_deg.unwrap_or(0.0);
+    let Some(default_copper) = pad_stack.copper_layers.first() else {
+        return Vec::new();
+    };
+
+    let copper_layers: Vec<i32> = pad_stack
+        .layers
+        .iter()
+        .map(|layer| layer.id)
+        .filter(|layer| is_copper_layer(*layer))
+        .collect();
+    let copper_layers = if copper_layers.is_empty() {
+        pad_stack
+            .copper_layers
+            .iter()
+            .map(|layer| layer.layer.id)
+            .collect()
+    } else {
+        copper_layers
+    };
+
+    copper_layers
+        .into_iter()
+        .filter_map(|layer| {
+            let copper = pad_stack
+                .copper_layers
+                .iter()
+                .find(|copper| copper.layer.id == layer)
+                .unwrap_or(default_copper);
+            geometry::pad_shape(position, copper, angle).map(|shape| (layer, shape))
+        })
+        .collect()
+}
+
+fn graphic_shapes(shape: &PcbBoardGraphicShape) -> Vec<Shape> {
+    geometry::graphic_shapes(
+        &shape.geometry,
+        shape.stroke_width_nm.unwrap_or(0),
+        shape.filled,
+    )
+}
+
+/// Filled convex hull of a footprint's courtyard graphics on `layer_id`.
+fn courtyard_hull(footprint: &PcbFootprint, layer_id: i32) -> Option<Shape> {
+    let points: Vec<Vector2Nm> = footprint
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            PcbItem::BoardGraphicShape(shape) if shape.layer.id == layer_id => Some(shape),
+            _ => None,
+        })
+        .flat_map(|shape| geometry::graphic_shapes(&shape.geometry, 0, false))
+        .flat_map(|shape| shape.points)
+        .collect();
+    let hull = geometry::convex_hull(points);
+    (hull.len() >= 3).then(|| Shape::polygon(hull))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        CopperToEdgeRule, CourtyardOverlapRule, DrcChecker, DrcInput, DrcRule, DrcViolation,
+        MinAnnularRingRule, MinTrackWidthRule, SilkOverPadRule, ViaInPadRule,
+    };
+    use crate::model::board::{
+        BoardLayerInfo, BoardNet, DrcSeverity, NetClassBoardSettings, NetClassForNetEntry,
+        NetClassInfo, NetClassType, PcbBoardGraphicShape, PcbDrill, PcbFootprint, PcbItem, PcbPad,
+        PcbPadStack, PcbPadStackCopperLayer, PcbPadStackShape, PcbPadType, PcbTrack, PcbVia,
+        PcbViaType, Vector2Nm,
+    };
+    use crate::model::common::TextShapeGeometry;
+
+    fn v(x_nm: i64, y_nm: i64) -> Vector2Nm {
+        Vector2Nm { x_nm, y_nm }
+    }
+
+    fn layer(id: i32) -> BoardLayerInfo {
+        BoardLayerInfo {
+            id,
+            name: format!("L{id}"),
+        }
+    }
+
+    fn net(code: i32, name: &str) -> BoardNet {
+        BoardNet {
+            code,
+            name: name.to_string(),
+        }
+    }
+
+    fn smd_pad(id: &str, position: Vector2Nm, size: Vector2Nm) -> PcbPad {
+        PcbPad {
+            id: Some(id.to_string()),
+            number: "1".to_string(),
+            pad_type: PcbPadType::Smd,
+            position_nm: Some(position),
+            net: Some(net(1, "GND")),
+            pad_stack: Some(PcbPadStack {
+                layers: vec![layer(3)],
+                copper_layers: vec![PcbPadStackCopperLayer {
+                    layer: layer(3),
+                    shape: PcbPadStackShape::Rectangle,
+                    size_nm: Some(size),
+                    offset_nm: None,
+                }],
+                drill: None,
+                angle_deg: None,
+            }),
+        }
+    }
+
+    fn via(id: &str, position: Vector2Nm, pad: i64, drill: i64) -> PcbVia {
+        PcbVia {
+            id: Some(id.to_string()),
+            position_nm: Some(position),
+            via_type: PcbViaType::Through,
+            layers: None,
+            net: Some(net(2, "VCC")),
+            drill_diameter_nm: Some(drill),
+            pad_diameter_nm: Some(pad),
+        }
+    }
+
+    fn rect(id: &str, layer_id: i32, top_left: Vector2Nm, bottom_right: Vector2Nm) -> PcbItem {
+        PcbItem::BoardGraphicShape(PcbBoardGraphicShape {
+            id: Some(id.to_string()),
+            layer: layer(layer_id),
+            net: None,
+            geometry_kind: None,
+            geometry: TextShapeGeometry::Rectangle {
+                top_left_nm: Some(top_left),
+                bottom_right_nm: Some(bottom_right),
+                corner_radius_nm: None,
+            },
+            stroke_width_nm: Some(100_000),
+            filled: false,
+        })
+    }
+
+    fn footprint(id: &str, reference: &str, items: Vec<PcbItem>) -> PcbFootprint {
+        PcbFootprint {
+            id: Some(id.to_string()),
+            reference: Some(reference.to_string()),
+            position_nm: Some(v(0, 0)),
+            orientation_deg: None,
+            layer: layer(3),
+            pad_count: 0,
+            value: None,
+            library_id: None,
+            datasheet: None,
+            attributes: None,
+            items,
+        }
+    }
+
+    #[test]
+    fn min_track_width_uses_net_class_width_and_overrides() {
+        let class = NetClassInfo {
+            name: "Power".to_string(),
+            priority: Some(1),
+            class_type: NetClassType::Explicit,
+            constituents: vec!["VCC".to_string()],
+            board: Some(NetClassBoardSettings {
+                clearance_nm: None,
+                track_width_nm: Some(500_000),
+                diff_pair_track_width_nm: None,
+                diff_pair_gap_nm: None,
+                diff_pair_via_gap_nm: None,
+                color: None,
+                tuning_profile: None,
+                via_stack: None,
+                microvia_stack: None,
+            }),
+            schematic: None,
+        };
+        let track = PcbItem::Track(PcbTrack {
+            id: Some("t1".to_string()),
+            start_nm: Some(v(0, 0)),
+            end_nm: Some(v(1_000_000, 0)),
+            width_nm: Some(250_000),
+            layer: layer(3),
+            net: Some(net(2, "VCC")),
+        });
+        let input = DrcInput::new(vec![track]).with_net_classes(
+            vec![class.clone()],
+            vec![NetClassForNetEntry {
+                net_name: "VCC".to_string(),
+                net_class: class,
+            }],
+        );
+
+        let violations = MinTrackWidthRule::default().check(&input);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].item_ids, vec!["t1".to_string()]);
+        assert_eq!(violations[0].position_nm, Some(v(500_000, 0)));
+
+        let relaxed = MinTrackWidthRule {
+            min_width_by_class_nm: BTreeMap::from([("Power".to_string(), 200_000)]),
+            ..MinTrackWidthRule::default()
+        };
+        assert!(relaxed.check(&input).is_empty());
+    }
+
+    #[test]
+    fn min_annular_ring_checks_vias_and_plated_pads() {
+        let mut pth = smd_pad("p1", v(0, 0), v(1_000_000, 1_000_000));
+        pth.pad_type = PcbPadType::Pth;
+        if let Some(pad_stack) = pth.pad_stack.as_mut() {
+            pad_stack.drill = Some(PcbDrill {
+                start_layer: layer(3),
+                end_layer: layer(34),
+                diameter_nm: Some(v(900_000, 900_000)),
+            });
+        }
+        let input = DrcInput::new(vec![
+            PcbItem::Via(via("v1", v(0, 0), 600_000, 300_000)),
+            PcbItem::Via(via("v2", v(0, 0), 450_000, 300_000)),
+            PcbItem::Footprint(footprint("f1", "J1", vec![PcbItem::Pad(pth)])),
+        ]);
+
+        let flagged: Vec<_> = MinAnnularRingRule::default()
+            .check(&input)
+            .into_iter()
+            .flat_map(|violation| violation.item_ids)
+            .collect();
+        assert_eq!(flagged, vec!["v2".to_string(), "p1".to_string()]);
+    }
+
+    #[test]
+    fn via_in_pad_reports_via_and_pad_ids() {
+        let pad = smd_pad("p1", v(0, 0), v(1_000_000, 600_000));
+        let input = DrcInput::new(vec![
+            PcbItem::Via(via("v1", v(100_000, 0), 400_000, 200_000)),
+            PcbItem::Via(via("v2", v(5_000_000, 0), 400_000, 200_000)),
+            PcbItem::Footprint(footprint("f1", "U1", vec![PcbItem::Pad(pad)])),
+        ]);
+
+        let violations = ViaInPadRule::default().check(&input);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].item_ids,
+            vec!["v1".to_string(), "p1".to_string()]
+        );
+        assert_eq!(violations[0].severity, DrcSeverity::Warning);
+    }
+
+    #[test]
+    fn silk_over_pad_flags_only_overlapping_silk() {
+        let pad = smd_pad("p1", v(0, 0), v(1_000_000, 1_000_000));
+        let input = DrcInput::new(vec![
+            rect("s1", 40, v(-200_000, -200_000), v(200_000, 200_000)),
+            rect("s2", 40, v(2_000_000, 2_000_000), v(3_000_000, 3_000_000)),
+            PcbItem::Footprint(footprint("f1", "U1", vec![PcbItem::Pad(pad)])),
+        ]);
+
+        let violations = SilkOverPadRule::default().check(&input);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].item_ids[0], "s1");
+    }
+
+    #[test]
+    fn courtyard_overlap_compares_footprints_on_same_side() {
+        let input = DrcInput::new(vec![
+            PcbItem::Footprint(footprint(
+                "f1",
+                "R1",
+                vec![rect("c1", 50, v(0, 0), v(1_000_000, 1_000_000))],
+            )),
+            PcbItem::Footprint(footprint(
+                "f2",
+                "R2",
+                vec![rect("c2", 50, v(500_000, 500_000), v(1_500_000, 1_500_000))],
+            )),
+            PcbItem::Footprint(footprint(
+                "f3",
+                "R3",
+                vec![rect("c3", 49, v(0, 0), v(1_000_000, 1_000_000))],
+            )),
+        ]);
+
+        let violations = CourtyardOverlapRule::default().check(&input);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message, "courtyards of R1 and R2 overlap");
+    }
+
+    #[test]
+    fn copper_to_edge_measures_gap_to_outline() {
+        let input = DrcInput::new(vec![
+            rect("edge", 47, v(0, 0), v(10_000_000, 10_000_000)),
+            PcbItem::Via(via("near", v(200_000, 5_000_000), 200_000, 100_000)),
+            PcbItem::Via(via("far", v(5_000_000, 5_000_000), 200_000, 100_000)),
+        ]);
+
+        let violations = CopperToEdgeRule::default().check(&input);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].item_ids, vec!["near".to_string()]);
+    }
+
+    struct AlwaysRule;
+
+    impl DrcRule for AlwaysRule {
+        fn name(&self) -> &str {
+            "always"
+        }
+
+        fn check(&self, _input: &DrcInput) -> Vec<DrcViolation> {
+            vec![DrcViolation {
+                rule: self.name().to_string(),
+                severity: DrcSeverity::Info,
+                message: "custom".to_string(),
+                position_nm: None,
+                item_ids: Vec::new(),
+            }]
+        }
+    }
+
+    #[test]
+    fn checker_runs_custom_rules_in_order() {
+        let checker = DrcChecker::new().rule(AlwaysRule);
+        assert_eq!(checker.rule_names(), vec!["always"]);
+
+        let violations = checker.run(&DrcInput::new(Vec::new()));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].marker_message(), "[always] custom");
+        assert_eq!(DrcChecker::with_default_rules().rule_names().len(), 6);
+    }
+}
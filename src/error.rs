@@ -2,6 +2,8 @@ use std::time::Duration;
 
 use thiserror::Error;
 
+use crate::outline::OutlineIssue;
+
 #[derive(Debug, Error)]
 /// Error type returned by `kicad-ipc-rs` operations.
 pub enum KiCadError {
@@ -45,6 +47,24 @@ pub enum KiCadError {
     #[error("invalid API response: {reason}")]
     InvalidResponse { reason: String },
 
+    /// KiCad s-expression text could not be parsed.
+    #[error("invalid s-expression at line {line}, column {column}: {reason}")]
+    SExpression {
+        /// 1-based line of the offending token.
+        line: usize,
+        /// 1-based column of the offending token.
+        column: usize,
+        /// What the parser expected.
+        reason: String,
+    },
+
+    /// Edge.Cuts shapes do not form a closed board outline.
+    #[error("invalid board outline: {}", format_outline_issues(.issues))]
+    BoardOutline {
+        /// Every gap and crossing found, with its location.
+        issues: Vec<OutlineIssue>,
+    },
+
     /// Response payload was missing when required.
     #[error("API response missing payload for `{expected_type_url}`")]
     MissingPayload { expected_type_url: String },
@@ -88,3 +108,11 @@ pub enum KiCadError {
     #[error("multiple PCB documents are open; unable to choose one board context: {boards:?}")]
     AmbiguousBoardSelection { boards: Vec<String> },
 }
+
+fn format_outline_issues(issues: &[OutlineIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,741 @@
+//! Excellon drill files from via and pad padstacks.
+//!
+//! [`DrillSet::from_items`] collects every drilled via and pad, splits plated from non-plated
+//! holes, and (by default) gives each blind/buried layer pair its own file. Oval pad drills are
+//! written as `G85` slots. Coordinates are relative to [`ExcellonOptions::origin`] with the Y axis
+//! pointing up, in decimal format.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::geometry;
+use crate::model::board::{
+    BoardLayerInfo, BoardOriginKind, PcbItem, PcbPad, PcbPadType, PcbVia, Vector2Nm,
+};
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+use crate::text_out::write_to_string;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// Length unit for coordinates and tool diameters.
+pub enum ExcellonUnits {
+    /// Millimetres (`METRIC`).
+    #[default]
+    Millimeters,
+    /// Inches (`INCH`).
+    Inches,
+}
+
+impl ExcellonUnits {
+    fn convert_nm(self, value_nm: i64) -> f64 {
+        match self {
+            Self::Millimeters => value_nm as f64 / 1_000_000.0,
+            Self::Inches => value_nm as f64 / 25_400_000.0,
+        }
+    }
+
+    fn coordinate_decimals(self) -> usize {
+        match self {
+            Self::Millimeters => 4,
+            Self::Inches => 5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Controls file splitting, units, and coordinate origin.
+pub struct ExcellonOptions {
+    /// Output unit.
+    pub units: ExcellonUnits,
+    /// Coordinate origin in board coordinates; `None` uses the absolute origin, or the board's
+    /// drill/place origin when read through [`DrillSet::capture`].
+    pub origin: Option<Vector2Nm>,
+    /// Write one plated file per blind/buried layer pair instead of a single PTH file.
+    pub separate_layer_pairs: bool,
+}
+
+impl Default for ExcellonOptions {
+    fn default() -> Self {
+        Self {
+            units: ExcellonUnits::Millimeters,
+            origin: None,
+            separate_layer_pairs: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Copper layers a drill spans.
+pub struct DrillLayerPair {
+    /// First drilled layer.
+    pub start: BoardLayerInfo,
+    /// Last drilled layer.
+    pub end: BoardLayerInfo,
+}
+
+impl DrillLayerPair {
+    /// Span between two copper layers given in either order; the lower layer id becomes `start`.
+    pub fn new(first: BoardLayerInfo, second: BoardLayerInfo) -> Self {
+        if first.id <= second.id {
+            Self {
+                start: first,
+                end: second,
+            }
+        } else {
+            Self {
+                start: second,
+                end: first,
+            }
+        }
+    }
+
+    /// Front-to-back span.
+    pub fn through() -> Self {
+        Self {
+            start: layer_info(BoardLayer::BlFCu),
+            end: layer_info(BoardLayer::BlBCu),
+        }
+    }
+
+    /// Whether the pair spans the whole board.
+    pub fn is_through(&self) -> bool {
+        self.start.id == BoardLayer::BlFCu as i32 && self.end.id == BoardLayer::BlBCu as i32
+    }
+
+    fn label(&self) -> String {
+        format!(
+            "{}-{}",
+            short_layer_name(&self.start),
+            short_layer_name(&self.end)
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One drilled hole or routed slot.
+pub struct DrillHole {
+    /// Source via or pad id.
+    pub item_id: Option<String>,
+    /// Hole centre in board coordinates.
+    pub position_nm: Vector2Nm,
+    /// Tool diameter in nm (the slot width for slots).
+    pub diameter_nm: i64,
+    /// Slot end points for oval holes.
+    pub slot_nm: Option<[Vector2Nm; 2]>,
+    /// Whether the hole is plated.
+    pub plated: bool,
+    /// Drilled layer span.
+    pub layer_pair: DrillLayerPair,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// One tool in a drill file.
+pub struct DrillTool {
+    /// Tool number (`T<n>`), assigned by ascending diameter.
+    pub number: usize,
+    /// Tool diameter in nm.
+    pub diameter_nm: i64,
+    /// Holes drilled with this tool, slots included.
+    pub hole_count: usize,
+    /// Slots routed with this tool.
+    pub slot_count: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Holes sharing a plating type and layer span, plus their tool list.
+pub struct DrillFile {
+    /// Whether the file holds plated holes.
+    pub plated: bool,
+    /// Layer span; `None` when blind/buried pairs are merged into one plated file.
+    pub layer_pair: Option<DrillLayerPair>,
+    /// Tools in ascending diameter order.
+    pub tools: Vec<DrillTool>,
+    /// Holes in tool order.
+    pub holes: Vec<DrillHole>,
+    /// Output unit.
+    pub units: ExcellonUnits,
+    /// Coordinate origin in board coordinates.
+    pub origin: Vector2Nm,
+}
+
+impl DrillFile {
+    /// File name for this drill file, e.g. `board-PTH.drl` or `board-F_Cu-In2_Cu.drl`.
+    pub fn file_name(&self, base: &str) -> String {
+        if !self.plated {
+            return format!("{base}-NPTH.drl");
+        }
+        match &self.layer_pair {
+            Some(pair) if !pair.is_through() => format!("{base}-{}.drl", pair.label()),
+            _ => format!("{base}-PTH.drl"),
+        }
+    }
+
+    /// Writes the file in Excellon decimal format.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "M48")?;
+        writeln!(out, "; DRILL file generated by kicad-ipc-rs")?;
+        let (unit_name, unit_header) = match self.units {
+            ExcellonUnits::Millimeters => ("metric", "METRIC"),
+            ExcellonUnits::Inches => ("inch", "INCH"),
+        };
+        writeln!(out, "; FORMAT={{-:-/ absolute / {unit_name} / decimal}}")?;
+        writeln!(
+            out,
+            "; TYPE={}",
+            if self.plated { "PLATED" } else { "NON_PLATED" }
+        )?;
+        if let Some(pair) = &self.layer_pair {
+            writeln!(out, "; LAYERS={}", pair.label())?;
+        }
+        writeln!(out, "FMAT,2")?;
+        writeln!(out, "{unit_header}")?;
+        for tool in &self.tools {
+            writeln!(
+                out,
+                "T{}C{}",
+                tool.number,
+                self.format_diameter(tool.diameter_nm)
+            )?;
+        }
+        writeln!(out, "%")?;
+        writeln!(out, "G90")?;
+        writeln!(out, "G05")?;
+
+        for tool in &self.tools {
+            writeln!(out, "T{}", tool.number)?;
+            for hole in self
+                .holes
+                .iter()
+                .filter(|hole| hole.diameter_nm == tool.diameter_nm)
+            {
+                match hole.slot_nm {
+                    Some([start, end]) => writeln!(
+                        out,
+                        "{}G85{}",
+                        self.format_point(start),
+                        self.format_point(end)
+                    )?,
+                    None => writeln!(out, "{}", self.format_point(hole.position_nm))?,
+                }
+            }
+        }
+        writeln!(out, "M30")
+    }
+
+    /// Returns the file as text.
+    pub fn to_text(&self) -> String {
+        write_to_string(|out| self.write(out))
+    }
+
+    fn format_diameter(&self, diameter_nm: i64) -> String {
+        let decimals = self.units.coordinate_decimals() - 1;
+        format!("{:.decimals$}", self.units.convert_nm(diameter_nm))
+    }
+
+    fn format_point(&self, point: Vector2Nm) -> String {
+        format!(
+            "X{}Y{}",
+            self.format_coordinate(point.x_nm - self.origin.x_nm),
+            self.format_coordinate(self.origin.y_nm - point.y_nm)
+        )
+    }
+
+    fn format_coordinate(&self, value_nm: i64) -> String {
+        let decimals = self.units.coordinate_decimals();
+        let text = format!("{:.decimals$}", self.units.convert_nm(value_nm));
+        let text = text.trim_end_matches('0').trim_end_matches('.');
+        if text == "-0" {
+            "0".to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One drill-table row: a tool within a file.
+pub struct DrillTableRow {
+    /// Whether the holes are plated.
+    pub plated: bool,
+    /// Layer span of the file, when split by layer pair.
+    pub layer_pair: Option<DrillLayerPair>,
+    /// Tool within that file.
+    pub tool: DrillTool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// All drill files for a board.
+pub struct DrillSet {
+    /// Plated files first (through, then blind/buried pairs), then the NPTH file.
+    pub files: Vec<DrillFile>,
+}
+
+impl DrillSet {
+    /// Item type codes [`DrillSet::from_items`] needs.
+    pub fn capture_type_codes() -> Vec<i32> {
+        vec![
+            KiCadObjectType::KotPcbFootprint as i32,
+            KiCadObjectType::KotPcbVia as i32,
+        ]
+    }
+
+    /// Reads footprints and vias from the open board.
+    ///
+    /// When `options.origin` is `None`, the board's drill/place origin is used.
+    pub async fn capture(
+        client: &KiCadClient,
+        mut options: ExcellonOptions,
+    ) -> Result<Self, KiCadError> {
+        if options.origin.is_none() {
+            options.origin = Some(client.get_board_origin(BoardOriginKind::Drill).await?);
+        }
+        let items = client
+            .get_items_by_type_codes(Self::capture_type_codes())
+            .await?;
+        Ok(Self::from_items(&items, &options))
+    }
+
+    /// Collects drilled vias and pads (footprint children) into drill files.
+    pub fn from_items(items: &[PcbItem], options: &ExcellonOptions) -> Self {
+        let mut holes: Vec<DrillHole> = Vec::new();
+        for item in items {
+            match item {
+                PcbItem::Via(via) => holes.extend(via_hole(via)),
+                PcbItem::Footprint(footprint) => {
+                    holes.extend(footprint.items.iter().filter_map(|child| match child {
+                        PcbItem::Pad(pad) => pad_hole(pad),
+                        _ => None,
+                    }))
+                }
+                PcbItem::Pad(pad) => holes.extend(pad_hole(pad)),
+                _ => {}
+            }
+        }
+
+        let mut groups: BTreeMap<(bool, i32, i32), Vec<DrillHole>> = BTreeMap::new();
+        for hole in holes {
+            let key = if !hole.plated {
+                (true, 0, 0)
+            } else if options.separate_layer_pairs && !hole.layer_pair.is_through() {
+                (false, hole.layer_pair.start.id, hole.layer_pair.end.id)
+            } else {
+                (false, BoardLayer::BlFCu as i32, BoardLayer::BlBCu as i32)
+            };
+            groups.entry(key).or_default().push(hole);
+        }
+
+        let origin = options.origin.unwrap_or(Vector2Nm { x_nm: 0, y_nm: 0 });
+        let mut files: Vec<DrillFile> = groups
+            .into_iter()
+            .map(|((non_plated, _, _), holes)| {
+                let plated = !non_plated;
+                let layer_pair = if !plated {
+                    Some(DrillLayerPair::through())
+                } else if options.separate_layer_pairs {
+                    Some(holes[0].layer_pair.clone())
+                } else {
+                    None
+                };
+                build_file(plated, layer_pair, holes, options.units, origin)
+            })
+            .collect();
+        files.sort_by_key(|file| {
+            (
+                !file.plated,
+                !file
+                    .layer_pair
+                    .as_ref()
+                    .is_none_or(DrillLayerPair::is_through),
+            )
+        });
+
+        Self { files }
+    }
+
+    /// Tool rows across all files, for CAM sanity checks.
+    pub fn drill_table(&self) -> Vec<DrillTableRow> {
+        self.files
+            .iter()
+            .flat_map(|file| {
+                file.tools.iter().map(|tool| DrillTableRow {
+                    plated: file.plated,
+                    layer_pair: file.layer_pair.clone(),
+                    tool: *tool,
+                })
+            })
+            .collect()
+    }
+
+    /// Total hole count (slots included) across all files.
+    pub fn hole_count(&self) -> usize {
+        self.files.iter().map(|file| file.holes.len()).sum()
+    }
+
+    /// Writes the drill table as aligned text with a total line.
+    pub fn write_drill_table<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:<10}  {:<16}  {:<4}  {:>10}  {:>6}  {:>6}",
+            "Plating", "Layers", "Tool", "Diameter", "Holes", "Slots"
+        )?;
+        let units = self
+            .files
+            .first()
+            .map(|file| file.units)
+            .unwrap_or_default();
+        for row in self.drill_table() {
+            let diameter = match units {
+                ExcellonUnits::Millimeters => {
+                    format!("{:.3}mm", units.convert_nm(row.tool.diameter_nm))
+                }
+                ExcellonUnits::Inches => format!("{:.4}in", units.convert_nm(row.tool.diameter_nm)),
+            };
+            writeln!(
+                out,
+                "{:<10}  {:<16}  {:<4}  {:>10}  {:>6}  {:>6}",
+                if row.plated { "PTH" } else { "NPTH" },
+                row.layer_pair
+                    .as_ref()
+                    .map_or_else(|| "all".to_string(), DrillLayerPair::label),
+                format!("T{}", row.tool.number),
+                diameter,
+                row.tool.hole_count,
+                row.tool.slot_count,
+            )?;
+        }
+        writeln!(out, "Total holes: {}", self.hole_count())
+    }
+
+    /// Returns the drill table as text.
+    pub fn drill_table_text(&self) -> String {
+        write_to_string(|out| self.write_drill_table(out))
+    }
+}
+
+fn layer_info(layer: BoardLayer) -> BoardLayerInfo {
+    BoardLayerInfo {
+        id: layer as i32,
+        name: layer.as_str_name().to_string(),
+    }
+}
+
+fn short_layer_name(layer: &BoardLayerInfo) -> &str {
+    layer.name.strip_prefix("BL_").unwrap_or(&layer.name)
+}
+
+fn via_hole(via: &PcbVia) -> Option<DrillHole> {
+    let diameter_nm = via.drill_diameter_nm.filter(|diameter| *diameter > 0)?;
+    let layer_pair = via
+        .layers
+        .as_ref()
+        .and_then(|layers| {
+            Some(DrillLayerPair::new(
+                layers.drill_start_layer.clone()?,
+                layers.drill_end_layer.clone()?,
+            ))
+        })
+        .unwrap_or_else(DrillLayerPair::through);
+
+    Some(DrillHole {
+        item_id: via.id.clone(),
+        position_nm: via.position_nm?,
+        diameter_nm,
+        slot_nm: None,
+        plated: true,
+        layer_pair,
+    })
+}
+
+fn pad_hole(pad: &PcbPad) -> Option<DrillHole> {
+    let plated = match pad.pad_type {
+        PcbPadType::Pth => true,
+        PcbPadType::Npth => false,
+        _ => return None,
+    };
+    let stack = pad.pad_stack.as_ref()?;
+    let drill = stack.drill.as_ref()?;
+    let size = drill.diameter_nm?;
+    let position_nm = pad.position_nm?;
+    let diameter_nm = size.x_nm.min(size.y_nm);
+    if diameter_nm <= 0 {
+        return None;
+    }
+
+    let slot_nm = (size.x_nm != size.y_nm).then(|| {
+        let half_length = (size.x_nm - size.y_nm).abs() / 2;
+        let offset = if size.x_nm > size.y_nm {
+            Vector2Nm {
+                x_nm: half_length,
+                y_nm: 0,
+            }
+        } else {
+            Vector2Nm {
+                x_nm: 0,
+                y_nm: half_length,
+            }
+        };
+        let angle = stack.angle_deg.unwrap_or_default();
+        [-1, 1].map(|sign| {
+            geometry::rotate_about(
+                Vector2Nm {
+                    x_nm: position_nm.x_nm + sign * offset.x_nm,
+                    y_nm: position_nm.y_nm + sign * offset.y_nm,
+                },
+                position_nm,
+                angle,
+            )
+        })
+    });
+
+    Some(DrillHole {
+        item_id: pad.id.clone(),
+        position_nm,
+        diameter_nm,
+        slot_nm,
+        plated,
+        layer_pair: DrillLayerPair::new(drill.start_layer.clone(), drill.end_layer.clone()),
+    })
+}
+
+fn build_file(
+    plated: bool,
+    layer_pair: Option<DrillLayerPair>,
+    mut holes: Vec<DrillHole>,
+    units: ExcellonUnits,
+    origin: Vector2Nm,
+) -> DrillFile {
+    holes.sort_by_key(|hole| {
+        (
+            hole.diameter_nm,
+            hole.position_nm.x_nm,
+            hole.position_nm.y_nm,
+        )
+    });
+
+    let mut tools: Vec<DrillTool> = Vec::new();
+    for hole in &holes {
+        match tools.last_mut() {
+            Some(tool) if tool.diameter_nm == hole.diameter_nm => {
+                tool.hole_count += 1;
+                tool.slot_count += usize::from(hole.slot_nm.is_some());
+            }
+            _ => tools.push(DrillTool {
+                number: tools.len() + 1,
+                diameter_nm: hole.diameter_nm,
+                hole_count: 1,
+                slot_count: usize::from(hole.slot_nm.is_some()),
+            }),
+        }
+    }
+
+    DrillFile {
+        plated,
+        layer_pair,
+        tools,
+        holes,
+        units,
+        origin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DrillSet, ExcellonOptions};
+    use crate::model::board::{
+        BoardLayerInfo, PcbDrill, PcbFootprint, PcbItem, PcbPad, PcbPadStack, PcbPadType, PcbVia,
+        PcbViaLayers, PcbViaType, Vector2Nm,
+    };
+
+    fn layer(id: i32, name: &str) -> BoardLayerInfo {
+        BoardLayerInfo {
+            id,
+            name: name.to_string(),
+        }
+    }
+
+    fn copper(id: i32) -> BoardLayerInfo {
+        match id {
+            3 => layer(id, "BL_F_Cu"),
+            34 => layer(id, "BL_B_Cu"),
+            _ => layer(id, &format!("BL_In{}_Cu", id - 3)),
+        }
+    }
+
+    fn point(x_nm: i64, y_nm: i64) -> Vector2Nm {
+        Vector2Nm { x_nm, y_nm }
+    }
+
+    fn via(id: &str, position: Vector2Nm, drill_nm: i64, pair: Option<(i32, i32)>) -> PcbItem {
+        PcbItem::Via(PcbVia {
+            id: Some(id.to_string()),
+            position_nm: Some(position),
+            via_type: if pair.is_some() {
+                PcbViaType::Blind
+            } else {
+                PcbViaType::Through
+            },
+            layers: pair.map(|(start, end)| PcbViaLayers {
+                padstack_layers: Vec::new(),
+                drill_start_layer: Some(copper(start)),
+                drill_end_layer: Some(copper(end)),
+            }),
+            net: None,
+            drill_diameter_nm: Some(drill_nm),
+            pad_diameter_nm: Some(drill_nm * 2),
+        })
+    }
+
+    fn pad(id: &str, pad_type: PcbPadType, position: Vector2Nm, drill: Vector2Nm) -> PcbItem {
+        PcbItem::Pad(PcbPad {
+            id: Some(id.to_string()),
+            number: "1".to_string(),
+            pad_type,
+            position_nm: Some(position),
+            net: None,
+            pad_stack: Some(PcbPadStack {
+                layers: Vec::new(),
+                copper_layers: Vec::new(),
+                drill: Some(PcbDrill {
+                    start_layer: layer(3, "BL_F_Cu"),
+                    end_layer: layer(34, "BL_B_Cu"),
+                    diameter_nm: Some(drill),
+                }),
+                angle_deg: Some(90.0),
+            }),
+        })
+    }
+
+    fn board_items() -> Vec<PcbItem> {
+        vec![
+            via("v1", point(1_000_000, 2_000_000), 300_000, None),
+            via("v2", point(3_000_000, 2_000_000), 300_000, None),
+            via("v3", point(5_000_000, 5_000_000), 200_000, Some((3, 4))),
+            PcbItem::Footprint(PcbFootprint {
+                id: Some("fp".to_string()),
+                reference: Some("J1".to_string()),
+                position_nm: None,
+                orientation_deg: None,
+                layer: layer(3, "BL_F_Cu"),
+                pad_count: 3,
+                value: None,
+                library_id: None,
+                datasheet: None,
+                attributes: None,
+                items: vec![
+                    pad(
+                        "p1",
+                        PcbPadType::Pth,
+                        point(10_000_000, 0),
+                        point(1_000_000, 1_000_000),
+                    ),
+                    pad(
+                        "p2",
+                        PcbPadType::Pth,
+                        point(20_000_000, 0),
+                        point(2_000_000, 1_000_000),
+                    ),
+                    pad(
+                        "mh",
+                        PcbPadType::Npth,
+                        point(0, 0),
+                        point(3_200_000, 3_200_000),
+                    ),
+                ],
+            }),
+        ]
+    }
+
+    #[test]
+    fn from_items_splits_plating_and_layer_pairs() {
+        let set = DrillSet::from_items(&board_items(), &ExcellonOptions::default());
+
+        assert_eq!(set.files.len(), 3);
+        assert_eq!(set.files[0].file_name("demo"), "demo-PTH.drl");
+        assert_eq!(set.files[1].file_name("demo"), "demo-F_Cu-In1_Cu.drl");
+        assert_eq!(set.files[2].file_name("demo"), "demo-NPTH.drl");
+        assert_eq!(set.hole_count(), 6);
+
+        let pth_tools: Vec<(i64, usize, usize)> = set.files[0]
+            .tools
+            .iter()
+            .map(|tool| (tool.diameter_nm, tool.hole_count, tool.slot_count))
+            .collect();
+        assert_eq!(pth_tools, vec![(300_000, 2, 0), (1_000_000, 2, 1)]);
+
+        let merged = DrillSet::from_items(
+            &board_items(),
+            &ExcellonOptions {
+                separate_layer_pairs: false,
+                ..ExcellonOptions::default()
+            },
+        );
+        assert_eq!(merged.files.len(), 2);
+        assert_eq!(merged.files[0].tools.len(), 3);
+    }
+
+    #[test]
+    fn drill_file_writes_excellon_with_slots_relative_to_origin() {
+        let set = DrillSet::from_items(
+            &board_items(),
+            &ExcellonOptions {
+                origin: Some(point(0, 1_000_000)),
+                ..ExcellonOptions::default()
+            },
+        );
+
+        assert_eq!(
+            set.files[0].to_text(),
+            "M48\n\
+             ; DRILL file generated by kicad-ipc-rs\n\
+             ; FORMAT={-:-/ absolute / metric / decimal}\n\
+             ; TYPE=PLATED\n\
+             ; LAYERS=F_Cu-B_Cu\n\
+             FMAT,2\n\
+             METRIC\n\
+             T1C0.300\n\
+             T2C1.000\n\
+             %\n\
+             G90\n\
+             G05\n\
+             T1\n\
+             X1Y-1\n\
+             X3Y-1\n\
+             T2\n\
+             X10Y1\n\
+             X20Y0.5G85X20Y1.5\n\
+             M30\n"
+        );
+        assert!(set.drill_table_text().ends_with(
+            "NPTH        F_Cu-B_Cu         T1       3.200mm       1       0\nTotal holes: 6\n"
+        ));
+    }
+
+    #[test]
+    fn from_items_normalises_reversed_layer_pairs() {
+        let mut items = board_items();
+        items.push(via(
+            "v4",
+            point(6_000_000, 5_000_000),
+            200_000,
+            Some((4, 3)),
+        ));
+        if let PcbItem::Footprint(footprint) = &mut items[3] {
+            if let PcbItem::Pad(pad) = &mut footprint.items[0] {
+                let drill = pad
+                    .pad_stack
+                    .as_mut()
+                    .and_then(|stack| stack.drill.as_mut());
+                let drill = drill.expect("test pad has a drill");
+                std::mem::swap(&mut drill.start_layer, &mut drill.end_layer);
+            }
+        }
+        let set = DrillSet::from_items(&items, &ExcellonOptions::default());
+
+        assert_eq!(set.files.len(), 3);
+        assert_eq!(set.files[0].tools[1].hole_count, 2);
+        assert_eq!(set.files[1].file_name("demo"), "demo-F_Cu-In1_Cu.drl");
+        assert_eq!(set.files[1].tools[0].hole_count, 2);
+    }
+}
@@ -0,0 +1,4 @@
+//! Fabrication outputs built from decoded board items.
+
+/// Excellon drill files and drill-table summaries.
+pub mod excellon;
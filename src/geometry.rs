@@ -0,0 +1,510 @@
+//! Planar geometry helpers shared by the client-side analysis modules.
+//!
+//! All coordinates are board nanometers; math is carried out in `f64`.
+
+use crate::model::board::{
+    PcbPadStackCopperLayer, PcbPadStackShape, PolyLineNm, PolyLineNodeGeometryNm, Vector2Nm,
+};
+use crate::model::common::TextShapeGeometry;
+
+/// Segments used to approximate a full circle.
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Polyline skeleton inflated by a half-width; the common currency for clearance math.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Shape {
+    pub points: Vec<Vector2Nm>,
+    /// Closed shapes connect the last point back to the first.
+    pub closed: bool,
+    /// Filled closed shapes also cover their interior.
+    pub filled: bool,
+    pub half_width_nm: f64,
+}
+
+impl Shape {
+    pub fn point(center: Vector2Nm, radius_nm: f64) -> Self {
+        Self {
+            points: vec![center],
+            closed: false,
+            filled: false,
+            half_width_nm: radius_nm,
+        }
+    }
+
+    pub fn segment(start: Vector2Nm, end: Vector2Nm, width_nm: i64) -> Self {
+        Self {
+            points: vec![start, end],
+            closed: false,
+            filled: false,
+            half_width_nm: width_nm as f64 / 2.0,
+        }
+    }
+
+    pub fn polygon(points: Vec<Vector2Nm>) -> Self {
+        Self {
+            points,
+            closed: true,
+            filled: true,
+            half_width_nm: 0.0,
+        }
+    }
+
+    pub fn edges(&self) -> Vec<(Vector2Nm, Vector2Nm)> {
+        let mut edges: Vec<_> = self
+            .points
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+        if self.closed && self.points.len() > 2 {
+            edges.push((self.points[self.points.len() - 1], self.points[0]));
+        }
+        if edges.is_empty() {
+            if let Some(point) = self.points.first() {
+                edges.push((*point, *point));
+            }
+        }
+        edges
+    }
+
+    fn covers_interior_point(&self, point: Vector2Nm) -> bool {
+        self.filled && self.closed && point_in_polygon(point, &self.points)
+    }
+}
+
+/// Edge-to-edge gap between two shapes; `0.0` when they touch or overlap.
+pub(crate) fn shape_gap_nm(a: &Shape, b: &Shape) -> f64 {
+    if a.points.is_empty() || b.points.is_empty() {
+        return f64::INFINITY;
+    }
+    if a.covers_interior_point(b.points[0]) || b.covers_interior_point(a.points[0]) {
+        return 0.0;
+    }
+
+    let mut best = f64::INFINITY;
+    for (a1, a2) in a.edges() {
+        for (b1, b2) in b.edges() {
+            best = best.min(segment_segment_distance(a1, a2, b1, b2));
+        }
+    }
+    (best - a.half_width_nm - b.half_width_nm).max(0.0)
+}
+
+pub(crate) fn shapes_overlap(a: &Shape, b: &Shape) -> bool {
+    shape_gap_nm(a, b) <= 0.0
+}
+
+pub(crate) fn distance(a: Vector2Nm, b: Vector2Nm) -> f64 {
+    let dx = (a.x_nm - b.x_nm) as f64;
+    let dy = (a.y_nm - b.y_nm) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+pub(crate) fn point_segment_distance(point: Vector2Nm, start: Vector2Nm, end: Vector2Nm) -> f64 {
+    let (px, py) = (point.x_nm as f64, point.y_nm as f64);
+    let (ax, ay) = (start.x_nm as f64, start.y_nm as f64);
+    let (bx, by) = (end.x_nm as f64, end.y_nm as f64);
+    let (dx, dy) = (bx - ax, by - ay);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return ((px - ax).powi(2) + (py - ay).powi(2)).sqrt();
+    }
+    let t = (((px - ax) * dx + (py - ay) * dy) / length_sq).clamp(0.0, 1.0);
+    let (cx, cy) = (ax + t * dx, ay + t * dy);
+    ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+}
+
+fn orientation(a: Vector2Nm, b: Vector2Nm, c: Vector2Nm) -> i128 {
+    let value = (b.x_nm - a.x_nm) as i128 * (c.y_nm - a.y_nm) as i128
+        - (b.y_nm - a.y_nm) as i128 * (c.x_nm - a.x_nm) as i128;
+    value.signum()
+}
+
+fn on_segment(point: Vector2Nm, start: Vector2Nm, end: Vector2Nm) -> bool {
+    point.x_nm >= start.x_nm.min(end.x_nm)
+        && point.x_nm <= start.x_nm.max(end.x_nm)
+        && point.y_nm >= start.y_nm.min(end.y_nm)
+        && point.y_nm <= start.y_nm.max(end.y_nm)
+}
+
+pub(crate) fn segments_intersect(
+    a1: Vector2Nm,
+    a2: Vector2Nm,
+    b1: Vector2Nm,
+    b2: Vector2Nm,
+) -> bool {
+    let o1 = orientation(a1, a2, b1);
+    let o2 = orientation(a1, a2, b2);
+    let o3 = orientation(b1, b2, a1);
+    let o4 = orientation(b1, b2, a2);
+
+    if o1 != o2 && o3 != o4 {
+        return true;
+    }
+
+    (o1 == 0 && on_segment(b1, a1, a2))
+        || (o2 == 0 && on_segment(b2, a1, a2))
+        || (o3 == 0 && on_segment(a1, b1, b2))
+        || (o4 == 0 && on_segment(a2, b1, b2))
+}
+
+pub(crate) fn segment_segment_distance(
+    a1: Vector2Nm,
+    a2: Vector2Nm,
+    b1: Vector2Nm,
+    b2: Vector2Nm,
+) -> f64 {
+    if segments_intersect(a1, a2, b1, b2) {
+        return 0.0;
+    }
+    point_segment_distance(a1, b1, b2)
+        .min(point_segment_distance(a2, b1, b2))
+        .min(point_segment_distance(b1, a1, a2))
+        .min(point_segment_distance(b2, a1, a2))
+}
+
+/// Even-odd point-in-polygon test; points on the boundary count as inside.
+pub(crate) fn point_in_polygon(point: Vector2Nm, polygon: &[Vector2Nm]) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if point_segment_distance(point, a, b) == 0.0 {
+            return true;
+        }
+        if (a.y_nm > point.y_nm) != (b.y_nm > point.y_nm) {
+            let x_cross = a.x_nm as f64
+                + (point.y_nm - a.y_nm) as f64 * (b.x_nm - a.x_nm) as f64
+                    / (b.y_nm - a.y_nm) as f64;
+            if (point.x_nm as f64) < x_cross {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+pub(crate) fn rotate_about(point: Vector2Nm, center: Vector2Nm, angle_deg: f64) -> Vector2Nm {
+    if angle_deg == 0.0 {
+        return point;
+    }
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    let dx = (point.x_nm - center.x_nm) as f64;
+    let dy = (point.y_nm - center.y_nm) as f64;
+    // KiCad's Y axis points down; positive angles are counter-clockwise on screen.
+    Vector2Nm {
+        x_nm: center.x_nm + (dx * cos + dy * sin).round() as i64,
+        y_nm: center.y_nm + (-dx * sin + dy * cos).round() as i64,
+    }
+}
+
+fn circle_points(center: Vector2Nm, radius_nm: f64) -> Vec<Vector2Nm> {
+    (0..CIRCLE_SEGMENTS)
+        .map(|index| {
+            let angle = std::f64::consts::TAU * index as f64 / CIRCLE_SEGMENTS as f64;
+            Vector2Nm {
+                x_nm: center.x_nm + (radius_nm * angle.cos()).round() as i64,
+                y_nm: center.y_nm + (radius_nm * angle.sin()).round() as i64,
+            }
+        })
+        .collect()
+}
+
+/// Center of the circle through three points, or `None` when they are collinear.
+pub(crate) fn circumcenter(a: Vector2Nm, b: Vector2Nm, c: Vector2Nm) -> Option<(f64, f64)> {
+    let (ax, ay) = (a.x_nm as f64, a.y_nm as f64);
+    let (bx, by) = (b.x_nm as f64, b.y_nm as f64);
+    let (cx, cy) = (c.x_nm as f64, c.y_nm as f64);
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if d.abs() < f64::EPSILON {
+        return None;
+    }
+    let a_sq = ax * ax + ay * ay;
+    let b_sq = bx * bx + by * by;
+    let c_sq = cx * cx + cy * cy;
+    Some((
+        (a_sq * (by - cy) + b_sq * (cy - ay) + c_sq * (ay - by)) / d,
+        (a_sq * (cx - bx) + b_sq * (ax - cx) + c_sq * (bx - ax)) / d,
+    ))
+}
+
+/// Approximates a start/mid/end arc with a polyline, endpoints included.
+pub(crate) fn arc_points(start: Vector2Nm, mid: Vector2Nm, end: Vector2Nm) -> Vec<Vector2Nm> {
+    let Some((cx, cy)) = circumcenter(start, mid, end) else {
+        return vec![start, end];
+    };
+    let radius = ((start.x_nm as f64 - cx).powi(2) + (start.y_nm as f64 - cy).powi(2)).sqrt();
+    let angle_of = |point: Vector2Nm| (point.y_nm as f64 - cy).atan2(point.x_nm as f64 - cx);
+    let tau = std::f64::consts::TAU;
+    let a0 = angle_of(start);
+    let sweep_mid = (angle_of(mid) - a0).rem_euclid(tau);
+    let mut sweep = (angle_of(end) - a0).rem_euclid(tau);
+    if sweep_mid > sweep {
+        // The mid point is not on the increasing-angle path: walk the other way.
+        sweep -= tau;
+    }
+    let steps = ((sweep.abs() / tau) * CIRCLE_SEGMENTS as f64)
+        .ceil()
+        .max(1.0) as usize;
+
+    let mut points = Vec::with_capacity(steps + 1);
+    points.push(start);
+    for step in 1..steps {
+        let angle = a0 + sweep * step as f64 / steps as f64;
+        points.push(Vector2Nm {
+            x_nm: (cx + radius * angle.cos()).round() as i64,
+            y_nm: (cy + radius * angle.sin()).round() as i64,
+        });
+    }
+    points.push(end);
+    points
+}
+
+/// Flattens a polyline (expanding arc nodes) into points.
+pub(crate) fn polyline_points(line: &PolyLineNm) -> Vec<Vector2Nm> {
+    let mut points: Vec<Vector2Nm> = Vec::new();
+    for node in &line.nodes {
+        match node {
+            PolyLineNodeGeometryNm::Point(point) => points.push(*point),
+            PolyLineNodeGeometryNm::Arc(arc) => {
+                let expanded = arc_points(arc.start, arc.mid, arc.end);
+                let skip = usize::from(points.last() == expanded.first());
+                points.extend(expanded.into_iter().skip(skip));
+            }
+        }
+    }
+    if line.closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    points
+}
+
+/// Converts graphic geometry into stroked shapes.
+///
+/// Returns an empty list for geometry that cannot be resolved (missing points, bezier).
+pub(crate) fn graphic_shapes(
+    geometry: &TextShapeGeometry,
+    stroke_width_nm: i64,
+    filled: bool,
+) -> Vec<Shape> {
+    let half_width_nm = stroke_width_nm.max(0) as f64 / 2.0;
+    let closed = |points: Vec<Vector2Nm>| Shape {
+        points,
+        closed: true,
+        filled,
+        half_width_nm,
+    };
+
+    match geometry {
+        TextShapeGeometry::Segment {
+            start_nm: Some(start),
+            end_nm: Some(end),
+        } => vec![Shape::segment(*start, *end, stroke_width_nm)],
+        TextShapeGeometry::Rectangle {
+            top_left_nm: Some(top_left),
+            bottom_right_nm: Some(bottom_right),
+            ..
+        } => vec![closed(vec![
+            *top_left,
+            Vector2Nm {
+                x_nm: bottom_right.x_nm,
+                y_nm: top_left.y_nm,
+            },
+            *bottom_right,
+            Vector2Nm {
+                x_nm: top_left.x_nm,
+                y_nm: bottom_right.y_nm,
+            },
+        ])],
+        TextShapeGeometry::Arc {
+            start_nm: Some(start),
+            mid_nm: Some(mid),
+            end_nm: Some(end),
+        } => vec![Shape {
+            points: arc_points(*start, *mid, *end),
+            closed: false,
+            filled: false,
+            half_width_nm,
+        }],
+        TextShapeGeometry::Circle {
+            center_nm: Some(center),
+            radius_point_nm: Some(radius_point),
+        } => vec![closed(circle_points(
+            *center,
+            distance(*center, *radius_point),
+        ))],
+        TextShapeGeometry::Polygon { polygons } => polygons
+            .iter()
+            .filter_map(|polygon| polygon.outline.as_ref())
+            .map(|outline| closed(polyline_points(outline)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Copper outline of one padstack layer placed at `position`.
+pub(crate) fn pad_shape(
+    position: Vector2Nm,
+    copper: &PcbPadStackCopperLayer,
+    angle_deg: f64,
+) -> Option<Shape> {
+    let size = copper.size_nm?;
+    let offset = copper.offset_nm.unwrap_or(Vector2Nm { x_nm: 0, y_nm: 0 });
+    let center = rotate_about(
+        Vector2Nm {
+            x_nm: position.x_nm + offset.x_nm,
+            y_nm: position.y_nm + offset.y_nm,
+        },
+        position,
+        angle_deg,
+    );
+    let (half_x, half_y) = (size.x_nm / 2, size.y_nm / 2);
+
+    match copper.shape {
+        PcbPadStackShape::Circle => Some(Shape::point(center, half_x as f64)),
+        PcbPadStackShape::Oval => {
+            let (along, radius) = if half_x >= half_y {
+                ((half_x - half_y, 0), half_y)
+            } else {
+                ((0, half_y - half_x), half_x)
+            };
+            let a = rotate_about(
+                Vector2Nm {
+                    x_nm: center.x_nm - along.0,
+                    y_nm: center.y_nm - along.1,
+                },
+                center,
+                angle_deg,
+            );
+            let b = rotate_about(
+                Vector2Nm {
+                    x_nm: center.x_nm + along.0,
+                    y_nm: center.y_nm + along.1,
+                },
+                center,
+                angle_deg,
+            );
+            Some(Shape {
+                points: vec![a, b],
+                closed: false,
+                filled: false,
+                half_width_nm: radius as f64,
+            })
+        }
+        _ => {
+            let corners = [
+                (-half_x, -half_y),
+                (half_x, -half_y),
+                (half_x, half_y),
+                (-half_x, half_y),
+            ]
+            .into_iter()
+            .map(|(dx, dy)| {
+                rotate_about(
+                    Vector2Nm {
+                        x_nm: center.x_nm + dx,
+                        y_nm: center.y_nm + dy,
+                    },
+                    center,
+                    angle_deg,
+                )
+            })
+            .collect();
+            Some(Shape::polygon(corners))
+        }
+    }
+}
+
+/// Monotone-chain convex hull, counter-clockwise, without repeated endpoints.
+pub(crate) fn convex_hull(mut points: Vec<Vector2Nm>) -> Vec<Vector2Nm> {
+    points.sort_by_key(|point| (point.x_nm, point.y_nm));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: Vector2Nm, a: Vector2Nm, b: Vector2Nm| {
+        (a.x_nm - o.x_nm) as i128 * (b.y_nm - o.y_nm) as i128
+            - (a.y_nm - o.y_nm) as i128 * (b.x_nm - o.x_nm) as i128
+    };
+
+    let mut hull: Vec<Vector2Nm> = Vec::with_capacity(points.len() * 2);
+    for point in points.iter().copied() {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0 {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    let lower_len = hull.len() + 1;
+    for point in points.iter().rev().skip(1).copied() {
+        while hull.len() >= lower_len
+            && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+        {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arc_points, convex_hull, point_in_polygon, segment_segment_distance, shape_gap_nm, Shape,
+    };
+    use crate::model::board::Vector2Nm;
+
+    fn v(x_nm: i64, y_nm: i64) -> Vector2Nm {
+        Vector2Nm { x_nm, y_nm }
+    }
+
+    #[test]
+    fn segment_segment_distance_handles_parallel_and_crossing_segments() {
+        assert_eq!(
+            segment_segment_distance(v(0, 0), v(10, 0), v(0, 5), v(10, 5)),
+            5.0
+        );
+        assert_eq!(
+            segment_segment_distance(v(0, 0), v(10, 10), v(0, 10), v(10, 0)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn shape_gap_subtracts_half_widths_and_detects_containment() {
+        let track = Shape::segment(v(0, 0), v(100, 0), 10);
+        let via = Shape::point(v(50, 30), 10.0);
+        assert_eq!(shape_gap_nm(&track, &via), 15.0);
+
+        let square = Shape::polygon(vec![v(0, 0), v(100, 0), v(100, 100), v(0, 100)]);
+        assert_eq!(shape_gap_nm(&square, &Shape::point(v(50, 50), 1.0)), 0.0);
+    }
+
+    #[test]
+    fn point_in_polygon_uses_even_odd_rule() {
+        let square = [v(0, 0), v(10, 0), v(10, 10), v(0, 10)];
+        assert!(point_in_polygon(v(5, 5), &square));
+        assert!(point_in_polygon(v(0, 5), &square));
+        assert!(!point_in_polygon(v(15, 5), &square));
+    }
+
+    #[test]
+    fn arc_points_follow_the_mid_point_side() {
+        let points = arc_points(v(-100, 0), v(0, 100), v(100, 0));
+        assert_eq!(points.first(), Some(&v(-100, 0)));
+        assert_eq!(points.last(), Some(&v(100, 0)));
+        assert!(points.iter().all(|point| point.y_nm >= 0));
+    }
+
+    #[test]
+    fn convex_hull_drops_interior_points() {
+        let hull = convex_hull(vec![v(0, 0), v(10, 0), v(5, 5), v(10, 10), v(0, 10)]);
+        assert_eq!(hull.len(), 4);
+        assert!(!hull.contains(&v(5, 5)));
+    }
+}
@@ -54,6 +54,10 @@
 
 #![warn(missing_docs)]
 
+/// Typed footprint move, rotate, flip, align, and distribute operations.
+pub mod arrange;
+/// Bill of materials generation with CSV and JSON writers.
+pub mod bom;
 /// High-level async client and request/response convenience methods.
 pub mod client;
 /// Low-level command payload builders.
@@ -61,19 +65,55 @@ pub mod client;
 /// This module is public for advanced integrations and debugging, but most users
 /// should prefer [`crate::client::KiCadClient`] methods.
 pub mod commands;
+/// Client-side design-rule checks with optional editor marker injection.
+pub mod drc;
 /// Envelope helpers for command/response packing and unpacking.
 ///
 /// This is primarily an advanced/internal surface.
 pub mod envelope;
 /// Error types returned by this crate.
 pub mod error;
+/// Fabrication outputs (Excellon drill files).
+pub mod fab;
+mod geometry;
 mod kicad_api_version;
 /// Stable data models used by typed client APIs.
 pub mod model;
+/// Local net class pattern resolution and audit against KiCad.
+pub mod netclass_audit;
+/// Declarative net class sync from version-controlled definitions.
+pub mod netclass_sync;
+/// Netlist exporters (IPC-D-356A).
+pub mod netlist;
+/// Board outline extraction from Edge.Cuts shapes.
+pub mod outline;
+mod pattern;
+/// Pick-and-place (centroid) export in CSV and KiCad `.pos` formats.
+pub mod placement;
+/// Composable board item queries.
+pub mod query;
+/// Headless board renderers (SVG).
+pub mod render;
+/// Lossless KiCad s-expression parser, printer, and typed board views.
+pub mod sexpr;
+/// Indexed whole-board snapshots and item-level diffs.
+pub mod snapshot;
+/// R-tree spatial queries over board item bounds.
+pub mod spatial;
+/// Board stackup editing, validation, and TOML/CSV exchange.
+pub mod stackup;
+mod text_out;
+/// Text variable reference checks and build metadata stamping.
+pub mod text_vars;
+mod toml;
 /// IPC transport implementation details.
 ///
 /// Most applications should not need to use this module directly.
 pub mod transport;
+/// Named layer visibility and appearance view presets.
+pub mod view;
+/// Polling board change watcher.
+pub mod watch;
 
 #[cfg(feature = "blocking")]
 /// Blocking wrapper over the async client.
@@ -82,8 +122,10 @@ pub mod blocking;
 pub(crate) mod proto;
 
 #[cfg(feature = "blocking")]
-pub use crate::blocking::{KiCadClientBlocking, KiCadClientBlockingBuilder};
-pub use crate::client::{ClientBuilder, KiCadClient};
+pub use crate::blocking::{
+    BlockingSelectionStream, KiCadClientBlocking, KiCadClientBlockingBuilder,
+};
+pub use crate::client::{BoardHandle, ClientBuilder, KiCadClient};
 pub use crate::error::KiCadError;
 pub use crate::kicad_api_version::KICAD_API_VERSION;
 pub use crate::model::board::{
@@ -91,17 +133,23 @@ pub use crate::model::board::{
     BoardLayerClass, BoardLayerGraphicsDefault, BoardLayerInfo, BoardNet, BoardOriginKind,
     BoardStackup, BoardStackupDielectricProperties, BoardStackupLayer, BoardStackupLayerType,
     ColorRgba, DrcSeverity, GraphicsDefaults, InactiveLayerDisplayMode, NetClassBoardSettings,
-    NetClassForNetEntry, NetClassInfo, NetClassType, NetColorDisplayMode, PadNetEntry,
-    PadShapeAsPolygonEntry, PadstackPresenceEntry, PadstackPresenceState, PcbArc,
-    PcbBoardGraphicShape, PcbBoardText, PcbBoardTextBox, PcbDimension, PcbField, PcbFootprint,
-    PcbGroup, PcbItem, PcbPad, PcbPadType, PcbTrack, PcbUnknownItem, PcbVia, PcbViaLayers,
-    PcbViaType, PcbZone, PcbZoneType, PolyLineNm, PolyLineNodeGeometryNm, PolygonWithHolesNm,
-    RatsnestDisplayMode, Vector2Nm,
+    NetClassForNetEntry, NetClassInfo, NetClassSchematicSettings, NetClassType,
+    NetColorDisplayMode, PadNetEntry, PadShapeAsPolygonEntry, PadstackPresenceEntry,
+    PadstackPresenceState, PcbArc, PcbBoardGraphicShape, PcbBoardText, PcbBoardTextBox,
+    PcbDimension, PcbDrill, PcbField, PcbFootprint, PcbFootprintAttributes,
+    PcbFootprintMountingStyle, PcbGroup, PcbItem, PcbPad, PcbPadStack, PcbPadStackCopperLayer,
+    PcbPadStackShape, PcbPadType, PcbTrack, PcbUnknownItem, PcbVia, PcbViaLayers, PcbViaType,
+    PcbZone, PcbZoneFilledPolygons, PcbZoneType, PolyLineNm, PolyLineNodeGeometryNm,
+    PolygonWithHolesNm, RatsnestDisplayMode, StrokeLineStyle, Vector2Nm,
 };
 pub use crate::model::common::{
-    CommitAction, CommitSession, DocumentSpecifier, DocumentType, EditorFrameType, ItemBoundingBox,
-    ItemHitTestResult, MapMergeMode, PcbObjectTypeCode, RunActionStatus, SelectionItemDetail,
-    SelectionSummary, SelectionTypeCount, TextAsShapesEntry, TextAttributesSpec, TextBoxSpec,
-    TextExtents, TextHorizontalAlignment, TextObjectSpec, TextShape, TextShapeGeometry, TextSpec,
-    TextVerticalAlignment, TitleBlockInfo, VersionInfo,
+    BoardSelector, CommitAction, CommitSession, DocumentSpecifier, DocumentType, EditorFrameType,
+    ItemBoundingBox, ItemHitTestResult, MapMergeMode, PcbObjectTypeCode, RunActionStatus,
+    SelectionItemDetail, SelectionSummary, SelectionTypeCount, SheetPath, TextAsShapesEntry,
+    TextAttributesSpec, TextBoxSpec, TextExtents, TextHorizontalAlignment, TextObjectSpec,
+    TextShape, TextShapeGeometry, TextSpec, TextVerticalAlignment, TitleBlockField, TitleBlockInfo,
+    VersionInfo,
+};
+pub use crate::model::schematic::{
+    SchematicItem, SchematicItemType, SchematicLabel, SchematicLine, SchematicUnknownItem,
 };
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::model::common::{TextBoxSpec, TextShapeGeometry, TextSpec};
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 /// KiCad net descriptor.
 pub struct BoardNet {
@@ -18,7 +20,7 @@ pub struct BoardLayerInfo {
     pub name: String,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 /// Enabled layer set for a board.
 pub struct BoardEnabledLayers {
     /// Number of copper layers configured in the board stack.
@@ -59,7 +61,7 @@ impl std::fmt::Display for BoardOriginKind {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 /// 2D coordinate in nanometer units.
 pub struct Vector2Nm {
     /// X coordinate in nm.
@@ -195,7 +197,7 @@ pub struct BoardStackupLayer {
     pub dielectric_layers: Vec<BoardStackupDielectricProperties>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct BoardStackup {
     pub finish_type_name: String,
     pub impedance_controlled: bool,
@@ -260,6 +262,123 @@ pub enum RatsnestDisplayMode {
     Unknown(i32),
 }
 
+impl std::fmt::Display for InactiveLayerDisplayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Dimmed => write!(f, "dimmed"),
+            Self::Hidden => write!(f, "hidden"),
+            Self::Unknown(value) => write!(f, "unknown({value})"),
+        }
+    }
+}
+
+impl FromStr for InactiveLayerDisplayMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "normal" => Ok(Self::Normal),
+            "dimmed" => Ok(Self::Dimmed),
+            "hidden" => Ok(Self::Hidden),
+            _ => parse_unknown_variant(value).map(Self::Unknown).ok_or_else(|| {
+                format!("unknown inactive layer display `{value}`; expected normal, dimmed, or hidden")
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for NetColorDisplayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Ratsnest => write!(f, "ratsnest"),
+            Self::Off => write!(f, "off"),
+            Self::Unknown(value) => write!(f, "unknown({value})"),
+        }
+    }
+}
+
+impl FromStr for NetColorDisplayMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "all" => Ok(Self::All),
+            "ratsnest" => Ok(Self::Ratsnest),
+            "off" => Ok(Self::Off),
+            _ => parse_unknown_variant(value)
+                .map(Self::Unknown)
+                .ok_or_else(|| {
+                    format!("unknown net color display `{value}`; expected all, ratsnest, or off")
+                }),
+        }
+    }
+}
+
+impl std::fmt::Display for BoardFlipMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::FlippedX => write!(f, "flipped-x"),
+            Self::Unknown(value) => write!(f, "unknown({value})"),
+        }
+    }
+}
+
+impl FromStr for BoardFlipMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "normal" => Ok(Self::Normal),
+            "flipped-x" => Ok(Self::FlippedX),
+            _ => parse_unknown_variant(value)
+                .map(Self::Unknown)
+                .ok_or_else(|| {
+                    format!("unknown board flip mode `{value}`; expected normal or flipped-x")
+                }),
+        }
+    }
+}
+
+impl std::fmt::Display for RatsnestDisplayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AllLayers => write!(f, "all-layers"),
+            Self::VisibleLayers => write!(f, "visible-layers"),
+            Self::Unknown(value) => write!(f, "unknown({value})"),
+        }
+    }
+}
+
+impl FromStr for RatsnestDisplayMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "all-layers" => Ok(Self::AllLayers),
+            "visible-layers" => Ok(Self::VisibleLayers),
+            _ => parse_unknown_variant(value)
+                .map(Self::Unknown)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown ratsnest display `{value}`; expected all-layers or visible-layers"
+                    )
+                }),
+        }
+    }
+}
+
+// Reads back the `unknown(<raw>)` form written by the appearance enum `Display` impls.
+fn parse_unknown_variant(value: &str) -> Option<i32> {
+    value
+        .strip_prefix("unknown(")?
+        .strip_suffix(')')?
+        .parse()
+        .ok()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DrcSeverity {
     Warning,
@@ -323,7 +442,7 @@ pub enum NetClassType {
     Unknown(i32),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct NetClassBoardSettings {
     pub clearance_nm: Option<i64>,
     pub track_width_nm: Option<i64>,
@@ -332,8 +451,46 @@ pub struct NetClassBoardSettings {
     pub diff_pair_via_gap_nm: Option<i64>,
     pub color: Option<ColorRgba>,
     pub tuning_profile: Option<String>,
-    pub has_via_stack: bool,
-    pub has_microvia_stack: bool,
+    /// Default via padstack for the class.
+    ///
+    /// Solder mask, drill fill and capping, and unconnected layer removal are not modelled;
+    /// see [`KiCadClient::set_net_classes_raw`](crate::client::KiCadClient::set_net_classes_raw)
+    /// for how they are written.
+    pub via_stack: Option<PcbPadStack>,
+    /// Default microvia padstack for the class.
+    pub microvia_stack: Option<PcbPadStack>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Stroke style for schematic wires and graphics.
+pub enum StrokeLineStyle {
+    /// Editor default style.
+    Default,
+    /// Solid line.
+    Solid,
+    /// Dashed line.
+    Dash,
+    /// Dotted line.
+    Dot,
+    /// Dash-dot line.
+    DashDot,
+    /// Dash-dot-dot line.
+    DashDotDot,
+    /// Unrecognized enum value.
+    Unknown(i32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Schematic-side net class settings.
+pub struct NetClassSchematicSettings {
+    /// Wire width.
+    pub wire_width_nm: Option<i64>,
+    /// Bus width.
+    pub bus_width_nm: Option<i64>,
+    /// Wire and bus colour.
+    pub color: Option<ColorRgba>,
+    /// Wire and bus line style.
+    pub line_style: Option<StrokeLineStyle>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -343,6 +500,8 @@ pub struct NetClassInfo {
     pub class_type: NetClassType,
     pub constituents: Vec<String>,
     pub board: Option<NetClassBoardSettings>,
+    /// Schematic wire and bus settings.
+    pub schematic: Option<NetClassSchematicSettings>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -414,6 +573,99 @@ pub struct PcbVia {
     pub via_type: PcbViaType,
     pub layers: Option<PcbViaLayers>,
     pub net: Option<BoardNet>,
+    /// Finished drill diameter in nm, when the padstack carries drill data.
+    pub drill_diameter_nm: Option<i64>,
+    /// Copper pad diameter in nm (smallest across padstack copper layers, so annular ring
+    /// checks are conservative).
+    pub pad_diameter_nm: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Copper shape of one padstack layer.
+pub enum PcbPadStackShape {
+    /// Circular pad.
+    Circle,
+    /// Rectangular pad.
+    Rectangle,
+    /// Oval (stadium) pad.
+    Oval,
+    /// Trapezoidal pad.
+    Trapezoid,
+    /// Rounded rectangle pad.
+    RoundRect,
+    /// Chamfered rectangle pad.
+    ChamferedRect,
+    /// Custom-shape pad.
+    Custom,
+    /// Unrecognized shape enum value.
+    Unknown(i32),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Copper definition for one padstack layer.
+pub struct PcbPadStackCopperLayer {
+    /// Copper layer this definition applies to.
+    pub layer: BoardLayerInfo,
+    /// Pad shape on this layer.
+    pub shape: PcbPadStackShape,
+    /// Pad size (unrotated) in nm.
+    pub size_nm: Option<Vector2Nm>,
+    /// Offset of the copper shape from the pad position in nm.
+    pub offset_nm: Option<Vector2Nm>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Drill span and diameter of a padstack.
+pub struct PcbDrill {
+    /// First layer of the drill span.
+    pub start_layer: BoardLayerInfo,
+    /// Last layer of the drill span.
+    pub end_layer: BoardLayerInfo,
+    /// Drill size in nm; `x_nm != y_nm` for slots.
+    pub diameter_nm: Option<Vector2Nm>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Padstack geometry of a pad.
+pub struct PcbPadStack {
+    /// Layers the padstack is present on.
+    pub layers: Vec<BoardLayerInfo>,
+    /// Per-layer copper definitions.
+    pub copper_layers: Vec<PcbPadStackCopperLayer>,
+    /// Drill definition, absent for SMD pads.
+    pub drill: Option<PcbDrill>,
+    /// Padstack rotation in degrees.
+    pub angle_deg: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Footprint mounting style.
+pub enum PcbFootprintMountingStyle {
+    /// Through-hole part.
+    ThroughHole,
+    /// Surface-mount part.
+    Smd,
+    /// Mounting style not specified.
+    Unspecified,
+    /// Unrecognized enum value.
+    Unknown(i32),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Fabrication attributes of a footprint.
+pub struct PcbFootprintAttributes {
+    /// Footprint has no schematic symbol.
+    pub not_in_schematic: bool,
+    /// Omit from position (centroid) files.
+    pub exclude_from_position_files: bool,
+    /// Omit from the bill of materials.
+    pub exclude_from_bill_of_materials: bool,
+    /// Skip the courtyard-required DRC check.
+    pub exempt_from_courtyard_requirement: bool,
+    /// Do not populate during assembly.
+    pub do_not_populate: bool,
+    /// Through-hole or SMD.
+    pub mounting_style: PcbFootprintMountingStyle,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -424,15 +676,27 @@ pub struct PcbFootprint {
     pub orientation_deg: Option<f64>,
     pub layer: BoardLayerInfo,
     pub pad_count: usize,
+    /// Value field text (e.g. `10k`).
+    pub value: Option<String>,
+    /// Library identifier as `nickname:entry`.
+    pub library_id: Option<String>,
+    /// Datasheet field text.
+    pub datasheet: Option<String>,
+    /// Fabrication attributes, when present.
+    pub attributes: Option<PcbFootprintAttributes>,
+    /// Child items of the footprint definition (pads, graphics, text), in board coordinates.
+    pub items: Vec<PcbItem>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PcbPad {
     pub id: Option<String>,
     pub number: String,
     pub pad_type: PcbPadType,
     pub position_nm: Option<Vector2Nm>,
     pub net: Option<BoardNet>,
+    /// Padstack geometry when present in the payload.
+    pub pad_stack: Option<PcbPadStack>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -441,20 +705,30 @@ pub struct PcbBoardGraphicShape {
     pub layer: BoardLayerInfo,
     pub net: Option<BoardNet>,
     pub geometry_kind: Option<String>,
+    /// Decoded shape geometry.
+    pub geometry: TextShapeGeometry,
+    /// Stroke width in nm.
+    pub stroke_width_nm: Option<i64>,
+    /// Whether the shape interior is filled.
+    pub filled: bool,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PcbBoardText {
     pub id: Option<String>,
     pub layer: BoardLayerInfo,
     pub text: Option<String>,
+    /// Full text payload (position, attributes), usable with `get_text_as_shapes`.
+    pub spec: Option<TextSpec>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PcbBoardTextBox {
     pub id: Option<String>,
     pub layer: BoardLayerInfo,
     pub text: Option<String>,
+    /// Full text box payload (corners, attributes), usable with `get_text_as_shapes`.
+    pub spec: Option<TextBoxSpec>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -472,6 +746,23 @@ pub struct PcbZone {
     pub layer_count: usize,
     pub filled: bool,
     pub polygon_count: usize,
+    /// Layers the zone is defined on.
+    pub layers: Vec<BoardLayerInfo>,
+    /// Net of a copper zone.
+    pub net: Option<BoardNet>,
+    /// Zone outline polygons.
+    pub outline: Vec<PolygonWithHolesNm>,
+    /// Per-layer fill polygons; empty until the zone is filled.
+    pub filled_polygons: Vec<PcbZoneFilledPolygons>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Fill of a zone on one layer.
+pub struct PcbZoneFilledPolygons {
+    /// Fill layer.
+    pub layer: BoardLayerInfo,
+    /// Fill polygons with holes.
+    pub polygons: Vec<PolygonWithHolesNm>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -512,6 +803,85 @@ pub enum PcbItem {
     Unknown(PcbUnknownItem),
 }
 
+impl PcbItem {
+    /// Item KIID; fields and undecoded items have none.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Track(value) => value.id.as_deref(),
+            Self::Arc(value) => value.id.as_deref(),
+            Self::Via(value) => value.id.as_deref(),
+            Self::Footprint(value) => value.id.as_deref(),
+            Self::Pad(value) => value.id.as_deref(),
+            Self::BoardGraphicShape(value) => value.id.as_deref(),
+            Self::BoardText(value) => value.id.as_deref(),
+            Self::BoardTextBox(value) => value.id.as_deref(),
+            Self::Zone(value) => value.id.as_deref(),
+            Self::Dimension(value) => value.id.as_deref(),
+            Self::Group(value) => value.id.as_deref(),
+            Self::Field(_) | Self::Unknown(_) => None,
+        }
+    }
+
+    /// Net of copper items.
+    pub fn net(&self) -> Option<&BoardNet> {
+        match self {
+            Self::Track(value) => value.net.as_ref(),
+            Self::Arc(value) => value.net.as_ref(),
+            Self::Via(value) => value.net.as_ref(),
+            Self::Pad(value) => value.net.as_ref(),
+            Self::BoardGraphicShape(value) => value.net.as_ref(),
+            Self::Zone(value) => value.net.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Layer ids the item is on; vias and pads report their padstack layers.
+    pub fn layer_ids(&self) -> Vec<i32> {
+        match self {
+            Self::Track(value) => vec![value.layer.id],
+            Self::Arc(value) => vec![value.layer.id],
+            Self::Via(value) => value
+                .layers
+                .iter()
+                .flat_map(|layers| &layers.padstack_layers)
+                .map(|layer| layer.id)
+                .collect(),
+            Self::Footprint(value) => vec![value.layer.id],
+            Self::Pad(value) => value
+                .pad_stack
+                .iter()
+                .flat_map(|stack| &stack.layers)
+                .map(|layer| layer.id)
+                .collect(),
+            Self::BoardGraphicShape(value) => vec![value.layer.id],
+            Self::BoardText(value) => vec![value.layer.id],
+            Self::BoardTextBox(value) => vec![value.layer.id],
+            Self::Zone(value) => value.layers.iter().map(|layer| layer.id).collect(),
+            Self::Dimension(value) => vec![value.layer.id],
+            Self::Field(_) | Self::Group(_) | Self::Unknown(_) => Vec::new(),
+        }
+    }
+
+    /// Short lowercase name of the item kind, e.g. `track` or `footprint`.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Track(_) => "track",
+            Self::Arc(_) => "arc",
+            Self::Via(_) => "via",
+            Self::Footprint(_) => "footprint",
+            Self::Pad(_) => "pad",
+            Self::BoardGraphicShape(_) => "shape",
+            Self::BoardText(_) => "text",
+            Self::BoardTextBox(_) => "textbox",
+            Self::Field(_) => "field",
+            Self::Zone(_) => "zone",
+            Self::Dimension(_) => "dimension",
+            Self::Group(_) => "group",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -176,10 +176,61 @@ pub struct DocumentSpecifier {
     pub document_type: DocumentType,
     /// Board filename when relevant.
     pub board_filename: Option<String>,
+    /// Schematic sheet the document refers to, for schematic documents.
+    pub sheet_path: Option<SheetPath>,
     /// Owning project metadata.
     pub project: ProjectInfo,
 }
 
+impl DocumentSpecifier {
+    /// Same document addressed at schematic sheet `sheet_path`.
+    pub fn with_sheet_path(mut self, sheet_path: SheetPath) -> Self {
+        self.sheet_path = Some(sheet_path);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Picks one open board out of several; see `KiCadClient::find_board`.
+pub enum BoardSelector {
+    /// Board filename, either as reported by KiCad or its file name / stem
+    /// (`main.kicad_pcb` or `main`).
+    Filename(String),
+    /// Project name or project directory path.
+    Project(String),
+}
+
+impl BoardSelector {
+    /// True when `document` is selected.
+    pub fn matches(&self, document: &DocumentSpecifier) -> bool {
+        match self {
+            Self::Filename(wanted) => document.board_filename.as_deref().is_some_and(|filename| {
+                let path = std::path::Path::new(filename);
+                filename == wanted
+                    || path.file_name().is_some_and(|name| name == wanted.as_str())
+                    || path.file_stem().is_some_and(|stem| stem == wanted.as_str())
+            }),
+            Self::Project(wanted) => {
+                document.project.name.as_deref() == Some(wanted.as_str())
+                    || document
+                        .project
+                        .path
+                        .as_deref()
+                        .is_some_and(|path| path == std::path::Path::new(wanted))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Path to a schematic sheet in the hierarchy.
+pub struct SheetPath {
+    /// Sheet KIIDs from the root sheet down.
+    pub path: Vec<String>,
+    /// Human-readable form such as `/` or `/child/grandchild`.
+    pub human_readable: String,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 /// Count of selected items for a specific protobuf type URL.
 pub struct SelectionTypeCount {
@@ -293,7 +344,7 @@ impl FromStr for CommitAction {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 /// Title block fields from the active document.
 pub struct TitleBlockInfo {
     /// Title block title.
@@ -304,8 +355,149 @@ pub struct TitleBlockInfo {
     pub revision: String,
     /// Company field.
     pub company: String,
-    /// Non-empty comment fields.
-    pub comments: Vec<String>,
+    /// Comment slots 1 through 9 in order; unused slots are empty.
+    pub comments: [String; 9],
+}
+
+impl TitleBlockInfo {
+    /// Text of one field.
+    pub fn field(&self, field: TitleBlockField) -> &str {
+        match field {
+            TitleBlockField::Title => &self.title,
+            TitleBlockField::Date => &self.date,
+            TitleBlockField::Revision => &self.revision,
+            TitleBlockField::Company => &self.company,
+            TitleBlockField::Comment1 => &self.comments[0],
+            TitleBlockField::Comment2 => &self.comments[1],
+            TitleBlockField::Comment3 => &self.comments[2],
+            TitleBlockField::Comment4 => &self.comments[3],
+            TitleBlockField::Comment5 => &self.comments[4],
+            TitleBlockField::Comment6 => &self.comments[5],
+            TitleBlockField::Comment7 => &self.comments[6],
+            TitleBlockField::Comment8 => &self.comments[7],
+            TitleBlockField::Comment9 => &self.comments[8],
+        }
+    }
+
+    /// Replaces the text of one field.
+    pub fn with_field(mut self, field: TitleBlockField, value: impl Into<String>) -> Self {
+        let slot = match field {
+            TitleBlockField::Title => &mut self.title,
+            TitleBlockField::Date => &mut self.date,
+            TitleBlockField::Revision => &mut self.revision,
+            TitleBlockField::Company => &mut self.company,
+            TitleBlockField::Comment1 => &mut self.comments[0],
+            TitleBlockField::Comment2 => &mut self.comments[1],
+            TitleBlockField::Comment3 => &mut self.comments[2],
+            TitleBlockField::Comment4 => &mut self.comments[3],
+            TitleBlockField::Comment5 => &mut self.comments[4],
+            TitleBlockField::Comment6 => &mut self.comments[5],
+            TitleBlockField::Comment7 => &mut self.comments[6],
+            TitleBlockField::Comment8 => &mut self.comments[7],
+            TitleBlockField::Comment9 => &mut self.comments[8],
+        };
+        *slot = value.into();
+        self
+    }
+
+    /// Every field with its text, in [`TitleBlockField::ALL`] order.
+    pub fn fields(&self) -> Vec<(TitleBlockField, &str)> {
+        TitleBlockField::ALL
+            .into_iter()
+            .map(|field| (field, self.field(field)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// One title block field.
+pub enum TitleBlockField {
+    /// Title.
+    Title,
+    /// Issue date.
+    Date,
+    /// Revision.
+    Revision,
+    /// Company.
+    Company,
+    /// Comment slot 1.
+    Comment1,
+    /// Comment slot 2.
+    Comment2,
+    /// Comment slot 3.
+    Comment3,
+    /// Comment slot 4.
+    Comment4,
+    /// Comment slot 5.
+    Comment5,
+    /// Comment slot 6.
+    Comment6,
+    /// Comment slot 7.
+    Comment7,
+    /// Comment slot 8.
+    Comment8,
+    /// Comment slot 9.
+    Comment9,
+}
+
+impl TitleBlockField {
+    /// Every field in title block order.
+    pub const ALL: [TitleBlockField; 13] = [
+        Self::Title,
+        Self::Date,
+        Self::Revision,
+        Self::Company,
+        Self::Comment1,
+        Self::Comment2,
+        Self::Comment3,
+        Self::Comment4,
+        Self::Comment5,
+        Self::Comment6,
+        Self::Comment7,
+        Self::Comment8,
+        Self::Comment9,
+    ];
+
+    /// Comment field for 1-based slot `slot`, if it exists.
+    pub fn comment(slot: usize) -> Option<Self> {
+        (1..=9).contains(&slot).then(|| Self::ALL[slot + 3])
+    }
+}
+
+impl std::fmt::Display for TitleBlockField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Title => "title",
+            Self::Date => "date",
+            Self::Revision => "revision",
+            Self::Company => "company",
+            Self::Comment1 => "comment1",
+            Self::Comment2 => "comment2",
+            Self::Comment3 => "comment3",
+            Self::Comment4 => "comment4",
+            Self::Comment5 => "comment5",
+            Self::Comment6 => "comment6",
+            Self::Comment7 => "comment7",
+            Self::Comment8 => "comment8",
+            Self::Comment9 => "comment9",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for TitleBlockField {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.to_string() == value)
+            .ok_or_else(|| {
+                format!(
+                    "unknown title block field `{value}`; expected title, date, revision, company, or comment1..comment9"
+                )
+            })
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -426,7 +618,7 @@ pub enum TextObjectSpec {
     TextBox(TextBoxSpec),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TextShapeGeometry {
     Segment {
         start_nm: Option<Vector2Nm>,
@@ -488,9 +680,34 @@ impl std::fmt::Display for ItemHitTestResult {
 
 #[cfg(test)]
 mod tests {
-    use super::{CommitAction, EditorFrameType, MapMergeMode};
+    use super::{
+        BoardSelector, CommitAction, DocumentSpecifier, DocumentType, EditorFrameType,
+        MapMergeMode, ProjectInfo,
+    };
+    use std::path::PathBuf;
     use std::str::FromStr;
 
+    #[test]
+    fn board_selector_matches_filename_forms_and_project() {
+        let document = DocumentSpecifier {
+            document_type: DocumentType::Pcb,
+            board_filename: Some("panel/main.kicad_pcb".to_string()),
+            sheet_path: None,
+            project: ProjectInfo {
+                name: Some("panel".to_string()),
+                path: Some(PathBuf::from("/work/panel")),
+            },
+        };
+
+        for wanted in ["panel/main.kicad_pcb", "main.kicad_pcb", "main"] {
+            assert!(BoardSelector::Filename(wanted.to_string()).matches(&document));
+        }
+        assert!(!BoardSelector::Filename("other".to_string()).matches(&document));
+        assert!(BoardSelector::Project("panel".to_string()).matches(&document));
+        assert!(BoardSelector::Project("/work/panel".to_string()).matches(&document));
+        assert!(!BoardSelector::Project("/work".to_string()).matches(&document));
+    }
+
     #[test]
     fn commit_action_parses_known_values() {
         assert_eq!(CommitAction::from_str("commit"), Ok(CommitAction::Commit));
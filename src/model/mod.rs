@@ -1,2 +1,4 @@
 pub mod board;
 pub mod common;
+/// Schematic item models.
+pub mod schematic;
@@ -0,0 +1,135 @@
+use crate::model::board::Vector2Nm;
+use crate::model::common::TextSpec;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// Schematic item kinds with typed models.
+pub enum SchematicItemType {
+    /// Wire, bus, or graphic line.
+    Line,
+    /// Local net label.
+    LocalLabel,
+    /// Global net label.
+    GlobalLabel,
+    /// Hierarchical sheet label.
+    HierarchicalLabel,
+    /// Directive (net class) label.
+    DirectiveLabel,
+}
+
+impl SchematicItemType {
+    /// Every type with a typed model.
+    pub const ALL: [SchematicItemType; 5] = [
+        Self::Line,
+        Self::LocalLabel,
+        Self::GlobalLabel,
+        Self::HierarchicalLabel,
+        Self::DirectiveLabel,
+    ];
+
+    /// KiCad object type code used in item requests.
+    pub fn type_code(self) -> i32 {
+        let object_type = match self {
+            Self::Line => KiCadObjectType::KotSchLine,
+            Self::LocalLabel => KiCadObjectType::KotSchLabel,
+            Self::GlobalLabel => KiCadObjectType::KotSchGlobalLabel,
+            Self::HierarchicalLabel => KiCadObjectType::KotSchHierLabel,
+            Self::DirectiveLabel => KiCadObjectType::KotSchDirectiveLabel,
+        };
+        object_type as i32
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Schematic line segment.
+pub struct SchematicLine {
+    /// Item KIID; `None` when creating a new item.
+    pub id: Option<String>,
+    /// Start point.
+    pub start_nm: Option<Vector2Nm>,
+    /// End point.
+    pub end_nm: Option<Vector2Nm>,
+    /// Raw schematic layer code.
+    pub layer: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Schematic label of any kind; the kind is the [`SchematicItem`] variant.
+pub struct SchematicLabel {
+    /// Item KIID; `None` when creating a new item.
+    pub id: Option<String>,
+    /// Anchor position.
+    pub position_nm: Option<Vector2Nm>,
+    /// Label text and attributes.
+    pub text: Option<TextSpec>,
+}
+
+impl SchematicLabel {
+    /// Label text, or `""` when the label has none.
+    pub fn text(&self) -> &str {
+        self.text.as_ref().map_or("", |text| text.text.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Schematic item payload without a typed model.
+pub struct SchematicUnknownItem {
+    /// Protobuf type URL.
+    pub type_url: String,
+    /// Encoded payload length in bytes.
+    pub raw_len: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Decoded schematic item.
+pub enum SchematicItem {
+    /// Wire, bus, or graphic line.
+    Line(SchematicLine),
+    /// Local net label.
+    LocalLabel(SchematicLabel),
+    /// Global net label.
+    GlobalLabel(SchematicLabel),
+    /// Hierarchical sheet label.
+    HierarchicalLabel(SchematicLabel),
+    /// Directive (net class) label.
+    DirectiveLabel(SchematicLabel),
+    /// Payload without a typed model.
+    Unknown(SchematicUnknownItem),
+}
+
+impl SchematicItem {
+    /// Item KIID; undecoded items have none.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Line(value) => value.id.as_deref(),
+            Self::LocalLabel(value)
+            | Self::GlobalLabel(value)
+            | Self::HierarchicalLabel(value)
+            | Self::DirectiveLabel(value) => value.id.as_deref(),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Item type; `None` for undecoded payloads.
+    pub fn item_type(&self) -> Option<SchematicItemType> {
+        Some(match self {
+            Self::Line(_) => SchematicItemType::Line,
+            Self::LocalLabel(_) => SchematicItemType::LocalLabel,
+            Self::GlobalLabel(_) => SchematicItemType::GlobalLabel,
+            Self::HierarchicalLabel(_) => SchematicItemType::HierarchicalLabel,
+            Self::DirectiveLabel(_) => SchematicItemType::DirectiveLabel,
+            Self::Unknown(_) => return None,
+        })
+    }
+
+    /// Label contents for any label kind.
+    pub fn label(&self) -> Option<&SchematicLabel> {
+        match self {
+            Self::LocalLabel(value)
+            | Self::GlobalLabel(value)
+            | Self::HierarchicalLabel(value)
+            | Self::DirectiveLabel(value) => Some(value),
+            Self::Line(_) | Self::Unknown(_) => None,
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Local net class resolution and pattern audit.
+//!
+//! [`NetClassResolver`] predicts which class KiCad assigns each net from the `constituents`
+//! patterns of the explicit classes, without asking KiCad. Like KiCad, a pattern matches a net
+//! name when it matches in full either as a regular expression or as a `*`/`?` wildcard. A net
+//! caught by several classes resolves to an implicit composite class named after them in
+//! priority order (lowest priority value first); a net caught by none falls back to `Default`.
+//!
+//! [`audit`] compares those predictions with KiCad's own answer from
+//! [`KiCadClient::get_netclass_for_nets`](crate::client::KiCadClient::get_netclass_for_nets).
+//! Nets assigned through schematic directive labels carry no pattern, so they show up as
+//! mismatches.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::model::board::{BoardNet, NetClassForNetEntry, NetClassInfo, NetClassType};
+use crate::pattern::Pattern;
+
+const DEFAULT_NET_CLASS: &str = "Default";
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// One `constituents` pattern of a net class.
+pub struct ClassPattern {
+    /// Owning class name.
+    pub class: String,
+    /// Pattern text as stored in the class.
+    pub pattern: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Predicted class assignment for one net.
+pub struct NetClassResolution {
+    /// Net name.
+    pub net_name: String,
+    /// Matching patterns, ordered by class priority.
+    pub matches: Vec<ClassPattern>,
+}
+
+impl NetClassResolution {
+    /// Matching class names in priority order, without repeats.
+    pub fn classes(&self) -> Vec<&str> {
+        let mut classes: Vec<&str> = Vec::new();
+        for found in &self.matches {
+            if !classes.contains(&found.class.as_str()) {
+                classes.push(&found.class);
+            }
+        }
+        classes
+    }
+
+    /// Effective class name: `Default`, a single class, or a comma-joined composite.
+    pub fn class_name(&self) -> String {
+        let classes = self.classes();
+        if classes.is_empty() {
+            DEFAULT_NET_CLASS.to_string()
+        } else {
+            classes.join(",")
+        }
+    }
+}
+
+/// Applies explicit net class patterns to net names.
+#[derive(Clone, Debug)]
+pub struct NetClassResolver {
+    patterns: Vec<(ClassPattern, Option<Pattern>, Pattern)>,
+}
+
+impl NetClassResolver {
+    /// Compiles the patterns of every explicit class, ordered by priority then name.
+    pub fn new(net_classes: &[NetClassInfo]) -> Self {
+        let mut classes: Vec<&NetClassInfo> = net_classes
+            .iter()
+            .filter(|class| class.class_type != NetClassType::Implicit)
+            .filter(|class| class.name != DEFAULT_NET_CLASS)
+            .collect();
+        classes.sort_by(|left, right| {
+            (left.priority.is_none(), left.priority, &left.name).cmp(&(
+                right.priority.is_none(),
+                right.priority,
+                &right.name,
+            ))
+        });
+
+        let patterns = classes
+            .into_iter()
+            .flat_map(|class| {
+                class.constituents.iter().map(|pattern| {
+                    (
+                        ClassPattern {
+                            class: class.name.clone(),
+                            pattern: pattern.clone(),
+                        },
+                        Pattern::regex(pattern),
+                        Pattern::wildcard(pattern),
+                    )
+                })
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Predicts the class assignment for `net_name`.
+    pub fn resolve(&self, net_name: &str) -> NetClassResolution {
+        NetClassResolution {
+            net_name: net_name.to_string(),
+            matches: self
+                .patterns
+                .iter()
+                .filter(|(_, regex, wildcard)| {
+                    regex
+                        .as_ref()
+                        .is_some_and(|regex| regex.is_full_match(net_name))
+                        || wildcard.is_full_match(net_name)
+                })
+                .map(|(pattern, _, _)| pattern.clone())
+                .collect(),
+        }
+    }
+
+    /// Every compiled pattern, in resolution order.
+    pub fn patterns(&self) -> impl Iterator<Item = &ClassPattern> {
+        self.patterns.iter().map(|(pattern, _, _)| pattern)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Net whose predicted class differs from KiCad's.
+pub struct NetClassMismatch {
+    /// Net name.
+    pub net_name: String,
+    /// Class predicted from the patterns.
+    pub predicted: String,
+    /// Class KiCad reported.
+    pub actual: String,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Findings from [`audit`].
+pub struct NetClassAudit {
+    /// Predicted assignment for every net, in input order.
+    pub resolutions: Vec<NetClassResolution>,
+    /// Nets where the prediction and KiCad disagree.
+    pub mismatches: Vec<NetClassMismatch>,
+    /// Nets matched by more than one pattern.
+    pub overlaps: Vec<NetClassResolution>,
+    /// Patterns that match no net.
+    pub unused_patterns: Vec<ClassPattern>,
+}
+
+impl NetClassAudit {
+    /// Returns true when nothing was flagged.
+    pub fn is_clean(&self) -> bool {
+        self.mismatches.is_empty() && self.overlaps.is_empty() && self.unused_patterns.is_empty()
+    }
+}
+
+impl fmt::Display for NetClassAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mismatch in &self.mismatches {
+            writeln!(
+                f,
+                "mismatch net={} predicted={} actual={}",
+                mismatch.net_name, mismatch.predicted, mismatch.actual
+            )?;
+        }
+        for overlap in &self.overlaps {
+            let patterns: Vec<String> = overlap
+                .matches
+                .iter()
+                .map(|found| format!("{}:{}", found.class, found.pattern))
+                .collect();
+            writeln!(
+                f,
+                "overlap net={} patterns={}",
+                overlap.net_name,
+                patterns.join(",")
+            )?;
+        }
+        for unused in &self.unused_patterns {
+            writeln!(
+                f,
+                "unused class={} pattern={}",
+                unused.class, unused.pattern
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves `nets` locally and checks the result against KiCad's `assignments`.
+///
+/// Nets missing from `assignments` are resolved but not compared.
+pub fn audit(
+    net_classes: &[NetClassInfo],
+    nets: &[BoardNet],
+    assignments: &[NetClassForNetEntry],
+) -> NetClassAudit {
+    let resolver = NetClassResolver::new(net_classes);
+    let actual: BTreeMap<&str, &str> = assignments
+        .iter()
+        .map(|entry| (entry.net_name.as_str(), entry.net_class.name.as_str()))
+        .collect();
+
+    let mut result = NetClassAudit::default();
+    let mut used = BTreeSet::new();
+    for net in nets {
+        let resolution = resolver.resolve(&net.name);
+        used.extend(resolution.matches.iter().cloned());
+        let predicted = resolution.class_name();
+        if let Some(actual) = actual.get(net.name.as_str()) {
+            if !same_class(&predicted, actual) {
+                result.mismatches.push(NetClassMismatch {
+                    net_name: net.name.clone(),
+                    predicted,
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        if resolution.matches.len() > 1 {
+            result.overlaps.push(resolution.clone());
+        }
+        result.resolutions.push(resolution);
+    }
+    result.unused_patterns = resolver
+        .patterns()
+        .filter(|pattern| !used.contains(*pattern))
+        .cloned()
+        .collect();
+    result
+}
+
+/// Reads net classes, nets, and KiCad's assignments from the open board and audits them.
+pub async fn audit_board(client: &KiCadClient) -> Result<NetClassAudit, KiCadError> {
+    let net_classes = client.get_net_classes().await?;
+    let nets = client.get_nets().await?;
+    let assignments = if nets.is_empty() {
+        Vec::new()
+    } else {
+        client.get_netclass_for_nets(nets.clone()).await?
+    };
+    Ok(audit(&net_classes, &nets, &assignments))
+}
+
+// Composite class names list their members; compare them as sets so member order is not
+// significant.
+fn same_class(predicted: &str, actual: &str) -> bool {
+    fn members(name: &str) -> BTreeSet<&str> {
+        name.split(',').map(str::trim).collect()
+    }
+    predicted == actual || members(predicted) == members(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(name: &str, priority: Option<i32>, constituents: &[&str]) -> NetClassInfo {
+        NetClassInfo {
+            name: name.to_string(),
+            priority,
+            class_type: NetClassType::Explicit,
+            constituents: constituents.iter().map(|value| value.to_string()).collect(),
+            board: None,
+            schematic: None,
+        }
+    }
+
+    fn net(name: &str) -> BoardNet {
+        BoardNet {
+            code: 0,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn resolver_orders_composite_classes_by_priority() {
+        let mut implicit = class("Power,USB", None, &["VBUS"]);
+        implicit.class_type = NetClassType::Implicit;
+        let resolver = NetClassResolver::new(&[
+            class("USB", Some(2), &["USB_*", "VBUS"]),
+            class("Power", Some(1), &["V*", "^GND$"]),
+            implicit,
+        ]);
+
+        assert_eq!(resolver.resolve("USB_DP").class_name(), "USB");
+        assert_eq!(resolver.resolve("VBUS").class_name(), "Power,USB");
+        assert_eq!(resolver.resolve("GND").class_name(), "Power");
+        assert_eq!(resolver.resolve("/sheet/SDA").class_name(), "Default");
+    }
+
+    #[test]
+    fn audit_reports_mismatches_overlaps_and_unused_patterns() {
+        let classes = vec![
+            class("Default", None, &[]),
+            class("USB", Some(2), &["USB_D[PN]", "VBUS"]),
+            class("Power", Some(1), &["VBUS", "+3V3", "+5V"]),
+        ];
+        let nets = vec![net("USB_DP"), net("VBUS"), net("+3V3"), net("SDA")];
+        let assignment = |net_name: &str, class_name: &str| NetClassForNetEntry {
+            net_name: net_name.to_string(),
+            net_class: class(class_name, None, &[]),
+        };
+        let assignments = vec![
+            assignment("USB_DP", "USB"),
+            assignment("VBUS", "USB,Power"),
+            assignment("+3V3", "Power"),
+            assignment("SDA", "I2C"),
+        ];
+
+        let audit = audit(&classes, &nets, &assignments);
+        assert_eq!(audit.resolutions.len(), 4);
+        assert_eq!(
+            audit.mismatches,
+            vec![NetClassMismatch {
+                net_name: "SDA".to_string(),
+                predicted: "Default".to_string(),
+                actual: "I2C".to_string(),
+            }]
+        );
+        assert_eq!(audit.overlaps.len(), 1);
+        assert_eq!(audit.overlaps[0].net_name, "VBUS");
+        assert_eq!(
+            audit.unused_patterns,
+            vec![ClassPattern {
+                class: "Power".to_string(),
+                pattern: "+5V".to_string(),
+            }]
+        );
+        assert!(!audit.is_clean());
+        assert!(audit.to_string().contains("unused class=Power pattern=+5V"));
+    }
+}
@@ -0,0 +1,763 @@
+//! Declarative net class sync.
+//!
+//! Net class definitions kept in version control are loaded with [`parse_toml`], compared with
+//! the project's classes by [`plan`], and pushed with [`apply`]. A [`NetClassPlan`] prints as a
+//! human-readable change list, so printing it without applying is a dry run.
+//!
+//! Only explicit classes take part: KiCad derives implicit classes from net assignments, and the
+//! `Default` class is never removed. Fields a desired class leaves unset (`None`, empty
+//! `constituents`, or no board or schematic settings at all) are not managed and keep their
+//! current values.
+//!
+//! Definitions are read from TOML only; the crate has no JSON parser, so JSON files must be
+//! converted first or built into [`NetClassInfo`] values directly.
+
+use std::fmt;
+
+use crate::client::{layer_to_model, KiCadClient};
+use crate::error::KiCadError;
+use crate::model::board::{
+    NetClassBoardSettings, NetClassInfo, NetClassSchematicSettings, NetClassType, PcbDrill,
+    PcbPadStack, PcbPadStackCopperLayer, PcbPadStackShape, Vector2Nm,
+};
+use crate::model::common::MapMergeMode;
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::toml::{self, TomlValue};
+
+const DEFAULT_NET_CLASS: &str = "Default";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// One field that differs between the current and desired class.
+pub struct FieldChange {
+    /// Field name as used in net class files.
+    pub field: &'static str,
+    /// Current value, `-` when unset.
+    pub before: String,
+    /// Desired value, `-` when unset.
+    pub after: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// One planned net class change.
+pub enum NetClassChange {
+    /// Class missing from the project.
+    Add(NetClassInfo),
+    /// Class present in both with differing fields; holds the merged class to push.
+    Update {
+        /// Class as it will be pushed.
+        class: NetClassInfo,
+        /// Fields that change.
+        fields: Vec<FieldChange>,
+    },
+    /// Explicit project class absent from the desired set.
+    Remove(NetClassInfo),
+}
+
+impl NetClassChange {
+    /// Name of the affected class.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Add(class) | Self::Update { class, .. } | Self::Remove(class) => &class.name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Changes needed to bring project net classes to a desired set.
+pub struct NetClassPlan {
+    /// Planned changes, sorted by class name.
+    pub changes: Vec<NetClassChange>,
+    unchanged: Vec<NetClassInfo>,
+}
+
+impl NetClassPlan {
+    /// Returns true when the project already matches.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Merge mode [`apply`] uses: `Replace` when classes are removed, `Merge` otherwise.
+    pub fn merge_mode(&self) -> MapMergeMode {
+        if self
+            .changes
+            .iter()
+            .any(|change| matches!(change, NetClassChange::Remove(_)))
+        {
+            MapMergeMode::Replace
+        } else {
+            MapMergeMode::Merge
+        }
+    }
+
+    /// Classes [`apply`] sends with [`NetClassPlan::merge_mode`].
+    ///
+    /// `Merge` sends only added and updated classes; `Replace` also resends unchanged ones so
+    /// they survive the replacement.
+    pub fn net_classes(&self) -> Vec<NetClassInfo> {
+        let mut classes: Vec<NetClassInfo> = self
+            .changes
+            .iter()
+            .filter_map(|change| match change {
+                NetClassChange::Add(class) | NetClassChange::Update { class, .. } => {
+                    Some(class.clone())
+                }
+                NetClassChange::Remove(_) => None,
+            })
+            .collect();
+        if self.merge_mode() == MapMergeMode::Replace {
+            classes.extend(self.unchanged.iter().cloned());
+            classes.sort_by(|left, right| left.name.cmp(&right.name));
+        }
+        classes
+    }
+}
+
+impl fmt::Display for NetClassPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "net classes up to date");
+        }
+        for change in &self.changes {
+            match change {
+                NetClassChange::Add(class) => {
+                    writeln!(f, "+ add {}", class.name)?;
+                    for (field, value) in describe(class) {
+                        writeln!(f, "    {field} = {value}")?;
+                    }
+                }
+                NetClassChange::Update { class, fields } => {
+                    writeln!(f, "~ update {}", class.name)?;
+                    for change in fields {
+                        writeln!(
+                            f,
+                            "    {}: {} -> {}",
+                            change.field, change.before, change.after
+                        )?;
+                    }
+                }
+                NetClassChange::Remove(class) => writeln!(f, "- remove {}", class.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Diffs desired explicit net classes against the project's current classes.
+pub fn plan(desired: Vec<NetClassInfo>, current: &[NetClassInfo]) -> NetClassPlan {
+    let current: Vec<&NetClassInfo> = current
+        .iter()
+        .filter(|class| class.class_type != NetClassType::Implicit)
+        .collect();
+    let mut result = NetClassPlan::default();
+
+    for class in &current {
+        match desired.iter().find(|wanted| wanted.name == class.name) {
+            Some(wanted) => {
+                let merged = overlay(wanted, class);
+                let fields = field_changes(class, &merged);
+                if fields.is_empty() {
+                    result.unchanged.push((*class).clone());
+                } else {
+                    result.changes.push(NetClassChange::Update {
+                        class: merged,
+                        fields,
+                    });
+                }
+            }
+            None if class.name == DEFAULT_NET_CLASS => result.unchanged.push((*class).clone()),
+            None => result
+                .changes
+                .push(NetClassChange::Remove((*class).clone())),
+        }
+    }
+    for wanted in desired {
+        if !current.iter().any(|class| class.name == wanted.name) {
+            result.changes.push(NetClassChange::Add(wanted));
+        }
+    }
+
+    result
+        .changes
+        .sort_by(|left, right| left.name().cmp(right.name()));
+    result
+}
+
+/// Pushes a plan and returns the project's net classes afterwards.
+///
+/// An empty plan sends nothing.
+pub async fn apply(
+    client: &KiCadClient,
+    plan: &NetClassPlan,
+) -> Result<Vec<NetClassInfo>, KiCadError> {
+    if plan.is_empty() {
+        return client.get_net_classes().await;
+    }
+    client
+        .set_net_classes(plan.net_classes(), plan.merge_mode())
+        .await
+}
+
+/// Reads the project's classes and plans the changes toward `desired`.
+pub async fn plan_for(
+    client: &KiCadClient,
+    desired: Vec<NetClassInfo>,
+) -> Result<NetClassPlan, KiCadError> {
+    let current = client.get_net_classes().await?;
+    Ok(plan(desired, &current))
+}
+
+fn overlay(desired: &NetClassInfo, current: &NetClassInfo) -> NetClassInfo {
+    let board = match (&desired.board, &current.board) {
+        (Some(wanted), Some(board)) => Some(NetClassBoardSettings {
+            clearance_nm: wanted.clearance_nm.or(board.clearance_nm),
+            track_width_nm: wanted.track_width_nm.or(board.track_width_nm),
+            diff_pair_track_width_nm: wanted
+                .diff_pair_track_width_nm
+                .or(board.diff_pair_track_width_nm),
+            diff_pair_gap_nm: wanted.diff_pair_gap_nm.or(board.diff_pair_gap_nm),
+            diff_pair_via_gap_nm: wanted.diff_pair_via_gap_nm.or(board.diff_pair_via_gap_nm),
+            color: wanted.color.or(board.color),
+            tuning_profile: wanted
+                .tuning_profile
+                .clone()
+                .or_else(|| board.tuning_profile.clone()),
+            via_stack: overlay_via(wanted.via_stack.clone(), board.via_stack.clone()),
+            microvia_stack: overlay_via(
+                wanted.microvia_stack.clone(),
+                board.microvia_stack.clone(),
+            ),
+        }),
+        (wanted, board) => wanted.clone().or_else(|| board.clone()),
+    };
+    let schematic = match (&desired.schematic, &current.schematic) {
+        (Some(wanted), Some(schematic)) => Some(NetClassSchematicSettings {
+            wire_width_nm: wanted.wire_width_nm.or(schematic.wire_width_nm),
+            bus_width_nm: wanted.bus_width_nm.or(schematic.bus_width_nm),
+            color: wanted.color.or(schematic.color),
+            line_style: wanted.line_style.or(schematic.line_style),
+        }),
+        (wanted, schematic) => wanted.clone().or_else(|| schematic.clone()),
+    };
+
+    NetClassInfo {
+        name: current.name.clone(),
+        priority: desired.priority.or(current.priority),
+        class_type: current.class_type,
+        constituents: if desired.constituents.is_empty() {
+            current.constituents.clone()
+        } else {
+            desired.constituents.clone()
+        },
+        board,
+        schematic,
+    }
+}
+
+/// Parts of a desired padstack left empty (no layers, no copper layers, no drill, no angle)
+/// keep the current padstack's values.
+fn overlay_via(desired: Option<PcbPadStack>, current: Option<PcbPadStack>) -> Option<PcbPadStack> {
+    match (desired, current) {
+        (Some(wanted), Some(via)) => Some(PcbPadStack {
+            layers: if wanted.layers.is_empty() {
+                via.layers
+            } else {
+                wanted.layers
+            },
+            copper_layers: if wanted.copper_layers.is_empty() {
+                via.copper_layers
+            } else {
+                wanted.copper_layers
+            },
+            drill: wanted.drill.or(via.drill),
+            angle_deg: wanted.angle_deg.or(via.angle_deg),
+        }),
+        (wanted, via) => wanted.or(via),
+    }
+}
+
+/// Pad diameter (first copper layer) and drill diameter of a via padstack.
+fn via_size(stack: Option<&PcbPadStack>) -> (Option<i64>, Option<i64>) {
+    let Some(stack) = stack else {
+        return (None, None);
+    };
+    (
+        stack
+            .copper_layers
+            .first()
+            .and_then(|layer| layer.size_nm)
+            .map(|size| size.x_nm),
+        stack
+            .drill
+            .as_ref()
+            .and_then(|drill| drill.diameter_nm)
+            .map(|size| size.x_nm),
+    )
+}
+
+fn field_changes(before: &NetClassInfo, after: &NetClassInfo) -> Vec<FieldChange> {
+    let before = describe(before);
+    describe(after)
+        .into_iter()
+        .zip(before)
+        .filter(|((_, after), (_, before))| after != before)
+        .map(|((field, after), (_, before))| FieldChange {
+            field,
+            before,
+            after,
+        })
+        .collect()
+}
+
+fn describe(class: &NetClassInfo) -> Vec<(&'static str, String)> {
+    fn value<T: fmt::Display>(value: Option<T>) -> String {
+        value.map_or_else(|| "-".to_string(), |value| value.to_string())
+    }
+    fn debug<T: fmt::Debug>(value: Option<T>) -> String {
+        value.map_or_else(|| "-".to_string(), |value| format!("{value:?}"))
+    }
+
+    let board = class.board.as_ref();
+    let (via_diameter, via_drill) = via_size(board.and_then(|board| board.via_stack.as_ref()));
+    let (microvia_diameter, microvia_drill) =
+        via_size(board.and_then(|board| board.microvia_stack.as_ref()));
+    let schematic = class.schematic.as_ref();
+    vec![
+        ("priority", value(class.priority)),
+        ("constituents", format!("{:?}", class.constituents)),
+        ("clearance_nm", value(board.and_then(|b| b.clearance_nm))),
+        (
+            "track_width_nm",
+            value(board.and_then(|b| b.track_width_nm)),
+        ),
+        (
+            "diff_pair_track_width_nm",
+            value(board.and_then(|b| b.diff_pair_track_width_nm)),
+        ),
+        (
+            "diff_pair_gap_nm",
+            value(board.and_then(|b| b.diff_pair_gap_nm)),
+        ),
+        (
+            "diff_pair_via_gap_nm",
+            value(board.and_then(|b| b.diff_pair_via_gap_nm)),
+        ),
+        ("via_diameter_nm", value(via_diameter)),
+        ("via_drill_nm", value(via_drill)),
+        ("microvia_diameter_nm", value(microvia_diameter)),
+        ("microvia_drill_nm", value(microvia_drill)),
+        ("color", debug(board.and_then(|b| b.color))),
+        (
+            "tuning_profile",
+            value(board.and_then(|b| b.tuning_profile.as_deref())),
+        ),
+        (
+            "wire_width_nm",
+            value(schematic.and_then(|s| s.wire_width_nm)),
+        ),
+        (
+            "bus_width_nm",
+            value(schematic.and_then(|s| s.bus_width_nm)),
+        ),
+        ("schematic_color", debug(schematic.and_then(|s| s.color))),
+        ("line_style", debug(schematic.and_then(|s| s.line_style))),
+    ]
+}
+
+/// Parses net class definitions from a TOML file.
+///
+/// Supports the subset net class files need: one `[[netclass]]` table per class holding
+/// strings, integers, and single-line string arrays. Distances are integer nanometres.
+///
+/// ```toml
+/// [[netclass]]
+/// name = "USB"
+/// priority = 1
+/// constituents = ["USB_D*"]
+/// track_width_nm = 200_000
+/// diff_pair_gap_nm = 150_000
+/// via_diameter_nm = 600_000
+/// via_drill_nm = 300_000
+/// ```
+///
+/// Via and microvia sizes become a circular padstack on F.Cu drilled through to B.Cu, which
+/// replaces the class's current copper layers or drill only when the matching key is set.
+///
+/// Omitting `constituents` keeps the class's current patterns; an empty list is rejected
+/// because it would be indistinguishable from omitting the key.
+///
+/// Recognised keys: `name` (required), `priority`, `constituents`, `clearance_nm`,
+/// `track_width_nm`, `diff_pair_track_width_nm`, `diff_pair_gap_nm`, `diff_pair_via_gap_nm`,
+/// `via_diameter_nm`, `via_drill_nm`, `microvia_diameter_nm`, `microvia_drill_nm`,
+/// `tuning_profile`, `wire_width_nm`, and `bus_width_nm`.
+pub fn parse_toml(text: &str) -> Result<Vec<NetClassInfo>, KiCadError> {
+    let document =
+        toml::parse_document(text).map_err(|(line, reason)| parse_error(line, reason))?;
+    if let Some(entry) = document.root.first() {
+        return Err(parse_error(
+            entry.line,
+            "key outside of a `[[netclass]]` table".to_string(),
+        ));
+    }
+
+    let mut classes: Vec<NetClassInfo> = Vec::new();
+    for table in document.tables {
+        if table.header != "[[netclass]]" {
+            return Err(parse_error(
+                table.line,
+                format!(
+                    "unsupported table `{}`; expected `[[netclass]]`",
+                    table.header
+                ),
+            ));
+        }
+        let mut builder = ClassBuilder::default();
+        for entry in table.entries {
+            builder
+                .set(&entry.key, entry.value)
+                .map_err(|reason| parse_error(entry.line, reason))?;
+        }
+        classes.push(builder.build(table.line)?);
+    }
+
+    for (index, class) in classes.iter().enumerate() {
+        if classes[..index]
+            .iter()
+            .any(|other| other.name == class.name)
+        {
+            return Err(KiCadError::Config {
+                reason: format!("net class `{}` is defined more than once", class.name),
+            });
+        }
+    }
+    Ok(classes)
+}
+
+fn parse_error(line: usize, reason: String) -> KiCadError {
+    KiCadError::Config {
+        reason: format!("net class file line {line}: {reason}"),
+    }
+}
+
+#[derive(Debug, Default)]
+struct ClassBuilder {
+    name: Option<String>,
+    priority: Option<i32>,
+    constituents: Option<Vec<String>>,
+    board: NetClassBoardSettings,
+    has_board: bool,
+    via: ViaSize,
+    microvia: ViaSize,
+    wire_width_nm: Option<i64>,
+    bus_width_nm: Option<i64>,
+}
+
+impl ClassBuilder {
+    fn set(&mut self, key: &str, value: TomlValue) -> Result<(), String> {
+        let mismatch = |expected: &str, value: &TomlValue| {
+            format!("`{key}` must be a {expected}, found {}", value.kind())
+        };
+        let distance = |value: TomlValue| match value {
+            TomlValue::Integer(value_nm) => Ok(Some(value_nm)),
+            other => Err(mismatch("integer", &other)),
+        };
+
+        match key {
+            "name" => match value {
+                TomlValue::String(name) => self.name = Some(name),
+                other => return Err(mismatch("string", &other)),
+            },
+            "priority" => match value {
+                TomlValue::Integer(priority) => {
+                    self.priority = Some(
+                        i32::try_from(priority)
+                            .map_err(|_| format!("priority {priority} is out of range"))?,
+                    )
+                }
+                other => return Err(mismatch("integer", &other)),
+            },
+            "constituents" => {
+                match value {
+                    TomlValue::Array(patterns) if patterns.is_empty() => return Err(
+                        "`constituents` must not be empty; omit it to keep the current patterns"
+                            .to_string(),
+                    ),
+                    TomlValue::Array(patterns) => self.constituents = Some(patterns),
+                    other => return Err(mismatch("string array", &other)),
+                }
+            }
+            "tuning_profile" => match value {
+                TomlValue::String(profile) => {
+                    self.board.tuning_profile = Some(profile);
+                    self.has_board = true;
+                }
+                other => return Err(mismatch("string", &other)),
+            },
+            "wire_width_nm" => self.wire_width_nm = distance(value)?,
+            "bus_width_nm" => self.bus_width_nm = distance(value)?,
+            "via_diameter_nm" => self.via.diameter_nm = distance(value)?,
+            "via_drill_nm" => self.via.drill_nm = distance(value)?,
+            "microvia_diameter_nm" => self.microvia.diameter_nm = distance(value)?,
+            "microvia_drill_nm" => self.microvia.drill_nm = distance(value)?,
+            _ => {
+                let field = match key {
+                    "clearance_nm" => &mut self.board.clearance_nm,
+                    "track_width_nm" => &mut self.board.track_width_nm,
+                    "diff_pair_track_width_nm" => &mut self.board.diff_pair_track_width_nm,
+                    "diff_pair_gap_nm" => &mut self.board.diff_pair_gap_nm,
+                    "diff_pair_via_gap_nm" => &mut self.board.diff_pair_via_gap_nm,
+                    _ => return Err(format!("unknown net class key `{key}`")),
+                };
+                *field = distance(value)?;
+                self.has_board = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn build(self, line: usize) -> Result<NetClassInfo, KiCadError> {
+        let name = self
+            .name
+            .ok_or_else(|| parse_error(line, "`[[netclass]]` table has no `name`".to_string()))?;
+        let via_stack = self.via.pad_stack();
+        let microvia_stack = self.microvia.pad_stack();
+        let board = (self.has_board || via_stack.is_some() || microvia_stack.is_some()).then_some(
+            NetClassBoardSettings {
+                via_stack,
+                microvia_stack,
+                ..self.board
+            },
+        );
+        let schematic = (self.wire_width_nm.is_some() || self.bus_width_nm.is_some()).then_some(
+            NetClassSchematicSettings {
+                wire_width_nm: self.wire_width_nm,
+                bus_width_nm: self.bus_width_nm,
+                color: None,
+                line_style: None,
+            },
+        );
+
+        Ok(NetClassInfo {
+            name,
+            priority: self.priority,
+            class_type: NetClassType::Explicit,
+            constituents: self.constituents.unwrap_or_default(),
+            board,
+            schematic,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ViaSize {
+    diameter_nm: Option<i64>,
+    drill_nm: Option<i64>,
+}
+
+impl ViaSize {
+    fn pad_stack(self) -> Option<PcbPadStack> {
+        let round = |diameter_nm| Vector2Nm {
+            x_nm: diameter_nm,
+            y_nm: diameter_nm,
+        };
+        let front = || layer_to_model(BoardLayer::BlFCu as i32);
+        (self.diameter_nm.is_some() || self.drill_nm.is_some()).then(|| PcbPadStack {
+            layers: Vec::new(),
+            copper_layers: self
+                .diameter_nm
+                .map(|diameter_nm| PcbPadStackCopperLayer {
+                    layer: front(),
+                    shape: PcbPadStackShape::Circle,
+                    size_nm: Some(round(diameter_nm)),
+                    offset_nm: None,
+                })
+                .into_iter()
+                .collect(),
+            drill: self.drill_nm.map(|drill_nm| PcbDrill {
+                start_layer: front(),
+                end_layer: layer_to_model(BoardLayer::BlBCu as i32),
+                diameter_nm: Some(round(drill_nm)),
+            }),
+            angle_deg: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(name: &str, clearance_nm: Option<i64>) -> NetClassInfo {
+        NetClassInfo {
+            name: name.to_string(),
+            priority: Some(1),
+            class_type: NetClassType::Explicit,
+            constituents: vec![format!("{name}_*")],
+            board: Some(NetClassBoardSettings {
+                clearance_nm,
+                ..NetClassBoardSettings::default()
+            }),
+            schematic: None,
+        }
+    }
+
+    #[test]
+    fn plan_adds_updates_and_removes_explicit_classes() {
+        let mut implicit = class("USB,Power", Some(1));
+        implicit.class_type = NetClassType::Implicit;
+        let current = vec![
+            class("Default", Some(200_000)),
+            class("Legacy", Some(200_000)),
+            class("Power", Some(200_000)),
+            class("USB", Some(150_000)),
+            implicit,
+        ];
+        let mut power = class("Power", Some(300_000));
+        power.priority = None;
+        let desired = vec![power, class("USB", None), class("HighSpeed", Some(100_000))];
+
+        let plan = plan(desired, &current);
+        let names: Vec<&str> = plan.changes.iter().map(NetClassChange::name).collect();
+        assert_eq!(names, vec!["HighSpeed", "Legacy", "Power"]);
+        match &plan.changes[2] {
+            NetClassChange::Update { class, fields } => {
+                assert_eq!(class.priority, Some(1));
+                assert_eq!(
+                    fields,
+                    &vec![FieldChange {
+                        field: "clearance_nm",
+                        before: "200000".to_string(),
+                        after: "300000".to_string(),
+                    }]
+                );
+            }
+            other => panic!("expected an update, got {other:?}"),
+        }
+        assert!(matches!(plan.changes[1], NetClassChange::Remove(_)));
+
+        assert_eq!(plan.merge_mode(), MapMergeMode::Replace);
+        let pushed: Vec<String> = plan
+            .net_classes()
+            .into_iter()
+            .map(|class| class.name)
+            .collect();
+        assert_eq!(pushed, vec!["Default", "HighSpeed", "Power", "USB"]);
+        assert!(plan.to_string().contains("clearance_nm: 200000 -> 300000"));
+    }
+
+    #[test]
+    fn plan_without_removals_merges_changed_classes_only() {
+        let current = vec![class("Default", Some(200_000)), class("USB", Some(150_000))];
+        let plan = super::plan(vec![class("USB", Some(120_000))], &current);
+        assert_eq!(plan.merge_mode(), MapMergeMode::Merge);
+        assert_eq!(plan.net_classes().len(), 1);
+
+        let plan = super::plan(vec![class("USB", Some(150_000))], &current);
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "net classes up to date\n");
+    }
+
+    #[test]
+    fn parse_toml_reads_net_class_tables() {
+        let classes = parse_toml(
+            r#"
+# Team net classes
+[[netclass]]
+name = "USB"   # differential pairs
+priority = 1
+constituents = ["USB_D*", "/usb/#*"]
+diff_pair_gap_nm = 150_000
+via_diameter_nm = 600_000
+via_drill_nm = 300_000
+wire_width_nm = 152_400
+
+[[netclass]]
+name = "Power"
+clearance_nm = 300000
+"#,
+        )
+        .expect("net class file should parse");
+
+        assert_eq!(classes.len(), 2);
+        let usb = &classes[0];
+        assert_eq!(usb.constituents, vec!["USB_D*", "/usb/#*"]);
+        let board = usb.board.as_ref().expect("board settings should be set");
+        assert_eq!(board.diff_pair_gap_nm, Some(150_000));
+        let via = board.via_stack.as_ref().expect("via stack should be set");
+        assert_eq!(via_size(Some(via)), (Some(600_000), Some(300_000)));
+        assert_eq!(via.copper_layers[0].layer.name, "BL_F_Cu");
+        assert_eq!(via.copper_layers[0].shape, PcbPadStackShape::Circle);
+        assert_eq!(board.microvia_stack, None);
+        assert_eq!(
+            usb.schematic.as_ref().and_then(|s| s.wire_width_nm),
+            Some(152_400)
+        );
+        assert_eq!(classes[1].priority, None);
+        assert!(classes[1].schematic.is_none());
+
+        let err = parse_toml("[[netclass]]\nname = \"A\"\nwidth = 3\n")
+            .expect_err("unknown keys must fail");
+        assert!(err.to_string().contains("line 3"));
+        assert!(parse_toml("[[netclass]]\npriority = 1\n").is_err());
+        assert!(parse_toml("[[netclass]]\nname = \"A\"\n[[netclass]]\nname = \"A\"\n").is_err());
+        assert!(parse_toml("[[netclass]]\nname = \"A\"\nconstituents = []\n").is_err());
+    }
+
+    #[test]
+    fn plan_keeps_current_constituents_when_omitted() {
+        let current = vec![class("USB", Some(150_000))];
+        let desired =
+            parse_toml("[[netclass]]\nname = \"USB\"\nclearance_nm = 120_000\n").expect("parse");
+        assert!(desired[0].constituents.is_empty());
+
+        let plan = plan(desired, &current);
+        match &plan.changes[..] {
+            [NetClassChange::Update { class, fields }] => {
+                assert_eq!(class.constituents, vec!["USB_*"]);
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].field, "clearance_nm");
+            }
+            other => panic!("expected one update, got {other:?}"),
+        }
+        assert!(!plan.to_string().contains("constituents"));
+    }
+
+    #[test]
+    fn plan_keeps_current_via_copper_when_only_the_drill_changes() {
+        let mut current = class("USB", Some(150_000));
+        let current_via = ViaSize {
+            diameter_nm: Some(600_000),
+            drill_nm: Some(300_000),
+        }
+        .pad_stack()
+        .map(|mut stack| {
+            let mut inner = stack.copper_layers[0].clone();
+            inner.layer = layer_to_model(BoardLayer::BlIn1Cu as i32);
+            inner.size_nm = Some(Vector2Nm {
+                x_nm: 500_000,
+                y_nm: 500_000,
+            });
+            stack.copper_layers.push(inner);
+            stack
+        });
+        current.board.as_mut().expect("board").via_stack = current_via.clone();
+        let desired =
+            parse_toml("[[netclass]]\nname = \"USB\"\nvia_drill_nm = 250_000\n").expect("parse");
+
+        let plan = plan(desired, &[current]);
+        let [NetClassChange::Update { class, fields }] = &plan.changes[..] else {
+            panic!("expected one update, got {:?}", plan.changes);
+        };
+        let via = class
+            .board
+            .as_ref()
+            .and_then(|board| board.via_stack.as_ref())
+            .expect("via stack");
+        assert_eq!(
+            via.copper_layers,
+            current_via.expect("current via").copper_layers
+        );
+        assert_eq!(via_size(Some(via)), (Some(600_000), Some(250_000)));
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field, "via_drill_nm");
+    }
+}
@@ -0,0 +1,552 @@
+//! IPC-D-356A netlist for bare-board electrical test.
+//!
+//! [`Ipc356Netlist::from_items`] turns pads (footprint children) and vias into fixed-column
+//! test-point records: `317` for through holes and `327` for surface features. Output uses
+//! `UNITS CUST 0` (inches, 0.0001" resolution) with the Y axis pointing up, matching KiCad's own
+//! exporter. Net names longer than 14 characters are written through `NNAME` aliases.
+//!
+//! Pad net names come from [`KiCadClient::get_pad_netlist`] when a pad netlist is supplied, and
+//! the Edge.Cuts outline (see [`BoardOutline`]) is written as `389 BOARD_EDGE` records.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::geometry;
+use crate::model::board::{
+    PadNetEntry, PcbFootprint, PcbItem, PcbPad, PcbPadType, PcbVia, PcbViaType, Vector2Nm,
+};
+use crate::outline::BoardOutline;
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+use crate::text_out::write_to_string;
+
+const NM_PER_TENTH_MIL: f64 = 2_540.0;
+const MAX_NET_NAME_LEN: usize = 14;
+const UNCONNECTED_NET: &str = "N/C";
+const OUTLINE_POINTS_PER_RECORD: usize = 2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Side(s) a test probe can reach a feature from.
+pub enum Ipc356Access {
+    /// Both sides (through holes).
+    Both,
+    /// Primary (top) side.
+    Top,
+    /// Secondary (bottom) side.
+    Bottom,
+}
+
+impl Ipc356Access {
+    fn code(self) -> u8 {
+        match self {
+            Self::Both => 0,
+            Self::Top => 1,
+            Self::Bottom => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// One test-point record.
+pub struct Ipc356TestPoint {
+    /// Net name; unconnected features use `N/C`.
+    pub net: String,
+    /// Reference designator, or `VIA` for vias.
+    pub reference: String,
+    /// Pad number; `None` for vias.
+    pub pin: Option<String>,
+    /// Whether this is a mid-net point (vias) rather than a component terminal.
+    pub mid_point: bool,
+    /// Finished hole diameter in nm for drilled features.
+    pub drill_nm: Option<i64>,
+    /// Whether the hole is plated.
+    pub plated: bool,
+    /// Probe access side.
+    pub access: Ipc356Access,
+    /// Feature centre in board coordinates (Y down).
+    pub position_nm: Vector2Nm,
+    /// Feature width and height in nm.
+    pub size_nm: Vector2Nm,
+    /// Feature rotation in degrees.
+    pub rotation_deg: f64,
+    /// Solder mask opening side; `None` means fully covered (tented).
+    pub soldermask: Option<Ipc356Access>,
+}
+
+impl Ipc356TestPoint {
+    fn is_through_hole(&self) -> bool {
+        self.drill_nm.is_some()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Test-point records, board outline, and the job name written to the header.
+pub struct Ipc356Netlist {
+    /// Job name for the `P  JOB` record.
+    pub job_name: String,
+    /// Records in output order: pads by footprint, then vias.
+    pub test_points: Vec<Ipc356TestPoint>,
+    /// Board outline for the `389` records; `None` when Edge.Cuts is missing or not closed.
+    pub outline: Option<BoardOutline>,
+}
+
+impl Ipc356Netlist {
+    /// Item type codes [`Ipc356Netlist::from_items`] needs.
+    pub fn capture_type_codes() -> Vec<i32> {
+        vec![
+            KiCadObjectType::KotPcbFootprint as i32,
+            KiCadObjectType::KotPcbVia as i32,
+            KiCadObjectType::KotPcbShape as i32,
+        ]
+    }
+
+    /// Reads footprints, vias, Edge.Cuts shapes, and the pad netlist from the open board.
+    pub async fn capture(
+        client: &KiCadClient,
+        job_name: impl Into<String>,
+    ) -> Result<Self, KiCadError> {
+        let items = client
+            .get_items_by_type_codes(Self::capture_type_codes())
+            .await?;
+        let pad_netlist = client.get_pad_netlist().await?;
+        Ok(Self::from_items(job_name, &items, &pad_netlist))
+    }
+
+    /// Builds records for every pad and every via reachable from an outer layer.
+    ///
+    /// Pads found in `pad_netlist` (matched by pad id) take their net name from it; other pads
+    /// and vias use their own net. Buried vias have no probe access and are skipped. The outline
+    /// is rebuilt from any Edge.Cuts shapes in `items` and left out when it is not closed.
+    pub fn from_items(
+        job_name: impl Into<String>,
+        items: &[PcbItem],
+        pad_netlist: &[PadNetEntry],
+    ) -> Self {
+        let pad_nets: HashMap<&str, Option<&str>> = pad_netlist
+            .iter()
+            .filter_map(|entry| Some((entry.pad_id.as_deref()?, entry.net_name.as_deref())))
+            .collect();
+        let mut test_points = Vec::new();
+        for item in items {
+            if let PcbItem::Footprint(footprint) = item {
+                test_points.extend(footprint.items.iter().filter_map(|child| match child {
+                    PcbItem::Pad(pad) => pad_test_point(footprint, pad, &pad_nets),
+                    _ => None,
+                }));
+            }
+        }
+        test_points.extend(items.iter().filter_map(|item| match item {
+            PcbItem::Via(via) => via_test_point(via),
+            _ => None,
+        }));
+
+        Self {
+            job_name: job_name.into(),
+            test_points,
+            outline: BoardOutline::from_items(items).ok(),
+        }
+    }
+
+    /// Writes the netlist, terminated by the `999` end record.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut aliases = BTreeMap::new();
+        for point in &self.test_points {
+            if point.net.len() > MAX_NET_NAME_LEN && !aliases.contains_key(&point.net) {
+                let alias = format!("NNAME{}", aliases.len() + 1);
+                aliases.insert(point.net.clone(), alias);
+            }
+        }
+
+        writeln!(out, "C  IPC-D-356A netlist generated by kicad-ipc-rs")?;
+        writeln!(out, "P  JOB   {}", self.job_name)?;
+        writeln!(out, "P  UNITS CUST 0")?;
+        writeln!(out, "P  DIM   N")?;
+        let mut alias_rows: Vec<_> = aliases.iter().collect();
+        alias_rows.sort_by_key(|(_, alias)| alias[5..].parse::<usize>().unwrap_or_default());
+        for (net, alias) in alias_rows {
+            writeln!(out, "P  {alias:<14}{net}")?;
+        }
+
+        for point in &self.test_points {
+            let net = aliases.get(&point.net).unwrap_or(&point.net);
+            let pin = point.pin.as_deref().unwrap_or_default();
+            write!(
+                out,
+                "{}{:<14.14}   {:<6.6}{}{:<4.4}{}",
+                if point.is_through_hole() {
+                    "317"
+                } else {
+                    "327"
+                },
+                net,
+                point.reference,
+                if pin.is_empty() { ' ' } else { '-' },
+                pin,
+                if point.mid_point { 'M' } else { ' ' },
+            )?;
+            match point.drill_nm {
+                Some(drill_nm) => write!(
+                    out,
+                    "D{:04}{}",
+                    to_tenth_mil(drill_nm).clamp(0, 9_999),
+                    if point.plated { 'P' } else { 'U' }
+                )?,
+                None => write!(out, "      ")?,
+            }
+            writeln!(
+                out,
+                "A{:02}X{:+07}Y{:+07}X{:04}Y{:04}R{:03} S{}",
+                point.access.code(),
+                to_tenth_mil(point.position_nm.x_nm),
+                -to_tenth_mil(point.position_nm.y_nm),
+                to_tenth_mil(point.size_nm.x_nm).clamp(0, 9_999),
+                to_tenth_mil(point.size_nm.y_nm).clamp(0, 9_999),
+                point.rotation_deg.rem_euclid(360.0).round() as i64 % 360,
+                point.soldermask.map_or(3, Ipc356Access::code),
+            )?;
+        }
+        if let Some(outline) = &self.outline {
+            for polygon in &outline.polygons {
+                for contour in polygon.outline.iter().chain(&polygon.holes) {
+                    write_outline_record(out, &geometry::polyline_points(contour))?;
+                }
+            }
+        }
+        writeln!(out, "999")
+    }
+
+    /// Returns the netlist as text.
+    pub fn to_text(&self) -> String {
+        write_to_string(|out| self.write(out))
+    }
+}
+
+fn to_tenth_mil(value_nm: i64) -> i64 {
+    (value_nm as f64 / NM_PER_TENTH_MIL).round() as i64
+}
+
+fn format_point(point: Vector2Nm) -> String {
+    format!(
+        "X{:+07}Y{:+07}",
+        to_tenth_mil(point.x_nm),
+        -to_tenth_mil(point.y_nm)
+    )
+}
+
+/// Writes one closed contour as a `389` record with `089` continuation lines.
+fn write_outline_record<W: Write>(out: &mut W, points: &[Vector2Nm]) -> io::Result<()> {
+    let Some(first) = points.first() else {
+        return Ok(());
+    };
+    let coordinates: Vec<String> = points
+        .iter()
+        .chain([first])
+        .copied()
+        .map(format_point)
+        .collect();
+    for (index, chunk) in coordinates.chunks(OUTLINE_POINTS_PER_RECORD).enumerate() {
+        let code = if index == 0 { "389" } else { "089" };
+        let name = if index == 0 { "BOARD_EDGE" } else { "" };
+        writeln!(out, "{code}{name:<14}{:15}{}", "", chunk.join(" "))?;
+    }
+    Ok(())
+}
+
+fn net_label(name: Option<&str>) -> String {
+    name.filter(|name| !name.is_empty())
+        .unwrap_or(UNCONNECTED_NET)
+        .to_string()
+}
+
+fn pad_test_point(
+    footprint: &PcbFootprint,
+    pad: &PcbPad,
+    pad_nets: &HashMap<&str, Option<&str>>,
+) -> Option<Ipc356TestPoint> {
+    let position_nm = pad.position_nm?;
+    let stack = pad.pad_stack.as_ref();
+    let drilled = matches!(pad.pad_type, PcbPadType::Pth | PcbPadType::Npth);
+
+    let access = if drilled {
+        Ipc356Access::Both
+    } else {
+        let layer_ids: Vec<i32> = stack
+            .map(|stack| stack.layers.iter().map(|layer| layer.id).collect())
+            .unwrap_or_default();
+        match (
+            layer_ids.contains(&(BoardLayer::BlFCu as i32)),
+            layer_ids.contains(&(BoardLayer::BlBCu as i32)),
+        ) {
+            (true, true) => Ipc356Access::Both,
+            (true, false) => Ipc356Access::Top,
+            (false, true) => Ipc356Access::Bottom,
+            (false, false) if footprint.layer.id == BoardLayer::BlBCu as i32 => {
+                Ipc356Access::Bottom
+            }
+            (false, false) => Ipc356Access::Top,
+        }
+    };
+
+    let probe_layer = match access {
+        Ipc356Access::Bottom => BoardLayer::BlBCu as i32,
+        _ => BoardLayer::BlFCu as i32,
+    };
+    let size_nm = stack
+        .and_then(|stack| {
+            stack
+                .copper_layers
+                .iter()
+                .find(|layer| layer.layer.id == probe_layer)
+                .or_else(|| stack.copper_layers.first())
+                .and_then(|layer| layer.size_nm)
+        })
+        .unwrap_or(Vector2Nm { x_nm: 0, y_nm: 0 });
+    let drill_nm = if drilled {
+        stack
+            .and_then(|stack| stack.drill.as_ref())
+            .and_then(|drill| drill.diameter_nm)
+            .map(|diameter| diameter.x_nm.min(diameter.y_nm))
+    } else {
+        None
+    };
+
+    Some(Ipc356TestPoint {
+        net: match pad.id.as_deref().and_then(|id| pad_nets.get(id)) {
+            Some(name) => net_label(*name),
+            None => net_label(pad.net.as_ref().map(|net| net.name.as_str())),
+        },
+        reference: footprint.reference.clone().unwrap_or_default(),
+        pin: Some(pad.number.clone()),
+        mid_point: false,
+        drill_nm,
+        plated: pad.pad_type == PcbPadType::Pth,
+        access,
+        position_nm,
+        size_nm,
+        rotation_deg: stack.and_then(|stack| stack.angle_deg).unwrap_or_default(),
+        soldermask: Some(access),
+    })
+}
+
+fn via_test_point(via: &PcbVia) -> Option<Ipc356TestPoint> {
+    let position_nm = via.position_nm?;
+    let layers = via.layers.as_ref();
+    let touches = |layer_id: i32| {
+        layers.is_some_and(|layers| {
+            [&layers.drill_start_layer, &layers.drill_end_layer]
+                .into_iter()
+                .flatten()
+                .chain(&layers.padstack_layers)
+                .any(|layer| layer.id == layer_id)
+        })
+    };
+
+    let access = match via.via_type {
+        PcbViaType::Through => Ipc356Access::Both,
+        _ => match (
+            touches(BoardLayer::BlFCu as i32),
+            touches(BoardLayer::BlBCu as i32),
+        ) {
+            (true, true) => Ipc356Access::Both,
+            (true, false) => Ipc356Access::Top,
+            (false, true) => Ipc356Access::Bottom,
+            (false, false) => return None,
+        },
+    };
+    let pad_nm = via.pad_diameter_nm.unwrap_or_default();
+
+    Some(Ipc356TestPoint {
+        net: net_label(via.net.as_ref().map(|net| net.name.as_str())),
+        reference: "VIA".to_string(),
+        pin: None,
+        mid_point: true,
+        drill_nm: Some(via.drill_diameter_nm.unwrap_or_default()),
+        plated: true,
+        access,
+        position_nm,
+        size_nm: Vector2Nm {
+            x_nm: pad_nm,
+            y_nm: pad_nm,
+        },
+        rotation_deg: 0.0,
+        soldermask: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ipc356Access, Ipc356Netlist};
+    use crate::model::board::{
+        BoardLayerInfo, BoardNet, PadNetEntry, PcbBoardGraphicShape, PcbDrill, PcbFootprint,
+        PcbItem, PcbPad, PcbPadStack, PcbPadStackCopperLayer, PcbPadStackShape, PcbPadType, PcbVia,
+        PcbViaLayers, PcbViaType, Vector2Nm,
+    };
+    use crate::model::common::TextShapeGeometry;
+
+    fn layer(id: i32) -> BoardLayerInfo {
+        BoardLayerInfo {
+            id,
+            name: String::new(),
+        }
+    }
+
+    fn point(x_nm: i64, y_nm: i64) -> Vector2Nm {
+        Vector2Nm { x_nm, y_nm }
+    }
+
+    fn net(name: &str) -> Option<BoardNet> {
+        Some(BoardNet {
+            code: 1,
+            name: name.to_string(),
+        })
+    }
+
+    fn pad(number: &str, pad_type: PcbPadType, layer_ids: &[i32], net_name: &str) -> PcbItem {
+        PcbItem::Pad(PcbPad {
+            id: Some(format!("pad-{number}")),
+            number: number.to_string(),
+            pad_type,
+            position_nm: Some(point(2_540_000, 5_080_000)),
+            net: net(net_name),
+            pad_stack: Some(PcbPadStack {
+                layers: layer_ids.iter().copied().map(layer).collect(),
+                copper_layers: layer_ids
+                    .iter()
+                    .map(|id| PcbPadStackCopperLayer {
+                        layer: layer(*id),
+                        shape: PcbPadStackShape::Rectangle,
+                        size_nm: Some(point(1_524_000, 762_000)),
+                        offset_nm: None,
+                    })
+                    .collect(),
+                drill: (pad_type == PcbPadType::Pth).then_some(PcbDrill {
+                    start_layer: layer(3),
+                    end_layer: layer(34),
+                    diameter_nm: Some(point(812_800, 812_800)),
+                }),
+                angle_deg: Some(90.0),
+            }),
+        })
+    }
+
+    fn board_items() -> Vec<PcbItem> {
+        vec![
+            PcbItem::Footprint(PcbFootprint {
+                id: Some("fp".to_string()),
+                reference: Some("J1".to_string()),
+                position_nm: None,
+                orientation_deg: None,
+                layer: layer(3),
+                pad_count: 2,
+                value: None,
+                library_id: None,
+                datasheet: None,
+                attributes: None,
+                items: vec![
+                    pad("1", PcbPadType::Smd, &[3], "GND"),
+                    pad("2", PcbPadType::Pth, &[3, 34], "/very/long/net/name"),
+                ],
+            }),
+            PcbItem::Via(PcbVia {
+                id: Some("via".to_string()),
+                position_nm: Some(point(0, -2_540_000)),
+                via_type: PcbViaType::Through,
+                layers: None,
+                net: net("GND"),
+                drill_diameter_nm: Some(304_800),
+                pad_diameter_nm: Some(609_600),
+            }),
+            PcbItem::Via(PcbVia {
+                id: Some("buried".to_string()),
+                position_nm: Some(point(0, 0)),
+                via_type: PcbViaType::Buried,
+                layers: Some(PcbViaLayers {
+                    padstack_layers: vec![layer(4), layer(5)],
+                    drill_start_layer: Some(layer(4)),
+                    drill_end_layer: Some(layer(5)),
+                }),
+                net: net("GND"),
+                drill_diameter_nm: Some(100_000),
+                pad_diameter_nm: Some(250_000),
+            }),
+        ]
+    }
+
+    #[test]
+    fn from_items_assigns_access_side_and_skips_buried_vias() {
+        let netlist = Ipc356Netlist::from_items("demo", &board_items(), &[]);
+
+        assert_eq!(netlist.test_points.len(), 3);
+        assert_eq!(netlist.test_points[0].access, Ipc356Access::Top);
+        assert_eq!(netlist.test_points[0].drill_nm, None);
+        assert_eq!(netlist.test_points[1].access, Ipc356Access::Both);
+        assert_eq!(netlist.test_points[1].drill_nm, Some(812_800));
+        assert_eq!(netlist.test_points[2].reference, "VIA");
+        assert!(netlist.test_points[2].mid_point);
+    }
+
+    #[test]
+    fn write_emits_fixed_column_records_with_net_aliases() {
+        let text = Ipc356Netlist::from_items("demo", &board_items(), &[]).to_text();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "C  IPC-D-356A netlist generated by kicad-ipc-rs",
+                "P  JOB   demo",
+                "P  UNITS CUST 0",
+                "P  DIM   N",
+                "P  NNAME1        /very/long/net/name",
+                "327GND              J1    -1          A01X+001000Y-002000X0600Y0300R090 S1",
+                "317NNAME1           J1    -2    D0320PA00X+001000Y-002000X0600Y0300R090 S0",
+                "317GND              VIA        MD0120PA00X+000000Y+001000X0240Y0240R000 S3",
+                "999",
+            ]
+        );
+        assert!(lines[5..8].iter().all(|line| line.len() == 74));
+    }
+
+    #[test]
+    fn from_items_uses_pad_netlist_names_and_writes_outline_records() {
+        let mut items = board_items();
+        items.push(PcbItem::BoardGraphicShape(PcbBoardGraphicShape {
+            id: Some("edge".to_string()),
+            layer: layer(47),
+            net: None,
+            geometry_kind: None,
+            geometry: TextShapeGeometry::Rectangle {
+                top_left_nm: Some(point(0, 0)),
+                bottom_right_nm: Some(point(25_400_000, 12_700_000)),
+                corner_radius_nm: None,
+            },
+            stroke_width_nm: Some(100_000),
+            filled: false,
+        }));
+        let pad_netlist = vec![PadNetEntry {
+            footprint_reference: Some("J1".to_string()),
+            footprint_id: Some("fp".to_string()),
+            pad_id: Some("pad-1".to_string()),
+            pad_number: "1".to_string(),
+            net_code: Some(2),
+            net_name: Some("VCC".to_string()),
+        }];
+        let netlist = Ipc356Netlist::from_items("demo", &items, &pad_netlist);
+
+        assert_eq!(netlist.test_points[0].net, "VCC");
+        assert_eq!(netlist.test_points[1].net, "/very/long/net/name");
+        let text = netlist.to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[lines.len() - 4..],
+            [
+                "389BOARD_EDGE                   X+000000Y+000000 X+010000Y+000000",
+                "089                             X+010000Y-005000 X+000000Y-005000",
+                "089                             X+000000Y+000000",
+                "999",
+            ]
+        );
+    }
+}
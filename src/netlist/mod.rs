@@ -0,0 +1,4 @@
+//! Netlist exporters built from decoded board items.
+
+/// IPC-D-356A bare-board test netlist writer.
+pub mod ipc356;
@@ -0,0 +1,756 @@
+//! Board outline extraction from Edge.Cuts graphics.
+//!
+//! KiCad has no IPC command that returns the board shape, so [`BoardOutline`] rebuilds it from the
+//! Edge.Cuts graphic shapes (board-level and footprint-owned): open segments, arcs, and beziers
+//! are chained end to end within [`CHAIN_TOLERANCE_NM`], closed shapes (rectangles, circles,
+//! polygons) are taken as-is, and the resulting contours are nested into outlines and cutouts.
+//!
+//! Arcs are kept as arc nodes in the returned polygons; area and point tests flatten them.
+
+use std::fmt;
+
+use crate::error::KiCadError;
+use crate::geometry;
+use crate::model::board::{
+    ArcStartMidEndNm, PcbBoardGraphicShape, PcbItem, PolyLineNm, PolyLineNodeGeometryNm,
+    PolygonWithHolesNm, Vector2Nm,
+};
+use crate::model::common::TextShapeGeometry;
+use crate::proto::kiapi::board::types::BoardLayer;
+
+/// Largest endpoint mismatch that still joins two Edge.Cuts shapes (KiCad uses the same 20 µm
+/// default when building board polygons).
+pub const CHAIN_TOLERANCE_NM: i64 = 20_000;
+
+/// Segments used to flatten a bezier curve.
+const BEZIER_SEGMENTS: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Problem that prevents the Edge.Cuts shapes from forming a closed outline.
+pub enum OutlineIssue {
+    /// There are no usable shapes on Edge.Cuts.
+    NoShapes,
+    /// A chain of shapes ends at `at` without meeting another shape.
+    Gap {
+        /// Dangling endpoint.
+        at: Vector2Nm,
+        /// Distance to the nearest other free endpoint, when there is one.
+        nearest_nm: Option<i64>,
+    },
+    /// Two outline edges cross at `at`.
+    SelfIntersection {
+        /// Approximate crossing point.
+        at: Vector2Nm,
+    },
+}
+
+impl fmt::Display for OutlineIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoShapes => write!(f, "no shapes on Edge.Cuts"),
+            Self::Gap {
+                at,
+                nearest_nm: Some(nearest_nm),
+            } => write!(
+                f,
+                "gap at ({}, {}) nm; nearest free endpoint is {nearest_nm} nm away",
+                at.x_nm, at.y_nm
+            ),
+            Self::Gap {
+                at,
+                nearest_nm: None,
+            } => write!(f, "gap at ({}, {}) nm", at.x_nm, at.y_nm),
+            Self::SelfIntersection { at } => {
+                write!(f, "self-intersection at ({}, {}) nm", at.x_nm, at.y_nm)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Closed board shape: one polygon per separate board region, each with its cutouts.
+pub struct BoardOutline {
+    /// Outer contours with their holes, in the order they were found.
+    pub polygons: Vec<PolygonWithHolesNm>,
+}
+
+impl BoardOutline {
+    /// Builds the outline from the Edge.Cuts shapes in `items`, including footprint graphics.
+    pub fn from_items(items: &[PcbItem]) -> Result<Self, KiCadError> {
+        let edge_cuts = BoardLayer::BlEdgeCuts as i32;
+        let children = items.iter().flat_map(|item| match item {
+            PcbItem::Footprint(footprint) => footprint.items.as_slice(),
+            _ => &[],
+        });
+        let shapes = items.iter().chain(children).filter_map(|item| match item {
+            PcbItem::BoardGraphicShape(shape) if shape.layer.id == edge_cuts => Some(shape),
+            _ => None,
+        });
+        Self::from_shapes(shapes)
+    }
+
+    /// Builds the outline from `shapes`, regardless of their layer.
+    ///
+    /// Fails with [`KiCadError::BoardOutline`] listing every gap and crossing found.
+    pub fn from_shapes<'a>(
+        shapes: impl IntoIterator<Item = &'a PcbBoardGraphicShape>,
+    ) -> Result<Self, KiCadError> {
+        let mut closed = Vec::new();
+        let mut open = Vec::new();
+        for shape in shapes {
+            if let TextShapeGeometry::Polygon { polygons } = &shape.geometry {
+                // Every outline and hole of a filled polygon is its own closed contour.
+                closed.extend(
+                    polygons
+                        .iter()
+                        .flat_map(|polygon| polygon.outline.iter().chain(&polygon.holes))
+                        .filter(|line| !line.nodes.is_empty())
+                        .map(|line| PolyLineNm {
+                            closed: true,
+                            ..line.clone()
+                        }),
+                );
+                continue;
+            }
+            match contour_of(&shape.geometry) {
+                Some(Piece::Closed(line)) => closed.push(line),
+                Some(Piece::Open(nodes)) => open.push(nodes),
+                None => {}
+            }
+        }
+        if closed.is_empty() && open.is_empty() {
+            return Err(KiCadError::BoardOutline {
+                issues: vec![OutlineIssue::NoShapes],
+            });
+        }
+
+        let (chained, mut issues) = chain(open);
+        closed.extend(chained);
+        let flattened: Vec<Vec<Vector2Nm>> = closed.iter().map(geometry::polyline_points).collect();
+        issues.extend(
+            crossings(&flattened)
+                .into_iter()
+                .map(|at| OutlineIssue::SelfIntersection { at }),
+        );
+        if !issues.is_empty() {
+            return Err(KiCadError::BoardOutline { issues });
+        }
+        Ok(Self {
+            polygons: nest(closed, &flattened),
+        })
+    }
+
+    /// Board area in nm² (outlines minus cutouts).
+    pub fn area_nm2(&self) -> f64 {
+        self.polygons
+            .iter()
+            .map(|polygon| {
+                let outer = polygon.outline.as_ref().map_or(0.0, line_area);
+                outer - polygon.holes.iter().map(line_area).sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Top-left and bottom-right corners of the outline's bounding box.
+    pub fn bounding_box(&self) -> Option<(Vector2Nm, Vector2Nm)> {
+        let mut points = self
+            .polygons
+            .iter()
+            .filter_map(|polygon| polygon.outline.as_ref())
+            .flat_map(geometry::polyline_points);
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                Vector2Nm {
+                    x_nm: min.x_nm.min(point.x_nm),
+                    y_nm: min.y_nm.min(point.y_nm),
+                },
+                Vector2Nm {
+                    x_nm: max.x_nm.max(point.x_nm),
+                    y_nm: max.y_nm.max(point.y_nm),
+                },
+            )
+        }))
+    }
+
+    /// Width and height of the bounding box.
+    pub fn dimensions_nm(&self) -> Option<Vector2Nm> {
+        self.bounding_box().map(|(min, max)| Vector2Nm {
+            x_nm: max.x_nm - min.x_nm,
+            y_nm: max.y_nm - min.y_nm,
+        })
+    }
+
+    /// Whether `point` lies on the board; edges (including cutout edges) count as on board.
+    pub fn contains(&self, point: Vector2Nm) -> bool {
+        self.polygons.iter().any(|polygon| {
+            let Some(outline) = &polygon.outline else {
+                return false;
+            };
+            geometry::point_in_polygon(point, &geometry::polyline_points(outline))
+                && !polygon.holes.iter().any(|hole| {
+                    let points = geometry::polyline_points(hole);
+                    geometry::point_in_polygon(point, &points) && !on_boundary(point, &points)
+                })
+        })
+    }
+}
+
+enum Piece {
+    Closed(PolyLineNm),
+    Open(Vec<PolyLineNodeGeometryNm>),
+}
+
+fn contour_of(geometry: &TextShapeGeometry) -> Option<Piece> {
+    let point = PolyLineNodeGeometryNm::Point;
+    match geometry {
+        TextShapeGeometry::Segment {
+            start_nm: Some(start),
+            end_nm: Some(end),
+        } if start != end => Some(Piece::Open(vec![point(*start), point(*end)])),
+        TextShapeGeometry::Arc {
+            start_nm: Some(start),
+            mid_nm: Some(mid),
+            end_nm: Some(end),
+        } => Some(Piece::Open(vec![PolyLineNodeGeometryNm::Arc(
+            ArcStartMidEndNm {
+                start: *start,
+                mid: *mid,
+                end: *end,
+            },
+        )])),
+        TextShapeGeometry::Bezier {
+            start_nm: Some(start),
+            control1_nm: Some(control1),
+            control2_nm: Some(control2),
+            end_nm: Some(end),
+        } => Some(Piece::Open(
+            bezier_points(*start, *control1, *control2, *end)
+                .into_iter()
+                .map(point)
+                .collect(),
+        )),
+        TextShapeGeometry::Rectangle {
+            top_left_nm: Some(top_left),
+            bottom_right_nm: Some(bottom_right),
+            corner_radius_nm,
+        } => Some(Piece::Closed(rectangle(
+            *top_left,
+            *bottom_right,
+            corner_radius_nm.unwrap_or(0),
+        ))),
+        TextShapeGeometry::Circle {
+            center_nm: Some(center),
+            radius_point_nm: Some(radius_point),
+        } => {
+            let radius = geometry::distance(*center, *radius_point).round() as i64;
+            let at = |dx: i64, dy: i64| Vector2Nm {
+                x_nm: center.x_nm + dx,
+                y_nm: center.y_nm + dy,
+            };
+            Some(Piece::Closed(PolyLineNm {
+                nodes: vec![
+                    PolyLineNodeGeometryNm::Arc(ArcStartMidEndNm {
+                        start: at(radius, 0),
+                        mid: at(0, radius),
+                        end: at(-radius, 0),
+                    }),
+                    PolyLineNodeGeometryNm::Arc(ArcStartMidEndNm {
+                        start: at(-radius, 0),
+                        mid: at(0, -radius),
+                        end: at(radius, 0),
+                    }),
+                ],
+                closed: true,
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn rectangle(top_left: Vector2Nm, bottom_right: Vector2Nm, corner_radius_nm: i64) -> PolyLineNm {
+    let (left, right) = (
+        top_left.x_nm.min(bottom_right.x_nm),
+        top_left.x_nm.max(bottom_right.x_nm),
+    );
+    let (top, bottom) = (
+        top_left.y_nm.min(bottom_right.y_nm),
+        top_left.y_nm.max(bottom_right.y_nm),
+    );
+    let at = |x_nm, y_nm| Vector2Nm { x_nm, y_nm };
+    let radius = corner_radius_nm
+        .max(0)
+        .min((right - left) / 2)
+        .min((bottom - top) / 2);
+    if radius == 0 {
+        return PolyLineNm {
+            nodes: [
+                at(left, top),
+                at(right, top),
+                at(right, bottom),
+                at(left, bottom),
+            ]
+            .into_iter()
+            .map(PolyLineNodeGeometryNm::Point)
+            .collect(),
+            closed: true,
+        };
+    }
+
+    // Offset of a 45° point on a corner arc from the arc's center.
+    let diagonal = (radius as f64 * std::f64::consts::FRAC_1_SQRT_2).round() as i64;
+    let corner = |center: Vector2Nm, start: Vector2Nm, end: Vector2Nm, dx: i64, dy: i64| {
+        PolyLineNodeGeometryNm::Arc(ArcStartMidEndNm {
+            start,
+            mid: at(center.x_nm + dx * diagonal, center.y_nm + dy * diagonal),
+            end,
+        })
+    };
+    PolyLineNm {
+        nodes: vec![
+            corner(
+                at(right - radius, top + radius),
+                at(right - radius, top),
+                at(right, top + radius),
+                1,
+                -1,
+            ),
+            corner(
+                at(right - radius, bottom - radius),
+                at(right, bottom - radius),
+                at(right - radius, bottom),
+                1,
+                1,
+            ),
+            corner(
+                at(left + radius, bottom - radius),
+                at(left + radius, bottom),
+                at(left, bottom - radius),
+                -1,
+                1,
+            ),
+            corner(
+                at(left + radius, top + radius),
+                at(left, top + radius),
+                at(left + radius, top),
+                -1,
+                -1,
+            ),
+        ],
+        closed: true,
+    }
+}
+
+fn bezier_points(
+    start: Vector2Nm,
+    control1: Vector2Nm,
+    control2: Vector2Nm,
+    end: Vector2Nm,
+) -> Vec<Vector2Nm> {
+    let mut points: Vec<Vector2Nm> = (0..=BEZIER_SEGMENTS)
+        .map(|step| {
+            let t = step as f64 / BEZIER_SEGMENTS as f64;
+            let u = 1.0 - t;
+            let blend = |a: i64, b: i64, c: i64, d: i64| {
+                (u * u * u * a as f64
+                    + 3.0 * u * u * t * b as f64
+                    + 3.0 * u * t * t * c as f64
+                    + t * t * t * d as f64)
+                    .round() as i64
+            };
+            Vector2Nm {
+                x_nm: blend(start.x_nm, control1.x_nm, control2.x_nm, end.x_nm),
+                y_nm: blend(start.y_nm, control1.y_nm, control2.y_nm, end.y_nm),
+            }
+        })
+        .collect();
+    points.dedup();
+    points
+}
+
+fn node_start(node: &PolyLineNodeGeometryNm) -> Vector2Nm {
+    match node {
+        PolyLineNodeGeometryNm::Point(point) => *point,
+        PolyLineNodeGeometryNm::Arc(arc) => arc.start,
+    }
+}
+
+fn node_end(node: &PolyLineNodeGeometryNm) -> Vector2Nm {
+    match node {
+        PolyLineNodeGeometryNm::Point(point) => *point,
+        PolyLineNodeGeometryNm::Arc(arc) => arc.end,
+    }
+}
+
+fn reversed(nodes: Vec<PolyLineNodeGeometryNm>) -> Vec<PolyLineNodeGeometryNm> {
+    nodes
+        .into_iter()
+        .rev()
+        .map(|node| match node {
+            PolyLineNodeGeometryNm::Arc(arc) => PolyLineNodeGeometryNm::Arc(ArcStartMidEndNm {
+                start: arc.end,
+                mid: arc.mid,
+                end: arc.start,
+            }),
+            point => point,
+        })
+        .collect()
+}
+
+fn within_tolerance(a: Vector2Nm, b: Vector2Nm) -> bool {
+    geometry::distance(a, b) <= CHAIN_TOLERANCE_NM as f64
+}
+
+/// Appends `piece` to `chain`, dropping the duplicated joint point.
+fn append(chain: &mut Vec<PolyLineNodeGeometryNm>, piece: Vec<PolyLineNodeGeometryNm>) {
+    let mut piece = piece.into_iter().peekable();
+    if let (Some(PolyLineNodeGeometryNm::Point(_)), Some(PolyLineNodeGeometryNm::Point(_))) =
+        (chain.last(), piece.peek())
+    {
+        chain.pop();
+    }
+    chain.extend(piece);
+}
+
+/// Joins open pieces end to end into closed contours; unclosed chains become gap issues.
+///
+/// A chain grows from its end, then from its start, so the piece it begins with does not
+/// matter. Both dangling ends of an unclosed chain are reported.
+fn chain(mut pieces: Vec<Vec<PolyLineNodeGeometryNm>>) -> (Vec<PolyLineNm>, Vec<OutlineIssue>) {
+    let mut contours = Vec::new();
+    let mut open_ends: Vec<(Vector2Nm, Vector2Nm, bool)> = Vec::new();
+    pieces.retain(|piece| !piece.is_empty());
+
+    while !pieces.is_empty() {
+        let mut nodes = pieces.remove(0);
+        let mut turned = false;
+        loop {
+            let start = node_start(&nodes[0]);
+            let end = node_end(nodes.last().expect("chains are never empty"));
+            if nodes.len() > 1 && within_tolerance(end, start) {
+                if let PolyLineNodeGeometryNm::Point(_) = nodes.last().expect("non-empty") {
+                    if let PolyLineNodeGeometryNm::Point(_) = nodes[0] {
+                        nodes.pop();
+                    }
+                }
+                contours.push(PolyLineNm {
+                    nodes,
+                    closed: true,
+                });
+                break;
+            }
+
+            let next = pieces
+                .iter()
+                .enumerate()
+                .flat_map(|(index, piece)| {
+                    let first = node_start(&piece[0]);
+                    let last = node_end(piece.last().expect("pieces are never empty"));
+                    [
+                        (index, false, geometry::distance(end, first)),
+                        (index, true, geometry::distance(end, last)),
+                    ]
+                })
+                .filter(|(_, _, gap)| *gap <= CHAIN_TOLERANCE_NM as f64)
+                .min_by(|a, b| a.2.total_cmp(&b.2));
+            match next {
+                Some((index, flip, _)) => {
+                    let piece = pieces.remove(index);
+                    append(&mut nodes, if flip { reversed(piece) } else { piece });
+                }
+                None if !turned => {
+                    nodes = reversed(nodes);
+                    turned = true;
+                }
+                None => {
+                    open_ends.push((start, end, nodes.len() > 1));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Nearest free endpoint: any end of another open chain, or the chain's own other end
+    // when it spans more than one node.
+    let mut issues = Vec::new();
+    for (index, &(start, end, joined)) in open_ends.iter().enumerate() {
+        for (at, other_end) in [(start, end), (end, start)] {
+            let nearest_nm = open_ends
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .flat_map(|(_, &(a, b, _))| [a, b])
+                .chain(joined.then_some(other_end))
+                .map(|point| geometry::distance(at, point).round() as i64)
+                .min();
+            issues.push(OutlineIssue::Gap { at, nearest_nm });
+        }
+    }
+    (contours, issues)
+}
+
+fn closed_edges(points: &[Vector2Nm]) -> Vec<(Vector2Nm, Vector2Nm)> {
+    (0..points.len())
+        .map(|index| (points[index], points[(index + 1) % points.len()]))
+        .filter(|(a, b)| a != b)
+        .collect()
+}
+
+/// Crossing points between non-adjacent edges of the contours, within and across contours.
+fn crossings(contours: &[Vec<Vector2Nm>]) -> Vec<Vector2Nm> {
+    let edges: Vec<Vec<_>> = contours.iter().map(|points| closed_edges(points)).collect();
+    let mut found = Vec::new();
+    for (a_index, a_edges) in edges.iter().enumerate() {
+        for (b_index, b_edges) in edges.iter().enumerate().skip(a_index) {
+            for (i, &(a1, a2)) in a_edges.iter().enumerate() {
+                for (j, &(b1, b2)) in b_edges.iter().enumerate() {
+                    if a_index == b_index {
+                        let count = a_edges.len();
+                        if j <= i || j == i + 1 || (i == 0 && j == count - 1) {
+                            continue;
+                        }
+                    }
+                    if geometry::segments_intersect(a1, a2, b1, b2) {
+                        let at = intersection_point(a1, a2, b1, b2);
+                        if !found.contains(&at) {
+                            found.push(at);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+fn intersection_point(a1: Vector2Nm, a2: Vector2Nm, b1: Vector2Nm, b2: Vector2Nm) -> Vector2Nm {
+    let (dax, day) = ((a2.x_nm - a1.x_nm) as f64, (a2.y_nm - a1.y_nm) as f64);
+    let (dbx, dby) = ((b2.x_nm - b1.x_nm) as f64, (b2.y_nm - b1.y_nm) as f64);
+    let denominator = dax * dby - day * dbx;
+    if denominator == 0.0 {
+        // Collinear overlap: report an endpoint that lies on the other edge.
+        return [b1, b2, a1]
+            .into_iter()
+            .find(|point| {
+                geometry::point_segment_distance(*point, a1, a2) == 0.0
+                    && geometry::point_segment_distance(*point, b1, b2) == 0.0
+            })
+            .unwrap_or(a1);
+    }
+    let t = ((b1.x_nm - a1.x_nm) as f64 * dby - (b1.y_nm - a1.y_nm) as f64 * dbx) / denominator;
+    Vector2Nm {
+        x_nm: (a1.x_nm as f64 + t * dax).round() as i64,
+        y_nm: (a1.y_nm as f64 + t * day).round() as i64,
+    }
+}
+
+/// Groups contours into outlines and holes by nesting depth: even depths are board regions,
+/// odd depths are cutouts of the smallest enclosing region.
+fn nest(contours: Vec<PolyLineNm>, flattened: &[Vec<Vector2Nm>]) -> Vec<PolygonWithHolesNm> {
+    let encloses = |outer: usize, inner: usize| {
+        outer != inner
+            && !flattened[inner].is_empty()
+            && geometry::point_in_polygon(flattened[inner][0], &flattened[outer])
+    };
+    let depth: Vec<usize> = (0..contours.len())
+        .map(|inner| {
+            (0..contours.len())
+                .filter(|&outer| encloses(outer, inner))
+                .count()
+        })
+        .collect();
+
+    let mut polygon_of = vec![None; contours.len()];
+    let mut polygons: Vec<PolygonWithHolesNm> = Vec::new();
+    for (index, contour) in contours.iter().enumerate() {
+        if depth[index].is_multiple_of(2) {
+            polygon_of[index] = Some(polygons.len());
+            polygons.push(PolygonWithHolesNm {
+                outline: Some(contour.clone()),
+                holes: Vec::new(),
+            });
+        }
+    }
+    for (index, contour) in contours.into_iter().enumerate() {
+        if depth[index].is_multiple_of(2) {
+            continue;
+        }
+        let parent = (0..flattened.len())
+            .find(|&outer| depth[outer] + 1 == depth[index] && encloses(outer, index))
+            .and_then(|outer| polygon_of[outer]);
+        if let Some(parent) = parent {
+            polygons[parent].holes.push(contour);
+        }
+    }
+    polygons
+}
+
+fn line_area(line: &PolyLineNm) -> f64 {
+    let points = geometry::polyline_points(line);
+    let twice: f64 = closed_edges(&points)
+        .into_iter()
+        .map(|(a, b)| a.x_nm as f64 * b.y_nm as f64 - b.x_nm as f64 * a.y_nm as f64)
+        .sum();
+    (twice / 2.0).abs()
+}
+
+fn on_boundary(point: Vector2Nm, points: &[Vector2Nm]) -> bool {
+    closed_edges(points)
+        .into_iter()
+        .any(|(a, b)| geometry::point_segment_distance(point, a, b) == 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoardOutline, OutlineIssue};
+    use crate::error::KiCadError;
+    use crate::model::board::{
+        BoardLayerInfo, PcbBoardGraphicShape, PolyLineNm, PolyLineNodeGeometryNm,
+        PolygonWithHolesNm, Vector2Nm,
+    };
+    use crate::model::common::TextShapeGeometry;
+
+    fn at(x_mm: i64, y_mm: i64) -> Vector2Nm {
+        Vector2Nm {
+            x_nm: x_mm * 1_000_000,
+            y_nm: y_mm * 1_000_000,
+        }
+    }
+
+    fn shape(geometry: TextShapeGeometry) -> PcbBoardGraphicShape {
+        PcbBoardGraphicShape {
+            id: None,
+            layer: BoardLayerInfo {
+                id: 47,
+                name: "BL_Edge_Cuts".to_string(),
+            },
+            net: None,
+            geometry_kind: None,
+            geometry,
+            stroke_width_nm: Some(100_000),
+            filled: false,
+        }
+    }
+
+    fn segment(start: Vector2Nm, end: Vector2Nm) -> PcbBoardGraphicShape {
+        shape(TextShapeGeometry::Segment {
+            start_nm: Some(start),
+            end_nm: Some(end),
+        })
+    }
+
+    #[test]
+    fn outline_chains_segments_and_nests_cutouts() {
+        // Segments deliberately out of order and direction.
+        let shapes = vec![
+            segment(at(0, 0), at(100, 0)),
+            segment(at(0, 50), at(100, 50)),
+            segment(at(0, 50), at(0, 0)),
+            segment(at(100, 0), at(100, 50)),
+            shape(TextShapeGeometry::Rectangle {
+                top_left_nm: Some(at(10, 10)),
+                bottom_right_nm: Some(at(20, 20)),
+                corner_radius_nm: None,
+            }),
+        ];
+        let outline = BoardOutline::from_shapes(&shapes).expect("outline should close");
+
+        assert_eq!(outline.polygons.len(), 1);
+        assert_eq!(outline.polygons[0].holes.len(), 1);
+        assert_eq!(outline.dimensions_nm(), Some(at(100, 50)));
+        let expected = (100.0 * 50.0 - 10.0 * 10.0) * 1e12;
+        assert!((outline.area_nm2() - expected).abs() < 1.0);
+        assert!(outline.contains(at(50, 40)));
+        assert!(outline.contains(at(10, 15)));
+        assert!(!outline.contains(at(15, 15)));
+        assert!(!outline.contains(at(150, 15)));
+    }
+
+    #[test]
+    fn outline_reports_gaps_and_crossings() {
+        let open = vec![
+            segment(at(0, 0), at(100, 0)),
+            segment(at(100, 0), at(100, 50)),
+            segment(at(100, 50), at(0, 50)),
+            segment(at(0, 50), at(0, 1)),
+        ];
+        let Err(KiCadError::BoardOutline { issues }) = BoardOutline::from_shapes(&open) else {
+            panic!("expected an outline error");
+        };
+        let gaps = vec![
+            OutlineIssue::Gap {
+                at: at(0, 1),
+                nearest_nm: Some(1_000_000),
+            },
+            OutlineIssue::Gap {
+                at: at(0, 0),
+                nearest_nm: Some(1_000_000),
+            },
+        ];
+        assert_eq!(issues, gaps);
+
+        // Starting mid-chain must find the same two ends and nothing else.
+        let shuffled = vec![
+            segment(at(100, 0), at(100, 50)),
+            segment(at(100, 50), at(0, 50)),
+            segment(at(0, 50), at(0, 1)),
+            segment(at(0, 0), at(100, 0)),
+        ];
+        let Err(KiCadError::BoardOutline { issues }) = BoardOutline::from_shapes(&shuffled) else {
+            panic!("expected an outline error");
+        };
+        assert_eq!(issues, gaps);
+
+        let bowtie = vec![
+            segment(at(0, 0), at(10, 10)),
+            segment(at(10, 10), at(10, 0)),
+            segment(at(10, 0), at(0, 10)),
+            segment(at(0, 10), at(0, 0)),
+        ];
+        let Err(KiCadError::BoardOutline { issues }) = BoardOutline::from_shapes(&bowtie) else {
+            panic!("expected an outline error");
+        };
+        assert_eq!(
+            issues,
+            vec![OutlineIssue::SelfIntersection { at: at(5, 5) }]
+        );
+    }
+
+    #[test]
+    fn outline_keeps_every_polygon_outline_and_hole() {
+        let square = |x_mm: i64, y_mm: i64, size_mm: i64| PolyLineNm {
+            nodes: [(0, 0), (size_mm, 0), (size_mm, size_mm), (0, size_mm)]
+                .into_iter()
+                .map(|(dx, dy)| PolyLineNodeGeometryNm::Point(at(x_mm + dx, y_mm + dy)))
+                .collect(),
+            closed: true,
+        };
+        let shapes = vec![shape(TextShapeGeometry::Polygon {
+            polygons: vec![
+                PolygonWithHolesNm {
+                    outline: Some(square(0, 0, 40)),
+                    holes: vec![square(10, 10, 10)],
+                },
+                PolygonWithHolesNm {
+                    outline: Some(square(100, 0, 20)),
+                    holes: Vec::new(),
+                },
+            ],
+        })];
+        let outline = BoardOutline::from_shapes(&shapes).expect("polygon outline should close");
+
+        assert_eq!(outline.polygons.len(), 2);
+        assert_eq!(
+            outline
+                .polygons
+                .iter()
+                .map(|polygon| polygon.holes.len())
+                .sum::<usize>(),
+            1
+        );
+        let expected = (40.0 * 40.0 - 10.0 * 10.0 + 20.0 * 20.0) * 1e12;
+        assert!((outline.area_nm2() - expected).abs() < 1.0);
+        assert!(outline.contains(at(110, 10)));
+        assert!(!outline.contains(at(15, 15)));
+    }
+}
@@ -0,0 +1,334 @@
+//! Anchored wildcard and regular-expression matching for net name patterns.
+//!
+//! Supports the regex subset net class patterns use in practice: literals, `.`, bracket
+//! classes with ranges and negation, `\d`/`\w`/`\s` (and their negations), groups with
+//! alternation, anchors, and the `*`, `+`, `?`, `{n}`, `{n,}`, `{n,m}` quantifiers. Lazy
+//! quantifier suffixes are accepted; they do not change whether a whole name matches.
+
+#[derive(Clone, Debug)]
+pub(crate) struct Pattern {
+    root: Vec<Vec<Piece>>,
+}
+
+#[derive(Clone, Debug)]
+struct Piece {
+    node: Node,
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Group(Vec<Vec<Piece>>),
+    Start,
+    End,
+}
+
+impl Pattern {
+    /// Compiles a regular expression; `None` when it uses unsupported or invalid syntax.
+    pub(crate) fn regex(pattern: &str) -> Option<Self> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let root = parser.alternation()?;
+        (parser.pos == parser.chars.len()).then_some(Self { root })
+    }
+
+    /// Compiles a shell-style wildcard where `*` matches any run and `?` one character.
+    pub(crate) fn wildcard(pattern: &str) -> Self {
+        let pieces = pattern
+            .chars()
+            .map(|ch| match ch {
+                '*' => Piece {
+                    node: Node::Any,
+                    min: 0,
+                    max: None,
+                },
+                '?' => Piece::once(Node::Any),
+                _ => Piece::once(Node::Char(ch)),
+            })
+            .collect();
+        Self { root: vec![pieces] }
+    }
+
+    /// Returns true when the whole of `text` matches.
+    pub(crate) fn is_full_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        match_alternation(&self.root, &chars, 0, &mut |end| end == chars.len())
+    }
+}
+
+impl Piece {
+    fn once(node: Node) -> Self {
+        Self {
+            node,
+            min: 1,
+            max: Some(1),
+        }
+    }
+}
+
+type Continuation<'a> = &'a mut dyn FnMut(usize) -> bool;
+
+fn match_alternation(branches: &[Vec<Piece>], text: &[char], pos: usize, k: Continuation) -> bool {
+    branches
+        .iter()
+        .any(|branch| match_sequence(branch, text, pos, k))
+}
+
+fn match_sequence(pieces: &[Piece], text: &[char], pos: usize, k: Continuation) -> bool {
+    match pieces.split_first() {
+        None => k(pos),
+        Some((piece, rest)) => match_piece(piece, text, pos, 0, &mut |next| {
+            match_sequence(rest, text, next, k)
+        }),
+    }
+}
+
+fn match_piece(piece: &Piece, text: &[char], pos: usize, count: usize, k: Continuation) -> bool {
+    // Greedy: try one more repetition first. Zero-width repetitions only count towards `min`,
+    // which keeps patterns like `(a*)*` from looping.
+    if piece.max.is_none_or(|max| count < max)
+        && match_node(&piece.node, text, pos, &mut |next| {
+            (next != pos || count < piece.min) && match_piece(piece, text, next, count + 1, k)
+        })
+    {
+        return true;
+    }
+    count >= piece.min && k(pos)
+}
+
+fn match_node(node: &Node, text: &[char], pos: usize, k: Continuation) -> bool {
+    match node {
+        Node::Char(expected) => text.get(pos) == Some(expected) && k(pos + 1),
+        Node::Any => pos < text.len() && k(pos + 1),
+        Node::Class { ranges, negated } => {
+            text.get(pos).is_some_and(|ch| {
+                ranges.iter().any(|(low, high)| (*low..=*high).contains(ch)) != *negated
+            }) && k(pos + 1)
+        }
+        Node::Group(branches) => match_alternation(branches, text, pos, k),
+        Node::Start => pos == 0 && k(pos),
+        Node::End => pos == text.len() && k(pos),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += 1;
+        Some(ch)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn alternation(&mut self) -> Option<Vec<Vec<Piece>>> {
+        let mut branches = vec![self.sequence()?];
+        while self.eat('|') {
+            branches.push(self.sequence()?);
+        }
+        Some(branches)
+    }
+
+    fn sequence(&mut self) -> Option<Vec<Piece>> {
+        let mut pieces = Vec::new();
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let node = self.atom()?;
+            let (min, max) = self.quantifier()?;
+            pieces.push(Piece { node, min, max });
+        }
+        Some(pieces)
+    }
+
+    fn atom(&mut self) -> Option<Node> {
+        Some(match self.next()? {
+            '(' => {
+                if self.eat('?') && !self.eat(':') {
+                    return None;
+                }
+                let branches = self.alternation()?;
+                if !self.eat(')') {
+                    return None;
+                }
+                Node::Group(branches)
+            }
+            '[' => self.class()?,
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '\\' => escape(self.next()?),
+            '*' | '+' | '?' | '{' | ')' => return None,
+            ch => Node::Char(ch),
+        })
+    }
+
+    fn quantifier(&mut self) -> Option<(usize, Option<usize>)> {
+        let bounds = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.number()?;
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') {
+                        None
+                    } else {
+                        Some(self.number()?)
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some('}') || max.is_some_and(|max| max < min) {
+                    return None;
+                }
+                (min, max)
+            }
+            _ => return Some((1, Some(1))),
+        };
+        self.pos += 1;
+        self.eat('?');
+        Some(bounds)
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn class(&mut self) -> Option<Node> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let ch = self.next()?;
+            if ch == ']' && !first {
+                break;
+            }
+            first = false;
+            let low = if ch == '\\' {
+                match escape(self.next()?) {
+                    Node::Char(ch) => ch,
+                    Node::Class {
+                        ranges: escaped,
+                        negated: false,
+                    } => {
+                        ranges.extend(escaped);
+                        continue;
+                    }
+                    _ => return None,
+                }
+            } else {
+                ch
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|ch| *ch != ']')
+            {
+                self.pos += 1;
+                let high = match self.next()? {
+                    '\\' => match escape(self.next()?) {
+                        Node::Char(ch) => ch,
+                        _ => return None,
+                    },
+                    ch => ch,
+                };
+                if high < low {
+                    return None;
+                }
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+        Some(Node::Class { ranges, negated })
+    }
+}
+
+fn escape(ch: char) -> Node {
+    let (ranges, negated) = match ch {
+        'd' | 'D' => (vec![('0', '9')], ch == 'D'),
+        'w' | 'W' => (
+            vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+            ch == 'W',
+        ),
+        's' | 'S' => (vec![(' ', ' '), ('\t', '\r')], ch == 'S'),
+        _ => return Node::Char(ch),
+    };
+    Node::Class { ranges, negated }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    #[test]
+    fn regex_and_wildcard_match_whole_names() {
+        let regex_cases = [
+            ("USB_D[PN]", "USB_DP", true),
+            ("USB_D[PN]", "USB_DPX", false),
+            ("^/DDR/DQ\\d+$", "/DDR/DQ12", true),
+            ("/DDR/DQ\\d+", "/DDR/DQS0", false),
+            ("(VCC|VDD)_(3V3|1V8)", "VDD_1V8", true),
+            ("(?:VCC|VDD)_.*", "VCC_", true),
+            ("[^A-Z]+", "abc", true),
+            ("[^A-Z]+", "aBc", false),
+            ("D{2,3}Q", "DDDQ", true),
+            ("D{2,3}Q", "DQ", false),
+            ("(a*)*b", "aaab", true),
+            ("GND?", "GN", true),
+        ];
+        for (pattern, text, expected) in regex_cases {
+            let compiled = Pattern::regex(pattern).expect("pattern should compile");
+            assert_eq!(
+                compiled.is_full_match(text),
+                expected,
+                "{pattern} vs {text}"
+            );
+        }
+        for invalid in ["(abc", "*abc", "[a-", "D{3,2}", "(?=a)"] {
+            assert!(
+                Pattern::regex(invalid).is_none(),
+                "{invalid} should not compile"
+            );
+        }
+
+        let wildcard = Pattern::wildcard("/usb/D?_*");
+        assert!(wildcard.is_full_match("/usb/DP_conn"));
+        assert!(wildcard.is_full_match("/usb/DN_"));
+        assert!(!wildcard.is_full_match("/usb/D_conn"));
+        assert!(Pattern::wildcard("a.b").is_full_match("a.b"));
+        assert!(!Pattern::wildcard("a.b").is_full_match("axb"));
+    }
+}
@@ -0,0 +1,354 @@
+//! Pick-and-place (centroid) files built from board footprints.
+//!
+//! Use [`KiCadClient::export_placement`](crate::client::KiCadClient::export_placement) to read the
+//! open board, or [`build_placement`] with footprints and an origin you already have.
+//!
+//! Coordinates follow KiCad's position files: relative to the drill/place origin with the Y axis
+//! pointing up, so a footprint below the origin on screen has a negative Y.
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::bom::natural_cmp;
+use crate::model::board::{PcbFootprint, PcbFootprintMountingStyle, Vector2Nm};
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::text_out::{write_csv_row, write_to_string};
+
+const NM_PER_MM: f64 = 1_000_000.0;
+const NM_PER_INCH: f64 = 25_400_000.0;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// Length unit used for placement coordinates.
+pub enum PlacementUnits {
+    /// Millimetres.
+    #[default]
+    Millimeters,
+    /// Inches.
+    Inches,
+}
+
+impl PlacementUnits {
+    fn convert_nm(self, value_nm: i64) -> f64 {
+        match self {
+            Self::Millimeters => value_nm as f64 / NM_PER_MM,
+            Self::Inches => value_nm as f64 / NM_PER_INCH,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Millimeters => "mm",
+            Self::Inches => "in",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Board side a footprint is mounted on.
+pub enum PlacementSide {
+    /// Front (`F.Cu`) side.
+    Top,
+    /// Back (`B.Cu`) side.
+    Bottom,
+}
+
+impl fmt::Display for PlacementSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Top => write!(f, "top"),
+            Self::Bottom => write!(f, "bottom"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Controls which footprints are exported and in which unit.
+pub struct PlacementOptions {
+    /// Coordinate unit.
+    pub units: PlacementUnits,
+    /// Export a single side only; `None` exports both.
+    pub side: Option<PlacementSide>,
+    /// Export only footprints whose mounting style is SMD.
+    pub smd_only: bool,
+    /// Include footprints flagged `exclude_from_position_files`.
+    pub include_excluded: bool,
+    /// Include footprints flagged `do_not_populate`.
+    pub include_do_not_populate: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// One placed footprint.
+pub struct PlacementEntry {
+    /// Reference designator.
+    pub reference: String,
+    /// Value field text.
+    pub value: String,
+    /// Footprint name without the library nickname.
+    pub package: String,
+    /// Position relative to the origin, Y up.
+    pub position_nm: Vector2Nm,
+    /// Orientation in degrees, normalised to `[0, 360)`.
+    pub rotation_deg: f64,
+    /// Mounting side.
+    pub side: PlacementSide,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Placement rows plus the options they were built with.
+pub struct Placement {
+    /// Rows in natural reference order.
+    pub entries: Vec<PlacementEntry>,
+    /// Coordinate unit used by the writers.
+    pub units: PlacementUnits,
+    /// Side filter, echoed into the `.pos` header.
+    pub side: Option<PlacementSide>,
+}
+
+impl Placement {
+    /// Writes `Ref,Val,Package,PosX,PosY,Rot,Side` CSV.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let header = ["Ref", "Val", "Package", "PosX", "PosY", "Rot", "Side"].map(String::from);
+        write_csv_row(out, &header)?;
+        for entry in &self.entries {
+            write_csv_row(out, &self.row(entry))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the placement as CSV text.
+    pub fn to_csv(&self) -> String {
+        write_to_string(|out| self.write_csv(out))
+    }
+
+    /// Writes KiCad's ASCII `.pos` format with aligned columns.
+    pub fn write_pos<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let side = match self.side {
+            Some(PlacementSide::Top) => "top",
+            Some(PlacementSide::Bottom) => "bottom",
+            None => "All",
+        };
+        writeln!(out, "### Footprint positions - created by kicad-ipc-rs ###")?;
+        writeln!(out, "## Unit = {}, Angle = deg.", self.units.label())?;
+        writeln!(out, "## Side : {side}")?;
+
+        let rows: Vec<[String; 7]> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                self.row(entry)
+                    .map(|cell| cell.replace(char::is_whitespace, "_"))
+            })
+            .collect();
+        let text_width = |column: usize, title: &str| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .chain([title.len()])
+                .max()
+                .unwrap_or_default()
+        };
+        let ref_width = text_width(0, "# Ref");
+        let val_width = text_width(1, "Val");
+        let package_width = text_width(2, "Package");
+
+        writeln!(
+            out,
+            "{:<ref_width$}  {:<val_width$}  {:<package_width$}  {:>10}  {:>10}  {:>8}  Side",
+            "# Ref", "Val", "Package", "PosX", "PosY", "Rot"
+        )?;
+        for row in &rows {
+            writeln!(
+                out,
+                "{:<ref_width$}  {:<val_width$}  {:<package_width$}  {:>10}  {:>10}  {:>8}  {}",
+                row[0], row[1], row[2], row[3], row[4], row[5], row[6]
+            )?;
+        }
+        writeln!(out, "## End")
+    }
+
+    /// Returns the placement as `.pos` text.
+    pub fn to_pos(&self) -> String {
+        write_to_string(|out| self.write_pos(out))
+    }
+
+    fn row(&self, entry: &PlacementEntry) -> [String; 7] {
+        [
+            entry.reference.clone(),
+            entry.value.clone(),
+            entry.package.clone(),
+            format!("{:.4}", self.units.convert_nm(entry.position_nm.x_nm)),
+            format!("{:.4}", self.units.convert_nm(entry.position_nm.y_nm)),
+            format!("{:.4}", entry.rotation_deg),
+            entry.side.to_string(),
+        ]
+    }
+}
+
+/// Builds placement rows for `footprints` relative to `origin` (board coordinates, Y down).
+pub fn build_placement<'a>(
+    footprints: impl IntoIterator<Item = &'a PcbFootprint>,
+    origin: Vector2Nm,
+    options: &PlacementOptions,
+) -> Placement {
+    let mut entries: Vec<PlacementEntry> = footprints
+        .into_iter()
+        .filter_map(|footprint| {
+            let attributes = footprint.attributes;
+            if attributes.is_some_and(|a| a.exclude_from_position_files)
+                && !options.include_excluded
+            {
+                return None;
+            }
+            if attributes.is_some_and(|a| a.do_not_populate) && !options.include_do_not_populate {
+                return None;
+            }
+            if options.smd_only
+                && !attributes.is_some_and(|a| a.mounting_style == PcbFootprintMountingStyle::Smd)
+            {
+                return None;
+            }
+
+            let side = if footprint.layer.id == BoardLayer::BlBCu as i32 {
+                PlacementSide::Bottom
+            } else {
+                PlacementSide::Top
+            };
+            if options.side.is_some_and(|wanted| wanted != side) {
+                return None;
+            }
+
+            let position = footprint.position_nm.unwrap_or(origin);
+            let library_id = footprint.library_id.as_deref().unwrap_or_default();
+            Some(PlacementEntry {
+                reference: footprint.reference.clone().unwrap_or_default(),
+                value: footprint.value.clone().unwrap_or_default(),
+                package: library_id
+                    .split_once(':')
+                    .map_or(library_id, |(_, entry)| entry)
+                    .to_string(),
+                position_nm: Vector2Nm {
+                    x_nm: position.x_nm - origin.x_nm,
+                    y_nm: origin.y_nm - position.y_nm,
+                },
+                rotation_deg: footprint
+                    .orientation_deg
+                    .unwrap_or_default()
+                    .rem_euclid(360.0),
+                side,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| natural_cmp(&a.reference, &b.reference));
+
+    Placement {
+        entries,
+        units: options.units,
+        side: options.side,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_placement, PlacementOptions, PlacementSide, PlacementUnits};
+    use crate::model::board::{
+        BoardLayerInfo, PcbFootprint, PcbFootprintAttributes, PcbFootprintMountingStyle, Vector2Nm,
+    };
+
+    fn footprint(reference: &str, x_mm: i64, y_mm: i64, layer_id: i32) -> PcbFootprint {
+        PcbFootprint {
+            id: Some(format!("id-{reference}")),
+            reference: Some(reference.to_string()),
+            position_nm: Some(Vector2Nm {
+                x_nm: x_mm * 1_000_000,
+                y_nm: y_mm * 1_000_000,
+            }),
+            orientation_deg: Some(-90.0),
+            layer: BoardLayerInfo {
+                id: layer_id,
+                name: String::new(),
+            },
+            pad_count: 2,
+            value: Some("10 k".to_string()),
+            library_id: Some("Resistor_SMD:R_0603_1608Metric".to_string()),
+            datasheet: None,
+            attributes: Some(PcbFootprintAttributes {
+                not_in_schematic: false,
+                exclude_from_position_files: false,
+                exclude_from_bill_of_materials: false,
+                exempt_from_courtyard_requirement: false,
+                do_not_populate: false,
+                mounting_style: PcbFootprintMountingStyle::Smd,
+            }),
+            items: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn build_placement_offsets_from_origin_with_y_up_and_detects_side() {
+        let mut excluded = footprint("R3", 0, 0, 3);
+        if let Some(attributes) = excluded.attributes.as_mut() {
+            attributes.exclude_from_position_files = true;
+        }
+        let footprints = [
+            footprint("R10", 30, 40, 34),
+            footprint("R2", 15, 25, 3),
+            excluded,
+        ];
+        let origin = Vector2Nm {
+            x_nm: 10_000_000,
+            y_nm: 20_000_000,
+        };
+
+        let placement = build_placement(&footprints, origin, &PlacementOptions::default());
+        assert_eq!(placement.entries.len(), 2);
+        assert_eq!(placement.entries[0].reference, "R2");
+        assert_eq!(
+            placement.entries[0].position_nm,
+            Vector2Nm {
+                x_nm: 5_000_000,
+                y_nm: -5_000_000,
+            }
+        );
+        assert_eq!(placement.entries[0].rotation_deg, 270.0);
+        assert_eq!(placement.entries[0].package, "R_0603_1608Metric");
+        assert_eq!(placement.entries[1].side, PlacementSide::Bottom);
+
+        let bottom = build_placement(
+            &footprints,
+            origin,
+            &PlacementOptions {
+                side: Some(PlacementSide::Bottom),
+                ..PlacementOptions::default()
+            },
+        );
+        assert_eq!(bottom.entries.len(), 1);
+        assert_eq!(bottom.entries[0].reference, "R10");
+    }
+
+    #[test]
+    fn placement_writers_emit_csv_and_pos_formats() {
+        let footprints = [footprint("R1", 25, 0, 3)];
+        let placement = build_placement(
+            &footprints,
+            Vector2Nm { x_nm: 0, y_nm: 0 },
+            &PlacementOptions {
+                units: PlacementUnits::Inches,
+                ..PlacementOptions::default()
+            },
+        );
+
+        assert_eq!(
+            placement.to_csv(),
+            "Ref,Val,Package,PosX,PosY,Rot,Side\n\
+             R1,10 k,R_0603_1608Metric,0.9843,0.0000,270.0000,top\n"
+        );
+        assert_eq!(
+            placement.to_pos(),
+            "### Footprint positions - created by kicad-ipc-rs ###\n\
+             ## Unit = in, Angle = deg.\n\
+             ## Side : All\n\
+             # Ref  Val   Package                  PosX        PosY       Rot  Side\n\
+             R1     10_k  R_0603_1608Metric      0.9843      0.0000  270.0000  top\n\
+             ## End\n"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Composable board item queries.
+//!
+//! An [`ItemQuery`] combines type, layer, net, net class, area, and lock-state
+//! filters. [`KiCadClient::query_items`](crate::KiCadClient::query_items) sends
+//! the most selective filter KiCad can evaluate (`GetItemsByNet`, then
+//! `GetItemsByNetClass`, then `GetItems`) and applies the rest locally;
+//! [`KiCadClient::add_query_to_selection`](crate::KiCadClient::add_query_to_selection)
+//! selects the result.
+
+use std::collections::BTreeSet;
+
+use crate::model::board::PcbItem;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+use crate::spatial::{item_bounds, BoundsNm};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// Board item kinds a query can select.
+pub enum PcbItemType {
+    /// Straight track segment.
+    Track,
+    /// Arc track.
+    Arc,
+    /// Via.
+    Via,
+    /// Footprint.
+    Footprint,
+    /// Footprint pad.
+    Pad,
+    /// Graphic shape.
+    Shape,
+    /// Board text.
+    Text,
+    /// Board text box.
+    TextBox,
+    /// Footprint field.
+    Field,
+    /// Zone or rule area.
+    Zone,
+    /// Dimension.
+    Dimension,
+    /// Group.
+    Group,
+}
+
+impl PcbItemType {
+    /// Every queryable type.
+    pub const ALL: [PcbItemType; 12] = [
+        Self::Track,
+        Self::Arc,
+        Self::Via,
+        Self::Footprint,
+        Self::Pad,
+        Self::Shape,
+        Self::Text,
+        Self::TextBox,
+        Self::Field,
+        Self::Zone,
+        Self::Dimension,
+        Self::Group,
+    ];
+
+    /// KiCad object type code used in item requests.
+    pub fn type_code(self) -> i32 {
+        let object_type = match self {
+            Self::Track => KiCadObjectType::KotPcbTrace,
+            Self::Arc => KiCadObjectType::KotPcbArc,
+            Self::Via => KiCadObjectType::KotPcbVia,
+            Self::Footprint => KiCadObjectType::KotPcbFootprint,
+            Self::Pad => KiCadObjectType::KotPcbPad,
+            Self::Shape => KiCadObjectType::KotPcbShape,
+            Self::Text => KiCadObjectType::KotPcbText,
+            Self::TextBox => KiCadObjectType::KotPcbTextbox,
+            Self::Field => KiCadObjectType::KotPcbField,
+            Self::Zone => KiCadObjectType::KotPcbZone,
+            Self::Dimension => KiCadObjectType::KotPcbDimension,
+            Self::Group => KiCadObjectType::KotPcbGroup,
+        };
+        object_type as i32
+    }
+
+    /// Type of a decoded item; `None` for undecoded payloads.
+    pub fn of(item: &PcbItem) -> Option<Self> {
+        Some(match item {
+            PcbItem::Track(_) => Self::Track,
+            PcbItem::Arc(_) => Self::Arc,
+            PcbItem::Via(_) => Self::Via,
+            PcbItem::Footprint(_) => Self::Footprint,
+            PcbItem::Pad(_) => Self::Pad,
+            PcbItem::BoardGraphicShape(_) => Self::Shape,
+            PcbItem::BoardText(_) => Self::Text,
+            PcbItem::BoardTextBox(_) => Self::TextBox,
+            PcbItem::Field(_) => Self::Field,
+            PcbItem::Zone(_) => Self::Zone,
+            PcbItem::Dimension(_) => Self::Dimension,
+            PcbItem::Group(_) => Self::Group,
+            PcbItem::Unknown(_) => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Server-side command an [`ItemQuery`] is fetched with.
+pub enum QueryPlan {
+    /// `GetItemsByNet` with net names resolved to codes.
+    ByNet,
+    /// `GetItemsByNetClass`.
+    ByNetClass,
+    /// `GetItems` by type only.
+    ByType,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Filter builder for board items; empty filters match everything.
+///
+/// Repeating a filter (two `on_layer` calls, two `net` calls) matches any of the values.
+pub struct ItemQuery {
+    types: BTreeSet<PcbItemType>,
+    layer_ids: BTreeSet<i32>,
+    nets: BTreeSet<String>,
+    net_classes: BTreeSet<String>,
+    within: Option<BoundsNm>,
+    locked: Option<bool>,
+}
+
+impl ItemQuery {
+    /// Query matching every item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts to the given item types.
+    pub fn types<I: IntoIterator<Item = PcbItemType>>(mut self, types: I) -> Self {
+        self.types.extend(types);
+        self
+    }
+
+    /// Restricts to items on layer `layer_id`.
+    pub fn on_layer(mut self, layer_id: i32) -> Self {
+        self.layer_ids.insert(layer_id);
+        self
+    }
+
+    /// Restricts to items on the net named `name`.
+    pub fn net(mut self, name: impl Into<String>) -> Self {
+        self.nets.insert(name.into());
+        self
+    }
+
+    /// Restricts to items whose net belongs to net class `name`.
+    pub fn net_class(mut self, name: impl Into<String>) -> Self {
+        self.net_classes.insert(name.into());
+        self
+    }
+
+    /// Restricts to items whose bounds intersect `area`; items without
+    /// locally computable bounds (text, groups) never match.
+    pub fn within(mut self, area: BoundsNm) -> Self {
+        self.within = Some(area);
+        self
+    }
+
+    /// Restricts to locked (`true`) or unlocked (`false`) items.
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Server-side command this query is fetched with.
+    pub fn plan(&self) -> QueryPlan {
+        if !self.nets.is_empty() {
+            QueryPlan::ByNet
+        } else if !self.net_classes.is_empty() {
+            QueryPlan::ByNetClass
+        } else {
+            QueryPlan::ByType
+        }
+    }
+
+    /// Object type codes to request; every queryable type when no type filter is set.
+    pub fn type_codes(&self) -> Vec<i32> {
+        if self.types.is_empty() {
+            PcbItemType::ALL
+                .iter()
+                .map(|kind| kind.type_code())
+                .collect()
+        } else {
+            self.types.iter().map(|kind| kind.type_code()).collect()
+        }
+    }
+
+    /// Net names filter.
+    pub fn nets(&self) -> &BTreeSet<String> {
+        &self.nets
+    }
+
+    /// Net class names filter.
+    pub fn net_classes(&self) -> &BTreeSet<String> {
+        &self.net_classes
+    }
+
+    /// True when the lock-state filter is set, so payloads must be inspected for it.
+    pub fn filters_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    /// Applies the filters that can be checked on a decoded item.
+    ///
+    /// `locked` is the item's lock state (`None` when unknown); net class
+    /// membership is left to the server-side fetch.
+    pub fn matches(&self, item: &PcbItem, locked: Option<bool>) -> bool {
+        if !self.types.is_empty()
+            && PcbItemType::of(item).is_none_or(|kind| !self.types.contains(&kind))
+        {
+            return false;
+        }
+        if !self.layer_ids.is_empty()
+            && !item
+                .layer_ids()
+                .iter()
+                .any(|layer_id| self.layer_ids.contains(layer_id))
+        {
+            return false;
+        }
+        if !self.nets.is_empty() && item.net().is_none_or(|net| !self.nets.contains(&net.name)) {
+            return false;
+        }
+        if let Some(area) = &self.within {
+            if item_bounds(item).is_none_or(|bounds| !bounds.intersects(area)) {
+                return false;
+            }
+        }
+        match self.locked {
+            Some(want) => locked == Some(want),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::board::{BoardLayerInfo, BoardNet, PcbTrack, PcbVia, PcbViaType, Vector2Nm};
+
+    fn track(layer_id: i32, net: &str, x_nm: i64) -> PcbItem {
+        PcbItem::Track(PcbTrack {
+            id: Some(format!("track-{layer_id}-{x_nm}")),
+            start_nm: Some(Vector2Nm { x_nm, y_nm: 0 }),
+            end_nm: Some(Vector2Nm {
+                x_nm: x_nm + 1_000,
+                y_nm: 0,
+            }),
+            width_nm: Some(200),
+            layer: BoardLayerInfo {
+                id: layer_id,
+                name: String::new(),
+            },
+            net: Some(BoardNet {
+                code: 1,
+                name: net.to_string(),
+            }),
+        })
+    }
+
+    #[test]
+    fn plan_prefers_most_selective_server_filter() {
+        assert_eq!(ItemQuery::new().plan(), QueryPlan::ByType);
+        assert_eq!(
+            ItemQuery::new().net_class("Power").plan(),
+            QueryPlan::ByNetClass
+        );
+        assert_eq!(
+            ItemQuery::new().net_class("Power").net("VCC").plan(),
+            QueryPlan::ByNet
+        );
+        assert_eq!(
+            ItemQuery::new()
+                .types([PcbItemType::Via, PcbItemType::Track])
+                .type_codes(),
+            vec![11, 12]
+        );
+        assert_eq!(ItemQuery::new().type_codes().len(), PcbItemType::ALL.len());
+    }
+
+    #[test]
+    fn matches_applies_client_side_filters() {
+        let query = ItemQuery::new()
+            .types([PcbItemType::Track])
+            .on_layer(3)
+            .within(BoundsNm::new(
+                Vector2Nm { x_nm: 0, y_nm: -10 },
+                Vector2Nm {
+                    x_nm: 5_000,
+                    y_nm: 10,
+                },
+            ))
+            .locked(false);
+
+        assert!(query.matches(&track(3, "GND", 0), Some(false)));
+        assert!(!query.matches(&track(3, "GND", 0), Some(true)));
+        assert!(!query.matches(&track(3, "GND", 0), None));
+        assert!(!query.matches(&track(34, "GND", 0), Some(false)));
+        assert!(!query.matches(&track(3, "GND", 9_000), Some(false)));
+
+        let via = PcbItem::Via(PcbVia {
+            id: Some("via".to_string()),
+            position_nm: Some(Vector2Nm { x_nm: 0, y_nm: 0 }),
+            via_type: PcbViaType::Through,
+            layers: None,
+            net: None,
+            drill_diameter_nm: None,
+            pad_diameter_nm: None,
+        });
+        assert!(!query.matches(&via, Some(false)));
+        assert!(ItemQuery::new()
+            .net("GND")
+            .matches(&track(3, "GND", 0), None));
+        assert!(!ItemQuery::new()
+            .net("VCC")
+            .matches(&track(3, "GND", 0), None));
+    }
+}
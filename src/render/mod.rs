@@ -0,0 +1,4 @@
+//! Headless board renderers built from IPC data only.
+
+/// SVG board preview renderer.
+pub mod svg;
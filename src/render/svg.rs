@@ -0,0 +1,1000 @@
+//! SVG board previews.
+//!
+//! [`render_svg`] draws tracks, arcs, vias, pads, zone fills, graphics, and text shapes into one
+//! `<g>` group per layer, back layers first. Pads use polygons from
+//! [`KiCadClient::get_pad_shape_as_polygon`] when present, otherwise their padstack outline; text
+//! uses strokes from [`KiCadClient::get_text_as_shapes`]. [`SvgInput::capture`] collects all of
+//! it from the open board.
+//!
+//! KiCad does not expose layer colours over IPC, so layers use a built-in palette modelled on
+//! KiCad's default theme. Copper items take their net class colour when one is set, and graphics
+//! without an explicit stroke width fall back to the board's graphics defaults.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::geometry::{self, Shape};
+use crate::model::board::{
+    BoardLayerClass, BoardLayerInfo, ColorRgba, GraphicsDefaults, NetClassForNetEntry,
+    PadShapeAsPolygonEntry, PcbFootprint, PcbItem, PcbPad, PcbViaType, PolygonWithHolesNm,
+    Vector2Nm,
+};
+use crate::model::common::{TextObjectSpec, TextShape};
+use crate::proto::kiapi::board::types::BoardLayer;
+use crate::proto::kiapi::common::types::{GraphicFillType, KiCadObjectType};
+
+const DEFAULT_LINE_WIDTH_NM: i64 = 100_000;
+const HIGHLIGHT_COLOR: &str = "#ffffff";
+const VIA_COLOR: &str = "#ececec";
+
+/// Back-to-front draw order; layers not listed are drawn afterwards in id order.
+const LAYER_ORDER: [BoardLayer; 22] = [
+    BoardLayer::BlBAdhes,
+    BoardLayer::BlBPaste,
+    BoardLayer::BlBFab,
+    BoardLayer::BlBCrtYd,
+    BoardLayer::BlBSilkS,
+    BoardLayer::BlBMask,
+    BoardLayer::BlBCu,
+    // Inner copper layers are inserted here, deepest first.
+    BoardLayer::BlFCu,
+    BoardLayer::BlFMask,
+    BoardLayer::BlFSilkS,
+    BoardLayer::BlFCrtYd,
+    BoardLayer::BlFFab,
+    BoardLayer::BlFPaste,
+    BoardLayer::BlFAdhes,
+    BoardLayer::BlDwgsUser,
+    BoardLayer::BlCmtsUser,
+    BoardLayer::BlEco1User,
+    BoardLayer::BlEco2User,
+    BoardLayer::BlMargin,
+    BoardLayer::BlUser1,
+    BoardLayer::BlUser2,
+    BoardLayer::BlEdgeCuts,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+/// Stroked text outlines for one text item.
+pub struct SvgTextShapes {
+    /// Source text item id.
+    pub item_id: Option<String>,
+    /// Layer the text is on.
+    pub layer: BoardLayerInfo,
+    /// Shapes returned by `get_text_as_shapes`.
+    pub shapes: Vec<TextShape>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Board data a render runs against.
+pub struct SvgInput {
+    /// Board items; footprint children are drawn through [`PcbFootprint::items`].
+    pub items: Vec<PcbItem>,
+    /// Pad outlines per layer; pads without an entry fall back to their padstack.
+    pub pad_polygons: Vec<PadShapeAsPolygonEntry>,
+    /// Text stroke shapes.
+    pub text_shapes: Vec<SvgTextShapes>,
+    /// Default line widths per layer class.
+    pub graphics_defaults: Option<GraphicsDefaults>,
+    /// Net name to net class colour.
+    pub net_colors: BTreeMap<String, ColorRgba>,
+}
+
+impl SvgInput {
+    /// Creates an input from items alone.
+    pub fn new(items: Vec<PcbItem>) -> Self {
+        Self {
+            items,
+            ..Self::default()
+        }
+    }
+
+    /// Takes net colours from each net's effective net class.
+    pub fn with_net_classes(mut self, assignments: &[NetClassForNetEntry]) -> Self {
+        self.net_colors = assignments
+            .iter()
+            .filter_map(|entry| {
+                let color = entry.net_class.board.as_ref()?.color?;
+                (color.a > 0.0).then(|| (entry.net_name.clone(), color))
+            })
+            .collect();
+        self
+    }
+
+    /// Item type codes [`SvgInput::capture`] reads.
+    pub fn capture_type_codes() -> Vec<i32> {
+        vec![
+            KiCadObjectType::KotPcbFootprint as i32,
+            KiCadObjectType::KotPcbTrace as i32,
+            KiCadObjectType::KotPcbArc as i32,
+            KiCadObjectType::KotPcbVia as i32,
+            KiCadObjectType::KotPcbZone as i32,
+            KiCadObjectType::KotPcbShape as i32,
+            KiCadObjectType::KotPcbText as i32,
+            KiCadObjectType::KotPcbTextbox as i32,
+        ]
+    }
+
+    /// Reads items, pad polygons, text shapes, graphics defaults, and net colours from the open
+    /// board.
+    pub async fn capture(client: &KiCadClient) -> Result<Self, KiCadError> {
+        let items = client
+            .get_items_by_type_codes(Self::capture_type_codes())
+            .await?;
+        let mut input = Self::new(items);
+
+        let mut pads_by_layer: BTreeMap<i32, Vec<String>> = BTreeMap::new();
+        for pad in input.pads() {
+            let (Some(id), Some(stack)) = (&pad.id, &pad.pad_stack) else {
+                continue;
+            };
+            for copper in &stack.copper_layers {
+                pads_by_layer
+                    .entry(copper.layer.id)
+                    .or_default()
+                    .push(id.clone());
+            }
+        }
+        for (layer_id, pad_ids) in pads_by_layer {
+            input
+                .pad_polygons
+                .extend(client.get_pad_shape_as_polygon(pad_ids, layer_id).await?);
+        }
+
+        let texts = input.text_specs();
+        if !texts.is_empty() {
+            let specs = texts.iter().map(|(_, _, spec)| spec.clone()).collect();
+            let shapes = client.get_text_as_shapes(specs).await?;
+            input.text_shapes = texts
+                .into_iter()
+                .zip(shapes)
+                .map(|((item_id, layer, _), entry)| SvgTextShapes {
+                    item_id,
+                    layer,
+                    shapes: entry.shapes,
+                })
+                .collect();
+        }
+
+        input.graphics_defaults = Some(client.get_graphics_defaults().await?);
+        let nets = client.get_nets().await?;
+        if !nets.is_empty() {
+            let assignments = client.get_netclass_for_nets(nets).await?;
+            input = input.with_net_classes(&assignments);
+        }
+        Ok(input)
+    }
+
+    fn footprints(&self) -> impl Iterator<Item = &PcbFootprint> {
+        self.items.iter().filter_map(|item| match item {
+            PcbItem::Footprint(footprint) => Some(footprint),
+            _ => None,
+        })
+    }
+
+    fn pads(&self) -> Vec<&PcbPad> {
+        let children = self
+            .footprints()
+            .flat_map(|footprint| footprint.items.iter());
+        self.items
+            .iter()
+            .chain(children)
+            .filter_map(|item| match item {
+                PcbItem::Pad(pad) => Some(pad),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn text_specs(&self) -> Vec<(Option<String>, BoardLayerInfo, TextObjectSpec)> {
+        let children = self
+            .footprints()
+            .flat_map(|footprint| footprint.items.iter());
+        self.items
+            .iter()
+            .chain(children)
+            .filter_map(|item| match item {
+                PcbItem::BoardText(text) => Some((
+                    text.id.clone(),
+                    text.layer.clone(),
+                    TextObjectSpec::Text(text.spec.clone()?),
+                )),
+                PcbItem::BoardTextBox(text) => Some((
+                    text.id.clone(),
+                    text.layer.clone(),
+                    TextObjectSpec::TextBox(text.spec.clone()?),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Layer visibility, highlighting, and styling for [`render_svg`].
+pub struct SvgOptions {
+    /// Layer ids to leave out.
+    pub hidden_layers: BTreeSet<i32>,
+    /// Item ids drawn in the highlight colour; a footprint id highlights its children.
+    pub highlight_ids: BTreeSet<String>,
+    /// Colour copper by net class colour when one is set.
+    pub use_net_colors: bool,
+    /// Background fill, also used to paint holes; `None` leaves the background transparent
+    /// and masks holes out of the layers below them.
+    pub background: Option<String>,
+    /// Margin around the drawn content in nm.
+    pub margin_nm: i64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            hidden_layers: BTreeSet::new(),
+            highlight_ids: BTreeSet::new(),
+            use_net_colors: true,
+            background: Some("#001023".to_string()),
+            margin_nm: 1_000_000,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Shows or hides one layer.
+    pub fn layer_visible(mut self, layer_id: i32, visible: bool) -> Self {
+        if visible {
+            self.hidden_layers.remove(&layer_id);
+        } else {
+            self.hidden_layers.insert(layer_id);
+        }
+        self
+    }
+
+    /// Adds item ids to highlight.
+    pub fn highlight<I, S>(mut self, item_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.highlight_ids
+            .extend(item_ids.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// Renders `input` as a standalone SVG document in millimetre units.
+pub fn render_svg(input: &SvgInput, options: &SvgOptions) -> String {
+    let mut canvas = Canvas::new(input, options);
+    canvas.draw_items(&input.items, None);
+    for footprint in input.footprints() {
+        canvas.draw_items(&footprint.items, Some(footprint));
+    }
+    for text in &input.text_shapes {
+        let highlighted = canvas.is_highlighted(text.item_id.as_deref(), None);
+        for shape in &text.shapes {
+            let filled = shape.fill_type == Some(GraphicFillType::GftFilled as i32);
+            let width = shape
+                .stroke_width_nm
+                .unwrap_or_else(|| canvas.default_line_width(text.layer.id));
+            for outline in geometry::graphic_shapes(&shape.geometry, width, filled) {
+                canvas.stroke(
+                    &text.layer,
+                    text.item_id.as_deref(),
+                    None,
+                    highlighted,
+                    &outline,
+                );
+            }
+        }
+    }
+    canvas.finish()
+}
+
+struct Group {
+    id: String,
+    elements: Vec<String>,
+}
+
+struct Canvas<'a> {
+    input: &'a SvgInput,
+    options: &'a SvgOptions,
+    /// Keyed by layer draw order; vias and holes sort directly above F.Cu.
+    groups: BTreeMap<(usize, u8), Group>,
+    /// Hole circles cut through the layers below them when the background is transparent.
+    hole_cutouts: Vec<String>,
+    min: (i64, i64),
+    max: (i64, i64),
+    pads_with_polygons: BTreeSet<&'a str>,
+}
+
+impl<'a> Canvas<'a> {
+    fn new(input: &'a SvgInput, options: &'a SvgOptions) -> Self {
+        Self {
+            input,
+            options,
+            groups: BTreeMap::new(),
+            hole_cutouts: Vec::new(),
+            min: (i64::MAX, i64::MAX),
+            max: (i64::MIN, i64::MIN),
+            pads_with_polygons: input
+                .pad_polygons
+                .iter()
+                .filter(|entry| !options.hidden_layers.contains(&entry.layer_id))
+                .map(|entry| entry.pad_id.as_str())
+                .collect(),
+        }
+    }
+
+    fn is_highlighted(&self, id: Option<&str>, parent: Option<&PcbFootprint>) -> bool {
+        let parent_id = parent.and_then(|footprint| footprint.id.as_deref());
+        [id, parent_id]
+            .into_iter()
+            .flatten()
+            .any(|id| self.options.highlight_ids.contains(id))
+    }
+
+    fn default_line_width(&self, layer_id: i32) -> i64 {
+        let class = layer_class(layer_id);
+        self.input
+            .graphics_defaults
+            .as_ref()
+            .and_then(|defaults| {
+                defaults
+                    .layers
+                    .iter()
+                    .find(|layer| layer.layer_class == class)
+            })
+            .and_then(|layer| layer.line_thickness_nm)
+            .unwrap_or(DEFAULT_LINE_WIDTH_NM)
+    }
+
+    fn color(&self, layer_id: i32, net: Option<&str>, highlighted: bool) -> String {
+        if highlighted {
+            return HIGHLIGHT_COLOR.to_string();
+        }
+        if self.options.use_net_colors && is_copper(layer_id) {
+            if let Some(color) = net.and_then(|net| self.input.net_colors.get(net)) {
+                return hex_color(color);
+            }
+        }
+        layer_color(layer_id).to_string()
+    }
+
+    fn extend_bounds(&mut self, point: Vector2Nm, radius_nm: i64) {
+        self.min.0 = self.min.0.min(point.x_nm - radius_nm);
+        self.min.1 = self.min.1.min(point.y_nm - radius_nm);
+        self.max.0 = self.max.0.max(point.x_nm + radius_nm);
+        self.max.1 = self.max.1.max(point.y_nm + radius_nm);
+    }
+
+    fn push(&mut self, order: (usize, u8), group_id: String, element: String) {
+        self.groups
+            .entry(order)
+            .or_insert_with(|| Group {
+                id: group_id,
+                elements: Vec::new(),
+            })
+            .elements
+            .push(element);
+    }
+
+    fn push_layer(&mut self, layer: &BoardLayerInfo, element: String) {
+        let name = layer.name.strip_prefix("BL_").unwrap_or(&layer.name);
+        self.push(
+            (layer_draw_order(layer.id), 0),
+            format!("layer-{name}"),
+            element,
+        );
+    }
+
+    fn draw_items(&mut self, items: &'a [PcbItem], parent: Option<&'a PcbFootprint>) {
+        for item in items {
+            match item {
+                PcbItem::Track(track) => {
+                    let (Some(start), Some(end)) = (track.start_nm, track.end_nm) else {
+                        continue;
+                    };
+                    let highlighted = self.is_highlighted(track.id.as_deref(), parent);
+                    let net = track.net.as_ref().map(|net| net.name.as_str());
+                    let shape = Shape::segment(start, end, track.width_nm.unwrap_or_default());
+                    self.stroke(&track.layer, track.id.as_deref(), net, highlighted, &shape);
+                }
+                PcbItem::Arc(arc) => {
+                    let (Some(start), Some(mid), Some(end)) =
+                        (arc.start_nm, arc.mid_nm, arc.end_nm)
+                    else {
+                        continue;
+                    };
+                    let highlighted = self.is_highlighted(arc.id.as_deref(), parent);
+                    let net = arc.net.as_ref().map(|net| net.name.as_str());
+                    let shape = Shape {
+                        points: geometry::arc_points(start, mid, end),
+                        closed: false,
+                        filled: false,
+                        half_width_nm: arc.width_nm.unwrap_or_default() as f64 / 2.0,
+                    };
+                    self.stroke(&arc.layer, arc.id.as_deref(), net, highlighted, &shape);
+                }
+                PcbItem::Via(via) => {
+                    let Some(position) = via.position_nm else {
+                        continue;
+                    };
+                    let span = via
+                        .layers
+                        .as_ref()
+                        .filter(|_| via.via_type != PcbViaType::Through)
+                        .and_then(|layers| {
+                            Some((
+                                layers.drill_start_layer.as_ref()?.id,
+                                layers.drill_end_layer.as_ref()?.id,
+                            ))
+                        })
+                        .unwrap_or((BoardLayer::BlFCu as i32, BoardLayer::BlBCu as i32));
+                    let visible = (span.0.min(span.1)..=span.0.max(span.1))
+                        .any(|layer_id| !self.options.hidden_layers.contains(&layer_id));
+                    if !visible {
+                        continue;
+                    }
+                    let highlighted = self.is_highlighted(via.id.as_deref(), parent);
+                    let radius = via
+                        .pad_diameter_nm
+                        .or(via.drill_diameter_nm)
+                        .unwrap_or_default()
+                        / 2;
+                    let color = if highlighted {
+                        HIGHLIGHT_COLOR
+                    } else {
+                        VIA_COLOR
+                    };
+                    self.extend_bounds(position, radius);
+                    let element = format!(
+                        "<circle{} cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{color}\"/>",
+                        id_attribute(via.id.as_deref()),
+                        mm(position.x_nm),
+                        mm(position.y_nm),
+                        mm(radius)
+                    );
+                    self.push(
+                        (layer_draw_order(BoardLayer::BlFCu as i32), 1),
+                        "vias".to_string(),
+                        element,
+                    );
+                    self.hole(position, via.drill_diameter_nm.unwrap_or_default());
+                }
+                PcbItem::Pad(pad) => self.pad(pad, parent),
+                PcbItem::Zone(zone) => {
+                    let highlighted = self.is_highlighted(zone.id.as_deref(), parent);
+                    let net = zone.net.as_ref().map(|net| net.name.as_str());
+                    for fill in &zone.filled_polygons {
+                        if self.options.hidden_layers.contains(&fill.layer.id) {
+                            continue;
+                        }
+                        let color = self.color(fill.layer.id, net, highlighted);
+                        for polygon in &fill.polygons {
+                            if let Some(path) = self.polygon_path(polygon) {
+                                let element = format!(
+                                    "<path{} d=\"{path}\" fill=\"{color}\" fill-opacity=\"0.6\" fill-rule=\"evenodd\"/>",
+                                    id_attribute(zone.id.as_deref())
+                                );
+                                self.push_layer(&fill.layer, element);
+                            }
+                        }
+                    }
+                }
+                PcbItem::BoardGraphicShape(graphic) => {
+                    let highlighted = self.is_highlighted(graphic.id.as_deref(), parent);
+                    let width = graphic
+                        .stroke_width_nm
+                        .filter(|width| *width > 0 || graphic.filled)
+                        .unwrap_or_else(|| self.default_line_width(graphic.layer.id));
+                    for shape in geometry::graphic_shapes(&graphic.geometry, width, graphic.filled)
+                    {
+                        let net = graphic.net.as_ref().map(|net| net.name.as_str());
+                        self.stroke(
+                            &graphic.layer,
+                            graphic.id.as_deref(),
+                            net,
+                            highlighted,
+                            &shape,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn pad(&mut self, pad: &PcbPad, parent: Option<&PcbFootprint>) {
+        let highlighted = self.is_highlighted(pad.id.as_deref(), parent);
+        let net = pad.net.as_ref().map(|net| net.name.as_str());
+        let pad_id = pad.id.as_deref();
+
+        if pad_id.is_some_and(|id| self.pads_with_polygons.contains(id)) {
+            let entries: Vec<&PadShapeAsPolygonEntry> = self
+                .input
+                .pad_polygons
+                .iter()
+                .filter(|entry| Some(entry.pad_id.as_str()) == pad_id)
+                .filter(|entry| !self.options.hidden_layers.contains(&entry.layer_id))
+                .collect();
+            for entry in entries {
+                let Some(path) = self.polygon_path(&entry.polygon) else {
+                    continue;
+                };
+                let color = self.color(entry.layer_id, net, highlighted);
+                let element = format!(
+                    "<path{} d=\"{path}\" fill=\"{color}\" fill-rule=\"evenodd\"/>",
+                    id_attribute(pad_id)
+                );
+                let layer = BoardLayerInfo {
+                    id: entry.layer_id,
+                    name: entry.layer_name.clone(),
+                };
+                self.push_layer(&layer, element);
+            }
+        } else if let (Some(position), Some(stack)) = (pad.position_nm, &pad.pad_stack) {
+            let angle = stack.angle_deg.unwrap_or_default();
+            for copper in &stack.copper_layers {
+                if let Some(shape) = geometry::pad_shape(position, copper, angle) {
+                    self.stroke(&copper.layer, pad_id, net, highlighted, &shape);
+                }
+            }
+        }
+
+        if let (Some(position), Some(drill)) = (
+            pad.position_nm,
+            pad.pad_stack
+                .as_ref()
+                .and_then(|stack| stack.drill.as_ref()),
+        ) {
+            let diameter = drill
+                .diameter_nm
+                .map(|size| size.x_nm.min(size.y_nm))
+                .unwrap_or_default();
+            self.hole(position, diameter);
+        }
+    }
+
+    fn hole(&mut self, position: Vector2Nm, diameter_nm: i64) {
+        if diameter_nm <= 0 {
+            return;
+        }
+        let circle = |fill: &str| {
+            format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{fill}\"/>",
+                mm(position.x_nm),
+                mm(position.y_nm),
+                mm(diameter_nm / 2)
+            )
+        };
+        match &self.options.background {
+            Some(background) => {
+                let element = circle(&escape_attribute(background));
+                self.push(holes_draw_order(), "holes".to_string(), element);
+            }
+            // Black marks the area a mask hides.
+            None => self.hole_cutouts.push(circle("#000000")),
+        }
+    }
+
+    fn stroke(
+        &mut self,
+        layer: &BoardLayerInfo,
+        id: Option<&str>,
+        net: Option<&str>,
+        highlighted: bool,
+        shape: &Shape,
+    ) {
+        if self.options.hidden_layers.contains(&layer.id) || shape.points.is_empty() {
+            return;
+        }
+        let color = self.color(layer.id, net, highlighted);
+        let half_width = shape.half_width_nm.round() as i64;
+        for point in &shape.points {
+            self.extend_bounds(*point, half_width);
+        }
+
+        let id = id_attribute(id);
+        let element = if shape.points.len() == 1 {
+            let center = shape.points[0];
+            format!(
+                "<circle{id} cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{color}\"/>",
+                mm(center.x_nm),
+                mm(center.y_nm),
+                mm(half_width)
+            )
+        } else {
+            let points = shape
+                .points
+                .iter()
+                .map(|point| format!("{},{}", mm(point.x_nm), mm(point.y_nm)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let tag = if shape.closed { "polygon" } else { "polyline" };
+            let fill = if shape.closed && shape.filled {
+                color.as_str()
+            } else {
+                "none"
+            };
+            if half_width > 0 {
+                format!(
+                    "<{tag}{id} points=\"{points}\" fill=\"{fill}\" stroke=\"{color}\" stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                    mm(half_width * 2)
+                )
+            } else {
+                format!("<{tag}{id} points=\"{points}\" fill=\"{fill}\"/>")
+            }
+        };
+        self.push_layer(layer, element);
+    }
+
+    fn polygon_path(&mut self, polygon: &PolygonWithHolesNm) -> Option<String> {
+        let mut path = String::new();
+        for line in polygon.outline.iter().chain(&polygon.holes) {
+            let points = geometry::polyline_points(line);
+            let Some((first, rest)) = points.split_first() else {
+                continue;
+            };
+            self.extend_bounds(*first, 0);
+            let _ = write!(path, "M{} {}", mm(first.x_nm), mm(first.y_nm));
+            for point in rest {
+                self.extend_bounds(*point, 0);
+                let _ = write!(path, " L{} {}", mm(point.x_nm), mm(point.y_nm));
+            }
+            path.push_str(" Z ");
+        }
+        let path = path.trim_end().to_string();
+        (!path.is_empty()).then_some(path)
+    }
+
+    fn finish(self) -> String {
+        let (min, max) = if self.min.0 <= self.max.0 {
+            (self.min, self.max)
+        } else {
+            ((0, 0), (0, 0))
+        };
+        let margin = self.options.margin_nm.max(0);
+        let (x, y) = (min.0 - margin, min.1 - margin);
+        let (width, height) = (
+            (max.0 - min.0 + 2 * margin).max(1),
+            (max.1 - min.1 + 2 * margin).max(1),
+        );
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}mm\" height=\"{}mm\" viewBox=\"{} {} {} {}\">",
+            mm(width),
+            mm(height),
+            mm(x),
+            mm(y),
+            mm(width),
+            mm(height)
+        );
+        if let Some(background) = &self.options.background {
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                mm(x),
+                mm(y),
+                mm(width),
+                mm(height),
+                escape_attribute(background)
+            );
+        }
+        let mut masking = !self.hole_cutouts.is_empty();
+        if masking {
+            let _ = writeln!(
+                out,
+                "<defs><mask id=\"hole-mask\" maskUnits=\"userSpaceOnUse\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\">",
+                mm(x),
+                mm(y),
+                mm(width),
+                mm(height)
+            );
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>",
+                mm(x),
+                mm(y),
+                mm(width),
+                mm(height)
+            );
+            for cutout in &self.hole_cutouts {
+                let _ = writeln!(out, "{cutout}");
+            }
+            let _ = writeln!(out, "</mask></defs>");
+            let _ = writeln!(out, "<g mask=\"url(#hole-mask)\">");
+        }
+        for (order, group) in &self.groups {
+            // Layers drawn above the holes are not cut.
+            if masking && *order > holes_draw_order() {
+                let _ = writeln!(out, "</g>");
+                masking = false;
+            }
+            let _ = writeln!(out, "<g id=\"{}\">", escape_attribute(&group.id));
+            for element in &group.elements {
+                let _ = writeln!(out, "{element}");
+            }
+            let _ = writeln!(out, "</g>");
+        }
+        if masking {
+            let _ = writeln!(out, "</g>");
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+/// Holes sit directly above F.Cu, after its vias.
+fn holes_draw_order() -> (usize, u8) {
+    (layer_draw_order(BoardLayer::BlFCu as i32), 2)
+}
+
+fn is_copper(layer_id: i32) -> bool {
+    (BoardLayer::BlFCu as i32..=BoardLayer::BlBCu as i32).contains(&layer_id)
+}
+
+fn layer_draw_order(layer_id: i32) -> usize {
+    let inner_copper = BoardLayer::BlFCu as i32 + 1..BoardLayer::BlBCu as i32;
+    let back_copper_index = LAYER_ORDER
+        .iter()
+        .position(|layer| *layer == BoardLayer::BlBCu)
+        .unwrap_or_default();
+    let inner_count = inner_copper.len();
+
+    if inner_copper.contains(&layer_id) {
+        // Deepest inner layer (largest id) first, right after B.Cu.
+        return back_copper_index + 1 + (BoardLayer::BlBCu as i32 - 1 - layer_id) as usize;
+    }
+    match LAYER_ORDER
+        .iter()
+        .position(|layer| *layer as i32 == layer_id)
+    {
+        Some(index) if index > back_copper_index => index + inner_count,
+        Some(index) => index,
+        None => LAYER_ORDER.len() + inner_count + layer_id.max(0) as usize,
+    }
+}
+
+fn layer_class(layer_id: i32) -> BoardLayerClass {
+    match BoardLayer::try_from(layer_id) {
+        _ if is_copper(layer_id) => BoardLayerClass::Copper,
+        Ok(BoardLayer::BlFSilkS | BoardLayer::BlBSilkS) => BoardLayerClass::Silkscreen,
+        Ok(BoardLayer::BlEdgeCuts) => BoardLayerClass::Edges,
+        Ok(BoardLayer::BlFCrtYd | BoardLayer::BlBCrtYd) => BoardLayerClass::Courtyard,
+        Ok(BoardLayer::BlFFab | BoardLayer::BlBFab) => BoardLayerClass::Fabrication,
+        _ => BoardLayerClass::Other,
+    }
+}
+
+fn layer_color(layer_id: i32) -> &'static str {
+    const INNER: [&str; 4] = ["#7fc87f", "#ce7d2c", "#4fcbcb", "#db628b"];
+    match BoardLayer::try_from(layer_id) {
+        Ok(BoardLayer::BlFCu) => "#c83434",
+        Ok(BoardLayer::BlBCu) => "#4d7fc4",
+        _ if is_copper(layer_id) => INNER[(layer_id - BoardLayer::BlIn1Cu as i32) as usize % 4],
+        Ok(BoardLayer::BlFSilkS) => "#f2eda1",
+        Ok(BoardLayer::BlBSilkS) => "#e8b2a7",
+        Ok(BoardLayer::BlFMask) => "#d864ff",
+        Ok(BoardLayer::BlBMask) => "#02ffee",
+        Ok(BoardLayer::BlFPaste) => "#b4a0a0",
+        Ok(BoardLayer::BlBPaste) => "#00c2c2",
+        Ok(BoardLayer::BlFAdhes) => "#843184",
+        Ok(BoardLayer::BlBAdhes) => "#0000c2",
+        Ok(BoardLayer::BlEdgeCuts) => "#d0d2cd",
+        Ok(BoardLayer::BlMargin) => "#ff26e2",
+        Ok(BoardLayer::BlFCrtYd) => "#ff26e2",
+        Ok(BoardLayer::BlBCrtYd) => "#26e9ff",
+        Ok(BoardLayer::BlFFab) => "#afafaf",
+        Ok(BoardLayer::BlBFab) => "#585d84",
+        Ok(BoardLayer::BlCmtsUser) => "#5994dc",
+        Ok(BoardLayer::BlEco1User) => "#b4dbd2",
+        Ok(BoardLayer::BlEco2User) => "#d8c852",
+        _ => "#c2c2c2",
+    }
+}
+
+fn hex_color(color: &ColorRgba) -> String {
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+fn mm(value_nm: i64) -> String {
+    let text = format!("{:.4}", value_nm as f64 / 1_000_000.0);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn id_attribute(id: Option<&str>) -> String {
+    id.map(|id| format!(" data-id=\"{}\"", escape_attribute(id)))
+        .unwrap_or_default()
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{layer_draw_order, render_svg, SvgInput, SvgOptions};
+    use crate::model::board::{
+        BoardLayerInfo, BoardNet, ColorRgba, PcbBoardGraphicShape, PcbItem, PcbTrack, PcbVia,
+        PcbViaType, PcbZone, PcbZoneFilledPolygons, PcbZoneType, PolyLineNm,
+        PolyLineNodeGeometryNm, PolygonWithHolesNm, Vector2Nm,
+    };
+    use crate::model::common::TextShapeGeometry;
+    use crate::proto::kiapi::board::types::BoardLayer;
+
+    fn layer(layer: BoardLayer) -> BoardLayerInfo {
+        BoardLayerInfo {
+            id: layer as i32,
+            name: layer.as_str_name().to_string(),
+        }
+    }
+
+    fn point(x_mm: i64, y_mm: i64) -> Vector2Nm {
+        Vector2Nm {
+            x_nm: x_mm * 1_000_000,
+            y_nm: y_mm * 1_000_000,
+        }
+    }
+
+    fn board_items() -> Vec<PcbItem> {
+        let square = PolyLineNm {
+            nodes: [point(0, 0), point(4, 0), point(4, 4), point(0, 4)]
+                .into_iter()
+                .map(PolyLineNodeGeometryNm::Point)
+                .collect(),
+            closed: true,
+        };
+        vec![
+            PcbItem::Track(PcbTrack {
+                id: Some("track-1".to_string()),
+                start_nm: Some(point(0, 0)),
+                end_nm: Some(point(10, 0)),
+                width_nm: Some(250_000),
+                layer: layer(BoardLayer::BlFCu),
+                net: Some(BoardNet {
+                    code: 1,
+                    name: "GND".to_string(),
+                }),
+            }),
+            PcbItem::Via(PcbVia {
+                id: Some("via-1".to_string()),
+                position_nm: Some(point(10, 0)),
+                via_type: PcbViaType::Through,
+                layers: None,
+                net: None,
+                drill_diameter_nm: Some(300_000),
+                pad_diameter_nm: Some(600_000),
+            }),
+            PcbItem::Zone(PcbZone {
+                id: Some("zone-1".to_string()),
+                name: String::new(),
+                zone_type: PcbZoneType::Copper,
+                layer_count: 1,
+                filled: true,
+                polygon_count: 1,
+                layers: vec![layer(BoardLayer::BlBCu)],
+                net: None,
+                outline: Vec::new(),
+                filled_polygons: vec![PcbZoneFilledPolygons {
+                    layer: layer(BoardLayer::BlBCu),
+                    polygons: vec![PolygonWithHolesNm {
+                        outline: Some(square),
+                        holes: Vec::new(),
+                    }],
+                }],
+            }),
+            PcbItem::BoardGraphicShape(PcbBoardGraphicShape {
+                id: Some("edge-1".to_string()),
+                layer: layer(BoardLayer::BlEdgeCuts),
+                net: None,
+                geometry_kind: Some("segment".to_string()),
+                geometry: TextShapeGeometry::Segment {
+                    start_nm: Some(point(-2, -2)),
+                    end_nm: Some(point(12, -2)),
+                },
+                stroke_width_nm: None,
+                filled: false,
+            }),
+        ]
+    }
+
+    #[test]
+    fn render_svg_groups_layers_back_to_front_with_net_colors() {
+        let mut input = SvgInput::new(board_items());
+        input.net_colors = BTreeMap::from([(
+            "GND".to_string(),
+            ColorRgba {
+                r: 0.0,
+                g: 1.0,
+                b: 0.0,
+                a: 1.0,
+            },
+        )]);
+        let svg = render_svg(&input, &SvgOptions::default());
+
+        let back = svg.find("<g id=\"layer-B_Cu\">").expect("B.Cu group");
+        let front = svg.find("<g id=\"layer-F_Cu\">").expect("F.Cu group");
+        let edge = svg
+            .find("<g id=\"layer-Edge_Cuts\">")
+            .expect("Edge.Cuts group");
+        assert!(back < front && front < edge);
+        assert!(svg.contains(
+            "<polyline data-id=\"track-1\" points=\"0,0 10,0\" fill=\"none\" stroke=\"#00ff00\" stroke-width=\"0.25\""
+        ));
+        assert!(svg.contains("<g id=\"vias\">"));
+        assert!(svg.contains("stroke=\"#d0d2cd\" stroke-width=\"0.1\""));
+        assert!(svg.contains("viewBox=\"-3.05 -3.05 16.1 8.05\""));
+    }
+
+    #[test]
+    fn render_svg_hides_layers_and_highlights_items() {
+        let options = SvgOptions::default()
+            .layer_visible(BoardLayer::BlBCu as i32, false)
+            .highlight(["track-1"]);
+        let svg = render_svg(&SvgInput::new(board_items()), &options);
+
+        assert!(!svg.contains("layer-B_Cu"));
+        assert!(svg
+            .contains("data-id=\"track-1\" points=\"0,0 10,0\" fill=\"none\" stroke=\"#ffffff\""));
+    }
+
+    #[test]
+    fn render_svg_escapes_hole_fill_and_masks_holes_without_background() {
+        let options = SvgOptions {
+            background: Some("#000\"/><script/>".to_string()),
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(&SvgInput::new(board_items()), &options);
+        assert!(svg.contains("r=\"0.15\" fill=\"#000&quot;/&gt;&lt;script/&gt;\"/>"));
+        assert!(!svg.contains("<script/>"));
+
+        let options = SvgOptions {
+            background: None,
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(&SvgInput::new(board_items()), &options);
+        assert!(!svg.contains("<g id=\"holes\">"));
+        assert!(svg
+            .contains("<circle cx=\"10\" cy=\"0\" r=\"0.15\" fill=\"#000000\"/>\n</mask></defs>"));
+        let mask = svg
+            .find("<g mask=\"url(#hole-mask)\">")
+            .expect("masked group");
+        let back = svg.find("<g id=\"layer-B_Cu\">").expect("B.Cu group");
+        let edge = svg
+            .find("<g id=\"layer-Edge_Cuts\">")
+            .expect("Edge.Cuts group");
+        let unmasked = svg[mask..].find("</g>\n</g>\n").expect("mask group closes") + mask;
+        assert!(mask < back && back < unmasked && unmasked < edge);
+    }
+
+    #[test]
+    fn layer_draw_order_places_inner_copper_between_outer_layers() {
+        let order = |layer: BoardLayer| layer_draw_order(layer as i32);
+        assert!(order(BoardLayer::BlBSilkS) < order(BoardLayer::BlBCu));
+        assert!(order(BoardLayer::BlBCu) < order(BoardLayer::BlIn2Cu));
+        assert!(order(BoardLayer::BlIn2Cu) < order(BoardLayer::BlIn1Cu));
+        assert!(order(BoardLayer::BlIn1Cu) < order(BoardLayer::BlFCu));
+        assert!(order(BoardLayer::BlFCu) < order(BoardLayer::BlFSilkS));
+        assert!(order(BoardLayer::BlFSilkS) < order(BoardLayer::BlEdgeCuts));
+    }
+}
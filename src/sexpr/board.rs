@@ -0,0 +1,629 @@
+use crate::model::board::Vector2Nm;
+
+use super::tree::{format_mm, Atom, Document, List, Node};
+
+/// Board item lists in `document`: the children of a `kicad_pcb` root, or the
+/// top-level lists themselves for selection strings.
+pub fn board_items(document: &Document) -> Vec<BoardNode<&List>> {
+    let mut out = Vec::new();
+    for list in document.lists() {
+        if list.head() == Some("kicad_pcb") {
+            out.extend(list.lists().map(BoardNode::new));
+        } else {
+            out.push(BoardNode::new(list));
+        }
+    }
+    out
+}
+
+/// Mutable form of [`board_items`].
+pub fn board_items_mut(document: &mut Document) -> Vec<BoardNode<&mut List>> {
+    let mut out = Vec::new();
+    for list in document.lists_mut() {
+        if list.head() == Some("kicad_pcb") {
+            out.extend(list.lists_mut().map(BoardNode::new));
+        } else {
+            out.push(BoardNode::new(list));
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+/// Typed view chosen from a list's head.
+///
+/// `L` is `&List` for reading or `&mut List` for patching.
+pub enum BoardNode<L> {
+    /// `footprint` (or legacy `module`).
+    Footprint(Footprint<L>),
+    /// Straight track `segment`.
+    Segment(Segment<L>),
+    /// `via`.
+    Via(Via<L>),
+    /// Copper or keepout `zone`.
+    Zone(Zone<L>),
+    /// Any `gr_*` node.
+    Graphic(Graphic<L>),
+    /// Everything else, left untyped.
+    Other(L),
+}
+
+impl<L: AsRef<List>> BoardNode<L> {
+    /// Wraps `list` in the view matching its head.
+    pub fn new(list: L) -> Self {
+        match list.as_ref().head() {
+            Some("footprint") | Some("module") => Self::Footprint(Footprint(list)),
+            Some("segment") => Self::Segment(Segment(list)),
+            Some("via") => Self::Via(Via(list)),
+            Some("zone") => Self::Zone(Zone(list)),
+            Some(head) if head.starts_with("gr_") => Self::Graphic(Graphic(list)),
+            _ => Self::Other(list),
+        }
+    }
+
+    /// The wrapped list.
+    pub fn list(&self) -> &List {
+        match self {
+            Self::Footprint(view) => view.0.as_ref(),
+            Self::Segment(view) => view.0.as_ref(),
+            Self::Via(view) => view.0.as_ref(),
+            Self::Zone(view) => view.0.as_ref(),
+            Self::Graphic(view) => view.0.as_ref(),
+            Self::Other(list) => list.as_ref(),
+        }
+    }
+
+    /// Item UUID from `(uuid ...)`, or `(tstamp ...)` in older files.
+    pub fn uuid(&self) -> Option<&str> {
+        uuid(self.list())
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> BoardNode<L> {
+    /// Moves the item by `delta`; untyped nodes are left alone.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        match self {
+            Self::Footprint(view) => view.translate(delta),
+            Self::Segment(view) => view.translate(delta),
+            Self::Via(view) => view.translate(delta),
+            Self::Zone(view) => view.translate(delta),
+            Self::Graphic(view) => view.translate(delta),
+            Self::Other(_) => {}
+        }
+    }
+}
+
+impl AsRef<List> for List {
+    fn as_ref(&self) -> &List {
+        self
+    }
+}
+
+impl AsMut<List> for List {
+    fn as_mut(&mut self) -> &mut List {
+        self
+    }
+}
+
+#[derive(Debug)]
+/// `(footprint "lib:name" ...)`.
+pub struct Footprint<L>(pub L);
+
+impl<L: AsRef<List>> Footprint<L> {
+    /// Library identifier, e.g. `Resistor_SMD:R_0603_1608Metric`.
+    pub fn lib_id(&self) -> Option<&str> {
+        self.0.as_ref().atom(0).map(Atom::value)
+    }
+
+    /// Placement from `(at x y [angle])`.
+    pub fn position_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "at")
+    }
+
+    /// Rotation in degrees from `(at x y angle)`.
+    pub fn rotation_deg(&self) -> f64 {
+        self.0
+            .as_ref()
+            .child("at")
+            .and_then(|at| at.atom(2))
+            .and_then(Atom::as_f64)
+            .unwrap_or(0.0)
+    }
+
+    /// Side layer, `F.Cu` or `B.Cu`.
+    pub fn layer(&self) -> Option<&str> {
+        self.0.as_ref().child_value("layer")
+    }
+
+    /// Reference designator.
+    pub fn reference(&self) -> Option<&str> {
+        self.field("Reference", "reference")
+    }
+
+    /// Value field.
+    pub fn value(&self) -> Option<&str> {
+        self.field("Value", "value")
+    }
+
+    /// Pad numbers in file order.
+    pub fn pad_numbers(&self) -> Vec<&str> {
+        self.0
+            .as_ref()
+            .children("pad")
+            .filter_map(|pad| pad.atom(0))
+            .map(Atom::value)
+            .collect()
+    }
+
+    fn field(&self, property: &str, fp_text: &str) -> Option<&str> {
+        field_list(self.0.as_ref(), property, fp_text)
+            .and_then(|list| list.atom(1).map(Atom::value))
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> Footprint<L> {
+    /// Moves the footprint; pads and footprint graphics are relative and follow it.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        translate_point(self.0.as_mut(), "at", delta);
+    }
+
+    /// Sets the placement, keeping the rotation.
+    pub fn set_position_nm(&mut self, position: Vector2Nm) {
+        set_point(self.0.as_mut(), "at", position);
+    }
+
+    /// Sets the reference designator.
+    pub fn set_reference(&mut self, reference: &str) -> bool {
+        self.set_field("Reference", "reference", reference)
+    }
+
+    /// Sets the value field.
+    pub fn set_value(&mut self, value: &str) -> bool {
+        self.set_field("Value", "value", value)
+    }
+
+    fn set_field(&mut self, property: &str, fp_text: &str, value: &str) -> bool {
+        match field_list_mut(self.0.as_mut(), property, fp_text) {
+            Some(list) => list.set_atom(1, value),
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// `(segment (start ...) (end ...) (width ...) (layer ...) (net ...))`.
+pub struct Segment<L>(pub L);
+
+impl<L: AsRef<List>> Segment<L> {
+    /// Start point.
+    pub fn start_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "start")
+    }
+
+    /// End point.
+    pub fn end_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "end")
+    }
+
+    /// Track width.
+    pub fn width_nm(&self) -> Option<i64> {
+        self.0.as_ref().child("width")?.atom(0)?.as_nm()
+    }
+
+    /// Layer name.
+    pub fn layer(&self) -> Option<&str> {
+        self.0.as_ref().child_value("layer")
+    }
+
+    /// Net code from `(net <code>)`.
+    pub fn net_code(&self) -> Option<i32> {
+        net_code(self.0.as_ref())
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> Segment<L> {
+    /// Moves the item by `delta`.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        translate_point(self.0.as_mut(), "start", delta);
+        translate_point(self.0.as_mut(), "end", delta);
+    }
+
+    /// Sets the start point.
+    pub fn set_start_nm(&mut self, start: Vector2Nm) {
+        set_point(self.0.as_mut(), "start", start);
+    }
+
+    /// Sets the end point.
+    pub fn set_end_nm(&mut self, end: Vector2Nm) {
+        set_point(self.0.as_mut(), "end", end);
+    }
+
+    /// Sets the width.
+    pub fn set_width_nm(&mut self, width_nm: i64) {
+        self.0
+            .as_mut()
+            .set_child_atoms("width", [format_mm(width_nm)]);
+    }
+
+    /// Sets the layer.
+    pub fn set_layer(&mut self, layer: &str) {
+        self.0.as_mut().set_child_atoms("layer", [layer]);
+    }
+
+    /// Sets the net code.
+    pub fn set_net_code(&mut self, code: i32) {
+        self.0.as_mut().set_child_atoms("net", [code.to_string()]);
+    }
+}
+
+#[derive(Debug)]
+/// `(via (at ...) (size ...) (drill ...) (layers ...) (net ...))`.
+pub struct Via<L>(pub L);
+
+impl<L: AsRef<List>> Via<L> {
+    /// Centre from `(at x y)`.
+    pub fn position_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "at")
+    }
+
+    /// Pad diameter.
+    pub fn size_nm(&self) -> Option<i64> {
+        self.0.as_ref().child("size")?.atom(0)?.as_nm()
+    }
+
+    /// Drill diameter.
+    pub fn drill_nm(&self) -> Option<i64> {
+        self.0.as_ref().child("drill")?.atom(0)?.as_nm()
+    }
+
+    /// Start and end copper layers.
+    pub fn layers(&self) -> Vec<&str> {
+        layers(self.0.as_ref())
+    }
+
+    /// Net code from `(net <code>)`.
+    pub fn net_code(&self) -> Option<i32> {
+        net_code(self.0.as_ref())
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> Via<L> {
+    /// Moves the item by `delta`.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        translate_point(self.0.as_mut(), "at", delta);
+    }
+
+    /// Sets the centre.
+    pub fn set_position_nm(&mut self, position: Vector2Nm) {
+        set_point(self.0.as_mut(), "at", position);
+    }
+
+    /// Sets the pad diameter.
+    pub fn set_size_nm(&mut self, size_nm: i64) {
+        self.0
+            .as_mut()
+            .set_child_atoms("size", [format_mm(size_nm)]);
+    }
+
+    /// Sets the drill diameter.
+    pub fn set_drill_nm(&mut self, drill_nm: i64) {
+        self.0
+            .as_mut()
+            .set_child_atoms("drill", [format_mm(drill_nm)]);
+    }
+
+    /// Sets the net code.
+    pub fn set_net_code(&mut self, code: i32) {
+        self.0.as_mut().set_child_atoms("net", [code.to_string()]);
+    }
+}
+
+#[derive(Debug)]
+/// `(zone (net ...) (net_name ...) (layers ...) (polygon (pts ...)) ...)`.
+pub struct Zone<L>(pub L);
+
+impl<L: AsRef<List>> Zone<L> {
+    /// Net code from `(net <code>)`.
+    pub fn net_code(&self) -> Option<i32> {
+        net_code(self.0.as_ref())
+    }
+
+    /// Net name from `(net_name ...)`.
+    pub fn net_name(&self) -> Option<&str> {
+        self.0.as_ref().child_value("net_name")
+    }
+
+    /// Layers from `(layer ...)` or `(layers ...)`.
+    pub fn layers(&self) -> Vec<&str> {
+        layers(self.0.as_ref())
+    }
+
+    /// Outline vertices from the first `(polygon (pts (xy ...)))`.
+    pub fn outline_nm(&self) -> Vec<Vector2Nm> {
+        self.0
+            .as_ref()
+            .child("polygon")
+            .and_then(|polygon| polygon.child("pts"))
+            .map(|pts| pts.children("xy").filter_map(xy).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> Zone<L> {
+    /// Moves the outline and any saved fill.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        for polygon in self.0.as_mut().lists_mut() {
+            if matches!(polygon.head(), Some("polygon") | Some("filled_polygon")) {
+                if let Some(pts) = polygon.child_mut("pts") {
+                    translate_pts(pts, delta);
+                }
+            }
+        }
+    }
+
+    /// Sets both the net code and the net name.
+    pub fn set_net(&mut self, code: i32, name: &str) {
+        let list = self.0.as_mut();
+        list.set_child_atoms("net", [code.to_string()]);
+        if list.child("net_name").is_some() {
+            list.set_child_atoms("net_name", [name]);
+        } else {
+            list.push(List::new("net_name").with(Atom::quoted(name)));
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Any `gr_*` node: `gr_line`, `gr_rect`, `gr_circle`, `gr_arc`, `gr_poly`, `gr_text`, ...
+pub struct Graphic<L>(pub L);
+
+impl<L: AsRef<List>> Graphic<L> {
+    /// Shape name without the `gr_` prefix, e.g. `line`.
+    pub fn kind(&self) -> &str {
+        self.0
+            .as_ref()
+            .head()
+            .and_then(|head| head.strip_prefix("gr_"))
+            .unwrap_or_default()
+    }
+
+    /// Layer name.
+    pub fn layer(&self) -> Option<&str> {
+        self.0.as_ref().child_value("layer")
+    }
+
+    /// Start point.
+    pub fn start_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "start")
+    }
+
+    /// End point.
+    pub fn end_nm(&self) -> Option<Vector2Nm> {
+        point(self.0.as_ref(), "end")
+    }
+
+    /// Stroke width from `(stroke (width ...))`, or `(width ...)` in older files.
+    pub fn width_nm(&self) -> Option<i64> {
+        let list = self.0.as_ref();
+        list.child("stroke")
+            .and_then(|stroke| stroke.child("width"))
+            .or_else(|| list.child("width"))?
+            .atom(0)?
+            .as_nm()
+    }
+
+    /// Text of `gr_text` nodes.
+    pub fn text(&self) -> Option<&str> {
+        (self.kind() == "text")
+            .then(|| self.0.as_ref().atom(0).map(Atom::value))
+            .flatten()
+    }
+}
+
+impl<L: AsRef<List> + AsMut<List>> Graphic<L> {
+    /// Moves the item by `delta`.
+    pub fn translate(&mut self, delta: Vector2Nm) {
+        let list = self.0.as_mut();
+        for head in ["start", "mid", "end", "center", "at"] {
+            translate_point(list, head, delta);
+        }
+        if let Some(pts) = list.child_mut("pts") {
+            translate_pts(pts, delta);
+        }
+    }
+
+    /// Sets the layer.
+    pub fn set_layer(&mut self, layer: &str) {
+        self.0.as_mut().set_child_atoms("layer", [layer]);
+    }
+
+    /// Sets the width.
+    pub fn set_width_nm(&mut self, width_nm: i64) {
+        let list = self.0.as_mut();
+        match list.child_mut("stroke") {
+            Some(stroke) => stroke.set_child_atoms("width", [format_mm(width_nm)]),
+            None => list.set_child_atoms("width", [format_mm(width_nm)]),
+        }
+    }
+}
+
+fn uuid(list: &List) -> Option<&str> {
+    list.child_value("uuid")
+        .or_else(|| list.child_value("tstamp"))
+}
+
+fn net_code(list: &List) -> Option<i32> {
+    list.child("net")?.atom(0)?.value().parse().ok()
+}
+
+fn layers(list: &List) -> Vec<&str> {
+    list.child("layers")
+        .or_else(|| list.child("layer"))
+        .map(|layers| {
+            layers
+                .items()
+                .iter()
+                .skip(1)
+                .filter_map(Node::as_atom)
+                .map(Atom::value)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn field_list<'a>(list: &'a List, property: &str, fp_text: &str) -> Option<&'a List> {
+    list.lists()
+        .find(|child| is_field(child, property, fp_text))
+}
+
+fn field_list_mut<'a>(list: &'a mut List, property: &str, fp_text: &str) -> Option<&'a mut List> {
+    list.lists_mut()
+        .find(|child| is_field(child, property, fp_text))
+}
+
+/// KiCad 8+ stores fields as `(property "Reference" "R1")`; older files use
+/// `(fp_text reference "R1")`.
+fn is_field(child: &List, property: &str, fp_text: &str) -> bool {
+    let name = child.atom(0).map(Atom::value);
+    match child.head() {
+        Some("property") => name == Some(property),
+        Some("fp_text") => name == Some(fp_text),
+        _ => false,
+    }
+}
+
+fn point(list: &List, head: &str) -> Option<Vector2Nm> {
+    list.child(head).and_then(xy)
+}
+
+fn xy(list: &List) -> Option<Vector2Nm> {
+    Some(Vector2Nm {
+        x_nm: list.atom(0)?.as_nm()?,
+        y_nm: list.atom(1)?.as_nm()?,
+    })
+}
+
+fn set_point(list: &mut List, head: &str, value: Vector2Nm) {
+    list.set_child_atoms(head, [format_mm(value.x_nm), format_mm(value.y_nm)]);
+}
+
+fn translate_point(list: &mut List, head: &str, delta: Vector2Nm) {
+    if let Some(child) = list.child_mut(head) {
+        shift_xy(child, delta);
+    }
+}
+
+fn translate_pts(pts: &mut List, delta: Vector2Nm) {
+    for vertex in pts.lists_mut() {
+        shift_xy(vertex, delta);
+    }
+}
+
+fn shift_xy(list: &mut List, delta: Vector2Nm) {
+    if let Some(current) = xy(list) {
+        list.set_atom(0, format_mm(current.x_nm + delta.x_nm));
+        list.set_atom(1, format_mm(current.y_nm + delta.y_nm));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sexpr::parse;
+
+    const SELECTION: &str = r#"(footprint "Resistor_SMD:R_0603"
+	(layer "F.Cu")
+	(uuid "fp-1")
+	(at 10 20 90)
+	(property "Reference" "R1" (at 0 -1.5 90) (layer "F.SilkS"))
+	(property "Value" "10k" (at 0 1.5 90) (layer "F.Fab"))
+	(pad "1" smd roundrect (at -0.8 0 90) (size 0.8 0.9) (layers "F.Cu" "F.Mask"))
+	(pad "2" smd roundrect (at 0.8 0 90) (size 0.8 0.9) (layers "F.Cu" "F.Mask"))
+)
+(segment (start 1 1) (end 2 1) (width 0.25) (layer "F.Cu") (net 3) (uuid "seg-1"))
+(via (at 2 1) (size 0.6) (drill 0.3) (layers "F.Cu" "B.Cu") (net 3) (uuid "via-1"))
+(zone (net 3) (net_name "GND") (layers "F.Cu" "B.Cu") (uuid "zone-1")
+	(polygon (pts (xy 0 0) (xy 5 0) (xy 5 5)))
+)
+(gr_line (start 0 0) (end 1 0) (stroke (width 0.1) (type default)) (layer "Edge.Cuts") (uuid "gr-1"))
+(group "" (uuid "grp-1") (members "seg-1"))
+"#;
+
+    #[test]
+    fn typed_views_read_selection_items() {
+        let document = parse(SELECTION).expect("selection should parse");
+        let items = board_items(&document);
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[5].uuid(), Some("grp-1"));
+        assert!(matches!(items[5], BoardNode::Other(_)));
+
+        let BoardNode::Footprint(footprint) = &items[0] else {
+            panic!("expected footprint");
+        };
+        assert_eq!(footprint.lib_id(), Some("Resistor_SMD:R_0603"));
+        assert_eq!(footprint.reference(), Some("R1"));
+        assert_eq!(footprint.value(), Some("10k"));
+        assert_eq!(footprint.rotation_deg(), 90.0);
+        assert_eq!(footprint.pad_numbers(), vec!["1", "2"]);
+
+        let BoardNode::Via(via) = &items[2] else {
+            panic!("expected via");
+        };
+        assert_eq!(via.drill_nm(), Some(300_000));
+        assert_eq!(via.layers(), vec!["F.Cu", "B.Cu"]);
+
+        let BoardNode::Zone(zone) = &items[3] else {
+            panic!("expected zone");
+        };
+        assert_eq!(zone.net_name(), Some("GND"));
+        assert_eq!(zone.outline_nm().len(), 3);
+
+        let BoardNode::Graphic(graphic) = &items[4] else {
+            panic!("expected graphic");
+        };
+        assert_eq!(graphic.kind(), "line");
+        assert_eq!(graphic.width_nm(), Some(100_000));
+    }
+
+    #[test]
+    fn patching_selection_only_touches_edited_atoms() {
+        let mut document = parse(SELECTION).expect("selection should parse");
+        let delta = Vector2Nm {
+            x_nm: 1_000_000,
+            y_nm: -500_000,
+        };
+        for mut item in board_items_mut(&mut document) {
+            item.translate(delta);
+            match item {
+                BoardNode::Footprint(mut footprint) => {
+                    assert!(footprint.set_reference("R7"));
+                }
+                BoardNode::Segment(mut segment) => {
+                    segment.set_width_nm(400_000);
+                    segment.set_layer("B.Cu");
+                }
+                BoardNode::Zone(mut zone) => zone.set_net(4, "VCC"),
+                _ => {}
+            }
+        }
+
+        let expected = SELECTION
+            .replace("(at 10 20 90)", "(at 11 19.5 90)")
+            .replace("\"R1\"", "\"R7\"")
+            .replace(
+                "(start 1 1) (end 2 1) (width 0.25) (layer \"F.Cu\") (net 3)",
+                "(start 2 0.5) (end 3 0.5) (width 0.4) (layer \"B.Cu\") (net 3)",
+            )
+            .replace("(via (at 2 1)", "(via (at 3 0.5)")
+            .replace(
+                "(zone (net 3) (net_name \"GND\")",
+                "(zone (net 4) (net_name \"VCC\")",
+            )
+            .replace(
+                "(xy 0 0) (xy 5 0) (xy 5 5)",
+                "(xy 1 -0.5) (xy 6 -0.5) (xy 6 4.5)",
+            )
+            .replace("(start 0 0) (end 1 0)", "(start 1 -0.5) (end 2 -0.5)");
+        assert_eq!(document.to_string(), expected);
+    }
+}
@@ -0,0 +1,14 @@
+//! Lossless KiCad s-expression parsing and printing.
+//!
+//! `get_board_as_string`, `get_selection_as_string`, and
+//! `parse_and_create_items_from_string` trade KiCad file text. [`parse`] keeps
+//! every byte of whitespace and unknown nodes, so an edited [`Document`] writes
+//! back with only the touched atoms changed and can be sent straight back to
+//! KiCad.
+
+/// Typed views over `footprint`, `segment`, `via`, `zone`, and `gr_*` nodes.
+pub mod board;
+/// Node tree, parser, and writers.
+pub mod tree;
+
+pub use tree::{format_mm, parse, parse_mm, Atom, Document, List, Node};
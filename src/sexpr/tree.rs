@@ -0,0 +1,697 @@
+use std::fmt;
+
+use crate::error::KiCadError;
+
+#[derive(Clone, Debug, PartialEq)]
+/// One node of a KiCad s-expression: an atom or a parenthesised list.
+pub enum Node {
+    /// Symbol, number, or quoted string.
+    Atom(Atom),
+    /// Parenthesised list.
+    List(List),
+}
+
+impl Node {
+    /// Whitespace that preceded this node in the source text.
+    pub fn leading(&self) -> &str {
+        match self {
+            Self::Atom(atom) => &atom.leading,
+            Self::List(list) => &list.leading,
+        }
+    }
+
+    /// Replaces the whitespace written before this node.
+    pub fn set_leading(&mut self, leading: impl Into<String>) {
+        match self {
+            Self::Atom(atom) => atom.leading = leading.into(),
+            Self::List(list) => list.leading = leading.into(),
+        }
+    }
+
+    /// Returns the atom, if this node is one.
+    pub fn as_atom(&self) -> Option<&Atom> {
+        match self {
+            Self::Atom(atom) => Some(atom),
+            Self::List(_) => None,
+        }
+    }
+
+    /// Returns the list, if this node is one.
+    pub fn as_list(&self) -> Option<&List> {
+        match self {
+            Self::List(list) => Some(list),
+            Self::Atom(_) => None,
+        }
+    }
+
+    /// Returns the list mutably, if this node is one.
+    pub fn as_list_mut(&mut self) -> Option<&mut List> {
+        match self {
+            Self::List(list) => Some(list),
+            Self::Atom(_) => None,
+        }
+    }
+
+    /// Re-formats this node in KiCad's tab-indented layout, dropping source whitespace.
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        write_pretty(self, 0, &mut out);
+        out
+    }
+}
+
+impl From<Atom> for Node {
+    fn from(value: Atom) -> Self {
+        Self::Atom(value)
+    }
+}
+
+impl From<List> for Node {
+    fn from(value: List) -> Self {
+        Self::List(value)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Atom(atom) => atom.fmt(f),
+            Self::List(list) => list.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Symbol, number, or quoted string, with its original spelling.
+pub struct Atom {
+    leading: String,
+    value: String,
+    quoted: bool,
+    raw: String,
+}
+
+impl Atom {
+    /// Bare symbol such as `segment`, `F.Cu`, or `0.25`.
+    ///
+    /// Values that cannot be written bare (empty, whitespace, parentheses, quotes)
+    /// are quoted instead.
+    pub fn symbol(value: impl Into<String>) -> Self {
+        let value = value.into();
+        if needs_quotes(&value) {
+            Self::quoted(value)
+        } else {
+            Self {
+                leading: String::new(),
+                raw: value.clone(),
+                value,
+                quoted: false,
+            }
+        }
+    }
+
+    /// Double-quoted string.
+    pub fn quoted(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            leading: String::new(),
+            raw: quote(&value),
+            value,
+            quoted: true,
+        }
+    }
+
+    /// Millimetre number formatted the way KiCad writes coordinates.
+    pub fn mm(value_nm: i64) -> Self {
+        Self::symbol(format_mm(value_nm))
+    }
+
+    /// Unescaped value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Source spelling, including quotes and escapes.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Whether the atom was written as a quoted string.
+    pub fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+
+    /// Parses the value as a floating-point number.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.value.parse().ok()
+    }
+
+    /// Parses the value as an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.value.parse().ok()
+    }
+
+    /// Parses the value as millimetres and converts to nm.
+    pub fn as_nm(&self) -> Option<i64> {
+        parse_mm(&self.value)
+    }
+
+    /// Replaces the value, keeping quoting unless the new value must be quoted.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let leading = std::mem::take(&mut self.leading);
+        *self = if self.quoted {
+            Self::quoted(value)
+        } else {
+            Self::symbol(value)
+        };
+        self.leading = leading;
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Parenthesised list; the first item is normally the node name.
+pub struct List {
+    leading: String,
+    items: Vec<Node>,
+    trailing: String,
+}
+
+impl List {
+    /// New list whose first item is the symbol `head`.
+    pub fn new(head: impl Into<String>) -> Self {
+        Self {
+            leading: String::new(),
+            items: vec![Node::Atom(Atom::symbol(head))],
+            trailing: String::new(),
+        }
+    }
+
+    /// Builder form of [`List::push`].
+    pub fn with(mut self, node: impl Into<Node>) -> Self {
+        self.push(node);
+        self
+    }
+
+    /// Node name: the first item when it is an unquoted symbol.
+    pub fn head(&self) -> Option<&str> {
+        match self.items.first() {
+            Some(Node::Atom(atom)) if !atom.quoted => Some(atom.value()),
+            _ => None,
+        }
+    }
+
+    /// All items, including the head.
+    pub fn items(&self) -> &[Node] {
+        &self.items
+    }
+
+    /// All items mutably, including the head.
+    pub fn items_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.items
+    }
+
+    /// Atom `index` positions after the head, skipping nested lists.
+    pub fn atom(&self, index: usize) -> Option<&Atom> {
+        self.items
+            .iter()
+            .skip(1)
+            .filter_map(Node::as_atom)
+            .nth(index)
+    }
+
+    /// Replaces the atom `index` positions after the head, skipping nested lists.
+    ///
+    /// Returns `false` when there is no atom at that position.
+    pub fn set_atom(&mut self, index: usize, value: impl Into<String>) -> bool {
+        let atom = self
+            .items
+            .iter_mut()
+            .skip(1)
+            .filter_map(|node| match node {
+                Node::Atom(atom) => Some(atom),
+                Node::List(_) => None,
+            })
+            .nth(index);
+        match atom {
+            Some(atom) => {
+                atom.set_value(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Nested lists in order.
+    pub fn lists(&self) -> impl Iterator<Item = &List> {
+        self.items.iter().filter_map(Node::as_list)
+    }
+
+    /// Nested lists in order, mutably.
+    pub fn lists_mut(&mut self) -> impl Iterator<Item = &mut List> {
+        self.items.iter_mut().filter_map(Node::as_list_mut)
+    }
+
+    /// First nested list named `head`.
+    pub fn child(&self, head: &str) -> Option<&List> {
+        self.lists().find(|list| list.head() == Some(head))
+    }
+
+    /// First nested list named `head`, mutably.
+    pub fn child_mut(&mut self, head: &str) -> Option<&mut List> {
+        self.lists_mut().find(|list| list.head() == Some(head))
+    }
+
+    /// All nested lists named `head`.
+    pub fn children<'a>(&'a self, head: &'a str) -> impl Iterator<Item = &'a List> + 'a {
+        self.lists().filter(move |list| list.head() == Some(head))
+    }
+
+    /// All nested lists named `head`, mutably.
+    pub fn children_mut<'a>(
+        &'a mut self,
+        head: &'a str,
+    ) -> impl Iterator<Item = &'a mut List> + 'a {
+        self.lists_mut()
+            .filter(move |list| list.head() == Some(head))
+    }
+
+    /// First atom of the child `(head value ...)`.
+    pub fn child_value(&self, head: &str) -> Option<&str> {
+        self.child(head)
+            .and_then(|child| child.atom(0))
+            .map(Atom::value)
+    }
+
+    /// Reads `(head x y)` in mm as a nm point.
+    pub fn child_point_nm(&self, head: &str) -> Option<(i64, i64)> {
+        let child = self.child(head)?;
+        Some((child.atom(0)?.as_nm()?, child.atom(1)?.as_nm()?))
+    }
+
+    /// Sets the atoms of `(head ...)`, appending the child if it is missing.
+    ///
+    /// Atoms already present beyond `values.len()` are kept; quoted atoms stay quoted.
+    pub fn set_child_atoms<I, S>(&mut self, head: &str, values: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if self.child(head).is_none() {
+            self.push(List::new(head));
+        }
+        let child = self
+            .child_mut(head)
+            .expect("child was appended when missing");
+        for (index, value) in values.into_iter().enumerate() {
+            let value = value.into();
+            if !child.set_atom(index, value.clone()) {
+                child.push(Atom::symbol(value));
+            }
+        }
+    }
+
+    /// Removes every direct child named `head` and returns how many were removed.
+    pub fn remove_children(&mut self, head: &str) -> usize {
+        let before = self.items.len();
+        self.items
+            .retain(|node| node.as_list().and_then(List::head) != Some(head));
+        before - self.items.len()
+    }
+
+    /// Appends a node, borrowing indentation from the existing children so
+    /// patched text keeps the surrounding layout.
+    pub fn push(&mut self, node: impl Into<Node>) {
+        let mut node = node.into();
+        if node.leading().is_empty() && !self.items.is_empty() {
+            let leading = match &node {
+                Node::List(_) => self
+                    .lists()
+                    .last()
+                    .map(|list| list.leading.clone())
+                    .filter(|leading| !leading.is_empty())
+                    .or_else(|| {
+                        self.trailing
+                            .contains('\n')
+                            .then(|| format!("{}\t", self.trailing))
+                    })
+                    .unwrap_or_else(|| " ".to_string()),
+                Node::Atom(_) => " ".to_string(),
+            };
+            node.set_leading(leading);
+        }
+        self.items.push(node);
+    }
+}
+
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(")?;
+        write_items(&self.items, f)?;
+        f.write_str(&self.trailing)?;
+        f.write_str(")")
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Parsed text: a sequence of top-level nodes plus trailing whitespace.
+pub struct Document {
+    /// Top-level nodes in source order.
+    pub nodes: Vec<Node>,
+    trailing: String,
+}
+
+impl Document {
+    /// Top-level lists in source order.
+    pub fn lists(&self) -> impl Iterator<Item = &List> {
+        self.nodes.iter().filter_map(Node::as_list)
+    }
+
+    /// Top-level lists in source order, mutably.
+    pub fn lists_mut(&mut self) -> impl Iterator<Item = &mut List> {
+        self.nodes.iter_mut().filter_map(Node::as_list_mut)
+    }
+
+    /// Re-formats every top-level node in KiCad's tab-indented layout.
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        for node in &self.nodes {
+            write_pretty(node, 0, &mut out);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_items(&self.nodes, f)?;
+        f.write_str(&self.trailing)
+    }
+}
+
+impl std::str::FromStr for Document {
+    type Err = KiCadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+/// Parses KiCad s-expression text, keeping all whitespace so that
+/// `parse(text)?.to_string() == text`.
+pub fn parse(text: &str) -> Result<Document, KiCadError> {
+    let mut parser = Parser { text, pos: 0 };
+    let mut nodes = Vec::new();
+    loop {
+        let leading = parser.whitespace();
+        match parser.peek() {
+            None => {
+                return Ok(Document {
+                    nodes,
+                    trailing: leading,
+                })
+            }
+            Some(')') => return Err(parser.error_at(parser.pos, "unexpected `)`")),
+            Some(_) => {
+                let mut node = parser.node()?;
+                node.set_leading(leading);
+                nodes.push(node);
+            }
+        }
+    }
+}
+
+/// Formats a nm value as KiCad writes millimetres: no exponent, no trailing zeros.
+pub fn format_mm(value_nm: i64) -> String {
+    let sign = if value_nm < 0 { "-" } else { "" };
+    let abs = value_nm.unsigned_abs();
+    let whole = abs / 1_000_000;
+    let frac = abs % 1_000_000;
+    if frac == 0 {
+        format!("{sign}{whole}")
+    } else {
+        let frac = format!("{frac:06}");
+        format!("{sign}{whole}.{}", frac.trim_end_matches('0'))
+    }
+}
+
+/// Parses a millimetre value into nm, rounding to the nearest nm.
+pub fn parse_mm(value: &str) -> Option<i64> {
+    let mm: f64 = value.parse().ok()?;
+    mm.is_finite().then(|| (mm * 1_000_000.0).round() as i64)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn whitespace(&mut self) -> String {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|ch: char| !ch.is_whitespace())
+            .unwrap_or(rest.len());
+        self.pos += len;
+        rest[..len].to_string()
+    }
+
+    fn node(&mut self) -> Result<Node, KiCadError> {
+        match self.peek() {
+            Some('(') => self.list().map(Node::List),
+            Some('"') => self.quoted().map(Node::Atom),
+            _ => Ok(Node::Atom(self.symbol())),
+        }
+    }
+
+    fn list(&mut self) -> Result<List, KiCadError> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            let leading = self.whitespace();
+            match self.peek() {
+                None => return Err(self.error_at(open, "unclosed `(`")),
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(List {
+                        leading: String::new(),
+                        items,
+                        trailing: leading,
+                    });
+                }
+                Some(_) => {
+                    let mut node = self.node()?;
+                    node.set_leading(leading);
+                    items.push(node);
+                }
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<Atom, KiCadError> {
+        let start = self.pos;
+        let mut value = String::new();
+        let mut chars = self.text[start + 1..].char_indices();
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.pos = start + 1 + offset + 1;
+                    return Ok(Atom {
+                        leading: String::new(),
+                        value,
+                        quoted: true,
+                        raw: self.text[start..self.pos].to_string(),
+                    });
+                }
+                '\\' => match chars.next().map(|(_, escaped)| escaped) {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                    Some(other) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => break,
+                },
+                other => value.push(other),
+            }
+        }
+        Err(self.error_at(start, "unterminated string"))
+    }
+
+    fn symbol(&mut self) -> Atom {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '(' | ')' | '"'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        let value = rest[..len].to_string();
+        Atom {
+            leading: String::new(),
+            raw: value.clone(),
+            value,
+            quoted: false,
+        }
+    }
+
+    fn error_at(&self, pos: usize, reason: &str) -> KiCadError {
+        let before = &self.text[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |newline| &before[newline + 1..])
+            .chars()
+            .count()
+            + 1;
+        KiCadError::SExpression {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+fn needs_quotes(value: &str) -> bool {
+    value.is_empty()
+        || value
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '(' | ')' | '"' | '\\'))
+}
+
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_items(items: &[Node], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut previous_atom = false;
+    for node in items {
+        let is_atom = matches!(node, Node::Atom(_));
+        if node.leading().is_empty() && previous_atom && is_atom {
+            f.write_str(" ")?;
+        }
+        f.write_str(node.leading())?;
+        write!(f, "{node}")?;
+        previous_atom = is_atom;
+    }
+    Ok(())
+}
+
+fn write_pretty(node: &Node, depth: usize, out: &mut String) {
+    let list = match node {
+        Node::Atom(atom) => {
+            out.push_str(atom.raw());
+            return;
+        }
+        Node::List(list) => list,
+    };
+
+    out.push('(');
+    let nested = list.items.iter().any(|item| item.as_list().is_some());
+    let mut broke = false;
+    for (index, item) in list.items.iter().enumerate() {
+        if nested && (broke || item.as_list().is_some()) {
+            broke = true;
+            out.push('\n');
+            push_tabs(out, depth + 1);
+        } else if index > 0 {
+            out.push(' ');
+        }
+        write_pretty(item, depth + 1, out);
+    }
+    if broke {
+        out.push('\n');
+        push_tabs(out, depth);
+    }
+    out.push(')');
+}
+
+fn push_tabs(out: &mut String, depth: usize) {
+    out.extend(std::iter::repeat_n('\t', depth));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "(kicad_pcb (version 20240108)\n\t(segment (start 1 2.5) (end 3 4)\n\t\t(width 0.25) (layer \"F.Cu\") (net 1) (uuid \"a-b\"))\n\t(unknown_node x\t\"q \\\" s\")\n)\n";
+
+    #[test]
+    fn parse_round_trips_source_text() {
+        let document = parse(SAMPLE).expect("sample should parse");
+        assert_eq!(document.to_string(), SAMPLE);
+
+        let board = document.lists().next().expect("root list");
+        assert_eq!(board.head(), Some("kicad_pcb"));
+        let segment = board.child("segment").expect("segment");
+        assert_eq!(segment.child_value("layer"), Some("F.Cu"));
+        assert_eq!(
+            segment.child_point_nm("start"),
+            Some((1_000_000, 2_500_000))
+        );
+        let unknown = board.child("unknown_node").expect("unknown node kept");
+        assert_eq!(unknown.atom(1).map(Atom::value), Some("q \" s"));
+    }
+
+    #[test]
+    fn edits_keep_untouched_text_and_indentation() {
+        let mut document = parse(SAMPLE).expect("sample should parse");
+        let board = document.lists_mut().next().expect("root list");
+        let segment = board.child_mut("segment").expect("segment");
+        segment.set_child_atoms("width", ["0.5"]);
+        segment.set_child_atoms("layer", ["B.Cu"]);
+        board.push(List::new("gr_line").with(List::new("start").with(Atom::mm(-1_500_000))));
+
+        assert_eq!(
+            document.to_string(),
+            SAMPLE
+                .replace("(width 0.25)", "(width 0.5)")
+                .replace("\"F.Cu\"", "\"B.Cu\"")
+                .replace("\n)\n", "\n\t(gr_line (start -1.5))\n)\n")
+        );
+    }
+
+    #[test]
+    fn pretty_printer_uses_kicad_layout() {
+        let document = parse("(segment (start 1 2) (end 3 4) (net 1))").expect("parse");
+        assert_eq!(
+            document.to_pretty_string(),
+            "(segment\n\t(start 1 2)\n\t(end 3 4)\n\t(net 1)\n)\n"
+        );
+    }
+
+    #[test]
+    fn parse_reports_position_of_errors() {
+        let err = parse("(a\n  (b \"open)").expect_err("unterminated string");
+        match err {
+            KiCadError::SExpression { line, column, .. } => assert_eq!((line, column), (2, 6)),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(parse("(a))").is_err());
+        assert_eq!(format_mm(-1_250_000), "-1.25");
+        assert_eq!(parse_mm("0.0001"), Some(100));
+    }
+}
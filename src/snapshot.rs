@@ -0,0 +1,508 @@
+//! Whole-board snapshots with lookup indexes and item-level diffs.
+//!
+//! [`BoardSnapshot::capture`] gathers items, nets, layers, stackup, net
+//! classes, origins, and the title block in one pass so analyses read from a
+//! single consistent copy. [`BoardSnapshot::diff`] compares two captures by
+//! KIID, which is how a script can show what it changed before committing.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::model::board::{
+    BoardEnabledLayers, BoardLayerInfo, BoardNet, BoardOriginKind, BoardStackup,
+    NetClassForNetEntry, NetClassInfo, PcbItem, PolygonWithHolesNm, Vector2Nm,
+};
+use crate::model::common::{PcbObjectTypeCode, TitleBlockInfo};
+use crate::text_out::write_to_string;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// One capture of the open board, indexed by KIID, net, layer, and object type.
+pub struct BoardSnapshot {
+    items: Vec<PcbItem>,
+    type_codes: Vec<i32>,
+    by_id: BTreeMap<String, usize>,
+    by_net: BTreeMap<i32, Vec<usize>>,
+    by_layer: BTreeMap<i32, Vec<usize>>,
+    by_type: BTreeMap<i32, Vec<usize>>,
+    /// Nets on the board.
+    pub nets: Vec<BoardNet>,
+    /// Enabled layer set.
+    pub enabled_layers: BoardEnabledLayers,
+    /// Physical stackup.
+    pub stackup: BoardStackup,
+    /// Net class definitions.
+    pub net_classes: Vec<NetClassInfo>,
+    /// Effective net class of each net.
+    pub net_class_assignments: Vec<NetClassForNetEntry>,
+    /// Grid origin.
+    pub grid_origin_nm: Vector2Nm,
+    /// Drill/place origin.
+    pub drill_origin_nm: Vector2Nm,
+    /// Title block fields.
+    pub title_block: TitleBlockInfo,
+}
+
+impl BoardSnapshot {
+    /// Indexes `rows` as returned by [`KiCadClient::get_all_pcb_items`]; board-level
+    /// fields start empty.
+    pub fn from_items(rows: Vec<(PcbObjectTypeCode, Vec<PcbItem>)>) -> Self {
+        let mut snapshot = Self::default();
+        for (object_type, items) in rows {
+            for item in items {
+                snapshot.insert(object_type.code, item);
+            }
+        }
+        snapshot
+    }
+
+    /// Captures every item plus board-level data from the open board.
+    pub async fn capture(client: &KiCadClient) -> Result<Self, KiCadError> {
+        let mut snapshot = Self::from_items(client.get_all_pcb_items().await?);
+        snapshot.nets = client.get_nets().await?;
+        snapshot.enabled_layers = client.get_board_enabled_layers().await?;
+        snapshot.stackup = client.get_board_stackup().await?;
+        snapshot.net_classes = client.get_net_classes().await?;
+        if !snapshot.nets.is_empty() {
+            snapshot.net_class_assignments =
+                client.get_netclass_for_nets(snapshot.nets.clone()).await?;
+        }
+        snapshot.grid_origin_nm = client.get_board_origin(BoardOriginKind::Grid).await?;
+        snapshot.drill_origin_nm = client.get_board_origin(BoardOriginKind::Drill).await?;
+        snapshot.title_block = client.get_title_block_info().await?;
+        Ok(snapshot)
+    }
+
+    fn insert(&mut self, type_code: i32, item: PcbItem) {
+        let index = self.items.len();
+        if let Some(id) = item.id() {
+            self.by_id.entry(id.to_string()).or_insert(index);
+        }
+        if let Some(net) = item.net() {
+            self.by_net.entry(net.code).or_default().push(index);
+        }
+        let mut layer_ids = item.layer_ids();
+        layer_ids.dedup();
+        for layer_id in layer_ids {
+            self.by_layer.entry(layer_id).or_default().push(index);
+        }
+        self.by_type.entry(type_code).or_default().push(index);
+        self.items.push(item);
+        self.type_codes.push(type_code);
+    }
+
+    /// All items in capture order.
+    pub fn items(&self) -> &[PcbItem] {
+        &self.items
+    }
+
+    /// Item with KIID `id`.
+    pub fn item(&self, id: &str) -> Option<&PcbItem> {
+        self.by_id.get(id).map(|&index| &self.items[index])
+    }
+
+    /// Object type code the item with KIID `id` was captured under.
+    pub fn type_code(&self, id: &str) -> Option<i32> {
+        self.by_id.get(id).map(|&index| self.type_codes[index])
+    }
+
+    /// Items on net `net_code`.
+    pub fn items_on_net(&self, net_code: i32) -> impl Iterator<Item = &PcbItem> {
+        self.indexed(self.by_net.get(&net_code))
+    }
+
+    /// Items on layer `layer_id`.
+    pub fn items_on_layer(&self, layer_id: i32) -> impl Iterator<Item = &PcbItem> {
+        self.indexed(self.by_layer.get(&layer_id))
+    }
+
+    /// Items captured under object type `type_code` (a `KiCadObjectType` value).
+    pub fn items_of_type(&self, type_code: i32) -> impl Iterator<Item = &PcbItem> {
+        self.indexed(self.by_type.get(&type_code))
+    }
+
+    /// Net named `name`.
+    pub fn net_by_name(&self, name: &str) -> Option<&BoardNet> {
+        self.nets.iter().find(|net| net.name == name)
+    }
+
+    fn indexed<'a>(&'a self, indexes: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a PcbItem> {
+        indexes
+            .into_iter()
+            .flatten()
+            .map(move |&index| &self.items[index])
+    }
+
+    /// Changes from `self` (before) to `other` (after), matched by KIID.
+    ///
+    /// Items without a KIID (fields, undecoded payloads) are not compared.
+    pub fn diff(&self, other: &BoardSnapshot) -> BoardDiff {
+        let mut diff = BoardDiff::default();
+        for (id, &index) in &self.by_id {
+            let before = &self.items[index];
+            match other.item(id) {
+                None => diff.removed.push(before.clone()),
+                Some(after) if after != before => diff.modified.push(ItemModification {
+                    id: id.clone(),
+                    changes: field_changes(before, after),
+                    before: before.clone(),
+                    after: after.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for (id, &index) in &other.by_id {
+            if !self.by_id.contains_key(id) {
+                diff.added.push(other.items[index].clone());
+            }
+        }
+        diff
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One changed field of a modified item, rendered for display.
+pub struct FieldChange {
+    /// Field name on the typed item, e.g. `position_nm`.
+    pub field: &'static str,
+    /// Value before the change.
+    pub before: String,
+    /// Value after the change.
+    pub after: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// An item present in both snapshots whose contents differ.
+pub struct ItemModification {
+    /// Item KIID.
+    pub id: String,
+    /// Item in the earlier snapshot.
+    pub before: PcbItem,
+    /// Item in the later snapshot.
+    pub after: PcbItem,
+    /// Changed fields in declaration order.
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Result of [`BoardSnapshot::diff`], sorted by KIID within each list.
+pub struct BoardDiff {
+    /// Items only in the later snapshot.
+    pub added: Vec<PcbItem>,
+    /// Items only in the earlier snapshot.
+    pub removed: Vec<PcbItem>,
+    /// Items in both snapshots with different contents.
+    pub modified: Vec<ItemModification>,
+}
+
+impl BoardDiff {
+    /// True when the snapshots hold the same items.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Writes a review summary: `+` added, `-` removed, `~` modified with one
+    /// indented line per changed field.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(
+            out,
+            "added={} removed={} modified={}",
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len()
+        )?;
+        for item in &self.added {
+            writeln!(out, "+ {}", describe_item(item))?;
+        }
+        for item in &self.removed {
+            writeln!(out, "- {}", describe_item(item))?;
+        }
+        for modification in &self.modified {
+            writeln!(out, "~ {}", describe_item(&modification.after))?;
+            for change in &modification.changes {
+                writeln!(
+                    out,
+                    "    {}: {} -> {}",
+                    change.field, change.before, change.after
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// [`BoardDiff::write`] into a `String`.
+    pub fn to_text(&self) -> String {
+        write_to_string(|out| self.write(out))
+    }
+}
+
+fn describe_item(item: &PcbItem) -> String {
+    let mut text = format!("{} {}", item.kind_name(), item.id().unwrap_or("-"));
+    if let PcbItem::Footprint(footprint) = item {
+        if let Some(reference) = &footprint.reference {
+            text.push_str(&format!(" ({reference})"));
+        }
+    }
+    text
+}
+
+/// Display form used for field values in a diff.
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: Describe> Describe for Option<T> {
+    fn describe(&self) -> String {
+        match self {
+            Some(value) => value.describe(),
+            None => "none".to_string(),
+        }
+    }
+}
+
+impl<T: Describe> Describe for Vec<T> {
+    fn describe(&self) -> String {
+        let parts: Vec<String> = self.iter().map(Describe::describe).collect();
+        format!("[{}]", parts.join(", "))
+    }
+}
+
+impl Describe for Vector2Nm {
+    fn describe(&self) -> String {
+        format!("({}, {})", self.x_nm, self.y_nm)
+    }
+}
+
+impl Describe for BoardLayerInfo {
+    fn describe(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl Describe for BoardNet {
+    fn describe(&self) -> String {
+        format!("{} ({})", self.name, self.code)
+    }
+}
+
+impl Describe for String {
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl Describe for PolygonWithHolesNm {
+    fn describe(&self) -> String {
+        format!("polygon with {} holes", self.holes.len())
+    }
+}
+
+impl Describe for PcbItem {
+    fn describe(&self) -> String {
+        describe_item(self)
+    }
+}
+
+macro_rules! describe_with_display {
+    ($($ty:ty),+ $(,)?) => {
+        $(impl Describe for $ty {
+            fn describe(&self) -> String {
+                self.to_string()
+            }
+        })+
+    };
+}
+
+macro_rules! describe_with_debug {
+    ($($ty:ty),+ $(,)?) => {
+        $(impl Describe for $ty {
+            fn describe(&self) -> String {
+                format!("{self:?}")
+            }
+        })+
+    };
+}
+
+describe_with_display!(bool, i64, f64, usize);
+describe_with_debug!(
+    crate::model::board::PcbViaType,
+    crate::model::board::PcbViaLayers,
+    crate::model::board::PcbPadType,
+    crate::model::board::PcbPadStack,
+    crate::model::board::PcbFootprintAttributes,
+    crate::model::board::PcbZoneType,
+    crate::model::board::PcbZoneFilledPolygons,
+    crate::model::common::TextShapeGeometry,
+    crate::model::common::TextSpec,
+    crate::model::common::TextBoxSpec,
+);
+
+macro_rules! changed_fields {
+    ($before:expr, $after:expr, [$($field:ident),+ $(,)?]) => {{
+        let mut changes = Vec::new();
+        $(
+            if $before.$field != $after.$field {
+                changes.push(FieldChange {
+                    field: stringify!($field),
+                    before: $before.$field.describe(),
+                    after: $after.$field.describe(),
+                });
+            }
+        )+
+        changes
+    }};
+}
+
+fn field_changes(before: &PcbItem, after: &PcbItem) -> Vec<FieldChange> {
+    match (before, after) {
+        (PcbItem::Track(a), PcbItem::Track(b)) => {
+            changed_fields!(a, b, [start_nm, end_nm, width_nm, layer, net])
+        }
+        (PcbItem::Arc(a), PcbItem::Arc(b)) => {
+            changed_fields!(a, b, [start_nm, mid_nm, end_nm, width_nm, layer, net])
+        }
+        (PcbItem::Via(a), PcbItem::Via(b)) => changed_fields!(
+            a,
+            b,
+            [
+                position_nm,
+                via_type,
+                layers,
+                net,
+                drill_diameter_nm,
+                pad_diameter_nm
+            ]
+        ),
+        (PcbItem::Footprint(a), PcbItem::Footprint(b)) => changed_fields!(
+            a,
+            b,
+            [
+                reference,
+                position_nm,
+                orientation_deg,
+                layer,
+                pad_count,
+                value,
+                library_id,
+                datasheet,
+                attributes,
+                items
+            ]
+        ),
+        (PcbItem::Pad(a), PcbItem::Pad(b)) => {
+            changed_fields!(a, b, [number, pad_type, position_nm, net, pad_stack])
+        }
+        (PcbItem::BoardGraphicShape(a), PcbItem::BoardGraphicShape(b)) => changed_fields!(
+            a,
+            b,
+            [layer, net, geometry_kind, geometry, stroke_width_nm, filled]
+        ),
+        (PcbItem::BoardText(a), PcbItem::BoardText(b)) => {
+            changed_fields!(a, b, [layer, text, spec])
+        }
+        (PcbItem::BoardTextBox(a), PcbItem::BoardTextBox(b)) => {
+            changed_fields!(a, b, [layer, text, spec])
+        }
+        (PcbItem::Zone(a), PcbItem::Zone(b)) => changed_fields!(
+            a,
+            b,
+            [
+                name,
+                zone_type,
+                filled,
+                layers,
+                net,
+                outline,
+                filled_polygons
+            ]
+        ),
+        (PcbItem::Dimension(a), PcbItem::Dimension(b)) => {
+            changed_fields!(a, b, [layer, text, style_kind])
+        }
+        (PcbItem::Group(a), PcbItem::Group(b)) => changed_fields!(a, b, [name, item_count]),
+        _ => vec![FieldChange {
+            field: "kind",
+            before: before.kind_name().to_string(),
+            after: after.kind_name().to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::board::PcbTrack;
+
+    fn track(id: &str, x_nm: i64, net_code: i32) -> PcbItem {
+        PcbItem::Track(PcbTrack {
+            id: Some(id.to_string()),
+            start_nm: Some(Vector2Nm { x_nm, y_nm: 0 }),
+            end_nm: Some(Vector2Nm {
+                x_nm: x_nm + 1_000_000,
+                y_nm: 0,
+            }),
+            width_nm: Some(250_000),
+            layer: BoardLayerInfo {
+                id: 3,
+                name: "F.Cu".to_string(),
+            },
+            net: Some(BoardNet {
+                code: net_code,
+                name: format!("N{net_code}"),
+            }),
+        })
+    }
+
+    const TRACKS: PcbObjectTypeCode = PcbObjectTypeCode {
+        code: 11,
+        name: "KOT_PCB_TRACE",
+    };
+
+    #[test]
+    fn snapshot_indexes_items_by_id_net_layer_and_type() {
+        let snapshot = BoardSnapshot::from_items(vec![(
+            TRACKS,
+            vec![track("a", 0, 1), track("b", 0, 2), track("c", 0, 1)],
+        )]);
+
+        assert!(matches!(snapshot.item("b"), Some(PcbItem::Track(_))));
+        assert_eq!(snapshot.type_code("c"), Some(11));
+        assert_eq!(snapshot.items_on_net(1).count(), 2);
+        assert_eq!(snapshot.items_on_layer(3).count(), 3);
+        assert_eq!(snapshot.items_of_type(11).count(), 3);
+        assert_eq!(snapshot.items_on_net(9).count(), 0);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_fields() {
+        let before =
+            BoardSnapshot::from_items(vec![(TRACKS, vec![track("a", 0, 1), track("b", 0, 1)])]);
+        let after = BoardSnapshot::from_items(vec![(
+            TRACKS,
+            vec![track("a", 500_000, 2), track("c", 0, 1)],
+        )]);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.modified.len(), 1);
+        let fields: Vec<&str> = diff.modified[0]
+            .changes
+            .iter()
+            .map(|change| change.field)
+            .collect();
+        assert_eq!(fields, vec!["start_nm", "end_nm", "net"]);
+        assert_eq!(
+            diff.to_text(),
+            "added=1 removed=1 modified=1\n\
+             + track c\n\
+             - track b\n\
+             ~ track a\n    \
+             start_nm: (0, 0) -> (500000, 0)\n    \
+             end_nm: (1000000, 0) -> (1500000, 0)\n    \
+             net: N1 (1) -> N2 (2)\n"
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -0,0 +1,552 @@
+//! R-tree spatial queries over board items.
+//!
+//! [`SpatialIndex`] is a static, bulk-loaded (sort-tile-recursive) R-tree of
+//! axis-aligned item bounds. Bounds come either from local geometry
+//! ([`SpatialIndex::from_snapshot`]) or from KiCad's own
+//! `get_item_bounding_boxes` ([`SpatialIndex::capture`]). Queries work on
+//! bounds only, so results are a superset of the exact answer;
+//! [`SpatialIndex::hit_test`] narrows candidates with `hit_test_item`.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::geometry::{self, Shape};
+use crate::model::board::{PcbItem, Vector2Nm};
+use crate::model::common::{ItemBoundingBox, ItemHitTestResult};
+use crate::snapshot::BoardSnapshot;
+
+/// Maximum children per R-tree node.
+const NODE_CAPACITY: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Axis-aligned rectangle in nm, inclusive on all edges.
+pub struct BoundsNm {
+    /// Lower-left corner (minimum x and y).
+    pub min: Vector2Nm,
+    /// Upper-right corner (maximum x and y).
+    pub max: Vector2Nm,
+}
+
+impl BoundsNm {
+    /// Rectangle spanning two opposite corners in any order.
+    pub fn new(a: Vector2Nm, b: Vector2Nm) -> Self {
+        Self {
+            min: Vector2Nm {
+                x_nm: a.x_nm.min(b.x_nm),
+                y_nm: a.y_nm.min(b.y_nm),
+            },
+            max: Vector2Nm {
+                x_nm: a.x_nm.max(b.x_nm),
+                y_nm: a.y_nm.max(b.y_nm),
+            },
+        }
+    }
+
+    /// Smallest rectangle holding all `points`.
+    pub fn from_points<I: IntoIterator<Item = Vector2Nm>>(points: I) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|point| Self::new(point, point))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Smallest rectangle holding both.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            Vector2Nm {
+                x_nm: self.min.x_nm.min(other.min.x_nm),
+                y_nm: self.min.y_nm.min(other.min.y_nm),
+            },
+            Vector2Nm {
+                x_nm: self.max.x_nm.max(other.max.x_nm),
+                y_nm: self.max.y_nm.max(other.max.y_nm),
+            },
+        )
+    }
+
+    /// Grows every edge outward by `margin_nm`.
+    pub fn expanded(&self, margin_nm: i64) -> Self {
+        Self::new(
+            Vector2Nm {
+                x_nm: self.min.x_nm - margin_nm,
+                y_nm: self.min.y_nm - margin_nm,
+            },
+            Vector2Nm {
+                x_nm: self.max.x_nm + margin_nm,
+                y_nm: self.max.y_nm + margin_nm,
+            },
+        )
+    }
+
+    /// True when the rectangles share any point.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x_nm <= other.max.x_nm
+            && other.min.x_nm <= self.max.x_nm
+            && self.min.y_nm <= other.max.y_nm
+            && other.min.y_nm <= self.max.y_nm
+    }
+
+    /// Distance from `point` to the rectangle; `0.0` inside.
+    pub fn distance_to(&self, point: Vector2Nm) -> f64 {
+        (self.distance_squared(point) as f64).sqrt()
+    }
+
+    fn distance_squared(&self, point: Vector2Nm) -> u128 {
+        let dx = axis_gap(point.x_nm, self.min.x_nm, self.max.x_nm);
+        let dy = axis_gap(point.y_nm, self.min.y_nm, self.max.y_nm);
+        dx * dx + dy * dy
+    }
+
+    fn center(&self) -> (i64, i64) {
+        (
+            self.min.x_nm / 2 + self.max.x_nm / 2,
+            self.min.y_nm / 2 + self.max.y_nm / 2,
+        )
+    }
+}
+
+impl From<&ItemBoundingBox> for BoundsNm {
+    fn from(value: &ItemBoundingBox) -> Self {
+        Self::new(
+            Vector2Nm {
+                x_nm: value.x_nm,
+                y_nm: value.y_nm,
+            },
+            Vector2Nm {
+                x_nm: value.x_nm + value.width_nm,
+                y_nm: value.y_nm + value.height_nm,
+            },
+        )
+    }
+}
+
+fn axis_gap(value: i64, min: i64, max: i64) -> u128 {
+    if value < min {
+        (min - value) as u128
+    } else if value > max {
+        (value - max) as u128
+    } else {
+        0
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One indexed item.
+pub struct SpatialEntry {
+    /// Item KIID.
+    pub id: String,
+    /// Layers the item is on; see [`PcbItem::layer_ids`].
+    pub layer_ids: Vec<i32>,
+    /// Axis-aligned bounds.
+    pub bounds: BoundsNm,
+}
+
+impl SpatialEntry {
+    fn on_layer(&self, layer_id: Option<i32>) -> bool {
+        layer_id.is_none_or(|layer_id| self.layer_ids.contains(&layer_id))
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Children {
+    Entries(Vec<usize>),
+    Nodes(Vec<Node>),
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bounds: BoundsNm,
+    children: Children,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Static R-tree over item bounds.
+pub struct SpatialIndex {
+    entries: Vec<SpatialEntry>,
+    root: Option<Node>,
+}
+
+impl SpatialIndex {
+    /// Bulk-loads `entries`.
+    pub fn new(entries: Vec<SpatialEntry>) -> Self {
+        let leaves = pack(
+            (0..entries.len()).collect(),
+            |&index| entries[index].bounds,
+            |indexes| Node {
+                bounds: union_all(indexes.iter().map(|&index| entries[index].bounds)),
+                children: Children::Entries(indexes),
+            },
+        );
+        let mut level = leaves;
+        while level.len() > 1 {
+            level = pack(
+                level,
+                |node| node.bounds,
+                |nodes| Node {
+                    bounds: union_all(nodes.iter().map(|node| node.bounds)),
+                    children: Children::Nodes(nodes),
+                },
+            );
+        }
+        Self {
+            root: level.pop(),
+            entries,
+        }
+    }
+
+    /// Indexes snapshot items with bounds computed from their decoded geometry.
+    ///
+    /// Text, dimensions, and groups have no local geometry and are skipped; use
+    /// [`SpatialIndex::capture`] to include them.
+    pub fn from_snapshot(snapshot: &BoardSnapshot) -> Self {
+        let entries = snapshot
+            .items()
+            .iter()
+            .filter_map(|item| {
+                Some(SpatialEntry {
+                    id: item.id()?.to_string(),
+                    layer_ids: item.layer_ids(),
+                    bounds: item_bounds(item)?,
+                })
+            })
+            .collect();
+        Self::new(entries)
+    }
+
+    /// Indexes KiCad-reported bounding boxes, taking layers from `snapshot`.
+    pub fn from_bounding_boxes(snapshot: &BoardSnapshot, boxes: &[ItemBoundingBox]) -> Self {
+        let entries = boxes
+            .iter()
+            .map(|bbox| SpatialEntry {
+                id: bbox.item_id.clone(),
+                layer_ids: snapshot
+                    .item(&bbox.item_id)
+                    .map(PcbItem::layer_ids)
+                    .unwrap_or_default(),
+                bounds: BoundsNm::from(bbox),
+            })
+            .collect();
+        Self::new(entries)
+    }
+
+    /// Fetches KiCad's bounding boxes for every snapshot item with a KIID and indexes them.
+    pub async fn capture(
+        client: &KiCadClient,
+        snapshot: &BoardSnapshot,
+    ) -> Result<Self, KiCadError> {
+        let ids = snapshot
+            .items()
+            .iter()
+            .filter_map(PcbItem::id)
+            .map(str::to_string)
+            .collect();
+        let boxes = client.get_item_bounding_boxes(ids, false).await?;
+        Ok(Self::from_bounding_boxes(snapshot, &boxes))
+    }
+
+    /// Number of indexed items.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True when nothing is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries whose bounds intersect `area`.
+    pub fn intersecting(&self, area: &BoundsNm) -> Vec<&SpatialEntry> {
+        self.collect(|bounds| bounds.intersects(area))
+    }
+
+    /// Entries on `layer_id` (or any layer) whose bounds lie within `distance_nm` of `point`.
+    pub fn within_distance(
+        &self,
+        point: Vector2Nm,
+        distance_nm: i64,
+        layer_id: Option<i32>,
+    ) -> Vec<&SpatialEntry> {
+        let limit = (distance_nm.max(0) as u128).pow(2);
+        self.collect(|bounds| bounds.distance_squared(point) <= limit)
+            .into_iter()
+            .filter(|entry| entry.on_layer(layer_id))
+            .collect()
+    }
+
+    /// Entry on `layer_id` (or any layer) whose bounds are closest to `point`,
+    /// with that distance in nm.
+    pub fn nearest(&self, point: Vector2Nm, layer_id: Option<i32>) -> Option<(&SpatialEntry, f64)> {
+        #[derive(Clone, Copy)]
+        enum Candidate<'a> {
+            Node(&'a Node),
+            Entry(usize),
+        }
+
+        let root = self.root.as_ref()?;
+        let mut candidates = vec![Candidate::Node(root)];
+        let mut heap = BinaryHeap::from([Reverse((root.bounds.distance_squared(point), 0))]);
+        while let Some(Reverse((distance, slot))) = heap.pop() {
+            let next: Vec<(u128, Candidate)> = match candidates[slot] {
+                Candidate::Entry(index) => {
+                    return Some((&self.entries[index], (distance as f64).sqrt()));
+                }
+                Candidate::Node(node) => match &node.children {
+                    Children::Entries(indexes) => indexes
+                        .iter()
+                        .filter(|&&index| self.entries[index].on_layer(layer_id))
+                        .map(|&index| {
+                            (
+                                self.entries[index].bounds.distance_squared(point),
+                                Candidate::Entry(index),
+                            )
+                        })
+                        .collect(),
+                    Children::Nodes(nodes) => nodes
+                        .iter()
+                        .map(|child| (child.bounds.distance_squared(point), Candidate::Node(child)))
+                        .collect(),
+                },
+            };
+            for (distance, candidate) in next {
+                heap.push(Reverse((distance, candidates.len())));
+                candidates.push(candidate);
+            }
+        }
+        None
+    }
+
+    /// Items KiCad reports as hit at `point`: bounds candidates within
+    /// `tolerance_nm`, confirmed one by one with `hit_test_item`.
+    pub async fn hit_test(
+        &self,
+        client: &KiCadClient,
+        point: Vector2Nm,
+        tolerance_nm: i32,
+        layer_id: Option<i32>,
+    ) -> Result<Vec<String>, KiCadError> {
+        let mut hits = Vec::new();
+        for entry in self.within_distance(point, i64::from(tolerance_nm), layer_id) {
+            let result = client
+                .hit_test_item(entry.id.clone(), point, tolerance_nm)
+                .await?;
+            if result == ItemHitTestResult::Hit {
+                hits.push(entry.id.clone());
+            }
+        }
+        Ok(hits)
+    }
+
+    fn collect(&self, accept: impl Fn(&BoundsNm) -> bool) -> Vec<&SpatialEntry> {
+        let mut out = Vec::new();
+        let mut stack: Vec<&Node> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            if !accept(&node.bounds) {
+                continue;
+            }
+            match &node.children {
+                Children::Entries(indexes) => out.extend(
+                    indexes
+                        .iter()
+                        .map(|&index| &self.entries[index])
+                        .filter(|entry| accept(&entry.bounds)),
+                ),
+                Children::Nodes(nodes) => stack.extend(nodes),
+            }
+        }
+        out
+    }
+}
+
+/// Sort-tile-recursive packing of one tree level.
+fn pack<T>(
+    mut values: Vec<T>,
+    bounds: impl Fn(&T) -> BoundsNm,
+    make: impl Fn(Vec<T>) -> Node,
+) -> Vec<Node> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let node_count = values.len().div_ceil(NODE_CAPACITY);
+    let slab_count = (node_count as f64).sqrt().ceil() as usize;
+    let slab_size = slab_count * NODE_CAPACITY;
+
+    values.sort_by_key(|value| bounds(value).center().0);
+    let mut nodes = Vec::with_capacity(node_count);
+    while !values.is_empty() {
+        let rest = values.split_off(slab_size.min(values.len()));
+        let mut slab = std::mem::replace(&mut values, rest);
+        slab.sort_by_key(|value| bounds(value).center().1);
+        while !slab.is_empty() {
+            let rest = slab.split_off(NODE_CAPACITY.min(slab.len()));
+            nodes.push(make(std::mem::replace(&mut slab, rest)));
+        }
+    }
+    nodes
+}
+
+fn union_all(mut bounds: impl Iterator<Item = BoundsNm>) -> BoundsNm {
+    let first = bounds.next().expect("R-tree nodes are never empty");
+    bounds.fold(first, |acc, next| acc.union(&next))
+}
+
+/// Local axis-aligned bounds of an item, including stroke and pad extents.
+pub fn item_bounds(item: &PcbItem) -> Option<BoundsNm> {
+    match item {
+        PcbItem::Track(track) => shape_bounds(&Shape::segment(
+            track.start_nm?,
+            track.end_nm?,
+            track.width_nm.unwrap_or(0),
+        )),
+        PcbItem::Arc(arc) => shape_bounds(&Shape {
+            points: geometry::arc_points(arc.start_nm?, arc.mid_nm?, arc.end_nm?),
+            closed: false,
+            filled: false,
+            half_width_nm: arc.width_nm.unwrap_or(0) as f64 / 2.0,
+        }),
+        PcbItem::Via(via) => {
+            let diameter = via.pad_diameter_nm.or(via.drill_diameter_nm).unwrap_or(0);
+            shape_bounds(&Shape::point(via.position_nm?, diameter as f64 / 2.0))
+        }
+        PcbItem::Pad(pad) => {
+            let position = pad.position_nm?;
+            let shapes: Vec<Shape> = pad
+                .pad_stack
+                .iter()
+                .flat_map(|stack| {
+                    stack.copper_layers.iter().filter_map(|copper| {
+                        geometry::pad_shape(position, copper, stack.angle_deg.unwrap_or(0.0))
+                    })
+                })
+                .collect();
+            shapes
+                .iter()
+                .filter_map(shape_bounds)
+                .reduce(|a, b| a.union(&b))
+                .or_else(|| shape_bounds(&Shape::point(position, 0.0)))
+        }
+        PcbItem::BoardGraphicShape(shape) => geometry::graphic_shapes(
+            &shape.geometry,
+            shape.stroke_width_nm.unwrap_or(0),
+            shape.filled,
+        )
+        .iter()
+        .filter_map(shape_bounds)
+        .reduce(|a, b| a.union(&b)),
+        PcbItem::Zone(zone) => BoundsNm::from_points(
+            zone.outline
+                .iter()
+                .filter_map(|polygon| polygon.outline.as_ref())
+                .flat_map(geometry::polyline_points),
+        ),
+        PcbItem::Footprint(footprint) => footprint
+            .items
+            .iter()
+            .filter_map(item_bounds)
+            .reduce(|a, b| a.union(&b))
+            .or_else(|| {
+                footprint
+                    .position_nm
+                    .map(|point| BoundsNm::new(point, point))
+            }),
+        PcbItem::BoardText(_)
+        | PcbItem::BoardTextBox(_)
+        | PcbItem::Field(_)
+        | PcbItem::Dimension(_)
+        | PcbItem::Group(_)
+        | PcbItem::Unknown(_) => None,
+    }
+}
+
+fn shape_bounds(shape: &Shape) -> Option<BoundsNm> {
+    let margin = shape.half_width_nm.ceil() as i64;
+    BoundsNm::from_points(shape.points.iter().copied()).map(|bounds| bounds.expanded(margin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: usize, x_nm: i64, y_nm: i64, layer_id: i32) -> SpatialEntry {
+        let corner = Vector2Nm { x_nm, y_nm };
+        SpatialEntry {
+            id: format!("item-{id}"),
+            layer_ids: vec![layer_id],
+            bounds: BoundsNm::new(
+                corner,
+                Vector2Nm {
+                    x_nm: x_nm + 100,
+                    y_nm: y_nm + 100,
+                },
+            ),
+        }
+    }
+
+    /// 20x20 grid of 100 nm squares on a 1000 nm pitch; odd columns on layer 2.
+    fn grid() -> SpatialIndex {
+        let mut entries = Vec::new();
+        for row in 0..20 {
+            for col in 0..20 {
+                entries.push(entry(
+                    row * 20 + col,
+                    col as i64 * 1000,
+                    row as i64 * 1000,
+                    (col % 2) as i32 + 1,
+                ));
+            }
+        }
+        SpatialIndex::new(entries)
+    }
+
+    #[test]
+    fn box_and_distance_queries_match_brute_force() {
+        let index = grid();
+        assert_eq!(index.len(), 400);
+
+        let area = BoundsNm::new(
+            Vector2Nm {
+                x_nm: 950,
+                y_nm: 950,
+            },
+            Vector2Nm {
+                x_nm: 3050,
+                y_nm: 2050,
+            },
+        );
+        let mut ids: Vec<&str> = index
+            .intersecting(&area)
+            .into_iter()
+            .map(|entry| entry.id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec!["item-21", "item-22", "item-23", "item-41", "item-42", "item-43"]
+        );
+
+        let center = Vector2Nm {
+            x_nm: 5050,
+            y_nm: 5050,
+        };
+        assert_eq!(index.within_distance(center, 950, None).len(), 5);
+        assert_eq!(index.within_distance(center, 950, Some(2)).len(), 3);
+    }
+
+    #[test]
+    fn nearest_respects_layer_filter() {
+        let index = grid();
+        let point = Vector2Nm {
+            x_nm: 4060,
+            y_nm: 7040,
+        };
+        let (entry, distance) = index.nearest(point, None).expect("nearest");
+        assert_eq!(entry.id, "item-144");
+        assert_eq!(distance, 0.0);
+
+        let (entry, distance) = index.nearest(point, Some(2)).expect("nearest on layer 2");
+        assert_eq!(entry.id, "item-145");
+        assert_eq!(distance, 940.0);
+        assert!(SpatialIndex::default().nearest(point, None).is_none());
+    }
+}
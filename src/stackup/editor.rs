@@ -0,0 +1,562 @@
+//! Stackup editing with validation.
+//!
+//! [`StackupEditor`] works on a [`BoardStackup`] read from KiCad: copper/dielectric pairs can be
+//! inserted or removed, per-layer thickness, material and permittivity changed, and the finish,
+//! impedance control and edge settings set. Copper layers are renumbered after structural edits
+//! so they always run `F.Cu`, `In1.Cu`, ... `B.Cu` from top to bottom.
+//!
+//! [`StackupEditor::validate`] checks layer ordering and thicknesses; [`apply`] validates, syncs
+//! the board's enabled copper layer count, then writes the stackup.
+
+use crate::client::{layer_to_model, KiCadClient};
+use crate::error::KiCadError;
+use crate::model::board::{BoardLayerInfo, BoardStackup, BoardStackupLayer, BoardStackupLayerType};
+use crate::proto::kiapi::board::types as board_types;
+
+const MAX_COPPER_LAYERS: usize = 32;
+
+/// Edits a board stackup and validates it before it is written back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackupEditor {
+    stackup: BoardStackup,
+    min_total_thickness_nm: Option<i64>,
+    max_total_thickness_nm: Option<i64>,
+}
+
+impl StackupEditor {
+    /// Starts editing `stackup`.
+    pub fn new(stackup: BoardStackup) -> Self {
+        Self {
+            stackup,
+            min_total_thickness_nm: None,
+            max_total_thickness_nm: None,
+        }
+    }
+
+    /// Current state of the edited stackup.
+    pub fn stackup(&self) -> &BoardStackup {
+        &self.stackup
+    }
+
+    /// Requires the total board thickness to fall within `min_nm..=max_nm` when validating.
+    pub fn total_thickness_limits(
+        &mut self,
+        min_nm: Option<i64>,
+        max_nm: Option<i64>,
+    ) -> &mut Self {
+        self.min_total_thickness_nm = min_nm;
+        self.max_total_thickness_nm = max_nm;
+        self
+    }
+
+    /// Number of copper layers in the stackup.
+    pub fn copper_layer_count(&self) -> usize {
+        self.copper_indices().len()
+    }
+
+    /// Sum of copper and dielectric thicknesses, in nanometers.
+    ///
+    /// Dielectric slots made of several physical layers count each of them.
+    pub fn total_thickness_nm(&self) -> i64 {
+        self.stackup
+            .layers
+            .iter()
+            .filter(|layer| is_core_layer(layer))
+            .filter_map(layer_thickness_nm)
+            .sum()
+    }
+
+    /// Inserts a dielectric and copper layer below copper layer `after_copper` (0 is `F.Cu`).
+    ///
+    /// The new layers copy the properties of copper layer `after_copper` and the dielectric
+    /// below it. Returns the stackup index of the new copper layer.
+    pub fn insert_copper_pair(&mut self, after_copper: usize) -> Result<usize, KiCadError> {
+        let copper = self.copper_indices();
+        if copper.len() >= MAX_COPPER_LAYERS {
+            return Err(config(format!(
+                "stackup already has {} copper layers; KiCad supports at most {MAX_COPPER_LAYERS}",
+                copper.len()
+            )));
+        }
+        if after_copper + 1 >= copper.len() {
+            return Err(config(format!(
+                "cannot insert a copper pair below copper layer {after_copper}; \
+                 expected 0..{}",
+                copper.len().saturating_sub(1)
+            )));
+        }
+        let copper_index = copper[after_copper];
+        let dielectric = self.stackup.layers[copper_index + 1..]
+            .iter()
+            .find(|layer| is_core_layer(layer))
+            .filter(|layer| layer.layer_type == BoardStackupLayerType::Dielectric)
+            .cloned()
+            .ok_or_else(|| {
+                config(format!(
+                    "copper layer {after_copper} is not followed by a dielectric layer"
+                ))
+            })?;
+        let mut new_copper = self.stackup.layers[copper_index].clone();
+        new_copper.enabled = true;
+
+        self.stackup
+            .layers
+            .splice(copper_index + 1..copper_index + 1, [dielectric, new_copper]);
+        self.renumber_copper();
+        Ok(copper_index + 2)
+    }
+
+    /// Removes inner copper layer `copper` (1 is `In1.Cu`) and the dielectric below it.
+    pub fn remove_copper_pair(&mut self, copper: usize) -> Result<&mut Self, KiCadError> {
+        let indices = self.copper_indices();
+        if copper == 0 || copper + 1 >= indices.len() {
+            return Err(config(format!(
+                "copper layer {copper} is not an inner layer; expected 1..{}",
+                indices.len().saturating_sub(2)
+            )));
+        }
+        let copper_index = indices[copper];
+        let dielectric_index = (copper_index + 1..indices[copper + 1])
+            .find(|index| {
+                self.stackup.layers[*index].layer_type == BoardStackupLayerType::Dielectric
+            })
+            .ok_or_else(|| {
+                config(format!(
+                    "copper layer {copper} is not followed by a dielectric layer"
+                ))
+            })?;
+
+        self.stackup.layers.remove(dielectric_index);
+        self.stackup.layers.remove(copper_index);
+        self.renumber_copper();
+        Ok(self)
+    }
+
+    /// Sets the thickness of the layer at stackup `index`.
+    ///
+    /// For a dielectric made of several physical layers, only the first is changed.
+    pub fn set_thickness(
+        &mut self,
+        index: usize,
+        thickness_nm: i64,
+    ) -> Result<&mut Self, KiCadError> {
+        let layer = self.layer_mut(index)?;
+        layer.thickness_nm = Some(thickness_nm);
+        if let Some(dielectric) = layer.dielectric_layers.first_mut() {
+            dielectric.thickness_nm = Some(thickness_nm);
+        }
+        Ok(self)
+    }
+
+    /// Sets the material name of the layer at stackup `index`, including its dielectric layers.
+    pub fn set_material(
+        &mut self,
+        index: usize,
+        material_name: impl Into<String>,
+    ) -> Result<&mut Self, KiCadError> {
+        let material_name = material_name.into();
+        let layer = self.layer_mut(index)?;
+        for dielectric in &mut layer.dielectric_layers {
+            dielectric.material_name = material_name.clone();
+        }
+        layer.material_name = material_name;
+        Ok(self)
+    }
+
+    /// Sets the relative permittivity of the dielectric layer at stackup `index`.
+    pub fn set_epsilon_r(&mut self, index: usize, epsilon_r: f64) -> Result<&mut Self, KiCadError> {
+        let layer = self.layer_mut(index)?;
+        if layer.dielectric_layers.is_empty() {
+            return Err(config(format!(
+                "stackup layer {index} has no dielectric properties"
+            )));
+        }
+        for dielectric in &mut layer.dielectric_layers {
+            dielectric.epsilon_r = epsilon_r;
+        }
+        Ok(self)
+    }
+
+    /// Sets the copper finish type name, e.g. `ENIG`; empty clears it.
+    pub fn set_finish(&mut self, finish_type_name: impl Into<String>) -> &mut Self {
+        self.stackup.finish_type_name = finish_type_name.into();
+        self
+    }
+
+    /// Sets whether the stackup is impedance controlled.
+    pub fn set_impedance_controlled(&mut self, controlled: bool) -> &mut Self {
+        self.stackup.impedance_controlled = controlled;
+        self
+    }
+
+    /// Sets whether the board has an edge connector.
+    pub fn set_edge_connector(&mut self, has_connector: bool) -> &mut Self {
+        self.stackup.edge_has_connector = has_connector;
+        self
+    }
+
+    /// Sets whether the board has castellated pads.
+    pub fn set_castellated_pads(&mut self, has_castellated_pads: bool) -> &mut Self {
+        self.stackup.edge_has_castellated_pads = has_castellated_pads;
+        self
+    }
+
+    /// Sets whether the board edge is plated.
+    pub fn set_edge_plating(&mut self, has_edge_plating: bool) -> &mut Self {
+        self.stackup.edge_has_edge_plating = has_edge_plating;
+        self
+    }
+
+    /// Checks layer ordering and thicknesses.
+    ///
+    /// Copper and dielectric layers must alternate, starting and ending with copper, with an
+    /// even number of copper layers in `F.Cu`, `In1.Cu`, ... `B.Cu` order. Every copper and
+    /// dielectric thickness must be positive, and the total must respect
+    /// [`total_thickness_limits`](Self::total_thickness_limits).
+    pub fn validate(&self) -> Result<(), KiCadError> {
+        let core: Vec<(usize, &BoardStackupLayer)> = self
+            .stackup
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| is_core_layer(layer))
+            .collect();
+
+        for (position, (index, layer)) in core.iter().enumerate() {
+            let expected = if position % 2 == 0 {
+                BoardStackupLayerType::Copper
+            } else {
+                BoardStackupLayerType::Dielectric
+            };
+            if layer.layer_type != expected {
+                return Err(config(format!(
+                    "stackup layer {index} is {:?}, expected {expected:?}",
+                    layer.layer_type
+                )));
+            }
+            if layer_thickness_nm(layer).is_none_or(|thickness| thickness <= 0) {
+                return Err(config(format!(
+                    "stackup layer {index} ({}) needs a positive thickness",
+                    layer_label(layer)
+                )));
+            }
+        }
+        if core
+            .last()
+            .is_some_and(|(_, layer)| layer.layer_type != BoardStackupLayerType::Copper)
+        {
+            return Err(config("stackup must end with a copper layer".to_string()));
+        }
+
+        let copper: Vec<&BoardStackupLayer> = core
+            .iter()
+            .map(|(_, layer)| *layer)
+            .filter(|layer| layer.layer_type == BoardStackupLayerType::Copper)
+            .collect();
+        if copper.len() < 2 || !copper.len().is_multiple_of(2) || copper.len() > MAX_COPPER_LAYERS {
+            return Err(config(format!(
+                "stackup has {} copper layers; expected an even count from 2 to {MAX_COPPER_LAYERS}",
+                copper.len()
+            )));
+        }
+        for (position, layer) in copper.iter().enumerate() {
+            let expected = copper_layer_id(position, copper.len());
+            if layer.layer.id != expected {
+                return Err(config(format!(
+                    "copper layer {position} is {}, expected {}",
+                    layer.layer.name,
+                    layer_to_model(expected).name
+                )));
+            }
+        }
+
+        let total = self.total_thickness_nm();
+        if self.min_total_thickness_nm.is_some_and(|min| total < min)
+            || self.max_total_thickness_nm.is_some_and(|max| total > max)
+        {
+            return Err(config(format!(
+                "total stackup thickness {total} nm is outside {}..={} nm",
+                limit_label(self.min_total_thickness_nm),
+                limit_label(self.max_total_thickness_nm)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates and returns the edited stackup.
+    pub fn finish(self) -> Result<BoardStackup, KiCadError> {
+        self.validate()?;
+        Ok(self.stackup)
+    }
+
+    fn copper_indices(&self) -> Vec<usize> {
+        self.stackup
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| layer.layer_type == BoardStackupLayerType::Copper)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn layer_mut(&mut self, index: usize) -> Result<&mut BoardStackupLayer, KiCadError> {
+        let count = self.stackup.layers.len();
+        self.stackup.layers.get_mut(index).ok_or_else(|| {
+            config(format!(
+                "stackup layer index {index} out of range; stackup has {count} layers"
+            ))
+        })
+    }
+
+    fn renumber_copper(&mut self) {
+        let indices = self.copper_indices();
+        let count = indices.len();
+        for (position, index) in indices.into_iter().enumerate() {
+            let layer = &mut self.stackup.layers[index];
+            let id = copper_layer_id(position, count);
+            if layer.user_name.is_empty() || layer.user_name == default_user_name(layer.layer.id) {
+                layer.user_name = default_user_name(id);
+            }
+            layer.layer = layer_to_model(id);
+        }
+    }
+}
+
+/// Validates `editor`, syncs the enabled copper layer count, and writes the stackup.
+///
+/// Returns KiCad's resulting stackup. The layer count has to change before the stackup is
+/// written, so if writing the stackup fails the previous enabled layers are put back (best
+/// effort) and the original error is returned.
+pub async fn apply(
+    client: &KiCadClient,
+    editor: &StackupEditor,
+) -> Result<BoardStackup, KiCadError> {
+    let stackup = editor.clone().finish()?;
+    let copper_layer_count = editor.copper_layer_count() as u32;
+
+    let enabled = client.get_board_enabled_layers().await?;
+    let resize = enabled.copper_layer_count != copper_layer_count;
+    if resize {
+        let layer_ids = enabled_layer_ids(&enabled.layers, copper_layer_count as usize);
+        client
+            .set_board_enabled_layers(copper_layer_count, layer_ids)
+            .await?;
+    }
+    match client.update_board_stackup(stackup).await {
+        Err(err) if resize => {
+            let previous = enabled.layers.iter().map(|layer| layer.id).collect();
+            let _ = client
+                .set_board_enabled_layers(enabled.copper_layer_count, previous)
+                .await;
+            Err(err)
+        }
+        result => result,
+    }
+}
+
+/// Layer ids for `set_board_enabled_layers`: the copper set for `copper_layer_count` plus the
+/// non-copper layers of `enabled`.
+pub fn enabled_layer_ids(enabled: &[BoardLayerInfo], copper_layer_count: usize) -> Vec<i32> {
+    let mut ids: Vec<i32> = (0..copper_layer_count)
+        .map(|position| copper_layer_id(position, copper_layer_count))
+        .collect();
+    ids.extend(
+        enabled
+            .iter()
+            .map(|layer| layer.id)
+            .filter(|id| !is_copper_layer_id(*id)),
+    );
+    ids
+}
+
+pub(super) fn is_core_layer(layer: &BoardStackupLayer) -> bool {
+    matches!(
+        layer.layer_type,
+        BoardStackupLayerType::Copper | BoardStackupLayerType::Dielectric
+    )
+}
+
+fn layer_thickness_nm(layer: &BoardStackupLayer) -> Option<i64> {
+    if layer.dielectric_layers.is_empty() {
+        layer.thickness_nm
+    } else {
+        layer
+            .dielectric_layers
+            .iter()
+            .map(|dielectric| dielectric.thickness_nm)
+            .sum()
+    }
+}
+
+fn layer_label(layer: &BoardStackupLayer) -> &str {
+    if layer.user_name.is_empty() {
+        &layer.layer.name
+    } else {
+        &layer.user_name
+    }
+}
+
+fn limit_label(limit: Option<i64>) -> String {
+    limit.map(|value| value.to_string()).unwrap_or_default()
+}
+
+pub(super) fn copper_layer_id(position: usize, count: usize) -> i32 {
+    if position + 1 == count {
+        board_types::BoardLayer::BlBCu as i32
+    } else {
+        board_types::BoardLayer::BlFCu as i32 + position as i32
+    }
+}
+
+fn is_copper_layer_id(id: i32) -> bool {
+    (board_types::BoardLayer::BlFCu as i32..=board_types::BoardLayer::BlBCu as i32).contains(&id)
+}
+
+pub(super) fn default_user_name(id: i32) -> String {
+    let front = board_types::BoardLayer::BlFCu as i32;
+    let back = board_types::BoardLayer::BlBCu as i32;
+    match id {
+        id if id == front => "F.Cu".to_string(),
+        id if id == back => "B.Cu".to_string(),
+        id => format!("In{}.Cu", id - front),
+    }
+}
+
+pub(super) fn config(reason: String) -> KiCadError {
+    KiCadError::Config { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::board::BoardStackupDielectricProperties;
+
+    fn copper(id: i32) -> BoardStackupLayer {
+        BoardStackupLayer {
+            layer: layer_to_model(id),
+            user_name: default_user_name(id),
+            material_name: String::new(),
+            enabled: true,
+            thickness_nm: Some(35_000),
+            layer_type: BoardStackupLayerType::Copper,
+            color: None,
+            dielectric_layers: Vec::new(),
+        }
+    }
+
+    fn dielectric(thickness_nm: i64) -> BoardStackupLayer {
+        BoardStackupLayer {
+            layer: layer_to_model(board_types::BoardLayer::BlUndefined as i32),
+            user_name: String::new(),
+            material_name: "FR4".to_string(),
+            enabled: true,
+            thickness_nm: Some(thickness_nm),
+            layer_type: BoardStackupLayerType::Dielectric,
+            color: None,
+            dielectric_layers: vec![BoardStackupDielectricProperties {
+                epsilon_r: 4.5,
+                loss_tangent: 0.02,
+                material_name: "FR4".to_string(),
+                thickness_nm: Some(thickness_nm),
+            }],
+        }
+    }
+
+    fn two_layer() -> BoardStackup {
+        BoardStackup {
+            layers: vec![
+                BoardStackupLayer {
+                    layer_type: BoardStackupLayerType::SolderMask,
+                    user_name: "F.Mask".to_string(),
+                    thickness_nm: Some(10_000),
+                    ..copper(board_types::BoardLayer::BlFMask as i32)
+                },
+                copper(board_types::BoardLayer::BlFCu as i32),
+                dielectric(1_510_000),
+                copper(board_types::BoardLayer::BlBCu as i32),
+            ],
+            ..BoardStackup::default()
+        }
+    }
+
+    #[test]
+    fn insert_and_remove_pairs_renumber_copper_layers() {
+        let mut editor = StackupEditor::new(two_layer());
+        assert_eq!(editor.total_thickness_nm(), 1_580_000);
+
+        let first = editor.insert_copper_pair(0).expect("pair should insert");
+        let second = editor.insert_copper_pair(1).expect("pair should insert");
+        assert_eq!((first, second), (3, 5));
+        assert_eq!(editor.copper_layer_count(), 4);
+        let names: Vec<&str> = editor
+            .stackup()
+            .layers
+            .iter()
+            .filter(|layer| layer.layer_type == BoardStackupLayerType::Copper)
+            .map(|layer| layer.user_name.as_str())
+            .collect();
+        assert_eq!(names, ["F.Cu", "In1.Cu", "In2.Cu", "B.Cu"]);
+        assert_eq!(editor.stackup().layers[3].layer.name, "BL_In1_Cu");
+        editor
+            .validate()
+            .expect("four-layer stackup should validate");
+
+        editor
+            .set_thickness(2, 200_000)
+            .and_then(|editor| editor.set_epsilon_r(2, 3.9))
+            .and_then(|editor| editor.set_material(2, "Prepreg"))
+            .expect("dielectric edits should apply");
+        assert_eq!(
+            editor.stackup().layers[2].dielectric_layers[0].epsilon_r,
+            3.9
+        );
+        assert!(editor.set_epsilon_r(1, 4.0).is_err());
+
+        editor
+            .remove_copper_pair(1)
+            .expect("inner pair should be removed");
+        assert!(editor.remove_copper_pair(0).is_err());
+        assert_eq!(editor.copper_layer_count(), 3);
+        assert!(editor.validate().is_err(), "odd copper count is invalid");
+
+        editor
+            .remove_copper_pair(1)
+            .expect("inner pair should be removed");
+        let stackup = editor
+            .set_finish("ENIG")
+            .set_castellated_pads(true)
+            .clone()
+            .finish()
+            .expect("two-layer stackup should validate");
+        assert_eq!(stackup.layers.len(), 4);
+        assert_eq!(stackup.finish_type_name, "ENIG");
+        assert!(stackup.edge_has_castellated_pads);
+    }
+
+    #[test]
+    fn validate_rejects_bad_ordering_and_thickness() {
+        let mut stackup = two_layer();
+        stackup.layers.swap(2, 3);
+        assert!(StackupEditor::new(stackup).validate().is_err());
+
+        let mut editor = StackupEditor::new(two_layer());
+        editor.set_thickness(2, 0).expect("index should exist");
+        assert!(editor.validate().is_err());
+
+        let mut editor = StackupEditor::new(two_layer());
+        editor.total_thickness_limits(Some(1_500_000), Some(1_570_000));
+        assert!(editor.validate().is_err());
+        editor.total_thickness_limits(None, Some(1_600_000));
+        assert!(editor.validate().is_ok());
+
+        let enabled = vec![
+            layer_to_model(board_types::BoardLayer::BlFCu as i32),
+            layer_to_model(board_types::BoardLayer::BlBCu as i32),
+            layer_to_model(board_types::BoardLayer::BlEdgeCuts as i32),
+        ];
+        assert_eq!(
+            enabled_layer_ids(&enabled, 4),
+            vec![3, 4, 5, 34, board_types::BoardLayer::BlEdgeCuts as i32]
+        );
+    }
+}
@@ -0,0 +1,782 @@
+//! Stackup import and export in vendor-neutral formats.
+//!
+//! Both formats list one row per physical layer from top to bottom with a name, type,
+//! material, thickness, dielectric constant (Dk, `epsilon_r`), loss tangent (Df), and colour.
+//! Consecutive dielectric rows form one KiCad dielectric slot (e.g. two prepregs between the
+//! same copper layers). Copper layers are numbered `F.Cu`, `In1.Cu`, ... `B.Cu` by position,
+//! and solder mask, silkscreen, and paste rows land on the front or back by which side of the
+//! copper they sit on, so fab naming like `L1` or `Top` does not matter.
+//!
+//! Type names are `copper`, `dielectric` (also `core`, `prepreg`), `soldermask` (`mask`),
+//! `silkscreen` (`silk`, `legend`), and `solderpaste` (`paste`), matched case-insensitively.
+//! Colours are `#rrggbb` or `#rrggbbaa`.
+//!
+//! # TOML
+//!
+//! Board-level settings are root keys; each layer is a `[[layer]]` table. Thicknesses are
+//! integer nanometres.
+//!
+//! ```toml
+//! finish = "ENIG"
+//! impedance_controlled = true
+//! edge_connector = false
+//! castellated_pads = false
+//! edge_plating = false
+//!
+//! [[layer]]
+//! name = "F.Cu"
+//! type = "copper"
+//! thickness_nm = 35_000
+//!
+//! [[layer]]
+//! name = "Core"
+//! type = "dielectric"
+//! material = "FR4"
+//! thickness_nm = 1_510_000
+//! epsilon_r = 4.5
+//! loss_tangent = 0.02
+//! ```
+//!
+//! # CSV
+//!
+//! [`to_csv`] writes `layer,type,material,thickness_mm,dk,df,color`. [`parse_csv`] finds
+//! columns by header name, so spreadsheet exports with extra or reordered columns load as
+//! long as they have `type` and a thickness column (`thickness_mm`, `thickness_um`,
+//! `thickness_nm`, or `thickness` in millimetres). `layer`/`name`, `material`, `dk`/`er`/
+//! `epsilon_r`, `df`/`loss_tangent`, and `color`/`colour` are optional. CSV carries layers
+//! only; board-level settings stay as they are.
+
+use std::io::{self, Write};
+
+use super::editor::{config, copper_layer_id, default_user_name, StackupEditor};
+use crate::client::{layer_to_model, KiCadClient};
+use crate::error::KiCadError;
+use crate::model::board::{
+    BoardEnabledLayers, BoardStackup, BoardStackupDielectricProperties, BoardStackupLayer,
+    BoardStackupLayerType, ColorRgba,
+};
+use crate::proto::kiapi::board::types as board_types;
+use crate::sexpr::format_mm;
+use crate::text_out::{write_csv_row, write_to_string};
+use crate::toml::{self, TomlValue};
+
+const CSV_HEADER: [&str; 7] = [
+    "layer",
+    "type",
+    "material",
+    "thickness_mm",
+    "dk",
+    "df",
+    "color",
+];
+
+/// Writes `stackup` as a TOML stackup file.
+pub fn to_toml(stackup: &BoardStackup) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "finish = {}\n",
+        toml::quote(&stackup.finish_type_name)
+    ));
+    out.push_str(&format!(
+        "impedance_controlled = {}\n",
+        stackup.impedance_controlled
+    ));
+    out.push_str(&format!(
+        "edge_connector = {}\n",
+        stackup.edge_has_connector
+    ));
+    out.push_str(&format!(
+        "castellated_pads = {}\n",
+        stackup.edge_has_castellated_pads
+    ));
+    out.push_str(&format!(
+        "edge_plating = {}\n",
+        stackup.edge_has_edge_plating
+    ));
+
+    for row in rows(stackup) {
+        out.push_str("\n[[layer]]\n");
+        out.push_str(&format!("name = {}\n", toml::quote(&row.name)));
+        out.push_str(&format!(
+            "type = {}\n",
+            toml::quote(type_name(row.layer_type))
+        ));
+        if !row.material.is_empty() {
+            out.push_str(&format!("material = {}\n", toml::quote(&row.material)));
+        }
+        if let Some(thickness_nm) = row.thickness_nm {
+            out.push_str(&format!("thickness_nm = {thickness_nm}\n"));
+        }
+        if let Some(epsilon_r) = row.epsilon_r {
+            out.push_str(&format!("epsilon_r = {epsilon_r:?}\n"));
+        }
+        if let Some(loss_tangent) = row.loss_tangent {
+            out.push_str(&format!("loss_tangent = {loss_tangent:?}\n"));
+        }
+        if let Some(color) = row.color {
+            out.push_str(&format!("color = {}\n", toml::quote(&hex_color(&color))));
+        }
+    }
+    out
+}
+
+/// Parses a TOML stackup file.
+pub fn parse_toml(text: &str) -> Result<BoardStackup, KiCadError> {
+    let document =
+        toml::parse_document(text).map_err(|(line, reason)| parse_error(line, reason))?;
+    let mut stackup = BoardStackup::default();
+    for entry in document.root {
+        let line = entry.line;
+        let flag = |value: TomlValue| match value {
+            TomlValue::Boolean(value) => Ok(value),
+            other => Err(parse_error(
+                line,
+                format!("`{}` must be a boolean, found {}", entry.key, other.kind()),
+            )),
+        };
+        match entry.key.as_str() {
+            "finish" => match entry.value {
+                TomlValue::String(name) => stackup.finish_type_name = name,
+                other => {
+                    return Err(parse_error(
+                        line,
+                        format!("`finish` must be a string, found {}", other.kind()),
+                    ))
+                }
+            },
+            "impedance_controlled" => stackup.impedance_controlled = flag(entry.value)?,
+            "edge_connector" => stackup.edge_has_connector = flag(entry.value)?,
+            "castellated_pads" => stackup.edge_has_castellated_pads = flag(entry.value)?,
+            "edge_plating" => stackup.edge_has_edge_plating = flag(entry.value)?,
+            key => return Err(parse_error(line, format!("unknown stackup key `{key}`"))),
+        }
+    }
+
+    let mut rows = Vec::new();
+    for table in document.tables {
+        if table.header != "[[layer]]" {
+            return Err(parse_error(
+                table.line,
+                format!("unsupported table `{}`; expected `[[layer]]`", table.header),
+            ));
+        }
+        let mut row = RowBuilder::default();
+        for entry in table.entries {
+            row.set_toml(&entry.key, entry.value)
+                .map_err(|reason| parse_error(entry.line, reason))?;
+        }
+        rows.push((
+            table.line,
+            row.build()
+                .map_err(|reason| parse_error(table.line, reason))?,
+        ));
+    }
+    stackup.layers = build_layers(rows)?;
+    Ok(stackup)
+}
+
+/// Writes the layers of `stackup` as CSV.
+pub fn write_csv<W: Write>(stackup: &BoardStackup, out: &mut W) -> io::Result<()> {
+    write_csv_row(out, &CSV_HEADER.map(str::to_string))?;
+    for row in rows(stackup) {
+        write_csv_row(
+            out,
+            &[
+                row.name,
+                type_name(row.layer_type).to_string(),
+                row.material,
+                row.thickness_nm.map(format_mm).unwrap_or_default(),
+                row.epsilon_r
+                    .map(|value| value.to_string())
+                    .unwrap_or_default(),
+                row.loss_tangent
+                    .map(|value| value.to_string())
+                    .unwrap_or_default(),
+                row.color.map(|color| hex_color(&color)).unwrap_or_default(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the layers of `stackup` as a CSV string.
+pub fn to_csv(stackup: &BoardStackup) -> String {
+    write_to_string(|out| write_csv(stackup, out))
+}
+
+/// Parses stackup layers from CSV with a header row.
+pub fn parse_csv(text: &str) -> Result<Vec<BoardStackupLayer>, KiCadError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (header_line, header) = lines
+        .next()
+        .ok_or_else(|| config("stackup CSV is empty".to_string()))?;
+    let header: Vec<String> = split_csv_line(header)
+        .map_err(|reason| parse_error(header_line, reason))?
+        .into_iter()
+        .map(|cell| cell.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|cell| names.contains(&cell.as_str()))
+    };
+
+    let name_column = column(&["layer", "name"]);
+    let type_column = column(&["type"])
+        .ok_or_else(|| parse_error(header_line, "missing `type` column".to_string()))?;
+    let material_column = column(&["material"]);
+    // Scale is nanometres per unit.
+    let (thickness_column, thickness_scale) = [
+        ("thickness_mm", 1e6),
+        ("thickness", 1e6),
+        ("thickness_um", 1e3),
+        ("thickness_nm", 1.0),
+    ]
+    .into_iter()
+    .find_map(|(name, scale)| column(&[name]).map(|index| (index, scale)))
+    .ok_or_else(|| parse_error(header_line, "missing thickness column".to_string()))?;
+    let dk_column = column(&["dk", "er", "epsilon_r"]);
+    let df_column = column(&["df", "loss_tangent", "tand"]);
+    let color_column = column(&["color", "colour"]);
+
+    let mut rows = Vec::new();
+    for (line, text) in lines {
+        let cells = split_csv_line(text).map_err(|reason| parse_error(line, reason))?;
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| cells.get(index))
+                .map(|cell| cell.trim())
+                .unwrap_or_default()
+        };
+        let mut row = RowBuilder::default();
+        let fields = [
+            ("name", name_column),
+            ("type", Some(type_column)),
+            ("material", material_column),
+            ("epsilon_r", dk_column),
+            ("loss_tangent", df_column),
+            ("color", color_column),
+        ];
+        for (field, index) in fields {
+            let value = cell(index);
+            if !value.is_empty() {
+                row.set_text(field, value)
+                    .map_err(|reason| parse_error(line, reason))?;
+            }
+        }
+        let thickness = cell(Some(thickness_column));
+        if !thickness.is_empty() {
+            let thickness_nm = thickness
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(|value| (value * thickness_scale).round() as i64)
+                .ok_or_else(|| parse_error(line, format!("invalid thickness `{thickness}`")))?;
+            row.thickness_nm = Some(thickness_nm);
+        }
+        rows.push((
+            line,
+            row.build().map_err(|reason| parse_error(line, reason))?,
+        ));
+    }
+    build_layers(rows)
+}
+
+/// Fails unless `stackup` has as many copper layers as the board has enabled.
+pub fn check_copper_layer_count(
+    stackup: &BoardStackup,
+    enabled: &BoardEnabledLayers,
+) -> Result<(), KiCadError> {
+    let count = stackup
+        .layers
+        .iter()
+        .filter(|layer| layer.layer_type == BoardStackupLayerType::Copper)
+        .count();
+    if count as u32 != enabled.copper_layer_count {
+        return Err(config(format!(
+            "imported stackup has {count} copper layers but the board has {} enabled",
+            enabled.copper_layer_count
+        )));
+    }
+    Ok(())
+}
+
+/// Validates an imported stackup, checks its copper layer count against the board, and writes
+/// it with `update_board_stackup`.
+///
+/// Returns KiCad's resulting stackup. Change the layer count first with
+/// [`apply`](super::apply) when the imported stackup adds or removes copper.
+pub async fn import(
+    client: &KiCadClient,
+    stackup: BoardStackup,
+) -> Result<BoardStackup, KiCadError> {
+    let stackup = StackupEditor::new(stackup).finish()?;
+    check_copper_layer_count(&stackup, &client.get_board_enabled_layers().await?)?;
+    client.update_board_stackup(stackup).await
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    name: String,
+    layer_type: BoardStackupLayerType,
+    material: String,
+    thickness_nm: Option<i64>,
+    epsilon_r: Option<f64>,
+    loss_tangent: Option<f64>,
+    color: Option<ColorRgba>,
+}
+
+#[derive(Debug, Default)]
+struct RowBuilder {
+    name: String,
+    layer_type: Option<BoardStackupLayerType>,
+    material: String,
+    thickness_nm: Option<i64>,
+    epsilon_r: Option<f64>,
+    loss_tangent: Option<f64>,
+    color: Option<ColorRgba>,
+}
+
+impl RowBuilder {
+    fn set_toml(&mut self, key: &str, value: TomlValue) -> Result<(), String> {
+        let mismatch = |expected: &str, value: &TomlValue| {
+            format!("`{key}` must be a {expected}, found {}", value.kind())
+        };
+        let number = |value: TomlValue| match value {
+            TomlValue::Float(value) => Ok(value),
+            TomlValue::Integer(value) => Ok(value as f64),
+            other => Err(mismatch("number", &other)),
+        };
+        match key {
+            "thickness_nm" => match value {
+                TomlValue::Integer(value_nm) => self.thickness_nm = Some(value_nm),
+                other => return Err(mismatch("integer", &other)),
+            },
+            "epsilon_r" => self.epsilon_r = Some(number(value)?),
+            "loss_tangent" => self.loss_tangent = Some(number(value)?),
+            "name" | "type" | "material" | "color" => match value {
+                TomlValue::String(text) => self.set_text(key, &text)?,
+                other => return Err(mismatch("string", &other)),
+            },
+            _ => return Err(format!("unknown layer key `{key}`")),
+        }
+        Ok(())
+    }
+
+    fn set_text(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let number = |value: &str| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| format!("invalid {field} `{value}`"))
+        };
+        match field {
+            "name" => self.name = value.to_string(),
+            "type" => self.layer_type = Some(parse_type(value)?),
+            "material" => self.material = value.to_string(),
+            "epsilon_r" => self.epsilon_r = Some(number(value)?),
+            "loss_tangent" => self.loss_tangent = Some(number(value)?),
+            "color" => self.color = Some(parse_color(value)?),
+            _ => return Err(format!("unknown layer field `{field}`")),
+        }
+        Ok(())
+    }
+
+    fn build(self) -> Result<Row, String> {
+        let layer_type = self
+            .layer_type
+            .ok_or_else(|| "layer has no `type`".to_string())?;
+        if layer_type == BoardStackupLayerType::Dielectric && self.epsilon_r.is_none() {
+            return Err("dielectric layer needs a dielectric constant".to_string());
+        }
+        Ok(Row {
+            name: self.name,
+            layer_type,
+            material: self.material,
+            thickness_nm: self.thickness_nm,
+            epsilon_r: self.epsilon_r,
+            loss_tangent: self.loss_tangent,
+            color: self.color,
+        })
+    }
+}
+
+fn rows(stackup: &BoardStackup) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut dielectric_count = 0;
+    for layer in &stackup.layers {
+        let name = if layer.layer_type == BoardStackupLayerType::Dielectric {
+            dielectric_count += 1;
+            if layer.user_name.is_empty() {
+                format!("Dielectric {dielectric_count}")
+            } else {
+                layer.user_name.clone()
+            }
+        } else {
+            layer.user_name.clone()
+        };
+
+        if layer.dielectric_layers.is_empty() {
+            rows.push(Row {
+                name,
+                layer_type: layer.layer_type,
+                material: layer.material_name.clone(),
+                thickness_nm: layer.thickness_nm,
+                epsilon_r: None,
+                loss_tangent: None,
+                color: layer.color,
+            });
+            continue;
+        }
+        for dielectric in &layer.dielectric_layers {
+            rows.push(Row {
+                name: name.clone(),
+                layer_type: layer.layer_type,
+                material: dielectric.material_name.clone(),
+                thickness_nm: dielectric.thickness_nm,
+                epsilon_r: Some(dielectric.epsilon_r),
+                loss_tangent: Some(dielectric.loss_tangent),
+                color: layer.color,
+            });
+        }
+    }
+    rows
+}
+
+fn build_layers(rows: Vec<(usize, Row)>) -> Result<Vec<BoardStackupLayer>, KiCadError> {
+    let copper_count = rows
+        .iter()
+        .filter(|(_, row)| row.layer_type == BoardStackupLayerType::Copper)
+        .count();
+    let mut copper_seen = 0;
+    let mut layers: Vec<BoardStackupLayer> = Vec::new();
+
+    for (line, row) in rows {
+        let properties = row
+            .epsilon_r
+            .map(|epsilon_r| BoardStackupDielectricProperties {
+                epsilon_r,
+                loss_tangent: row.loss_tangent.unwrap_or_default(),
+                material_name: row.material.clone(),
+                thickness_nm: row.thickness_nm,
+            });
+
+        if row.layer_type == BoardStackupLayerType::Dielectric {
+            if let Some(previous) = layers
+                .last_mut()
+                .filter(|layer| layer.layer_type == BoardStackupLayerType::Dielectric)
+            {
+                previous.dielectric_layers.extend(properties);
+                previous.thickness_nm = previous
+                    .dielectric_layers
+                    .iter()
+                    .map(|dielectric| dielectric.thickness_nm)
+                    .sum();
+                continue;
+            }
+        }
+
+        let id = match row.layer_type {
+            BoardStackupLayerType::Copper => {
+                copper_seen += 1;
+                copper_layer_id(copper_seen - 1, copper_count)
+            }
+            BoardStackupLayerType::Dielectric => board_types::BoardLayer::BlUndefined as i32,
+            layer_type => {
+                let front = copper_seen == 0;
+                if !front && copper_seen < copper_count {
+                    return Err(parse_error(
+                        line,
+                        format!(
+                            "{} layer `{}` sits between copper layers",
+                            type_name(layer_type),
+                            row.name
+                        ),
+                    ));
+                }
+                side_layer_id(layer_type, front)
+            }
+        };
+        let user_name = match row.layer_type {
+            BoardStackupLayerType::Dielectric => String::new(),
+            BoardStackupLayerType::Copper if row.name.is_empty() => default_user_name(id),
+            _ if row.name.is_empty() => layer_to_model(id).name,
+            _ => row.name,
+        };
+        layers.push(BoardStackupLayer {
+            layer: layer_to_model(id),
+            user_name,
+            material_name: row.material,
+            enabled: true,
+            thickness_nm: row.thickness_nm,
+            layer_type: row.layer_type,
+            color: row.color,
+            dielectric_layers: properties.into_iter().collect(),
+        });
+    }
+    Ok(layers)
+}
+
+fn side_layer_id(layer_type: BoardStackupLayerType, front: bool) -> i32 {
+    use board_types::BoardLayer;
+
+    let layer = match (layer_type, front) {
+        (BoardStackupLayerType::SolderMask, true) => BoardLayer::BlFMask,
+        (BoardStackupLayerType::SolderMask, false) => BoardLayer::BlBMask,
+        (BoardStackupLayerType::Silkscreen, true) => BoardLayer::BlFSilkS,
+        (BoardStackupLayerType::Silkscreen, false) => BoardLayer::BlBSilkS,
+        (BoardStackupLayerType::SolderPaste, true) => BoardLayer::BlFPaste,
+        (BoardStackupLayerType::SolderPaste, false) => BoardLayer::BlBPaste,
+        _ => BoardLayer::BlUndefined,
+    };
+    layer as i32
+}
+
+fn type_name(layer_type: BoardStackupLayerType) -> &'static str {
+    match layer_type {
+        BoardStackupLayerType::Copper => "copper",
+        BoardStackupLayerType::Dielectric => "dielectric",
+        BoardStackupLayerType::Silkscreen => "silkscreen",
+        BoardStackupLayerType::SolderMask => "soldermask",
+        BoardStackupLayerType::SolderPaste => "solderpaste",
+        BoardStackupLayerType::Undefined | BoardStackupLayerType::Unknown(_) => "undefined",
+    }
+}
+
+fn parse_type(value: &str) -> Result<BoardStackupLayerType, String> {
+    let normalized: String = value
+        .chars()
+        .filter(|ch| !matches!(ch, ' ' | '_' | '-'))
+        .collect::<String>()
+        .to_ascii_lowercase();
+    Ok(match normalized.as_str() {
+        "copper" => BoardStackupLayerType::Copper,
+        "dielectric" | "core" | "prepreg" => BoardStackupLayerType::Dielectric,
+        "soldermask" | "mask" => BoardStackupLayerType::SolderMask,
+        "silkscreen" | "silk" | "legend" => BoardStackupLayerType::Silkscreen,
+        "solderpaste" | "paste" => BoardStackupLayerType::SolderPaste,
+        _ => return Err(format!("unknown layer type `{value}`")),
+    })
+}
+
+fn hex_color(color: &ColorRgba) -> String {
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        channel(color.a)
+    )
+}
+
+fn parse_color(value: &str) -> Result<ColorRgba, String> {
+    let invalid = || format!("invalid colour `{value}`; expected `#rrggbb` or `#rrggbbaa`");
+    let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |index: usize| {
+        hex.get(index..index + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .map(|value| f64::from(value) / 255.0)
+            .ok_or_else(invalid)
+    };
+    Ok(ColorRgba {
+        r: channel(0)?,
+        g: channel(2)?,
+        b: channel(4)?,
+        a: if hex.len() == 8 { channel(6)? } else { 1.0 },
+    })
+}
+
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+            ch => cell.push(ch),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    cells.push(cell);
+    Ok(cells)
+}
+
+fn parse_error(line: usize, reason: String) -> KiCadError {
+    config(format!("stackup file line {line}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(
+        id: board_types::BoardLayer,
+        user_name: &str,
+        layer_type: BoardStackupLayerType,
+        thickness_nm: i64,
+    ) -> BoardStackupLayer {
+        BoardStackupLayer {
+            layer: layer_to_model(id as i32),
+            user_name: user_name.to_string(),
+            material_name: String::new(),
+            enabled: true,
+            thickness_nm: Some(thickness_nm),
+            layer_type,
+            color: None,
+            dielectric_layers: Vec::new(),
+        }
+    }
+
+    fn dielectric(
+        material_name: &str,
+        epsilon_r: f64,
+        thickness_nm: i64,
+    ) -> BoardStackupDielectricProperties {
+        BoardStackupDielectricProperties {
+            epsilon_r,
+            loss_tangent: 0.02,
+            material_name: material_name.to_string(),
+            thickness_nm: Some(thickness_nm),
+        }
+    }
+
+    fn sample() -> BoardStackup {
+        use board_types::BoardLayer;
+
+        let mut mask = layer(
+            BoardLayer::BlFMask,
+            "F.Mask",
+            BoardStackupLayerType::SolderMask,
+            10_000,
+        );
+        mask.color = Some(ColorRgba {
+            r: 0.0,
+            g: 0.4,
+            b: 0.0,
+            a: 1.0,
+        });
+        let mut core = layer(
+            BoardLayer::BlUndefined,
+            "",
+            BoardStackupLayerType::Dielectric,
+            1_500_000,
+        );
+        core.material_name = "FR4".to_string();
+        core.dielectric_layers = vec![
+            dielectric("FR4", 4.5, 1_400_000),
+            dielectric("Prepreg 1080, \"HR\"", 3.9, 100_000),
+        ];
+        BoardStackup {
+            finish_type_name: "ENIG".to_string(),
+            impedance_controlled: true,
+            edge_has_connector: false,
+            edge_has_castellated_pads: true,
+            edge_has_edge_plating: false,
+            layers: vec![
+                mask,
+                layer(
+                    BoardLayer::BlFCu,
+                    "F.Cu",
+                    BoardStackupLayerType::Copper,
+                    35_000,
+                ),
+                core,
+                layer(
+                    BoardLayer::BlBCu,
+                    "B.Cu",
+                    BoardStackupLayerType::Copper,
+                    35_000,
+                ),
+                layer(
+                    BoardLayer::BlBSilkS,
+                    "B.Silkscreen",
+                    BoardStackupLayerType::Silkscreen,
+                    0,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn toml_and_csv_round_trip_stackups() {
+        let stackup = sample();
+
+        let toml = to_toml(&stackup);
+        assert!(toml.contains("finish = \"ENIG\""));
+        assert_eq!(
+            parse_toml(&toml).expect("exported TOML should parse"),
+            stackup
+        );
+
+        let csv = to_csv(&stackup);
+        assert!(csv.starts_with("layer,type,material,thickness_mm,dk,df,color\n"));
+        assert!(csv.contains("Dielectric 1,dielectric,FR4,1.4,4.5,0.02,"));
+        assert!(csv.contains("F.Mask,soldermask,,0.01,,,#006600ff"));
+        assert_eq!(
+            parse_csv(&csv).expect("exported CSV should parse"),
+            stackup.layers
+        );
+    }
+
+    #[test]
+    fn parse_csv_maps_fab_spreadsheets_onto_kicad_layers() {
+        let layers = parse_csv(
+            "Layer,Type,Material,Thickness_um,Er,Notes\n\
+             Top,Copper,,35,,1 oz\n\
+             PP1,Prepreg,2116,120,4.2,\n\
+             L2,Copper,,17.5,,\n\
+             Core,Core,FR4,800,4.6,\n\
+             L3,Copper,,17.5,,\n\
+             PP2,Prepreg,2116,120,4.2,\n\
+             Bottom,Copper,,35,,\n\
+             Bottom mask,Solder Mask,,10,,\n",
+        )
+        .expect("fab CSV should parse");
+
+        let ids: Vec<i32> = layers.iter().map(|layer| layer.layer.id).collect();
+        assert_eq!(ids, vec![3, 1, 4, 1, 5, 1, 34, 41]);
+        assert_eq!(layers[0].user_name, "Top");
+        assert_eq!(layers[1].thickness_nm, Some(120_000));
+        assert_eq!(layers[2].thickness_nm, Some(17_500));
+        assert_eq!(layers[3].dielectric_layers[0].epsilon_r, 4.6);
+        assert!(StackupEditor::new(BoardStackup {
+            layers: layers.clone(),
+            ..BoardStackup::default()
+        })
+        .validate()
+        .is_ok());
+
+        let enabled = BoardEnabledLayers {
+            copper_layer_count: 2,
+            layers: Vec::new(),
+        };
+        let stackup = BoardStackup {
+            layers,
+            ..BoardStackup::default()
+        };
+        assert!(check_copper_layer_count(&stackup, &enabled).is_err());
+
+        let err = parse_csv("type,thickness_mm\ncopper,0.035\nmask,0.01\ncopper,0.035\n")
+            .expect_err("mask between copper layers must fail");
+        assert!(err.to_string().contains("line 3"));
+        assert!(parse_csv("type,thickness_mm\ndielectric,1.5\n").is_err());
+        assert!(parse_toml("[[layer]]\ntype = \"copper\"\nthickness_nm = 1.5\n").is_err());
+    }
+}
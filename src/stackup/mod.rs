@@ -0,0 +1,12 @@
+//! Board stackup editing, validation, and exchange with fab houses.
+//!
+//! [`StackupEditor`] edits a stackup read from KiCad and checks it before [`apply`] writes it
+//! back. [`exchange`] reads and writes stackups as TOML and CSV so fab-supplied stackups do not
+//! have to be re-typed.
+
+/// Stackup edits, validation, and enabled layer sync.
+pub mod editor;
+/// Vendor-neutral stackup import and export (TOML and CSV).
+pub mod exchange;
+
+pub use editor::{apply, enabled_layer_ids, StackupEditor};
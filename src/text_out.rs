@@ -0,0 +1,26 @@
+//! In-memory and CSV writer helpers shared by the text exporters.
+
+use std::io::{self, Write};
+
+/// Runs an exporter's `io::Write` method against an in-memory buffer and returns the text.
+pub(crate) fn write_to_string(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+    let mut out = Vec::new();
+    write(&mut out).expect("writing to a Vec<u8> cannot fail");
+    String::from_utf8(out).expect("exporters only write UTF-8 text")
+}
+
+/// Writes one CSV row, quoting cells that contain commas, quotes, or line breaks.
+pub(crate) fn write_csv_row<W: Write>(out: &mut W, cells: &[String]) -> io::Result<()> {
+    let line = cells
+        .iter()
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(out, "{line}")
+}
@@ -0,0 +1,605 @@
+//! Text variable references, validation, and build metadata stamping.
+//!
+//! [`scan_text`] collects every text-bearing string on a board (board text, text boxes,
+//! footprint fields and footprint text, dimensions, title block) and the `${NAME}` references
+//! in it. [`check_references`] compares those references with the project's text variables.
+//! [`KiCadClient::get_text_variable_report`](crate::client::KiCadClient::get_text_variable_report)
+//! runs both against the open board, and
+//! [`KiCadClient::preview_text_expansions`](crate::client::KiCadClient::preview_text_expansions)
+//! shows how KiCad expands each string.
+//!
+//! References KiCad resolves itself are never reported as undefined: built-in variables
+//! (`${TITLE}`, `${REVISION}`, `${CURRENT_DATE}`, ...), cross-references (`${U1:VALUE}`),
+//! environment variables (`${KIPRJMOD}`, `${KICAD9_3DMODEL_DIR}`), and, inside a footprint,
+//! the footprint's own field names.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::KiCadError;
+use crate::model::board::PcbItem;
+use crate::model::common::TitleBlockInfo;
+use crate::proto::kiapi::common::types::KiCadObjectType;
+
+/// Variables KiCad resolves on boards, title blocks, and footprints without project definitions.
+const BUILTIN_VARIABLES: [&str; 39] = [
+    "#",
+    "##",
+    "COMMENT1",
+    "COMMENT2",
+    "COMMENT3",
+    "COMMENT4",
+    "COMMENT5",
+    "COMMENT6",
+    "COMMENT7",
+    "COMMENT8",
+    "COMMENT9",
+    "COMPANY",
+    "CURRENT_DATE",
+    "DATASHEET",
+    "DESCRIPTION",
+    "DRILL_LAYER",
+    "FILENAME",
+    "FILEPATH",
+    "FOOTPRINT",
+    "FOOTPRINT_LIBRARY",
+    "FOOTPRINT_NAME",
+    "ISSUE_DATE",
+    "KICAD_VERSION",
+    "LAYER",
+    "NET_CLASS",
+    "NET_NAME",
+    "PAPER",
+    "PIN_NAME",
+    "PIN_TYPE",
+    "PROJECTNAME",
+    "REFERENCE",
+    "REVISION",
+    "SHEETNAME",
+    "SHEETPATH",
+    "SHORT_NET_NAME",
+    "SHORT_REFERENCE",
+    "TITLE",
+    "UNIT",
+    "VALUE",
+];
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// Where a scanned string lives.
+pub enum TextLocation {
+    /// Free board text.
+    BoardText {
+        /// Item KIID.
+        id: Option<String>,
+    },
+    /// Board text box.
+    TextBox {
+        /// Item KIID.
+        id: Option<String>,
+    },
+    /// Dimension text.
+    Dimension {
+        /// Item KIID.
+        id: Option<String>,
+    },
+    /// Field or text item inside a footprint.
+    Footprint {
+        /// Footprint KIID.
+        footprint_id: Option<String>,
+        /// Footprint reference designator.
+        reference: Option<String>,
+        /// Field name, or `text` for free footprint text.
+        field: String,
+    },
+    /// Title block field (`title`, `date`, `revision`, `company`, `comment1`, ...).
+    TitleBlock {
+        /// Field name.
+        field: String,
+    },
+}
+
+impl fmt::Display for TextLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = |id: &Option<String>| id.clone().unwrap_or_else(|| "-".to_string());
+        match self {
+            Self::BoardText { id: item } => write!(f, "text:{}", id(item)),
+            Self::TextBox { id: item } => write!(f, "textbox:{}", id(item)),
+            Self::Dimension { id: item } => write!(f, "dimension:{}", id(item)),
+            Self::Footprint {
+                footprint_id,
+                reference,
+                field,
+            } => write!(
+                f,
+                "footprint:{}:{field}",
+                reference.clone().unwrap_or_else(|| id(footprint_id))
+            ),
+            Self::TitleBlock { field } => write!(f, "title_block:{field}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// One text-bearing string and the variables it references.
+pub struct TextOccurrence {
+    /// Source of the string.
+    pub location: TextLocation,
+    /// Unexpanded text.
+    pub text: String,
+    /// Variable names referenced with `${NAME}`, in order of appearance.
+    pub references: Vec<String>,
+    /// Footprint field names in scope; empty outside footprints.
+    pub local_names: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Result of checking scanned text against project variables.
+pub struct TextVariableReport {
+    /// Every scanned string that contains at least one reference.
+    pub occurrences: Vec<TextOccurrence>,
+    /// Referenced names with no definition, and where each is used.
+    pub undefined: BTreeMap<String, Vec<TextLocation>>,
+    /// Project variables no scanned string or variable value references.
+    pub unused: Vec<String>,
+}
+
+impl TextVariableReport {
+    /// Returns true when nothing is undefined or unused.
+    pub fn is_clean(&self) -> bool {
+        self.undefined.is_empty() && self.unused.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// A scanned string next to KiCad's expansion of it.
+pub struct TextPreview {
+    /// Source of the string.
+    pub location: TextLocation,
+    /// Unexpanded text.
+    pub text: String,
+    /// Text after KiCad expanded its variables.
+    pub expanded: String,
+}
+
+/// Object types [`KiCadClient::get_text_variable_report`] scans.
+///
+/// [`KiCadClient::get_text_variable_report`]: crate::client::KiCadClient::get_text_variable_report
+pub fn scan_type_codes() -> Vec<i32> {
+    vec![
+        KiCadObjectType::KotPcbText as i32,
+        KiCadObjectType::KotPcbTextbox as i32,
+        KiCadObjectType::KotPcbDimension as i32,
+        KiCadObjectType::KotPcbFootprint as i32,
+    ]
+}
+
+/// Returns the `${NAME}` references in `text`, in order of appearance.
+///
+/// `\${` is an escaped literal. For nested references such as `${A${B}}` only the inner,
+/// statically known names are returned.
+pub fn variable_references(text: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    collect_references(text, &mut references);
+    references
+}
+
+fn collect_references(text: &str, references: &mut Vec<String>) {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    while index + 1 < bytes.len() {
+        if bytes[index] == b'\\' && bytes[index + 1..].starts_with(b"${") {
+            index += 3;
+            continue;
+        }
+        if !bytes[index..].starts_with(b"${") {
+            index += 1;
+            continue;
+        }
+        let start = index + 2;
+        let mut depth = 1;
+        let mut end = start;
+        while end < bytes.len() {
+            match bytes[end] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            end += 1;
+        }
+        if depth != 0 {
+            return;
+        }
+        let name = &text[start..end];
+        if name.contains("${") {
+            collect_references(name, references);
+        } else if !name.is_empty() {
+            references.push(name.to_string());
+        }
+        index = end + 1;
+    }
+}
+
+/// Collects every text-bearing string from board items and the title block.
+pub fn scan_text(items: &[PcbItem], title_block: Option<&TitleBlockInfo>) -> Vec<TextOccurrence> {
+    let mut occurrences = Vec::new();
+    for item in items {
+        match item {
+            PcbItem::BoardText(text) => push_occurrence(
+                &mut occurrences,
+                TextLocation::BoardText {
+                    id: text.id.clone(),
+                },
+                text.text.as_deref(),
+                &[],
+            ),
+            PcbItem::BoardTextBox(text) => push_occurrence(
+                &mut occurrences,
+                TextLocation::TextBox {
+                    id: text.id.clone(),
+                },
+                text.text.as_deref(),
+                &[],
+            ),
+            PcbItem::Dimension(dimension) => push_occurrence(
+                &mut occurrences,
+                TextLocation::Dimension {
+                    id: dimension.id.clone(),
+                },
+                dimension.text.as_deref(),
+                &[],
+            ),
+            PcbItem::Footprint(footprint) => {
+                let mut fields = vec![
+                    ("Reference", footprint.reference.as_deref()),
+                    ("Value", footprint.value.as_deref()),
+                    ("Datasheet", footprint.datasheet.as_deref()),
+                ];
+                let mut texts = Vec::new();
+                for child in &footprint.items {
+                    match child {
+                        PcbItem::Field(field) => {
+                            fields.push((field.name.as_str(), field.text.as_deref()))
+                        }
+                        PcbItem::BoardText(text) => texts.push(text.text.as_deref()),
+                        PcbItem::BoardTextBox(text) => texts.push(text.text.as_deref()),
+                        _ => {}
+                    }
+                }
+                let local_names: Vec<String> =
+                    fields.iter().map(|(name, _)| name.to_string()).collect();
+                let location = |field: &str| TextLocation::Footprint {
+                    footprint_id: footprint.id.clone(),
+                    reference: footprint.reference.clone(),
+                    field: field.to_string(),
+                };
+                for (name, text) in &fields {
+                    push_occurrence(&mut occurrences, location(name), *text, &local_names);
+                }
+                for text in texts {
+                    push_occurrence(&mut occurrences, location("text"), text, &local_names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(title_block) = title_block {
+        for (field, text) in title_block.fields() {
+            push_occurrence(
+                &mut occurrences,
+                TextLocation::TitleBlock {
+                    field: field.to_string(),
+                },
+                Some(text),
+                &[],
+            );
+        }
+    }
+    occurrences
+}
+
+fn push_occurrence(
+    occurrences: &mut Vec<TextOccurrence>,
+    location: TextLocation,
+    text: Option<&str>,
+    local_names: &[String],
+) {
+    let Some(text) = text else {
+        return;
+    };
+    occurrences.push(TextOccurrence {
+        location,
+        text: text.to_string(),
+        references: variable_references(text),
+        local_names: local_names.to_vec(),
+    });
+}
+
+/// Checks scanned references against the project's text variables.
+///
+/// Occurrences without references are dropped from the report. A variable referenced only from
+/// another variable's value still counts as used.
+pub fn check_references(
+    occurrences: Vec<TextOccurrence>,
+    variables: &BTreeMap<String, String>,
+) -> TextVariableReport {
+    let mut used: BTreeSet<&str> = BTreeSet::new();
+    let mut undefined: BTreeMap<String, Vec<TextLocation>> = BTreeMap::new();
+    let occurrences: Vec<TextOccurrence> = occurrences
+        .into_iter()
+        .filter(|occurrence| !occurrence.references.is_empty())
+        .collect();
+
+    for occurrence in &occurrences {
+        for name in &occurrence.references {
+            if let Some((defined, _)) = variables.get_key_value(name) {
+                used.insert(defined);
+            } else if !resolved_by_kicad(name, &occurrence.local_names) {
+                let locations = undefined.entry(name.clone()).or_default();
+                if !locations.contains(&occurrence.location) {
+                    locations.push(occurrence.location.clone());
+                }
+            }
+        }
+    }
+    let value_references: Vec<String> = variables
+        .values()
+        .flat_map(|value| variable_references(value))
+        .collect();
+    for name in &value_references {
+        if let Some((defined, _)) = variables.get_key_value(name) {
+            used.insert(defined);
+        }
+    }
+
+    TextVariableReport {
+        occurrences,
+        undefined,
+        unused: variables
+            .keys()
+            .filter(|name| !used.contains(name.as_str()))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Returns true for variables KiCad defines itself, which project variables cannot override.
+pub(crate) fn is_builtin_variable(name: &str) -> bool {
+    BUILTIN_VARIABLES.contains(&name.to_ascii_uppercase().as_str())
+}
+
+/// Name of the variable when `text` is exactly one `${NAME}` reference.
+pub(crate) fn lone_variable_reference(text: &str) -> Option<&str> {
+    let name = text.strip_prefix("${")?.strip_suffix('}')?;
+    (!name.is_empty() && !name.contains(['$', '{', '}', ':'])).then_some(name)
+}
+
+fn resolved_by_kicad(name: &str, local_names: &[String]) -> bool {
+    let upper = name.to_ascii_uppercase();
+    is_builtin_variable(name)
+        || name.contains(':')
+        || upper.starts_with("KICAD")
+        || upper == "KIPRJMOD"
+        || local_names
+            .iter()
+            .any(|local| local.eq_ignore_ascii_case(name))
+}
+
+/// Pairs occurrences with KiCad's expansions, in order.
+pub(crate) fn previews(
+    occurrences: Vec<TextOccurrence>,
+    expanded: Vec<String>,
+) -> Vec<TextPreview> {
+    occurrences
+        .into_iter()
+        .zip(expanded)
+        .map(|(occurrence, expanded)| TextPreview {
+            location: occurrence.location,
+            text: occurrence.text,
+            expanded,
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Build metadata to stamp into project text variables before fab output.
+///
+/// Unset values are left out, so stamping never clears an existing variable.
+pub struct BuildMetadata {
+    /// Commit hash, stored as `${GIT_HASH}`.
+    pub git_hash: Option<String>,
+    /// Build date, stored as `${BUILD_DATE}`.
+    pub date: Option<String>,
+    /// Board revision, stored as `${BUILD_REVISION}`.
+    pub revision: Option<String>,
+    /// Additional variables stamped as given.
+    pub extra: BTreeMap<String, String>,
+}
+
+impl BuildMetadata {
+    /// Metadata with today's UTC date (`YYYY-MM-DD`).
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+        Self {
+            date: Some(iso_date(days as i64)),
+            ..Self::default()
+        }
+    }
+
+    /// Metadata with today's date and the short `HEAD` hash of the git checkout at `dir`.
+    pub fn from_git(dir: impl AsRef<Path>) -> Result<Self, KiCadError> {
+        let dir = dir.as_ref();
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .map_err(|err| KiCadError::Config {
+                reason: format!("failed to run git in `{}`: {err}", dir.display()),
+            })?;
+        if !output.status.success() {
+            return Err(KiCadError::Config {
+                reason: format!(
+                    "git rev-parse failed in `{}`: {}",
+                    dir.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            });
+        }
+        Ok(Self {
+            git_hash: Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+            ..Self::today()
+        })
+    }
+
+    /// Sets the board revision.
+    pub fn revision(mut self, revision: impl Into<String>) -> Self {
+        self.revision = Some(revision.into());
+        self
+    }
+
+    /// Adds an extra variable.
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra.insert(name.into(), value.into());
+        self
+    }
+
+    /// Variables to merge into the project.
+    pub fn variables(&self) -> BTreeMap<String, String> {
+        let mut variables = self.extra.clone();
+        for (name, value) in [
+            ("GIT_HASH", &self.git_hash),
+            ("BUILD_DATE", &self.date),
+            ("BUILD_REVISION", &self.revision),
+        ] {
+            if let Some(value) = value {
+                variables.insert(name.to_string(), value.clone());
+            }
+        }
+        variables
+    }
+}
+
+// Civil-from-days conversion (proleptic Gregorian calendar).
+fn iso_date(days_since_epoch: i64) -> String {
+    let z = days_since_epoch + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::board::{BoardLayerInfo, PcbBoardText, PcbField, PcbFootprint};
+    use crate::model::common::TitleBlockField;
+
+    fn layer() -> BoardLayerInfo {
+        BoardLayerInfo {
+            id: 3,
+            name: "F.Cu".to_string(),
+        }
+    }
+
+    #[test]
+    fn variable_references_handles_escapes_and_nesting() {
+        assert_eq!(
+            variable_references("Rev ${REV} built ${BUILD_DATE}"),
+            vec!["REV", "BUILD_DATE"]
+        );
+        assert_eq!(variable_references(r"\${LITERAL} ${A${B}} ${"), vec!["B"]);
+        assert!(variable_references("${}").is_empty());
+        assert_eq!(iso_date(0), "1970-01-01");
+        assert_eq!(iso_date(20_513), "2026-03-01");
+    }
+
+    #[test]
+    fn check_references_reports_undefined_and_unused() {
+        let items = vec![
+            PcbItem::BoardText(PcbBoardText {
+                id: Some("t1".to_string()),
+                layer: layer(),
+                text: Some("${PART_NO} rev ${REVISION} ${U1:VALUE} ${MISSING}".to_string()),
+                spec: None,
+            }),
+            PcbItem::Footprint(PcbFootprint {
+                id: Some("f1".to_string()),
+                reference: Some("U1".to_string()),
+                position_nm: None,
+                orientation_deg: None,
+                layer: layer(),
+                pad_count: 0,
+                value: Some("${MPN}".to_string()),
+                library_id: None,
+                datasheet: None,
+                attributes: None,
+                items: vec![PcbItem::Field(PcbField {
+                    name: "MPN".to_string(),
+                    visible: false,
+                    text: Some("STM32".to_string()),
+                })],
+            }),
+        ];
+        let title_block = TitleBlockInfo {
+            title: "Main board".to_string(),
+            date: "${BUILD_DATE}".to_string(),
+            revision: "A".to_string(),
+            company: String::new(),
+            comments: Default::default(),
+        }
+        .with_field(TitleBlockField::Comment1, "${MISSING}");
+        let variables = BTreeMap::from([
+            ("PART_NO".to_string(), "PN-${BUILD_REVISION}".to_string()),
+            ("BUILD_REVISION".to_string(), "3".to_string()),
+            ("BUILD_DATE".to_string(), "2026-01-01".to_string()),
+            ("STALE".to_string(), "x".to_string()),
+        ]);
+
+        let report = check_references(scan_text(&items, Some(&title_block)), &variables);
+        assert_eq!(report.occurrences.len(), 4);
+        assert_eq!(
+            report.undefined,
+            BTreeMap::from([(
+                "MISSING".to_string(),
+                vec![
+                    TextLocation::BoardText {
+                        id: Some("t1".to_string())
+                    },
+                    TextLocation::TitleBlock {
+                        field: "comment1".to_string()
+                    },
+                ]
+            )])
+        );
+        assert_eq!(report.unused, vec!["STALE"]);
+        assert!(!report.is_clean());
+
+        let metadata = BuildMetadata::default()
+            .revision("B")
+            .variable("FAB_HOUSE", "jlc");
+        assert_eq!(
+            metadata.variables(),
+            BTreeMap::from([
+                ("BUILD_REVISION".to_string(), "B".to_string()),
+                ("FAB_HOUSE".to_string(), "jlc".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Minimal TOML reader and writer helpers for version-controlled definition files.
+//!
+//! Covers the subset those files use: `key = value` lines, `[[name]]` array-of-tables headers,
+//! `#` comments, and basic strings, integers (with `_` separators), floats, booleans, and
+//! single-line string arrays.
+
+#[derive(Debug)]
+pub(crate) struct TomlEntry {
+    pub(crate) line: usize,
+    pub(crate) key: String,
+    pub(crate) value: TomlValue,
+}
+
+#[derive(Debug)]
+pub(crate) struct TomlTable {
+    /// Header as written, e.g. `[[netclass]]`.
+    pub(crate) header: String,
+    pub(crate) line: usize,
+    pub(crate) entries: Vec<TomlEntry>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TomlDocument {
+    /// Keys before the first table header.
+    pub(crate) root: Vec<TomlEntry>,
+    pub(crate) tables: Vec<TomlTable>,
+}
+
+#[derive(Debug)]
+pub(crate) enum TomlValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Array(Vec<String>),
+}
+
+impl TomlValue {
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        if text.starts_with('"') {
+            let (value, rest) = parse_string(text)?;
+            if !rest.trim().is_empty() {
+                return Err(format!("unexpected text after string: `{}`", rest.trim()));
+            }
+            return Ok(Self::String(value));
+        }
+        if let Some(inner) = text.strip_prefix('[') {
+            let mut rest = inner
+                .strip_suffix(']')
+                .ok_or_else(|| "arrays must close on the same line".to_string())?
+                .trim();
+            let mut values = Vec::new();
+            while !rest.is_empty() {
+                let (value, after) = parse_string(rest)?;
+                values.push(value);
+                rest = after.trim_start();
+                rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+            }
+            return Ok(Self::Array(values));
+        }
+        match text {
+            "true" => return Ok(Self::Boolean(true)),
+            "false" => return Ok(Self::Boolean(false)),
+            _ => {}
+        }
+        let number = text.replace('_', "");
+        if let Ok(value) = number.parse::<i64>() {
+            return Ok(Self::Integer(value));
+        }
+        number
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(Self::Float)
+            .ok_or_else(|| format!("unsupported value `{text}`"))
+    }
+
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::String(_) => "string",
+            Self::Integer(_) => "integer",
+            Self::Float(_) => "float",
+            Self::Boolean(_) => "boolean",
+            Self::Array(_) => "array",
+        }
+    }
+}
+
+/// Splits `text` into root keys and tables; errors carry the 1-based line number.
+pub(crate) fn parse_document(text: &str) -> Result<TomlDocument, (usize, String)> {
+    let mut document = TomlDocument::default();
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            document.tables.push(TomlTable {
+                header: line.to_string(),
+                line: line_number,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| (line_number, "expected `key = value`".to_string()))?;
+        let entry = TomlEntry {
+            line: line_number,
+            key: key.trim().to_string(),
+            value: TomlValue::parse(value.trim()).map_err(|reason| (line_number, reason))?,
+        };
+        match document.tables.last_mut() {
+            Some(table) => table.entries.push(entry),
+            None => document.root.push(entry),
+        }
+    }
+    Ok(document)
+}
+
+/// Quotes `value` as a TOML basic string.
+pub(crate) fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_string(text: &str) -> Result<(String, &str), String> {
+    let inner = text
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected a string, found `{text}`"))?;
+    let mut value = String::new();
+    let mut chars = inner.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '"' => return Ok((value, &inner[index + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, other)) => return Err(format!("unsupported escape `\\{other}`")),
+                None => break,
+            },
+            _ => value.push(ch),
+        }
+    }
+    Err("unterminated string".to_string())
+}
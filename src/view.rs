@@ -0,0 +1,360 @@
+//! Named board editor view presets.
+//!
+//! A [`ViewPreset`] bundles the visible layers, the active layer, and the
+//! [`BoardEditorAppearanceSettings`] so a reviewer can switch between views such as
+//! "assembly top", "inner routing", and "fab" in one step.
+//! [`KiCadClient::capture_view_preset`](crate::client::KiCadClient::capture_view_preset) reads
+//! the current view, [`KiCadClient::apply_view_preset`](crate::client::KiCadClient::apply_view_preset)
+//! restores one, and [`ViewPresets`] persists a named set as `view_presets.toml` in the plugin
+//! settings directory.
+//!
+//! ```toml
+//! [[preset]]
+//! name = "assembly top"
+//! visible_layers = ["BL_F_Cu", "BL_F_SilkS", "BL_F_Fab", "BL_Edge_Cuts"]
+//! active_layer = "BL_F_SilkS"
+//! inactive_layer_display = "dimmed"
+//! net_color_display = "off"
+//! board_flip = "normal"
+//! ratsnest_display = "visible-layers"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::KiCadError;
+use crate::model::board::{
+    BoardEditorAppearanceSettings, BoardFlipMode, BoardLayerInfo, InactiveLayerDisplayMode,
+    NetColorDisplayMode, RatsnestDisplayMode,
+};
+use crate::proto::kiapi::board::types as board_types;
+use crate::toml::{self, TomlValue};
+
+/// File name of the preset store inside the plugin settings directory.
+pub const VIEW_PRESETS_FILE: &str = "view_presets.toml";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Visible layers, active layer, and appearance captured under one name.
+pub struct ViewPreset {
+    /// Preset name.
+    pub name: String,
+    /// Layers shown in the editor.
+    pub visible_layers: Vec<BoardLayerInfo>,
+    /// Layer selected for editing.
+    pub active_layer: BoardLayerInfo,
+    /// Editor appearance settings.
+    pub appearance: BoardEditorAppearanceSettings,
+}
+
+impl ViewPreset {
+    /// Visible layer ids, as taken by `set_visible_layers`.
+    pub fn visible_layer_ids(&self) -> Vec<i32> {
+        self.visible_layers.iter().map(|layer| layer.id).collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Named view presets, kept sorted by name.
+pub struct ViewPresets {
+    presets: BTreeMap<String, ViewPreset>,
+}
+
+impl ViewPresets {
+    /// Returns the preset called `name`.
+    pub fn get(&self, name: &str) -> Option<&ViewPreset> {
+        self.presets.get(name)
+    }
+
+    /// Adds `preset`, returning the one it replaced.
+    pub fn insert(&mut self, preset: ViewPreset) -> Option<ViewPreset> {
+        self.presets.insert(preset.name.clone(), preset)
+    }
+
+    /// Removes and returns the preset called `name`.
+    pub fn remove(&mut self, name: &str) -> Option<ViewPreset> {
+        self.presets.remove(name)
+    }
+
+    /// Presets in name order.
+    pub fn iter(&self) -> impl Iterator<Item = &ViewPreset> {
+        self.presets.values()
+    }
+
+    /// Number of presets.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Returns true when there are no presets.
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+
+    /// Path of the preset store inside `settings_dir`.
+    pub fn path_in(settings_dir: impl AsRef<Path>) -> PathBuf {
+        settings_dir.as_ref().join(VIEW_PRESETS_FILE)
+    }
+
+    /// Reads presets from `path`; a missing file gives an empty set.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KiCadError> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse_toml(&text),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(KiCadError::Config {
+                reason: format!("failed to read view presets `{}`: {err}", path.display()),
+            }),
+        }
+    }
+
+    /// Writes presets to `path`, creating its directory.
+    ///
+    /// The file is written next to `path` and renamed over it, so readers never see a partial
+    /// store.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), KiCadError> {
+        let path = path.as_ref();
+        let io_error = |err: std::io::Error| KiCadError::Config {
+            reason: format!("failed to write view presets `{}`: {err}", path.display()),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let staging = path.with_extension("toml.tmp");
+        fs::write(&staging, self.to_toml()).map_err(io_error)?;
+        fs::rename(&staging, path).map_err(io_error)
+    }
+
+    /// Writes presets in the store's TOML format.
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        for preset in self.iter() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let layers: Vec<String> = preset
+                .visible_layers
+                .iter()
+                .map(|layer| toml::quote(&layer.name))
+                .collect();
+            let appearance = &preset.appearance;
+            out.push_str("[[preset]]\n");
+            out.push_str(&format!("name = {}\n", toml::quote(&preset.name)));
+            out.push_str(&format!("visible_layers = [{}]\n", layers.join(", ")));
+            out.push_str(&format!(
+                "active_layer = {}\n",
+                toml::quote(&preset.active_layer.name)
+            ));
+            for (key, value) in [
+                (
+                    "inactive_layer_display",
+                    appearance.inactive_layer_display.to_string(),
+                ),
+                (
+                    "net_color_display",
+                    appearance.net_color_display.to_string(),
+                ),
+                ("board_flip", appearance.board_flip.to_string()),
+                ("ratsnest_display", appearance.ratsnest_display.to_string()),
+            ] {
+                out.push_str(&format!("{key} = {}\n", toml::quote(&value)));
+            }
+        }
+        out
+    }
+
+    /// Parses presets from the store's TOML format.
+    pub fn parse_toml(text: &str) -> Result<Self, KiCadError> {
+        let document =
+            toml::parse_document(text).map_err(|(line, reason)| parse_error(line, reason))?;
+        if let Some(entry) = document.root.first() {
+            return Err(parse_error(
+                entry.line,
+                "key outside of a `[[preset]]` table".to_string(),
+            ));
+        }
+
+        let mut presets = Self::default();
+        for table in document.tables {
+            if table.header != "[[preset]]" {
+                return Err(parse_error(
+                    table.line,
+                    format!(
+                        "unsupported table `{}`; expected `[[preset]]`",
+                        table.header
+                    ),
+                ));
+            }
+            let mut builder = PresetBuilder::default();
+            for entry in table.entries {
+                builder
+                    .set(&entry.key, entry.value)
+                    .map_err(|reason| parse_error(entry.line, reason))?;
+            }
+            let preset = builder
+                .build()
+                .map_err(|reason| parse_error(table.line, reason))?;
+            if presets.get(&preset.name).is_some() {
+                return Err(parse_error(
+                    table.line,
+                    format!("view preset `{}` is defined more than once", preset.name),
+                ));
+            }
+            presets.insert(preset);
+        }
+        Ok(presets)
+    }
+}
+
+#[derive(Debug, Default)]
+struct PresetBuilder {
+    name: Option<String>,
+    visible_layers: Option<Vec<BoardLayerInfo>>,
+    active_layer: Option<BoardLayerInfo>,
+    inactive_layer_display: Option<InactiveLayerDisplayMode>,
+    net_color_display: Option<NetColorDisplayMode>,
+    board_flip: Option<BoardFlipMode>,
+    ratsnest_display: Option<RatsnestDisplayMode>,
+}
+
+impl PresetBuilder {
+    fn set(&mut self, key: &str, value: TomlValue) -> Result<(), String> {
+        if key == "visible_layers" {
+            return match value {
+                TomlValue::Array(names) => {
+                    self.visible_layers = Some(
+                        names
+                            .iter()
+                            .map(|name| layer_from_name(name))
+                            .collect::<Result<_, _>>()?,
+                    );
+                    Ok(())
+                }
+                other => Err(format!(
+                    "`visible_layers` must be a string array, found {}",
+                    other.kind()
+                )),
+            };
+        }
+        let text = match value {
+            TomlValue::String(text) => text,
+            other => return Err(format!("`{key}` must be a string, found {}", other.kind())),
+        };
+        match key {
+            "name" => self.name = Some(text),
+            "active_layer" => self.active_layer = Some(layer_from_name(&text)?),
+            "inactive_layer_display" => self.inactive_layer_display = Some(text.parse()?),
+            "net_color_display" => self.net_color_display = Some(text.parse()?),
+            "board_flip" => self.board_flip = Some(text.parse()?),
+            "ratsnest_display" => self.ratsnest_display = Some(text.parse()?),
+            _ => return Err(format!("unknown view preset key `{key}`")),
+        }
+        Ok(())
+    }
+
+    fn build(self) -> Result<ViewPreset, String> {
+        let missing = |key: &str| format!("`[[preset]]` table has no `{key}`");
+        Ok(ViewPreset {
+            name: self.name.ok_or_else(|| missing("name"))?,
+            visible_layers: self
+                .visible_layers
+                .ok_or_else(|| missing("visible_layers"))?,
+            active_layer: self.active_layer.ok_or_else(|| missing("active_layer"))?,
+            appearance: BoardEditorAppearanceSettings {
+                inactive_layer_display: self
+                    .inactive_layer_display
+                    .ok_or_else(|| missing("inactive_layer_display"))?,
+                net_color_display: self
+                    .net_color_display
+                    .ok_or_else(|| missing("net_color_display"))?,
+                board_flip: self.board_flip.ok_or_else(|| missing("board_flip"))?,
+                ratsnest_display: self
+                    .ratsnest_display
+                    .ok_or_else(|| missing("ratsnest_display"))?,
+            },
+        })
+    }
+}
+
+fn layer_from_name(name: &str) -> Result<BoardLayerInfo, String> {
+    board_types::BoardLayer::from_str_name(name)
+        .map(|layer| BoardLayerInfo {
+            id: layer as i32,
+            name: name.to_string(),
+        })
+        .ok_or_else(|| format!("unknown board layer `{name}`"))
+}
+
+fn parse_error(line: usize, reason: String) -> KiCadError {
+    KiCadError::Config {
+        reason: format!("view preset file line {line}: {reason}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str, layers: &[&str], active: &str) -> ViewPreset {
+        ViewPreset {
+            name: name.to_string(),
+            visible_layers: layers
+                .iter()
+                .map(|layer| layer_from_name(layer).expect("layer name should be known"))
+                .collect(),
+            active_layer: layer_from_name(active).expect("layer name should be known"),
+            appearance: BoardEditorAppearanceSettings {
+                inactive_layer_display: InactiveLayerDisplayMode::Dimmed,
+                net_color_display: NetColorDisplayMode::Off,
+                board_flip: BoardFlipMode::Normal,
+                ratsnest_display: RatsnestDisplayMode::Unknown(7),
+            },
+        }
+    }
+
+    #[test]
+    fn presets_round_trip_through_toml_and_disk() {
+        let mut presets = ViewPresets::default();
+        presets.insert(preset(
+            "inner routing",
+            &["BL_In1_Cu", "BL_In2_Cu", "BL_Edge_Cuts"],
+            "BL_In1_Cu",
+        ));
+        let mut fab = preset("fab \"final\"", &["BL_F_Fab", "BL_Edge_Cuts"], "BL_F_Fab");
+        fab.appearance.board_flip = BoardFlipMode::FlippedX;
+        presets.insert(fab);
+
+        let text = presets.to_toml();
+        assert!(text.starts_with("[[preset]]\nname = \"fab \\\"final\\\"\"\n"));
+        assert!(text.contains("ratsnest_display = \"unknown(7)\""));
+        assert_eq!(
+            ViewPresets::parse_toml(&text).expect("presets should parse"),
+            presets
+        );
+        assert_eq!(
+            presets
+                .get("inner routing")
+                .map(ViewPreset::visible_layer_ids),
+            Some(vec![4, 5, 47])
+        );
+
+        let dir = std::env::temp_dir().join(format!("kicad-ipc-view-{}", std::process::id()));
+        let path = ViewPresets::path_in(&dir);
+        assert_eq!(
+            ViewPresets::load(&path).expect("missing store should load"),
+            ViewPresets::default()
+        );
+        presets.save(&path).expect("presets should save");
+        assert_eq!(
+            ViewPresets::load(&path).expect("saved presets should load"),
+            presets
+        );
+        fs::remove_dir_all(&dir).expect("temp dir should be removed");
+
+        assert!(ViewPresets::parse_toml("[[preset]]\nname = \"a\"\n").is_err());
+        let err =
+            ViewPresets::parse_toml("[[preset]]\nname = \"a\"\nvisible_layers = [\"F.Cu\"]\n")
+                .expect_err("layer names must be KiCad enum names");
+        assert!(err.to_string().contains("line 3"));
+    }
+}
@@ -0,0 +1,503 @@
+//! Polling board change watcher.
+//!
+//! The IPC API has no push notifications, so [`BoardWatcher`] polls on an
+//! interval. Each poll runs the cheap probes (open documents, then the raw
+//! selection payloads) and only fetches every board item when a probe changed
+//! or a periodic full scan is due; item payloads are decoded and diffed only
+//! when they differ from the previous scan. KiCad has no item-count or board
+//! revision query, so the periodic scan is what catches edits to unselected
+//! items (undo, scripts, zone refills). [`SelectionStream`] is the
+//! selection-only variant for tools that act on what the user just selected.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio::time::{self, Interval, MissedTickBehavior};
+
+use crate::client::KiCadClient;
+use crate::error::KiCadError;
+use crate::model::board::PcbItem;
+use crate::model::common::{DocumentSpecifier, DocumentType, PcbObjectTypeCode};
+use crate::snapshot::{BoardDiff, BoardSnapshot, ItemModification};
+
+const DEFAULT_FULL_SCAN_EVERY: u32 = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+/// One observed change on the open board.
+pub enum BoardChangeEvent {
+    /// Item present now but not in the previous poll.
+    Added(PcbItem),
+    /// Item present in the previous poll but not now.
+    Removed(PcbItem),
+    /// Item present in both polls with different contents.
+    Modified(Box<ItemModification>),
+    /// Selected item KIIDs changed; holds the new selection.
+    SelectionChanged(Vec<String>),
+    /// Open PCB documents changed; holds the new list.
+    OpenDocumentsChanged(Vec<DocumentSpecifier>),
+}
+
+/// Stream of [`BoardChangeEvent`]s produced by polling KiCad.
+///
+/// Created by [`KiCadClient::watch_board`]. The first poll records a baseline
+/// and emits nothing; later polls emit one event per observed change. Polls
+/// answered with `AS_BUSY` are retried on the next tick.
+///
+/// With the `stream` feature, `BoardWatcher` implements
+/// `futures_core::Stream<Item = Result<BoardChangeEvent, KiCadError>>`; the
+/// stream never ends, and a failed poll yields its error without dropping the
+/// baseline. [`BoardWatcher::next`] is the same operation without the feature.
+pub struct BoardWatcher {
+    /// Polling state; taken by an in-flight stream poll until it completes.
+    core: Option<WatchCore>,
+    in_flight: Option<InFlightPoll>,
+}
+
+type InFlightPoll =
+    Pin<Box<dyn Future<Output = (WatchCore, Result<BoardChangeEvent, KiCadError>)> + Send>>;
+
+impl BoardWatcher {
+    pub(crate) fn new(client: KiCadClient, period: Duration) -> Self {
+        Self {
+            core: Some(WatchCore {
+                client,
+                period,
+                interval: None,
+                full_scan_every: DEFAULT_FULL_SCAN_EVERY,
+                state: WatchState::default(),
+                pending: VecDeque::new(),
+            }),
+            in_flight: None,
+        }
+    }
+
+    /// Fetches every board item at least once per `polls` polls even when no
+    /// probe changed (default 10). `0` scans on every poll.
+    pub fn full_scan_every(mut self, polls: u32) -> Self {
+        if let Some(core) = &mut self.core {
+            core.full_scan_every = polls;
+        }
+        self
+    }
+
+    /// Waits for the next change event.
+    ///
+    /// A failed poll returns its error; the watcher keeps its baseline, so
+    /// calling `next` again resumes polling.
+    pub async fn next(&mut self) -> Result<BoardChangeEvent, KiCadError> {
+        if let Some(in_flight) = self.in_flight.take() {
+            let (core, result) = in_flight.await;
+            self.core = Some(core);
+            return result;
+        }
+        self.core
+            .as_mut()
+            .expect("the core is only taken while a poll is in flight")
+            .next()
+            .await
+    }
+}
+
+impl fmt::Debug for BoardWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoardWatcher")
+            .field("core", &self.core)
+            .field("in_flight", &self.in_flight.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl futures_core::Stream for BoardWatcher {
+    type Item = Result<BoardChangeEvent, KiCadError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let in_flight = this.in_flight.get_or_insert_with(|| {
+            let mut core = this
+                .core
+                .take()
+                .expect("the core is only taken while a poll is in flight");
+            Box::pin(async move {
+                let result = core.next().await;
+                (core, result)
+            })
+        });
+        let (core, result) = std::task::ready!(in_flight.as_mut().poll(cx));
+        this.in_flight = None;
+        this.core = Some(core);
+        std::task::Poll::Ready(Some(result))
+    }
+}
+
+#[derive(Debug)]
+struct WatchCore {
+    client: KiCadClient,
+    period: Duration,
+    interval: Option<Interval>,
+    full_scan_every: u32,
+    state: WatchState,
+    pending: VecDeque<BoardChangeEvent>,
+}
+
+impl WatchCore {
+    async fn next(&mut self) -> Result<BoardChangeEvent, KiCadError> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            tick(&mut self.interval, self.period).await;
+            self.poll().await?;
+        }
+    }
+
+    async fn poll(&mut self) -> Result<(), KiCadError> {
+        let documents = self.client.get_open_documents(DocumentType::Pcb).await?;
+        let board_open = !documents.is_empty();
+        self.pending.extend(self.state.documents(documents));
+        if !board_open {
+            return Ok(());
+        }
+
+        let selection_raw = match self.client.get_selection_raw().await {
+            Ok(raw) => raw,
+            Err(err) if is_busy(&err) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        let selection = crate::client::decode_pcb_items(selection_raw.clone())?
+            .iter()
+            .filter_map(|item| item.id().map(str::to_string))
+            .collect();
+        self.pending.extend(self.state.selection(selection));
+        if !self.state.probe(selection_raw, self.full_scan_every) {
+            return Ok(());
+        }
+
+        let rows = match self.client.get_all_pcb_items_raw().await {
+            Ok(rows) => rows,
+            Err(err) if is_busy(&err) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if self.state.items_unchanged(&rows) {
+            self.state.scanned();
+            return Ok(());
+        }
+        let mut decoded = Vec::with_capacity(rows.len());
+        for (object_type, items) in &rows {
+            decoded.push((
+                *object_type,
+                crate::client::decode_pcb_items(items.clone())?,
+            ));
+        }
+        self.pending.extend(self.state.items(rows, decoded));
+        self.state.scanned();
+        Ok(())
+    }
+}
+
+/// Debounced stream of selection changes.
+///
+/// Created by [`KiCadClient::selection_stream`]. The first poll records the
+/// current selection as a baseline; after that, [`SelectionStream::next`]
+/// yields the decoded selection whenever its KIID set changes and then stays
+/// the same for one more poll, so intermediate states of a rubber-band or
+/// shift-click sequence are skipped. Polls answered with `AS_BUSY` (KiCad is
+/// mid-drag or running a tool) are retried on the next tick.
+#[derive(Debug)]
+pub struct SelectionStream {
+    client: KiCadClient,
+    period: Duration,
+    interval: Option<Interval>,
+    debounce: SelectionDebounce,
+}
+
+impl SelectionStream {
+    pub(crate) fn new(client: KiCadClient, period: Duration) -> Self {
+        Self {
+            client,
+            period,
+            interval: None,
+            debounce: SelectionDebounce::default(),
+        }
+    }
+
+    /// Waits for the next settled selection change.
+    pub async fn next(&mut self) -> Result<Vec<PcbItem>, KiCadError> {
+        loop {
+            tick(&mut self.interval, self.period).await;
+            let raw = match self.client.get_selection_raw().await {
+                Ok(raw) => raw,
+                Err(err) if is_busy(&err) => continue,
+                Err(err) => return Err(err),
+            };
+            let items = crate::client::decode_pcb_items(raw)?;
+            let ids = items
+                .iter()
+                .filter_map(|item| item.id().map(str::to_string))
+                .collect();
+            if let Some(items) = self.debounce.observe(ids, items) {
+                return Ok(items);
+            }
+        }
+    }
+}
+
+fn is_busy(err: &KiCadError) -> bool {
+    matches!(err, KiCadError::ApiStatus { code, .. } if code == "AS_BUSY")
+}
+
+async fn tick(interval: &mut Option<Interval>, period: Duration) {
+    interval
+        .get_or_insert_with(|| {
+            let mut interval = time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        })
+        .tick()
+        .await;
+}
+
+#[derive(Debug, Default)]
+struct SelectionDebounce {
+    emitted: Option<BTreeSet<String>>,
+    candidate: Option<BTreeSet<String>>,
+}
+
+impl SelectionDebounce {
+    fn observe(&mut self, ids: BTreeSet<String>, items: Vec<PcbItem>) -> Option<Vec<PcbItem>> {
+        let Some(emitted) = &self.emitted else {
+            self.emitted = Some(ids);
+            return None;
+        };
+        if *emitted == ids {
+            self.candidate = None;
+            return None;
+        }
+        if self.candidate.as_ref() == Some(&ids) {
+            self.candidate = None;
+            self.emitted = Some(ids);
+            return Some(items);
+        }
+        self.candidate = Some(ids);
+        None
+    }
+}
+
+type RawRows = Vec<(PcbObjectTypeCode, Vec<prost_types::Any>)>;
+
+#[derive(Debug, Default)]
+struct WatchState {
+    documents: Option<Vec<DocumentSpecifier>>,
+    selection: Option<Vec<String>>,
+    selection_raw: Option<Vec<prost_types::Any>>,
+    scan_due: bool,
+    polls_since_scan: u32,
+    raw_items: Option<RawRows>,
+    snapshot: Option<BoardSnapshot>,
+}
+
+impl WatchState {
+    fn documents(&mut self, documents: Vec<DocumentSpecifier>) -> Option<BoardChangeEvent> {
+        let previous = self.documents.replace(documents.clone())?;
+        if previous == documents {
+            return None;
+        }
+        // A different board invalidates the item and selection baselines.
+        self.selection = None;
+        self.selection_raw = None;
+        self.raw_items = None;
+        self.snapshot = None;
+        Some(BoardChangeEvent::OpenDocumentsChanged(documents))
+    }
+
+    fn selection(&mut self, selection: Vec<String>) -> Option<BoardChangeEvent> {
+        let previous = self.selection.replace(selection.clone())?;
+        (previous != selection).then_some(BoardChangeEvent::SelectionChanged(selection))
+    }
+
+    /// Records the selection payloads and returns whether a full item scan is
+    /// due: no baseline yet, the selected items changed (moved, edited, or a
+    /// different set), or `full_scan_every` polls passed since the last scan.
+    /// Stays due until [`WatchState::scanned`], so a busy poll retries.
+    fn probe(&mut self, selection_raw: Vec<prost_types::Any>, full_scan_every: u32) -> bool {
+        self.polls_since_scan = self.polls_since_scan.saturating_add(1);
+        let selection_changed = self.selection_raw.as_ref() != Some(&selection_raw);
+        self.selection_raw = Some(selection_raw);
+        self.scan_due |= self.raw_items.is_none()
+            || selection_changed
+            || self.polls_since_scan >= full_scan_every;
+        self.scan_due
+    }
+
+    fn scanned(&mut self) {
+        self.scan_due = false;
+        self.polls_since_scan = 0;
+    }
+
+    fn items_unchanged(&self, rows: &RawRows) -> bool {
+        self.raw_items.as_ref() == Some(rows)
+    }
+
+    fn items(
+        &mut self,
+        rows: RawRows,
+        decoded: Vec<(PcbObjectTypeCode, Vec<PcbItem>)>,
+    ) -> Vec<BoardChangeEvent> {
+        self.raw_items = Some(rows);
+        let snapshot = BoardSnapshot::from_items(decoded);
+        match self.snapshot.replace(snapshot) {
+            Some(previous) => {
+                let current = self.snapshot.as_ref().expect("snapshot was just stored");
+                diff_events(previous.diff(current))
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+fn diff_events(diff: BoardDiff) -> Vec<BoardChangeEvent> {
+    diff.added
+        .into_iter()
+        .map(BoardChangeEvent::Added)
+        .chain(diff.removed.into_iter().map(BoardChangeEvent::Removed))
+        .chain(
+            diff.modified
+                .into_iter()
+                .map(|change| BoardChangeEvent::Modified(Box::new(change))),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::board::{BoardLayerInfo, PcbTrack, Vector2Nm};
+    use crate::model::common::ProjectInfo;
+
+    fn track(id: &str, x_nm: i64) -> PcbItem {
+        PcbItem::Track(PcbTrack {
+            id: Some(id.to_string()),
+            start_nm: Some(Vector2Nm { x_nm, y_nm: 0 }),
+            end_nm: Some(Vector2Nm {
+                x_nm: x_nm + 1_000,
+                y_nm: 0,
+            }),
+            width_nm: Some(200),
+            layer: BoardLayerInfo {
+                id: 3,
+                name: "F.Cu".to_string(),
+            },
+            net: None,
+        })
+    }
+
+    fn rows(items: Vec<PcbItem>) -> (RawRows, Vec<(PcbObjectTypeCode, Vec<PcbItem>)>) {
+        let object_type = KiCadClient::pcb_object_type_codes()[0];
+        let raw = items
+            .iter()
+            .map(|item| prost_types::Any {
+                type_url: format!("{item:?}"),
+                value: Vec::new(),
+            })
+            .collect();
+        (vec![(object_type, raw)], vec![(object_type, items)])
+    }
+
+    fn document(path: &str) -> DocumentSpecifier {
+        DocumentSpecifier {
+            document_type: DocumentType::Pcb,
+            board_filename: Some(path.to_string()),
+            sheet_path: None,
+            project: ProjectInfo {
+                name: None,
+                path: None,
+            },
+        }
+    }
+
+    #[test]
+    fn item_polls_emit_events_after_baseline() {
+        let mut state = WatchState::default();
+        let (raw, decoded) = rows(vec![track("a", 0), track("b", 0)]);
+        assert!(state.items(raw, decoded).is_empty());
+
+        let (raw, _) = rows(vec![track("a", 0), track("b", 0)]);
+        assert!(state.items_unchanged(&raw));
+
+        let (raw, decoded) = rows(vec![track("a", 500), track("c", 0)]);
+        assert!(!state.items_unchanged(&raw));
+        let events = state.items(raw, decoded);
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], BoardChangeEvent::Added(item) if item.id() == Some("c")));
+        assert!(matches!(&events[1], BoardChangeEvent::Removed(item) if item.id() == Some("b")));
+        assert!(matches!(&events[2], BoardChangeEvent::Modified(change) if change.id == "a"));
+    }
+
+    #[test]
+    fn item_scans_wait_for_a_probe_change() {
+        let payload = |name: &str| {
+            vec![prost_types::Any {
+                type_url: name.to_string(),
+                value: Vec::new(),
+            }]
+        };
+        let mut state = WatchState::default();
+        assert!(state.probe(Vec::new(), 3));
+        let (raw, decoded) = rows(vec![track("a", 0)]);
+        state.items(raw, decoded);
+        state.scanned();
+
+        assert!(!state.probe(Vec::new(), 3));
+        assert!(state.probe(payload("a"), 3));
+        // A busy scan leaves the scan due for the next poll.
+        assert!(state.probe(payload("a"), 3));
+        state.scanned();
+        assert!(!state.probe(payload("a"), 3));
+        assert!(state.probe(payload("a moved"), 3));
+        state.scanned();
+        assert!(!state.probe(payload("a moved"), 3));
+        assert!(!state.probe(payload("a moved"), 3));
+        assert!(state.probe(payload("a moved"), 3));
+    }
+
+    #[test]
+    fn document_change_resets_baselines() {
+        let mut state = WatchState::default();
+        assert_eq!(state.documents(vec![document("a.kicad_pcb")]), None);
+        assert_eq!(state.selection(vec!["x".to_string()]), None);
+        assert_eq!(
+            state.selection(vec!["y".to_string()]),
+            Some(BoardChangeEvent::SelectionChanged(vec!["y".to_string()]))
+        );
+        assert_eq!(state.documents(vec![document("a.kicad_pcb")]), None);
+        assert!(matches!(
+            state.documents(vec![document("b.kicad_pcb")]),
+            Some(BoardChangeEvent::OpenDocumentsChanged(_))
+        ));
+        assert_eq!(state.selection(vec!["z".to_string()]), None);
+    }
+
+    #[test]
+    fn selection_debounce_waits_for_a_settled_change() {
+        let ids = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        let mut debounce = SelectionDebounce::default();
+        assert_eq!(debounce.observe(ids(&[]), Vec::new()), None);
+        assert_eq!(debounce.observe(ids(&["a"]), vec![track("a", 0)]), None);
+        assert_eq!(debounce.observe(ids(&["a", "b"]), Vec::new()), None);
+        assert_eq!(
+            debounce.observe(ids(&["a", "b"]), vec![track("a", 0), track("b", 0)]),
+            Some(vec![track("a", 0), track("b", 0)])
+        );
+        assert_eq!(debounce.observe(ids(&["a", "b"]), Vec::new()), None);
+        assert_eq!(debounce.observe(ids(&["c"]), Vec::new()), None);
+        assert_eq!(debounce.observe(ids(&["a", "b"]), Vec::new()), None);
+        assert_eq!(debounce.observe(ids(&[]), Vec::new()), None);
+        assert_eq!(debounce.observe(ids(&[]), Vec::new()), Some(Vec::new()));
+    }
+}
@@ -5,11 +5,23 @@ use std::process::ExitCode;
 use std::str::FromStr;
 use std::time::Duration;
 
+use kicad_ipc_rs::arrange::{ArrangeOp, FootprintSelector};
+use kicad_ipc_rs::bom::BomOptions;
+use kicad_ipc_rs::drc::{DrcChecker, DrcInput};
+use kicad_ipc_rs::fab::excellon::{DrillSet, ExcellonOptions, ExcellonUnits};
+use kicad_ipc_rs::netlist::ipc356::Ipc356Netlist;
+use kicad_ipc_rs::placement::{PlacementOptions, PlacementSide, PlacementUnits};
+use kicad_ipc_rs::render::svg::SvgOptions;
+use kicad_ipc_rs::spatial::SpatialIndex;
+use kicad_ipc_rs::stackup::{exchange as stackup_exchange, StackupEditor};
+use kicad_ipc_rs::text_vars::BuildMetadata;
+use kicad_ipc_rs::{netclass_audit, netclass_sync};
 use kicad_ipc_rs::{
-    BoardFlipMode, BoardOriginKind, CommitAction, CommitSession, DocumentType, DrcSeverity,
-    EditorFrameType, InactiveLayerDisplayMode, KiCadClientBlocking, KiCadError, MapMergeMode,
-    NetColorDisplayMode, PadstackPresenceState, PcbObjectTypeCode, RatsnestDisplayMode,
-    TextObjectSpec, TextShapeGeometry, TextSpec, Vector2Nm,
+    BoardFlipMode, BoardOriginKind, BoardSelector, CommitAction, CommitSession, DocumentType,
+    DrcSeverity, EditorFrameType, InactiveLayerDisplayMode, ItemHitTestResult, KiCadClientBlocking,
+    KiCadError, MapMergeMode, NetColorDisplayMode, PadstackPresenceState, PcbObjectTypeCode,
+    RatsnestDisplayMode, SchematicItemType, TextObjectSpec, TextShapeGeometry, TextSpec,
+    TitleBlockField, Vector2Nm,
 };
 
 const REPORT_MAX_PAD_NET_ROWS: usize = 2_000;
@@ -23,9 +35,24 @@ struct CliConfig {
     socket: Option<String>,
     token: Option<String>,
     client_name: Option<String>,
+    board: Option<String>,
     timeout_ms: u64,
 }
 
+#[derive(Debug, PartialEq)]
+enum StackupEdit {
+    InsertPair(usize),
+    RemovePair(usize),
+    Thickness(usize, i64),
+    Material(usize, String),
+    EpsilonR(usize, f64),
+    Finish(String),
+    ImpedanceControlled(bool),
+    EdgeConnector(bool),
+    CastellatedPads(bool),
+    EdgePlating(bool),
+}
+
 #[derive(Debug)]
 enum Command {
     Ping,
@@ -33,6 +60,21 @@ enum Command {
     KiCadBinaryPath {
         binary_name: String,
     },
+    ViewPresets {
+        identifier: String,
+    },
+    SaveViewPreset {
+        identifier: String,
+        name: String,
+    },
+    ApplyViewPreset {
+        identifier: String,
+        name: String,
+    },
+    DeleteViewPreset {
+        identifier: String,
+        name: String,
+    },
     PluginSettingsPath {
         identifier: String,
     },
@@ -45,11 +87,23 @@ enum Command {
     SetNetClasses {
         merge_mode: MapMergeMode,
     },
+    NetClassSync {
+        file: String,
+        dry_run: bool,
+    },
+    NetClassAudit,
     TextVariables,
     SetTextVariables {
         merge_mode: MapMergeMode,
         variables: BTreeMap<String, String>,
     },
+    TextVarReport {
+        preview: bool,
+    },
+    StampBuildMetadata {
+        git_dir: Option<String>,
+        revision: Option<String>,
+    },
     ExpandTextVariables {
         text: Vec<String>,
     },
@@ -89,6 +143,36 @@ enum Command {
         y_nm: Option<i64>,
         item_ids: Vec<String>,
     },
+    Drc {
+        inject: bool,
+    },
+    BoardOutline {
+        x_nm: Option<i64>,
+        y_nm: Option<i64>,
+    },
+    ArrangeFootprints {
+        selectors: Vec<FootprintSelector>,
+        ops: Vec<ArrangeOp>,
+    },
+    Bom {
+        json: bool,
+        include_dnp: bool,
+    },
+    Placement {
+        pos: bool,
+        options: PlacementOptions,
+    },
+    Ipc356 {
+        job: String,
+    },
+    Drill {
+        options: ExcellonOptions,
+        write_base: Option<String>,
+    },
+    RenderSvg {
+        output: String,
+        options: SvgOptions,
+    },
     RefreshEditor {
         frame: EditorFrameType,
     },
@@ -145,6 +229,13 @@ enum Command {
         y_nm: i64,
         tolerance_nm: i32,
     },
+    ItemsNear {
+        x_nm: i64,
+        y_nm: i64,
+        radius_nm: i32,
+        layer_id: Option<i32>,
+        exact: bool,
+    },
     PcbTypes,
     ItemsRaw {
         type_codes: Vec<i32>,
@@ -164,10 +255,25 @@ enum Command {
         include_debug: bool,
     },
     TitleBlock,
+    SetTitleBlock {
+        fields: Vec<(TitleBlockField, String)>,
+    },
+    SchematicLabels,
     BoardAsString,
     SelectionAsString,
     Stackup,
     UpdateStackup,
+    EditStackup {
+        edits: Vec<StackupEdit>,
+        dry_run: bool,
+    },
+    ExportStackup {
+        csv: bool,
+    },
+    ImportStackup {
+        file: String,
+        dry_run: bool,
+    },
     GraphicsDefaults,
     Appearance,
     SetAppearance {
@@ -237,6 +343,13 @@ fn run() -> Result<(), KiCadError> {
     }
 
     let client = builder.connect()?;
+    let client = match config.board {
+        Some(board) => {
+            let document = client.find_board(BoardSelector::Filename(board))?;
+            client.board(&document)
+        }
+        None => client,
+    };
 
     match command {
         Command::Ping => {
@@ -254,6 +367,54 @@ fn run() -> Result<(), KiCadError> {
             let path = client.get_kicad_binary_path(binary_name)?;
             println!("kicad_binary_path={path}");
         }
+        Command::ViewPresets { identifier } => {
+            let presets = client.load_view_presets(identifier)?;
+            println!("view_preset_count={}", presets.len());
+            for preset in presets.iter() {
+                println!(
+                    "name={} active_layer={} visible_layers={} inactive_layer_display={} net_color_display={} board_flip={} ratsnest_display={}",
+                    preset.name,
+                    preset.active_layer.name,
+                    format_layer_names_for_cli(&preset.visible_layers),
+                    preset.appearance.inactive_layer_display,
+                    preset.appearance.net_color_display,
+                    preset.appearance.board_flip,
+                    preset.appearance.ratsnest_display
+                );
+            }
+        }
+        Command::SaveViewPreset { identifier, name } => {
+            let mut presets = client.load_view_presets(identifier.clone())?;
+            let replaced = presets
+                .insert(client.capture_view_preset(name.clone())?)
+                .is_some();
+            let path = client.save_view_presets(identifier, presets)?;
+            println!(
+                "saved view preset `{name}` replaced={replaced} path={}",
+                path.display()
+            );
+        }
+        Command::ApplyViewPreset { identifier, name } => {
+            let presets = client.load_view_presets(identifier)?;
+            let preset = presets
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| KiCadError::Config {
+                    reason: format!("no view preset named `{name}`"),
+                })?;
+            client.apply_view_preset(preset)?;
+            println!("applied view preset `{name}`");
+        }
+        Command::DeleteViewPreset { identifier, name } => {
+            let mut presets = client.load_view_presets(identifier.clone())?;
+            if presets.remove(&name).is_none() {
+                return Err(KiCadError::Config {
+                    reason: format!("no view preset named `{name}`"),
+                });
+            }
+            client.save_view_presets(identifier, presets)?;
+            println!("deleted view preset `{name}`");
+        }
         Command::PluginSettingsPath { identifier } => {
             let path = client.get_plugin_settings_path(identifier)?;
             println!("plugin_settings_path={path}");
@@ -317,6 +478,41 @@ fn run() -> Result<(), KiCadError> {
                 merge_mode
             );
         }
+        Command::NetClassSync { file, dry_run } => {
+            let text = fs::read_to_string(&file).map_err(|err| KiCadError::Config {
+                reason: format!("failed to read net class file `{file}`: {err}"),
+            })?;
+            let desired = netclass_sync::parse_toml(&text)?;
+            let plan = netclass_sync::plan(desired, &client.get_net_classes()?);
+            print!("{plan}");
+            if dry_run || plan.is_empty() {
+                return Ok(());
+            }
+            let updated = client.set_net_classes(plan.net_classes(), plan.merge_mode())?;
+            println!(
+                "net_class_count={} merge_mode={}",
+                updated.len(),
+                plan.merge_mode()
+            );
+        }
+        Command::NetClassAudit => {
+            let net_classes = client.get_net_classes()?;
+            let nets = client.get_nets()?;
+            let assignments = if nets.is_empty() {
+                Vec::new()
+            } else {
+                client.get_netclass_for_nets(nets.clone())?
+            };
+            let audit = netclass_audit::audit(&net_classes, &nets, &assignments);
+            println!(
+                "net_count={} mismatch_count={} overlap_count={} unused_pattern_count={}",
+                audit.resolutions.len(),
+                audit.mismatches.len(),
+                audit.overlaps.len(),
+                audit.unused_patterns.len()
+            );
+            print!("{audit}");
+        }
         Command::TextVariables => {
             let variables = client.get_text_variables()?;
             println!("text_variable_count={}", variables.len());
@@ -324,6 +520,44 @@ fn run() -> Result<(), KiCadError> {
                 println!("name={} value={}", name, value);
             }
         }
+        Command::TextVarReport { preview } => {
+            let report = client.get_text_variable_report()?;
+            println!(
+                "occurrence_count={} undefined_count={} unused_count={}",
+                report.occurrences.len(),
+                report.undefined.len(),
+                report.unused.len()
+            );
+            for (name, locations) in &report.undefined {
+                let locations: Vec<String> = locations.iter().map(ToString::to_string).collect();
+                println!("undefined name={} at={}", name, locations.join(","));
+            }
+            for name in &report.unused {
+                println!("unused name={name}");
+            }
+            if preview {
+                for row in client.preview_text_expansions(report.occurrences)? {
+                    println!(
+                        "preview at={} text={:?} expanded={:?}",
+                        row.location, row.text, row.expanded
+                    );
+                }
+            }
+        }
+        Command::StampBuildMetadata { git_dir, revision } => {
+            let mut metadata = match git_dir {
+                Some(dir) => BuildMetadata::from_git(dir)?,
+                None => BuildMetadata::today(),
+            };
+            if let Some(revision) = revision {
+                metadata = metadata.revision(revision);
+            }
+            for (name, value) in metadata.variables() {
+                println!("stamp name={} value={}", name, value);
+            }
+            let updated = client.stamp_build_metadata(metadata)?;
+            println!("text_variable_count={}", updated.len());
+        }
         Command::SetTextVariables {
             merge_mode,
             variables,
@@ -511,6 +745,147 @@ fn run() -> Result<(), KiCadError> {
                 marker.unwrap_or_else(|| "-".to_string())
             );
         }
+        Command::Drc { inject } => {
+            let items = client.get_items_by_type_codes(DrcInput::capture_type_codes())?;
+            let net_classes = client.get_net_classes()?;
+            let nets = client.get_nets()?;
+            let assignments = if nets.is_empty() {
+                Vec::new()
+            } else {
+                client.get_netclass_for_nets(nets)?
+            };
+            let input = DrcInput::new(items).with_net_classes(net_classes, assignments);
+            let violations = DrcChecker::with_default_rules().run(&input);
+            println!("violation_count={}", violations.len());
+            for violation in &violations {
+                let position = violation
+                    .position_nm
+                    .map(|point| format!("{},{}", point.x_nm, point.y_nm))
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "rule={} severity={} pos_nm={} items={} message={}",
+                    violation.rule,
+                    violation.severity,
+                    position,
+                    violation.item_ids.join(","),
+                    violation.message
+                );
+                if inject {
+                    client.inject_drc_error(
+                        violation.severity,
+                        violation.marker_message(),
+                        violation.position_nm,
+                        violation.item_ids.clone(),
+                    )?;
+                }
+            }
+            if inject {
+                println!("injected_marker_count={}", violations.len());
+            }
+        }
+        Command::BoardOutline { x_nm, y_nm } => {
+            let outline = client.get_board_outline()?;
+            println!("polygon_count={}", outline.polygons.len());
+            println!("area_mm2={:.4}", outline.area_nm2() / 1e12);
+            if let Some((min, max)) = outline.bounding_box() {
+                println!(
+                    "bbox_nm={},{} {},{} size_nm={}x{}",
+                    min.x_nm,
+                    min.y_nm,
+                    max.x_nm,
+                    max.y_nm,
+                    max.x_nm - min.x_nm,
+                    max.y_nm - min.y_nm
+                );
+            }
+            for (index, polygon) in outline.polygons.iter().enumerate() {
+                println!(
+                    "polygon={} outline_nodes={} holes={}",
+                    index,
+                    polygon
+                        .outline
+                        .as_ref()
+                        .map_or(0, |outline| outline.nodes.len()),
+                    polygon.holes.len()
+                );
+            }
+            if let (Some(x_nm), Some(y_nm)) = (x_nm, y_nm) {
+                println!("on_board={}", outline.contains(Vector2Nm { x_nm, y_nm }));
+            }
+        }
+        Command::ArrangeFootprints { selectors, ops } => {
+            let footprints = client.arrange_footprints(selectors, ops)?;
+            println!("updated_count={}", footprints.len());
+            for footprint in footprints {
+                let position = footprint
+                    .position_nm
+                    .map(|point| format!("{},{}", point.x_nm, point.y_nm))
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "ref={} id={} pos_nm={} orientation_deg={} layer={}",
+                    footprint.reference.as_deref().unwrap_or("-"),
+                    footprint.id.as_deref().unwrap_or("-"),
+                    position,
+                    footprint
+                        .orientation_deg
+                        .map(|angle| angle.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    footprint.layer.name
+                );
+            }
+        }
+        Command::Bom { json, include_dnp } => {
+            let bom = client.get_bom(BomOptions {
+                include_do_not_populate: include_dnp,
+                ..BomOptions::default()
+            })?;
+            if json {
+                print!("{}", bom.to_json());
+            } else {
+                print!("{}", bom.to_csv());
+            }
+        }
+        Command::Placement { pos, options } => {
+            let placement = client.export_placement(options)?;
+            if pos {
+                print!("{}", placement.to_pos());
+            } else {
+                print!("{}", placement.to_csv());
+            }
+        }
+        Command::Ipc356 { job } => {
+            let items = client.get_items_by_type_codes(Ipc356Netlist::capture_type_codes())?;
+            let pad_netlist = client.get_pad_netlist()?;
+            print!(
+                "{}",
+                Ipc356Netlist::from_items(job, &items, &pad_netlist).to_text()
+            );
+        }
+        Command::Drill {
+            mut options,
+            write_base,
+        } => {
+            options.origin = Some(client.get_board_origin(BoardOriginKind::Drill)?);
+            let items = client.get_items_by_type_codes(DrillSet::capture_type_codes())?;
+            let drills = DrillSet::from_items(&items, &options);
+            print!("{}", drills.drill_table_text());
+            if let Some(base) = write_base {
+                for file in &drills.files {
+                    let path = file.file_name(&base);
+                    fs::write(&path, file.to_text()).map_err(|err| KiCadError::Config {
+                        reason: format!("failed to write `{path}`: {err}"),
+                    })?;
+                    println!("wrote={path}");
+                }
+            }
+        }
+        Command::RenderSvg { output, options } => {
+            let svg = client.render_board_svg(options)?;
+            fs::write(&output, svg).map_err(|err| KiCadError::Config {
+                reason: format!("failed to write `{output}`: {err}"),
+            })?;
+            println!("wrote={output}");
+        }
         Command::RefreshEditor { frame } => {
             client.refresh_editor(frame)?;
             println!("refresh_editor=ok frame={}", frame);
@@ -690,6 +1065,40 @@ fn run() -> Result<(), KiCadError> {
             let result = client.hit_test_item(item_id, Vector2Nm { x_nm, y_nm }, tolerance_nm)?;
             println!("hit_test={result}");
         }
+        Command::ItemsNear {
+            x_nm,
+            y_nm,
+            radius_nm,
+            layer_id,
+            exact,
+        } => {
+            let point = Vector2Nm { x_nm, y_nm };
+            let snapshot = client.get_board_snapshot()?;
+            let index = SpatialIndex::from_snapshot(&snapshot);
+            let mut candidates = index.within_distance(point, i64::from(radius_nm), layer_id);
+            candidates.sort_by(|a, b| {
+                a.bounds
+                    .distance_to(point)
+                    .total_cmp(&b.bounds.distance_to(point))
+            });
+            for entry in candidates {
+                if exact
+                    && client.hit_test_item(entry.id.clone(), point, radius_nm)?
+                        != ItemHitTestResult::Hit
+                {
+                    continue;
+                }
+                let kind = snapshot
+                    .item(&entry.id)
+                    .map_or("unknown", |item| item.kind_name());
+                println!(
+                    "id={} type={} distance_nm={:.0}",
+                    entry.id,
+                    kind,
+                    entry.bounds.distance_to(point)
+                );
+            }
+        }
         Command::PcbTypes => {
             for entry in kicad_ipc_rs::KiCadClient::pcb_object_type_codes() {
                 println!("type_id={} type_name={}", entry.code, entry.name);
@@ -842,8 +1251,58 @@ fn run() -> Result<(), KiCadError> {
             println!("date={}", title_block.date);
             println!("revision={}", title_block.revision);
             println!("company={}", title_block.company);
-            for (index, comment) in title_block.comments.iter().enumerate() {
-                println!("comment{}={}", index + 1, comment);
+            for (field, comment) in title_block.fields().into_iter().skip(4) {
+                if !comment.is_empty() {
+                    println!("{field}={comment}");
+                }
+            }
+        }
+        Command::SetTitleBlock { fields } => {
+            let mut title_block = client.get_title_block_info()?;
+            for (field, value) in fields {
+                title_block = title_block.with_field(field, value);
+            }
+            let written = client.set_title_block_info(title_block)?;
+            println!("variable_count={}", written.len());
+            for (name, value) in written {
+                println!("name={} value={}", name, value);
+            }
+        }
+        Command::SchematicLabels => {
+            let document = client
+                .get_open_documents(DocumentType::Schematic)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| KiCadError::Config {
+                    reason: "no open schematic document".to_string(),
+                })?;
+            let labels = client.get_schematic_items(
+                document,
+                vec![
+                    SchematicItemType::LocalLabel,
+                    SchematicItemType::GlobalLabel,
+                    SchematicItemType::HierarchicalLabel,
+                    SchematicItemType::DirectiveLabel,
+                ],
+            )?;
+            println!("label_count={}", labels.len());
+            for item in &labels {
+                let Some(label) = item.label() else {
+                    continue;
+                };
+                let kind = item
+                    .item_type()
+                    .map(|kind| format!("{kind:?}"))
+                    .unwrap_or_else(|| "-".to_string());
+                let position = label
+                    .position_nm
+                    .map(|point| format!("{},{}", point.x_nm, point.y_nm))
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "kind={kind} id={} position_nm={position} text={}",
+                    label.id.as_deref().unwrap_or("-"),
+                    label.text()
+                );
             }
         }
         Command::BoardAsString => {
@@ -863,6 +1322,87 @@ fn run() -> Result<(), KiCadError> {
             let updated = client.update_board_stackup(stackup)?;
             println!("{updated:#?}");
         }
+        Command::EditStackup { edits, dry_run } => {
+            let mut editor = StackupEditor::new(client.get_board_stackup()?);
+            for edit in edits {
+                match edit {
+                    StackupEdit::InsertPair(after_copper) => {
+                        editor.insert_copper_pair(after_copper)?;
+                    }
+                    StackupEdit::RemovePair(copper) => {
+                        editor.remove_copper_pair(copper)?;
+                    }
+                    StackupEdit::Thickness(index, thickness_nm) => {
+                        editor.set_thickness(index, thickness_nm)?;
+                    }
+                    StackupEdit::Material(index, material_name) => {
+                        editor.set_material(index, material_name)?;
+                    }
+                    StackupEdit::EpsilonR(index, epsilon_r) => {
+                        editor.set_epsilon_r(index, epsilon_r)?;
+                    }
+                    StackupEdit::Finish(name) => {
+                        editor.set_finish(name);
+                    }
+                    StackupEdit::ImpedanceControlled(value) => {
+                        editor.set_impedance_controlled(value);
+                    }
+                    StackupEdit::EdgeConnector(value) => {
+                        editor.set_edge_connector(value);
+                    }
+                    StackupEdit::CastellatedPads(value) => {
+                        editor.set_castellated_pads(value);
+                    }
+                    StackupEdit::EdgePlating(value) => {
+                        editor.set_edge_plating(value);
+                    }
+                }
+            }
+            editor.validate()?;
+            println!(
+                "copper_layer_count={} total_thickness_nm={}",
+                editor.copper_layer_count(),
+                editor.total_thickness_nm()
+            );
+            if dry_run {
+                println!("{:#?}", editor.stackup());
+                return Ok(());
+            }
+
+            let updated = client.apply_stackup(&editor)?;
+            println!("{updated:#?}");
+        }
+        Command::ExportStackup { csv } => {
+            let stackup = client.get_board_stackup()?;
+            if csv {
+                print!("{}", stackup_exchange::to_csv(&stackup));
+            } else {
+                print!("{}", stackup_exchange::to_toml(&stackup));
+            }
+        }
+        Command::ImportStackup { file, dry_run } => {
+            let text = fs::read_to_string(&file).map_err(|err| KiCadError::Config {
+                reason: format!("failed to read stackup file `{file}`: {err}"),
+            })?;
+            let stackup = if file.to_ascii_lowercase().ends_with(".csv") {
+                let mut stackup = client.get_board_stackup()?;
+                stackup.layers = stackup_exchange::parse_csv(&text)?;
+                stackup
+            } else {
+                stackup_exchange::parse_toml(&text)?
+            };
+            let stackup = StackupEditor::new(stackup).finish()?;
+            stackup_exchange::check_copper_layer_count(
+                &stackup,
+                &client.get_board_enabled_layers()?,
+            )?;
+            if dry_run {
+                println!("{stackup:#?}");
+                return Ok(());
+            }
+            let updated = client.update_board_stackup(stackup)?;
+            println!("{updated:#?}");
+        }
         Command::GraphicsDefaults => {
             let defaults = client.get_graphics_defaults()?;
             println!("{defaults:#?}");
@@ -960,6 +1500,13 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
                 config.client_name = Some(value.clone());
                 args.drain(index..=index + 1);
             }
+            "--board" => {
+                let value = args.get(index + 1).ok_or_else(|| KiCadError::Config {
+                    reason: "missing value for --board".to_string(),
+                })?;
+                config.board = Some(value.clone());
+                args.drain(index..=index + 1);
+            }
             "--timeout-ms" => {
                 let value = args.get(index + 1).ok_or_else(|| KiCadError::Config {
                     reason: "missing value for --timeout-ms".to_string(),
@@ -999,6 +1546,18 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
             }
             Command::KiCadBinaryPath { binary_name }
         }
+        "view-presets" => Command::ViewPresets {
+            identifier: parse_view_preset_args(&args, "view-presets", false)?.0,
+        },
+        "save-view-preset" | "apply-view-preset" | "delete-view-preset" => {
+            let (identifier, name) = parse_view_preset_args(&args, &args[0], true)?;
+            let name = name.unwrap_or_default();
+            match args[0].as_str() {
+                "save-view-preset" => Command::SaveViewPreset { identifier, name },
+                "apply-view-preset" => Command::ApplyViewPreset { identifier, name },
+                _ => Command::DeleteViewPreset { identifier, name },
+            }
+        }
         "plugin-settings-path" => {
             let mut identifier = "kicad-ipc-rust".to_string();
             let mut i = 1;
@@ -1035,7 +1594,71 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
             }
             Command::SetNetClasses { merge_mode }
         }
+        "netclass-sync" => {
+            let mut file = None;
+            let mut dry_run = false;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--file" => {
+                        file = Some(
+                            args.get(i + 1)
+                                .ok_or_else(|| KiCadError::Config {
+                                    reason: "missing value for netclass-sync --file".to_string(),
+                                })?
+                                .clone(),
+                        );
+                        i += 1;
+                    }
+                    "--dry-run" => dry_run = true,
+                    _ => {}
+                }
+                i += 1;
+            }
+            let file = file.ok_or_else(|| KiCadError::Config {
+                reason: "netclass-sync requires --file <path>".to_string(),
+            })?;
+            Command::NetClassSync { file, dry_run }
+        }
+        "netclass-audit" => Command::NetClassAudit,
         "text-variables" => Command::TextVariables,
+        "text-var-report" => Command::TextVarReport {
+            preview: args.iter().skip(1).any(|arg| arg == "--preview"),
+        },
+        "stamp-build-metadata" => {
+            let mut git_dir = None;
+            let mut revision = None;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--git" => {
+                        git_dir = Some(
+                            args.get(i + 1)
+                                .ok_or_else(|| KiCadError::Config {
+                                    reason: "missing value for stamp-build-metadata --git"
+                                        .to_string(),
+                                })?
+                                .clone(),
+                        );
+                        i += 1;
+                    }
+                    "--revision" => {
+                        revision = Some(
+                            args.get(i + 1)
+                                .ok_or_else(|| KiCadError::Config {
+                                    reason: "missing value for stamp-build-metadata --revision"
+                                        .to_string(),
+                                })?
+                                .clone(),
+                        );
+                        i += 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Command::StampBuildMetadata { git_dir, revision }
+        }
         "set-text-variables" => {
             let mut merge_mode = MapMergeMode::Merge;
             let mut variables = BTreeMap::new();
@@ -1382,6 +2005,200 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
                 item_ids,
             }
         }
+        "drc" => Command::Drc {
+            inject: args.iter().skip(1).any(|arg| arg == "--inject"),
+        },
+        "board-outline" => {
+            let mut x_nm = None;
+            let mut y_nm = None;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--x-nm" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for board-outline --x-nm".to_string(),
+                        })?;
+                        x_nm = Some(value.parse::<i64>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid board-outline --x-nm `{value}`: {err}"),
+                        })?);
+                        i += 2;
+                    }
+                    "--y-nm" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for board-outline --y-nm".to_string(),
+                        })?;
+                        y_nm = Some(value.parse::<i64>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid board-outline --y-nm `{value}`: {err}"),
+                        })?);
+                        i += 2;
+                    }
+                    _ => {
+                        i += 1;
+                    }
+                }
+            }
+            if x_nm.is_some() != y_nm.is_some() {
+                return Err(KiCadError::Config {
+                    reason: "board-outline --x-nm and --y-nm must be given together".to_string(),
+                });
+            }
+            Command::BoardOutline { x_nm, y_nm }
+        }
+        "arrange-footprints" => {
+            let mut selectors = Vec::new();
+            let mut ops = Vec::new();
+            let mut i = 1;
+            while i < args.len() {
+                let flag = args[i].as_str();
+                match flag {
+                    "--flip" => {
+                        ops.push(ArrangeOp::Flip);
+                        i += 1;
+                    }
+                    "--ref" | "--id" | "--move-to" | "--move-by" | "--rotate" | "--align"
+                    | "--distribute" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: format!("missing value for arrange-footprints {flag}"),
+                        })?;
+                        match flag {
+                            "--ref" => selectors.push(FootprintSelector::Reference(value.clone())),
+                            "--id" => selectors.push(FootprintSelector::Id(value.clone())),
+                            _ => ops.push(parse_arrange_op(flag, value)?),
+                        }
+                        i += 2;
+                    }
+                    _ => i += 1,
+                }
+            }
+            if selectors.is_empty() || ops.is_empty() {
+                return Err(KiCadError::Config {
+                    reason: "arrange-footprints requires --ref/--id and at least one operation"
+                        .to_string(),
+                });
+            }
+            Command::ArrangeFootprints { selectors, ops }
+        }
+        "bom" => Command::Bom {
+            json: args.iter().skip(1).any(|arg| arg == "--json"),
+            include_dnp: args.iter().skip(1).any(|arg| arg == "--include-dnp"),
+        },
+        "placement" => {
+            let mut pos = false;
+            let mut options = PlacementOptions::default();
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--pos" => pos = true,
+                    "--inches" => options.units = PlacementUnits::Inches,
+                    "--smd-only" => options.smd_only = true,
+                    "--side" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for placement --side".to_string(),
+                        })?;
+                        options.side = Some(match value.as_str() {
+                            "top" => PlacementSide::Top,
+                            "bottom" => PlacementSide::Bottom,
+                            other => {
+                                return Err(KiCadError::Config {
+                                    reason: format!(
+                                        "unknown placement side `{other}`; expected top or bottom"
+                                    ),
+                                })
+                            }
+                        });
+                        i += 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Command::Placement { pos, options }
+        }
+        "ipc356" => {
+            let mut job = "board".to_string();
+            let mut i = 1;
+            while i < args.len() {
+                if args[i] == "--job" {
+                    job = args
+                        .get(i + 1)
+                        .ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for ipc356 --job".to_string(),
+                        })?
+                        .clone();
+                    i += 2;
+                    continue;
+                }
+                i += 1;
+            }
+            Command::Ipc356 { job }
+        }
+        "drill" => {
+            let mut options = ExcellonOptions::default();
+            let mut write_base = None;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--inches" => options.units = ExcellonUnits::Inches,
+                    "--merge-layer-pairs" => options.separate_layer_pairs = false,
+                    "--write" => {
+                        write_base = Some(
+                            args.get(i + 1)
+                                .ok_or_else(|| KiCadError::Config {
+                                    reason: "missing value for drill --write".to_string(),
+                                })?
+                                .clone(),
+                        );
+                        i += 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Command::Drill {
+                options,
+                write_base,
+            }
+        }
+        "render-svg" => {
+            let mut output = "board.svg".to_string();
+            let mut options = SvgOptions::default();
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--output" => {
+                        output = args
+                            .get(i + 1)
+                            .ok_or_else(|| KiCadError::Config {
+                                reason: "missing value for render-svg --output".to_string(),
+                            })?
+                            .clone();
+                        i += 1;
+                    }
+                    "--hide-layer" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for render-svg --hide-layer".to_string(),
+                        })?;
+                        let layer_id = value.parse::<i32>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid render-svg --hide-layer `{value}`: {err}"),
+                        })?;
+                        options = options.layer_visible(layer_id, false);
+                        i += 1;
+                    }
+                    "--highlight" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for render-svg --highlight".to_string(),
+                        })?;
+                        options = options.highlight([value.clone()]);
+                        i += 1;
+                    }
+                    "--transparent" => options.background = None,
+                    "--no-net-colors" => options.use_net_colors = false,
+                    _ => {}
+                }
+                i += 1;
+            }
+            Command::RenderSvg { output, options }
+        }
         "refresh-editor" => {
             let mut frame = EditorFrameType::PcbEditor;
             let mut i = 1;
@@ -1712,6 +2529,74 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
                 tolerance_nm,
             }
         }
+        "items-near" => {
+            let mut x_nm = None;
+            let mut y_nm = None;
+            let mut radius_nm = 0_i32;
+            let mut layer_id = None;
+            let mut exact = false;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--x-nm" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for items-near --x-nm".to_string(),
+                        })?;
+                        x_nm = Some(value.parse::<i64>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid items-near --x-nm `{value}`: {err}"),
+                        })?);
+                        i += 2;
+                    }
+                    "--y-nm" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for items-near --y-nm".to_string(),
+                        })?;
+                        y_nm = Some(value.parse::<i64>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid items-near --y-nm `{value}`: {err}"),
+                        })?);
+                        i += 2;
+                    }
+                    "--radius-nm" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for items-near --radius-nm".to_string(),
+                        })?;
+                        radius_nm = value.parse::<i32>().map_err(|err| KiCadError::Config {
+                            reason: format!("invalid items-near --radius-nm `{value}`: {err}"),
+                        })?;
+                        i += 2;
+                    }
+                    "--layer-id" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for items-near --layer-id".to_string(),
+                        })?;
+                        layer_id =
+                            Some(value.parse::<i32>().map_err(|err| KiCadError::Config {
+                                reason: format!("invalid items-near --layer-id `{value}`: {err}"),
+                            })?);
+                        i += 2;
+                    }
+                    "--exact" => {
+                        exact = true;
+                        i += 1;
+                    }
+                    _ => {
+                        i += 1;
+                    }
+                }
+            }
+
+            Command::ItemsNear {
+                x_nm: x_nm.ok_or_else(|| KiCadError::Config {
+                    reason: "items-near requires `--x-nm <value>`".to_string(),
+                })?,
+                y_nm: y_nm.ok_or_else(|| KiCadError::Config {
+                    reason: "items-near requires `--y-nm <value>`".to_string(),
+                })?,
+                radius_nm,
+                layer_id,
+                exact,
+            }
+        }
         "types-pcb" => Command::PcbTypes,
         "items-raw" => {
             let mut type_codes = Vec::new();
@@ -1821,51 +2706,142 @@ fn parse_args_from(mut args: Vec<String>) -> Result<(CliConfig, Command), KiCadE
                         item_ids.push(value.clone());
                         i += 2;
                     }
-                    "--layer-id" => {
+                    "--layer-id" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for padstack-presence --layer-id".to_string(),
+                        })?;
+                        layer_ids.push(value.parse::<i32>().map_err(|err| KiCadError::Config {
+                            reason: format!(
+                                "invalid padstack-presence --layer-id `{value}`: {err}"
+                            ),
+                        })?);
+                        i += 2;
+                    }
+                    "--debug" => {
+                        include_debug = true;
+                        i += 1;
+                    }
+                    _ => {
+                        i += 1;
+                    }
+                }
+            }
+
+            if item_ids.is_empty() {
+                return Err(KiCadError::Config {
+                    reason: "padstack-presence requires one or more `--item-id <uuid>` arguments"
+                        .to_string(),
+                });
+            }
+            if layer_ids.is_empty() {
+                return Err(KiCadError::Config {
+                    reason: "padstack-presence requires one or more `--layer-id <i32>` arguments"
+                        .to_string(),
+                });
+            }
+
+            Command::PadstackPresence {
+                item_ids,
+                layer_ids,
+                include_debug,
+            }
+        }
+        "title-block" => Command::TitleBlock,
+        "set-title-block" => {
+            let mut fields = Vec::new();
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--field" => {
+                        let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                            reason: "missing value for set-title-block --field".to_string(),
+                        })?;
+                        let (name, text) =
+                            value.split_once('=').ok_or_else(|| KiCadError::Config {
+                                reason: "set-title-block --field requires `<field>=<value>`"
+                                    .to_string(),
+                            })?;
+                        let field = TitleBlockField::from_str(name)
+                            .map_err(|reason| KiCadError::Config { reason })?;
+                        fields.push((field, text.to_string()));
+                        i += 2;
+                    }
+                    _ => i += 1,
+                }
+            }
+            Command::SetTitleBlock { fields }
+        }
+        "schematic-labels" => Command::SchematicLabels,
+        "board-as-string" => Command::BoardAsString,
+        "selection-as-string" => Command::SelectionAsString,
+        "stackup" => Command::Stackup,
+        "update-stackup" => Command::UpdateStackup,
+        "export-stackup" => Command::ExportStackup {
+            csv: args.iter().skip(1).any(|arg| arg == "--csv"),
+        },
+        "import-stackup" => {
+            let mut file = None;
+            let mut dry_run = false;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i].as_str() {
+                    "--file" => {
+                        file = Some(
+                            args.get(i + 1)
+                                .ok_or_else(|| KiCadError::Config {
+                                    reason: "missing value for import-stackup --file".to_string(),
+                                })?
+                                .clone(),
+                        );
+                        i += 1;
+                    }
+                    "--dry-run" => dry_run = true,
+                    _ => {}
+                }
+                i += 1;
+            }
+            let file = file.ok_or_else(|| KiCadError::Config {
+                reason: "import-stackup requires --file <path>".to_string(),
+            })?;
+            Command::ImportStackup { file, dry_run }
+        }
+        "edit-stackup" => {
+            let mut edits = Vec::new();
+            let mut dry_run = false;
+            let mut i = 1;
+            while i < args.len() {
+                let flag = args[i].as_str();
+                match flag {
+                    "--dry-run" => {
+                        dry_run = true;
+                        i += 1;
+                    }
+                    "--insert-pair-after"
+                    | "--remove-pair"
+                    | "--thickness"
+                    | "--material"
+                    | "--epsilon-r"
+                    | "--finish"
+                    | "--impedance-controlled"
+                    | "--edge-connector"
+                    | "--castellated-pads"
+                    | "--edge-plating" => {
                         let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
-                            reason: "missing value for padstack-presence --layer-id".to_string(),
+                            reason: format!("missing value for edit-stackup {flag}"),
                         })?;
-                        layer_ids.push(value.parse::<i32>().map_err(|err| KiCadError::Config {
-                            reason: format!(
-                                "invalid padstack-presence --layer-id `{value}`: {err}"
-                            ),
-                        })?);
+                        edits.push(parse_stackup_edit(flag, value)?);
                         i += 2;
                     }
-                    "--debug" => {
-                        include_debug = true;
-                        i += 1;
-                    }
-                    _ => {
-                        i += 1;
-                    }
+                    _ => i += 1,
                 }
             }
-
-            if item_ids.is_empty() {
-                return Err(KiCadError::Config {
-                    reason: "padstack-presence requires one or more `--item-id <uuid>` arguments"
-                        .to_string(),
-                });
-            }
-            if layer_ids.is_empty() {
+            if edits.is_empty() {
                 return Err(KiCadError::Config {
-                    reason: "padstack-presence requires one or more `--layer-id <i32>` arguments"
-                        .to_string(),
+                    reason: "edit-stackup requires at least one edit option".to_string(),
                 });
             }
-
-            Command::PadstackPresence {
-                item_ids,
-                layer_ids,
-                include_debug,
-            }
+            Command::EditStackup { edits, dry_run }
         }
-        "title-block" => Command::TitleBlock,
-        "board-as-string" => Command::BoardAsString,
-        "selection-as-string" => Command::SelectionAsString,
-        "stackup" => Command::Stackup,
-        "update-stackup" => Command::UpdateStackup,
         "graphics-defaults" => Command::GraphicsDefaults,
         "appearance" => Command::Appearance,
         "set-appearance" => {
@@ -2086,11 +3062,128 @@ fn parse_drc_severity(value: &str) -> Result<DrcSeverity, String> {
     }
 }
 
+fn parse_view_preset_args(
+    args: &[String],
+    command_name: &str,
+    name_required: bool,
+) -> Result<(String, Option<String>), KiCadError> {
+    let mut identifier = "kicad-ipc-rust".to_string();
+    let mut name = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            flag @ ("--identifier" | "--name") => {
+                let value = args.get(i + 1).ok_or_else(|| KiCadError::Config {
+                    reason: format!("missing value for {command_name} {flag}"),
+                })?;
+                if flag == "--name" {
+                    name = Some(value.clone());
+                } else {
+                    identifier = value.clone();
+                }
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+    if name_required && name.is_none() {
+        return Err(KiCadError::Config {
+            reason: format!("{command_name} requires --name <preset>"),
+        });
+    }
+    Ok((identifier, name))
+}
+
+fn parse_arrange_op(flag: &str, value: &str) -> Result<ArrangeOp, KiCadError> {
+    let invalid = |err: String| KiCadError::Config {
+        reason: format!("invalid arrange-footprints {flag} `{value}`: {err}"),
+    };
+    let point = |text: &str| {
+        let (x, y) = text
+            .split_once(',')
+            .ok_or_else(|| invalid("expected `<x_nm>,<y_nm>`".to_string()))?;
+        Ok::<_, KiCadError>(Vector2Nm {
+            x_nm: x.trim().parse().map_err(|err| invalid(format!("{err}")))?,
+            y_nm: y.trim().parse().map_err(|err| invalid(format!("{err}")))?,
+        })
+    };
+
+    Ok(match flag {
+        "--move-to" => ArrangeOp::MoveTo(point(value)?),
+        "--move-by" => ArrangeOp::MoveBy(point(value)?),
+        "--rotate" => {
+            let (angle, center) = match value.split_once('@') {
+                Some((angle, center)) => (angle, Some(point(center)?)),
+                None => (value, None),
+            };
+            ArrangeOp::Rotate {
+                angle_deg: angle.parse().map_err(|err| invalid(format!("{err}")))?,
+                center,
+            }
+        }
+        "--align" => ArrangeOp::Align(value.parse().map_err(invalid)?),
+        "--distribute" => ArrangeOp::Distribute(value.parse().map_err(invalid)?),
+        _ => return Err(invalid("unknown option".to_string())),
+    })
+}
+
+fn parse_stackup_edit(flag: &str, value: &str) -> Result<StackupEdit, KiCadError> {
+    let invalid = |err: String| KiCadError::Config {
+        reason: format!("invalid edit-stackup {flag} `{value}`: {err}"),
+    };
+    let index_value = || {
+        let (index, rest) = value
+            .split_once('=')
+            .ok_or_else(|| invalid("expected `<index>=<value>`".to_string()))?;
+        let index = index
+            .parse::<usize>()
+            .map_err(|err| invalid(err.to_string()))?;
+        Ok::<_, KiCadError>((index, rest))
+    };
+    let index = || {
+        value
+            .parse::<usize>()
+            .map_err(|err| invalid(err.to_string()))
+    };
+    let flag_value = || {
+        value
+            .parse::<bool>()
+            .map_err(|err| invalid(err.to_string()))
+    };
+
+    Ok(match flag {
+        "--insert-pair-after" => StackupEdit::InsertPair(index()?),
+        "--remove-pair" => StackupEdit::RemovePair(index()?),
+        "--thickness" => {
+            let (index, nm) = index_value()?;
+            StackupEdit::Thickness(index, nm.parse().map_err(|err| invalid(format!("{err}")))?)
+        }
+        "--material" => {
+            let (index, name) = index_value()?;
+            StackupEdit::Material(index, name.to_string())
+        }
+        "--epsilon-r" => {
+            let (index, epsilon_r) = index_value()?;
+            StackupEdit::EpsilonR(
+                index,
+                epsilon_r.parse().map_err(|err| invalid(format!("{err}")))?,
+            )
+        }
+        "--finish" => StackupEdit::Finish(value.to_string()),
+        "--impedance-controlled" => StackupEdit::ImpedanceControlled(flag_value()?),
+        "--edge-connector" => StackupEdit::EdgeConnector(flag_value()?),
+        "--castellated-pads" => StackupEdit::CastellatedPads(flag_value()?),
+        "--edge-plating" => StackupEdit::EdgePlating(flag_value()?),
+        _ => return Err(invalid("unknown option".to_string())),
+    })
+}
+
 fn default_config() -> CliConfig {
     CliConfig {
         socket: None,
         token: None,
         client_name: None,
+        board: None,
         timeout_ms: 15_000,
     }
 }
@@ -2100,13 +3193,23 @@ fn print_help() {
         r#"kicad-ipc-cli
 
 USAGE:
-  cargo run --bin kicad-ipc-cli -- [--socket URI] [--token TOKEN] [--client-name NAME] [--timeout-ms N] <command> [command options]
+  cargo run --bin kicad-ipc-cli -- [--socket URI] [--token TOKEN] [--client-name NAME] [--board FILE] [--timeout-ms N] <command> [command options]
+
+  --board FILE                 Target the open board with this filename or stem when several are open
 
 COMMANDS:
   ping                         Check IPC connectivity
   version                      Fetch KiCad version
   kicad-binary-path [--binary-name <name>]
                                Resolve absolute path for a KiCad binary (default: kicad-cli)
+  view-presets [--identifier <id>]
+                               List view presets saved in the plugin settings directory
+  save-view-preset --name <name> [--identifier <id>]
+                               Save visible layers, active layer, and appearance as a preset
+  apply-view-preset --name <name> [--identifier <id>]
+                               Restore a saved view preset, rolling back on failure
+  delete-view-preset --name <name> [--identifier <id>]
+                               Remove a saved view preset
   plugin-settings-path [--identifier <id>]
                                Resolve writeable plugin settings directory (default: kicad-ipc-rust)
   open-docs [--type <type>]    List open docs (default type: pcb)
@@ -2115,9 +3218,15 @@ COMMANDS:
   net-classes                  List project netclass definitions
   set-net-classes [--merge-mode <merge|replace>]
                                Write current netclass set back with selected merge mode
+  netclass-sync --file <path> [--dry-run]
+                               Plan and apply netclass definitions from a TOML file
+  netclass-audit               Compare locally resolved netclass patterns with KiCad's assignments
   text-variables               List text variables for current board document
   set-text-variables [--merge-mode <merge|replace>] [--var <name=value> ...]
                                Set text variables for current board document
+  text-var-report [--preview]  Report undefined/unused ${{VAR}} references; --preview expands each text
+  stamp-build-metadata [--git <dir>] [--revision <rev>]
+                               Merge BUILD_DATE, GIT_HASH, and BUILD_REVISION into text variables
   expand-text-variables        Expand variables in provided text values
                                Options: --text <value> (repeatable)
   text-extents                 Measure text bounding box
@@ -2131,6 +3240,8 @@ COMMANDS:
   item-bbox --id <uuid> ...    Show bounding boxes for item IDs
   hit-test --id <uuid> --x-nm <x> --y-nm <y> [--tolerance-nm <n>]
                                Hit-test one item at a point
+  items-near --x-nm <x> --y-nm <y> [--radius-nm <n>] [--layer-id <i32>] [--exact]
+                               List items whose bounds are near a point; --exact confirms with hit-test
   types-pcb                    List PCB KiCad object type IDs from proto enum
   items-raw --type-id <id> ... Dump raw Any payloads for requested item type IDs
   items-raw-all-pcb [--debug]  Dump all PCB item payloads across all PCB object types
@@ -2139,10 +3250,21 @@ COMMANDS:
   padstack-presence --item-id <uuid> ... --layer-id <i32> ... [--debug]
                                Check padstack shape presence matrix across layers
   title-block                  Show title block fields
+  set-title-block [--field <field=value> ...]
+                               Edit title block fields bound to ${{VAR}} text variables
+  schematic-labels             List labels in the open schematic
   board-as-string              Dump board as KiCad s-expression text
   selection-as-string          Dump current selection as KiCad s-expression text
   stackup                      Show typed board stackup
   update-stackup               Round-trip current stackup through UpdateBoardStackup
+  edit-stackup [--insert-pair-after <copper>] [--remove-pair <copper>]
+               [--thickness <index=nm>] [--material <index=name>] [--epsilon-r <index=value>]
+               [--finish <name>] [--impedance-controlled <bool>] [--edge-connector <bool>]
+               [--castellated-pads <bool>] [--edge-plating <bool>] [--dry-run]
+                               Edit, validate, and write the stackup, syncing copper layer count
+  export-stackup [--csv]       Print the stackup as TOML (default) or CSV
+  import-stackup --file <path> [--dry-run]
+                               Load a TOML or .csv stackup and write it through UpdateBoardStackup
   graphics-defaults            Show typed graphics defaults
   appearance                   Show typed editor appearance settings
   set-appearance --inactive-layer-display <normal|dimmed|hidden>
@@ -2152,6 +3274,21 @@ COMMANDS:
                                Set editor appearance settings
   inject-drc-error --severity <s> --message <text> [--x-nm <i64> --y-nm <i64>] [--item-id <uuid> ...]
                                Inject a DRC marker (severity: warning|error|exclusion|ignore|info|action|debug|undefined)
+  drc [--inject]               Run client-side DRC rules; optionally push violations as markers
+  board-outline [--x-nm <i64> --y-nm <i64>]
+                               Print the Edge.Cuts outline area and size; optionally test a point
+  arrange-footprints (--ref <ref> | --id <uuid>)... [--move-to <x_nm>,<y_nm>] [--move-by <dx_nm>,<dy_nm>]
+                 [--rotate <deg>[@<x_nm>,<y_nm>]] [--flip] [--align <left|right|top|bottom>]
+                 [--distribute <horizontal|vertical>]
+                               Apply placement operations in order as one commit
+  bom [--json] [--include-dnp] Print grouped bill of materials as CSV (default) or JSON
+  placement [--pos] [--inches] [--side <top|bottom>] [--smd-only]
+                               Print pick-and-place data relative to the drill origin as CSV or .pos
+  ipc356 [--job <name>]        Print an IPC-D-356A bare-board test netlist
+  drill [--inches] [--merge-layer-pairs] [--write <base>]
+                               Print the drill table; optionally write Excellon files as <base>-*.drl
+  render-svg [--output <path>] [--hide-layer <id> ...] [--highlight <uuid> ...] [--transparent] [--no-net-colors]
+                               Render the board to an SVG preview (default board.svg)
   refill-zones [--zone-id <uuid> ...]
                                Refill all zones or a provided subset
   interactive-move --id <uuid> ...
@@ -2409,8 +3546,10 @@ fn build_board_read_report_markdown(client: &KiCadClientBlocking) -> Result<Stri
     out.push_str(&format!("- date: {}\n", title_block.date));
     out.push_str(&format!("- revision: {}\n", title_block.revision));
     out.push_str(&format!("- company: {}\n", title_block.company));
-    for (index, comment) in title_block.comments.iter().enumerate() {
-        out.push_str(&format!("- comment{}: {}\n", index + 1, comment));
+    for (field, comment) in title_block.fields().into_iter().skip(4) {
+        if !comment.is_empty() {
+            out.push_str(&format!("- {field}: {comment}\n"));
+        }
     }
     out.push('\n');
 
@@ -2780,10 +3919,12 @@ fn hex_nibble(c: char) -> Result<u8, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_args_from, Command};
+    use super::{parse_args_from, Command, StackupEdit};
+    use kicad_ipc_rs::arrange::{AlignEdge, ArrangeOp, FootprintSelector};
+    use kicad_ipc_rs::placement::{PlacementSide, PlacementUnits};
     use kicad_ipc_rs::{
         BoardFlipMode, BoardOriginKind, CommitAction, DrcSeverity, InactiveLayerDisplayMode,
-        NetColorDisplayMode, RatsnestDisplayMode,
+        NetColorDisplayMode, RatsnestDisplayMode, Vector2Nm,
     };
 
     #[test]
@@ -2799,6 +3940,19 @@ mod tests {
         assert!(matches!(command, Command::BeginCommit));
     }
 
+    #[test]
+    fn parse_args_accepts_board_selector() {
+        let (config, command) = parse_args_from(vec![
+            "--board".to_string(),
+            "main.kicad_pcb".to_string(),
+            "nets".to_string(),
+        ])
+        .expect("board + nets should parse");
+
+        assert_eq!(config.board.as_deref(), Some("main.kicad_pcb"));
+        assert!(matches!(command, Command::Nets));
+    }
+
     #[test]
     fn parse_args_parses_end_commit_flags() {
         let (_, command) = parse_args_from(vec![
@@ -2958,6 +4112,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_args_parses_netclass_sync() {
+        let (_, command) = parse_args_from(vec![
+            "netclass-sync".to_string(),
+            "--file".to_string(),
+            "netclasses.toml".to_string(),
+            "--dry-run".to_string(),
+        ])
+        .expect("netclass-sync args should parse");
+
+        match command {
+            Command::NetClassSync { file, dry_run } => {
+                assert_eq!(file, "netclasses.toml");
+                assert!(dry_run);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+        assert!(parse_args_from(vec!["netclass-sync".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_args_parses_stamp_build_metadata() {
+        let (_, command) = parse_args_from(vec![
+            "stamp-build-metadata".to_string(),
+            "--git".to_string(),
+            ".".to_string(),
+            "--revision".to_string(),
+            "C".to_string(),
+        ])
+        .expect("stamp-build-metadata args should parse");
+
+        match command {
+            Command::StampBuildMetadata { git_dir, revision } => {
+                assert_eq!(git_dir.as_deref(), Some("."));
+                assert_eq!(revision.as_deref(), Some("C"));
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_set_title_block() {
+        let (_, command) = parse_args_from(vec![
+            "set-title-block".to_string(),
+            "--field".to_string(),
+            "revision=C".to_string(),
+            "--field".to_string(),
+            "comment3=Released".to_string(),
+        ])
+        .expect("set-title-block args should parse");
+
+        match command {
+            Command::SetTitleBlock { fields } => assert_eq!(
+                fields,
+                vec![
+                    (kicad_ipc_rs::TitleBlockField::Revision, "C".to_string()),
+                    (
+                        kicad_ipc_rs::TitleBlockField::Comment3,
+                        "Released".to_string()
+                    ),
+                ]
+            ),
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+        assert!(parse_args_from(vec![
+            "set-title-block".to_string(),
+            "--field".to_string(),
+            "comment10=x".to_string(),
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_args_parses_set_text_variables() {
         let (_, command) = parse_args_from(vec![
@@ -3252,6 +4478,234 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_args_parses_drc() {
+        let (_, command) = parse_args_from(vec!["drc".to_string(), "--inject".to_string()])
+            .expect("drc args should parse");
+
+        match command {
+            Command::Drc { inject } => assert!(inject),
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_board_outline() {
+        let (_, command) = parse_args_from(vec![
+            "board-outline".to_string(),
+            "--x-nm".to_string(),
+            "1000000".to_string(),
+            "--y-nm".to_string(),
+            "-250".to_string(),
+        ])
+        .expect("board-outline args should parse");
+
+        match command {
+            Command::BoardOutline { x_nm, y_nm } => {
+                assert_eq!(x_nm, Some(1_000_000));
+                assert_eq!(y_nm, Some(-250));
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+
+        assert!(parse_args_from(vec![
+            "board-outline".to_string(),
+            "--x-nm".to_string(),
+            "5".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_args_parses_arrange_footprints() {
+        let (_, command) = parse_args_from(vec![
+            "arrange-footprints".to_string(),
+            "--ref".to_string(),
+            "R1".to_string(),
+            "--id".to_string(),
+            "abc".to_string(),
+            "--rotate".to_string(),
+            "90@100,-200".to_string(),
+            "--flip".to_string(),
+            "--align".to_string(),
+            "left".to_string(),
+            "--move-by".to_string(),
+            "5,6".to_string(),
+        ])
+        .expect("arrange-footprints args should parse");
+
+        match command {
+            Command::ArrangeFootprints { selectors, ops } => {
+                assert_eq!(
+                    selectors,
+                    vec![
+                        FootprintSelector::Reference("R1".to_string()),
+                        FootprintSelector::Id("abc".to_string())
+                    ]
+                );
+                assert_eq!(
+                    ops,
+                    vec![
+                        ArrangeOp::Rotate {
+                            angle_deg: 90.0,
+                            center: Some(Vector2Nm {
+                                x_nm: 100,
+                                y_nm: -200
+                            }),
+                        },
+                        ArrangeOp::Flip,
+                        ArrangeOp::Align(AlignEdge::Left),
+                        ArrangeOp::MoveBy(Vector2Nm { x_nm: 5, y_nm: 6 }),
+                    ]
+                );
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+
+        assert!(parse_args_from(vec![
+            "arrange-footprints".to_string(),
+            "--ref".to_string(),
+            "R1".to_string(),
+            "--align".to_string(),
+            "middle".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_args_parses_bom() {
+        let (_, command) = parse_args_from(vec![
+            "bom".to_string(),
+            "--json".to_string(),
+            "--include-dnp".to_string(),
+        ])
+        .expect("bom args should parse");
+
+        match command {
+            Command::Bom { json, include_dnp } => {
+                assert!(json);
+                assert!(include_dnp);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_placement() {
+        let (_, command) = parse_args_from(vec![
+            "placement".to_string(),
+            "--pos".to_string(),
+            "--inches".to_string(),
+            "--side".to_string(),
+            "bottom".to_string(),
+        ])
+        .expect("placement args should parse");
+
+        match command {
+            Command::Placement { pos, options } => {
+                assert!(pos);
+                assert_eq!(options.units, PlacementUnits::Inches);
+                assert_eq!(options.side, Some(PlacementSide::Bottom));
+                assert!(!options.smd_only);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_ipc356() {
+        let (_, command) = parse_args_from(vec![
+            "ipc356".to_string(),
+            "--job".to_string(),
+            "demo".to_string(),
+        ])
+        .expect("ipc356 args should parse");
+
+        match command {
+            Command::Ipc356 { job } => assert_eq!(job, "demo"),
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_drill() {
+        let (_, command) = parse_args_from(vec![
+            "drill".to_string(),
+            "--merge-layer-pairs".to_string(),
+            "--write".to_string(),
+            "out/demo".to_string(),
+        ])
+        .expect("drill args should parse");
+
+        match command {
+            Command::Drill {
+                options,
+                write_base,
+            } => {
+                assert!(!options.separate_layer_pairs);
+                assert_eq!(write_base.as_deref(), Some("out/demo"));
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_render_svg() {
+        let (_, command) = parse_args_from(vec![
+            "render-svg".to_string(),
+            "--output".to_string(),
+            "out/board.svg".to_string(),
+            "--hide-layer".to_string(),
+            "5".to_string(),
+            "--highlight".to_string(),
+            "fp-1".to_string(),
+            "--transparent".to_string(),
+        ])
+        .expect("render-svg args should parse");
+
+        match command {
+            Command::RenderSvg { output, options } => {
+                assert_eq!(output, "out/board.svg");
+                assert!(options.hidden_layers.contains(&5));
+                assert!(options.highlight_ids.contains("fp-1"));
+                assert_eq!(options.background, None);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_args_parses_items_near() {
+        let (_, command) = parse_args_from(vec![
+            "items-near".to_string(),
+            "--x-nm".to_string(),
+            "1000".to_string(),
+            "--y-nm".to_string(),
+            "-2000".to_string(),
+            "--radius-nm".to_string(),
+            "500".to_string(),
+            "--layer-id".to_string(),
+            "3".to_string(),
+            "--exact".to_string(),
+        ])
+        .expect("items-near args should parse");
+
+        match command {
+            Command::ItemsNear {
+                x_nm,
+                y_nm,
+                radius_nm,
+                layer_id,
+                exact,
+            } => {
+                assert_eq!((x_nm, y_nm, radius_nm), (1000, -2000, 500));
+                assert_eq!(layer_id, Some(3));
+                assert!(exact);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_args_parses_refill_zones() {
         let (_, command) = parse_args_from(vec![
@@ -3278,6 +4732,100 @@ mod tests {
         assert!(matches!(command, Command::UpdateStackup));
     }
 
+    #[test]
+    fn parse_args_parses_view_presets() {
+        let (_, command) = parse_args_from(vec![
+            "apply-view-preset".to_string(),
+            "--name".to_string(),
+            "assembly top".to_string(),
+            "--identifier".to_string(),
+            "review".to_string(),
+        ])
+        .expect("apply-view-preset args should parse");
+        match command {
+            Command::ApplyViewPreset { identifier, name } => {
+                assert_eq!(identifier, "review");
+                assert_eq!(name, "assembly top");
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+
+        let (_, command) =
+            parse_args_from(vec!["view-presets".to_string()]).expect("view-presets should parse");
+        assert!(matches!(
+            command,
+            Command::ViewPresets { identifier } if identifier == "kicad-ipc-rust"
+        ));
+        assert!(parse_args_from(vec!["save-view-preset".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_args_parses_stackup_exchange() {
+        let (_, command) = parse_args_from(vec!["export-stackup".to_string(), "--csv".to_string()])
+            .expect("export-stackup should parse");
+        assert!(matches!(command, Command::ExportStackup { csv: true }));
+
+        let (_, command) = parse_args_from(vec![
+            "import-stackup".to_string(),
+            "--file".to_string(),
+            "fab-stackup.csv".to_string(),
+            "--dry-run".to_string(),
+        ])
+        .expect("import-stackup should parse");
+        match command {
+            Command::ImportStackup { file, dry_run } => {
+                assert_eq!(file, "fab-stackup.csv");
+                assert!(dry_run);
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+        assert!(parse_args_from(vec!["import-stackup".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_args_parses_edit_stackup() {
+        let (_, command) = parse_args_from(vec![
+            "edit-stackup".to_string(),
+            "--insert-pair-after".to_string(),
+            "0".to_string(),
+            "--thickness".to_string(),
+            "2=200000".to_string(),
+            "--epsilon-r".to_string(),
+            "2=4.2".to_string(),
+            "--finish".to_string(),
+            "ENIG".to_string(),
+            "--castellated-pads".to_string(),
+            "true".to_string(),
+            "--dry-run".to_string(),
+        ])
+        .expect("edit-stackup args should parse");
+
+        match command {
+            Command::EditStackup { edits, dry_run } => {
+                assert!(dry_run);
+                assert_eq!(
+                    edits,
+                    vec![
+                        StackupEdit::InsertPair(0),
+                        StackupEdit::Thickness(2, 200_000),
+                        StackupEdit::EpsilonR(2, 4.2),
+                        StackupEdit::Finish("ENIG".to_string()),
+                        StackupEdit::CastellatedPads(true),
+                    ]
+                );
+            }
+            other => panic!("unexpected command variant: {other:?}"),
+        }
+
+        assert!(parse_args_from(vec!["edit-stackup".to_string()]).is_err());
+        assert!(parse_args_from(vec![
+            "edit-stackup".to_string(),
+            "--thickness".to_string(),
+            "2".to_string(),
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_args_parses_interactive_move_items() {
         let (_, command) = parse_args_from(vec![